@@ -0,0 +1,291 @@
+use serde::Serialize;
+use specta::Type;
+
+use crate::commands::settings::{keys, read_bool_setting};
+use crate::commands::writing_styles::WritingStyleCategory;
+use crate::placeholders::{self, PromptContext};
+use crate::platform::{self, FocusedApp};
+use crate::redaction::{redact_if_enabled, redact_url_if_enabled};
+
+/// How the URL of a browser's active tab is read
+#[derive(Clone, Copy)]
+enum Browser {
+    /// Chrome and its forks share Chrome's AppleScript dictionary
+    Chromium,
+    Safari,
+    /// No AppleScript support; the address bar is read through accessibility
+    Firefox,
+}
+
+/// Browsers whose active tab URL can be read, by bundle id
+const BROWSERS: &[(&str, Browser)] = &[
+    ("com.google.Chrome", Browser::Chromium),
+    ("com.google.Chrome.beta", Browser::Chromium),
+    ("com.google.Chrome.canary", Browser::Chromium),
+    ("org.chromium.Chromium", Browser::Chromium),
+    ("company.thebrowser.Browser", Browser::Chromium),
+    ("com.microsoft.edgemac", Browser::Chromium),
+    ("com.brave.Browser", Browser::Chromium),
+    ("com.vivaldi.Vivaldi", Browser::Chromium),
+    ("com.apple.Safari", Browser::Safari),
+    ("com.apple.SafariTechnologyPreview", Browser::Safari),
+    ("org.mozilla.firefox", Browser::Firefox),
+    ("org.mozilla.firefoxdeveloperedition", Browser::Firefox),
+];
+
+/// Display names of apps whose process name isn't the one users know, mostly
+/// Electron apps that run as `Electron` or a short name like `Code`
+const KNOWN_APPS: &[(&str, &str)] = &[
+    ("com.tinyspeck.slackmacgap", "Slack"),
+    ("com.hnc.Discord", "Discord"),
+    ("com.microsoft.VSCode", "Visual Studio Code"),
+    ("com.microsoft.VSCodeInsiders", "Visual Studio Code"),
+    ("com.microsoft.teams2", "Microsoft Teams"),
+    ("net.whatsapp.WhatsApp", "WhatsApp"),
+    ("notion.id", "Notion"),
+];
+
+/// Apps whose text is Markdown, by bundle id. Dictation into them gets Markdown
+/// structure and is formatted with the Markdown style.
+const MARKDOWN_EDITORS: &[&str] = &[
+    "md.obsidian",
+    "abnerworks.Typora",
+    // Dicto itself: dictation into its own window lands in a note
+    "com.mac.dicto",
+];
+
+/// The app being dictated into, the same shape for native apps, browsers and
+/// Electron apps
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct AppContext {
+    /// e.g. `com.tinyspeck.slackmacgap`; `None` when it can't be read
+    pub bundle_id: Option<String>,
+    /// Display name, e.g. `Slack` even though its process is called `Electron`
+    pub name: String,
+    /// Active tab of a supported browser
+    pub url: Option<String>,
+    /// Writing style category from the URL's domain, falling back to the app
+    pub category: WritingStyleCategory,
+    /// Whether the app is a Markdown editor or Dicto's notes
+    pub markdown: bool,
+}
+
+/// A running app outside Dicto, by process so it can be brought back to the front
+#[derive(Debug, Clone)]
+pub struct RunningApp {
+    pub pid: i32,
+    pub name: String,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_frontmost_app() -> Result<AppContext, String> {
+    frontmost_app()
+}
+
+/// The frontmost app, with the URL of the active tab for supported browsers.
+/// Only macOS has bundle ids and readable browser tabs; elsewhere just the name
+/// of the focused window's process is known.
+pub fn frontmost_app() -> Result<AppContext, String> {
+    let FocusedApp {
+        process_name,
+        bundle_id,
+    } = platform::frontmost_apps()
+        .focused_app()
+        .ok_or_else(|| "Failed to read the frontmost app".to_string())?;
+
+    let name = bundle_id
+        .as_deref()
+        .and_then(|id| KNOWN_APPS.iter().find(|(known, _)| *known == id))
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| process_name.clone());
+
+    let url = bundle_id
+        .as_deref()
+        .and_then(|id| BROWSERS.iter().find(|(browser, _)| *browser == id))
+        .and_then(|(id, browser)| active_tab_url(id, &process_name, *browser));
+
+    let category = url
+        .as_deref()
+        .and_then(WritingStyleCategory::for_url)
+        .unwrap_or_else(|| WritingStyleCategory::for_app(&name));
+
+    let markdown = bundle_id
+        .as_deref()
+        .is_some_and(|id| MARKDOWN_EDITORS.contains(&id));
+
+    Ok(AppContext {
+        bundle_id,
+        name,
+        url,
+        category,
+        markdown,
+    })
+}
+
+/// URL of the active tab of a browser's front window
+fn active_tab_url(bundle_id: &str, process_name: &str, browser: Browser) -> Option<String> {
+    let script = match browser {
+        Browser::Chromium => format!(
+            "tell application id \"{}\" to get URL of active tab of front window",
+            bundle_id
+        ),
+        Browser::Safari => format!(
+            "tell application id \"{}\" to get URL of current tab of front window",
+            bundle_id
+        ),
+        Browser::Firefox => format!(
+            "tell application \"System Events\" to get value of UI element 1 of combo box 1 \
+             of toolbar \"Navigation\" of first group of front window of application process \"{}\"",
+            process_name
+        ),
+    };
+
+    let url = run_osascript(&script)?;
+    // Firefox shows the address without a scheme
+    if url.contains("://") {
+        Some(url)
+    } else if url.contains('.') && !url.contains(' ') {
+        Some(format!("https://{}", url))
+    } else {
+        None
+    }
+}
+
+/// Title of the focused window, e.g. "Re: Q3 budget", unless title capture is
+/// turned off in settings
+pub fn window_title_if_enabled() -> Option<String> {
+    if !read_bool_setting(keys::CAPTURE_WINDOW_TITLE, true) {
+        return None;
+    }
+    focused_window_title()
+}
+
+/// Title of the focused window of the frontmost app
+fn focused_window_title() -> Option<String> {
+    let title = platform::frontmost_apps().window_title()?;
+    let title = title.trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Text selected in the focused element of the frontmost app, if any
+pub fn selected_text() -> Option<String> {
+    platform::frontmost_apps()
+        .selected_text()
+        .filter(|text| !text.is_empty())
+}
+
+/// Length in characters of the text selected in the frontmost app
+pub fn selected_text_length() -> Option<usize> {
+    selected_text().map(|text| text.chars().count())
+}
+
+/// The app being dictated into, if it is still in front. Its URL and the rest
+/// only belong to the dictation while it is.
+pub fn target_context(target_app: Option<&str>) -> Option<AppContext> {
+    frontmost_app()
+        .ok()
+        .filter(|frontmost| is_target(frontmost, target_app))
+}
+
+fn is_target(frontmost: &AppContext, target_app: Option<&str>) -> bool {
+    !target_app.is_some_and(|name| name != frontmost.name)
+}
+
+/// The frontmost app, ignoring Dicto itself. Cheap enough to call at the start
+/// of every recording.
+pub fn frontmost_running_app() -> Option<RunningApp> {
+    platform::frontmost_apps().running_app()
+}
+
+/// Bring an app back to the front so keystrokes land in it. Returns false when
+/// the app is no longer running.
+pub fn activate_app(pid: i32) -> bool {
+    platform::frontmost_apps().activate(pid)
+}
+
+/// Whether the app is the frontmost one
+pub fn is_frontmost(pid: i32) -> bool {
+    frontmost_running_app().is_some_and(|app| app.pid == pid)
+}
+
+/// Whether the app is still running
+pub fn is_running(pid: i32) -> bool {
+    platform::frontmost_apps().is_running(pid)
+}
+
+/// Recipient of the message being written, when the app exposes it. Only Mail
+/// does: the first "To" recipient of the front compose window, by name when set.
+fn current_recipient(app_name: &str) -> Option<String> {
+    if app_name != "Mail" {
+        return None;
+    }
+
+    let recipient = "to recipient 1 of outgoing message 1";
+    run_osascript(&format!(
+        "tell application \"Mail\" to get name of {}",
+        recipient
+    ))
+    .filter(|name| name != "missing value")
+    .or_else(|| {
+        run_osascript(&format!(
+            "tell application \"Mail\" to get address of {}",
+            recipient
+        ))
+    })
+}
+
+/// Fill the variables of a custom writing style prompt from the app being
+/// dictated into. The app is only inspected when the prompt uses a variable.
+pub fn resolve_prompt(template: &str, target_app: Option<&str>) -> String {
+    if !placeholders::has_prompt_variables(template) {
+        return template.to_string();
+    }
+
+    let frontmost = frontmost_app().ok();
+    let app_name = target_app
+        .map(str::to_string)
+        .or_else(|| frontmost.as_ref().map(|app| app.name.clone()))
+        .filter(|name| !name.is_empty());
+    // The rendered prompt goes to the formatting server
+    let url = frontmost
+        .filter(|app| is_target(app, app_name.as_deref()))
+        .and_then(|app| app.url)
+        .and_then(|url| redact_url_if_enabled(&url));
+    let recipient = app_name
+        .as_deref()
+        .and_then(current_recipient)
+        .map(|recipient| redact_if_enabled(&recipient).text);
+
+    placeholders::render_prompt(
+        template,
+        &PromptContext {
+            app_name,
+            url,
+            recipient,
+        },
+    )
+}
+
+/// Output of an AppleScript snippet, `None` when it fails or prints nothing
+pub fn run_osascript(script: &str) -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        let output = std::process::Command::new("osascript")
+            .arg("-e")
+            .arg(script)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!text.is_empty()).then_some(text)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = script;
+        None
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::ipc::Channel;
+
+/// Frame rate used when a visualizer doesn't ask for one
+const DEFAULT_FPS: u32 = 30;
+
+/// Highest frame rate a visualizer can ask for
+const MAX_FPS: u32 = 60;
+
+static SUBSCRIBERS: Mutex<Vec<Subscriber>> = Mutex::new(Vec::new());
+
+/// A visualizer receiving the microphone level over its own IPC channel
+struct Subscriber {
+    channel: Channel<f32>,
+    interval_ms: u64,
+    last_sent_ms: u64,
+    /// Loudest level since the last frame, so short peaks aren't skipped
+    peak: f32,
+}
+
+/// Pass the level (0-100) of the latest audio buffer to the visualizers. Levels
+/// are coalesced into the loudest one per frame at each visualizer's frame rate.
+pub fn publish(level: f32) {
+    let Ok(mut subscribers) = SUBSCRIBERS.lock() else {
+        return;
+    };
+    if subscribers.is_empty() {
+        return;
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+
+    // A failed send means the webview behind the channel is gone
+    subscribers.retain_mut(|subscriber| {
+        subscriber.peak = subscriber.peak.max(level);
+        if now.saturating_sub(subscriber.last_sent_ms) < subscriber.interval_ms {
+            return true;
+        }
+
+        subscriber.last_sent_ms = now;
+        let peak = std::mem::take(&mut subscriber.peak);
+        subscriber.channel.send(peak).is_ok()
+    });
+}
+
+/// Stream the microphone level (0-100) to `on_level` at up to `fps` frames per
+/// second while recording. Returns an ID for `audio_level_unsubscribe`.
+#[tauri::command]
+#[specta::specta]
+pub fn audio_level_subscribe(on_level: Channel<f32>, fps: Option<u32>) -> u32 {
+    let fps = fps.unwrap_or(DEFAULT_FPS).clamp(1, MAX_FPS);
+    let id = on_level.id();
+
+    if let Ok(mut subscribers) = SUBSCRIBERS.lock() {
+        subscribers.push(Subscriber {
+            channel: on_level,
+            interval_ms: 1000 / fps as u64,
+            last_sent_ms: 0,
+            peak: 0.0,
+        });
+    }
+
+    id
+}
+
+/// Stop streaming the microphone level to a visualizer
+#[tauri::command]
+#[specta::specta]
+pub fn audio_level_unsubscribe(id: u32) {
+    if let Ok(mut subscribers) = SUBSCRIBERS.lock() {
+        subscribers.retain(|subscriber| subscriber.channel.id() != id);
+    }
+}
@@ -0,0 +1,198 @@
+use serde::Serialize;
+use specta::Type;
+use std::time::Instant;
+use tauri::AppHandle;
+use tracing::{error, info};
+
+use crate::commands::error::CommandError;
+use crate::corrections::similarity;
+use crate::model_download::{model_installed, model_path};
+use crate::model_registry::{ModelEntry, ModelKind, ModelRegistry};
+use crate::power;
+use crate::stt::{load_engine, ChunkRequest};
+use crate::transcription::{inference_thread_count, DecodingOptions};
+
+/// Text of the reference clip: about ten seconds of speech with names, numbers
+/// and punctuation. Keep it in step with the recording.
+const REFERENCE_TEXT: &str = "Hi Maria, the quarterly report is almost ready. \
+     Could you send me the final numbers by Thursday afternoon? \
+     I'd like to review them before the meeting with Daniel at three thirty.";
+
+/// Recording of `REFERENCE_TEXT` read aloud, bundled so every machine is
+/// measured on the same audio
+const REFERENCE_CLIP: &[u8] = include_bytes!("../resources/benchmark-reference.wav");
+
+/// Sample rate of the clip, the one models transcribe at
+const SAMPLE_RATE: u32 = 16000;
+
+/// Slowest real-time factor still recommended: transcribing must take at most
+/// this share of the audio's length so chunks don't pile up while dictating
+const MAX_RECOMMENDED_REALTIME_FACTOR: f32 = 0.5;
+
+/// How one model did on the reference clip
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ModelBenchmark {
+    /// Manifest id of the model
+    pub model: String,
+    /// Word similarity of the transcript to the reference text, 0.0 to 1.0
+    pub accuracy: f32,
+    /// Processing time divided by the clip's length; below 1.0 is faster than real time
+    pub realtime_factor: f32,
+    pub load_ms: u64,
+    pub transcribe_ms: u64,
+    pub transcript: String,
+}
+
+/// Result of `benchmark_models`
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct BenchmarkReport {
+    pub clip_duration_ms: u64,
+    /// One entry per installed model
+    pub results: Vec<ModelBenchmark>,
+    /// Most accurate model fast enough for this machine; the fastest one when
+    /// none is, and `None` when no model is installed
+    pub recommended: Option<String>,
+}
+
+/// Run the reference clip through every installed model and recommend the one
+/// that suits this machine best
+#[tauri::command]
+#[specta::specta]
+pub async fn benchmark_models(app: AppHandle) -> Result<BenchmarkReport, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || run_benchmark(&app))
+        .await
+        .map_err(|e| CommandError::from(format!("Benchmark failed: {}", e)))?
+}
+
+fn run_benchmark(app: &AppHandle) -> Result<BenchmarkReport, CommandError> {
+    let clip = reference_clip()?;
+    let clip_duration_ms = clip.len() as u64 * 1000 / SAMPLE_RATE as u64;
+    let threads = inference_thread_count(1, power::low_power_active());
+
+    let mut results = Vec::new();
+    for model in ModelRegistry::global()?.of_kind(ModelKind::Stt) {
+        if !model_installed(app, model) {
+            continue;
+        }
+        match benchmark_model(app, model, &clip, threads) {
+            Ok(result) => {
+                info!(
+                    "Benchmarked {}: accuracy {:.2}, real-time factor {:.2}",
+                    model.name, result.accuracy, result.realtime_factor
+                );
+                results.push(result);
+            }
+            Err(e) => error!("Failed to benchmark {}: {}", model.name, e),
+        }
+    }
+
+    let fast_enough = results
+        .iter()
+        .filter(|r| r.realtime_factor <= MAX_RECOMMENDED_REALTIME_FACTOR)
+        .max_by(|a, b| a.accuracy.total_cmp(&b.accuracy));
+    let recommended = fast_enough
+        .or_else(|| {
+            results
+                .iter()
+                .min_by(|a, b| a.realtime_factor.total_cmp(&b.realtime_factor))
+        })
+        .map(|r| r.model.clone());
+
+    Ok(BenchmarkReport {
+        clip_duration_ms,
+        results,
+        recommended,
+    })
+}
+
+fn benchmark_model(
+    app: &AppHandle,
+    model: &ModelEntry,
+    clip: &[f32],
+    threads: i32,
+) -> Result<ModelBenchmark, String> {
+    let model_path = model_path(app, model)?;
+
+    let started = Instant::now();
+    let mut engine = load_engine(model, &model_path, threads).map_err(|e| e.to_string())?;
+    let load_ms = started.elapsed().as_millis() as u64;
+
+    let started = Instant::now();
+    let transcript = engine
+        .transcribe(&ChunkRequest {
+            samples: clip,
+            offset_ms: 0,
+            previous_text: None,
+            language: Some("en-US"),
+            keyterms: &[],
+            decoding: DecodingOptions::from_saved_settings(),
+        })
+        .map_err(|e| e.to_string())?;
+    let transcribe_ms = started.elapsed().as_millis() as u64;
+
+    let clip_ms = (clip.len() as u64 * 1000 / SAMPLE_RATE as u64).max(1);
+
+    Ok(ModelBenchmark {
+        model: model.id.clone(),
+        accuracy: similarity(REFERENCE_TEXT, &transcript.text),
+        realtime_factor: transcribe_ms as f32 / clip_ms as f32,
+        load_ms,
+        transcribe_ms,
+        transcript: transcript.text,
+    })
+}
+
+/// The bundled reference clip as 16kHz mono samples
+fn reference_clip() -> Result<Vec<f32>, String> {
+    read_wav(REFERENCE_CLIP)
+}
+
+/// Samples of a mono 16-bit PCM or 32-bit float WAV file at `SAMPLE_RATE`
+fn read_wav(bytes: &[u8]) -> Result<Vec<f32>, String> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("Reference clip is not a WAV file".to_string());
+    }
+
+    let u16_at = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+    let u32_at =
+        |at: usize| u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]);
+
+    // Walk the chunks: `fmt ` describes the samples in `data`
+    let mut format = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let id = &bytes[offset..offset + 4];
+        let len = u32_at(offset + 4) as usize;
+        let body = offset + 8;
+        let end = (body + len).min(bytes.len());
+
+        if id == b"fmt " && len >= 16 && body + 16 <= bytes.len() {
+            // Format tag, channels, sample rate and bits per sample
+            format = Some((
+                u16_at(body),
+                u16_at(body + 2),
+                u32_at(body + 4),
+                u16_at(body + 14),
+            ));
+        } else if id == b"data" {
+            // 1 is integer PCM and 3 IEEE float; 0xFFFE (extensible) can be either
+            let data = &bytes[body..end];
+            return match format {
+                Some((1 | 0xFFFE, 1, SAMPLE_RATE, 16)) => Ok(data
+                    .chunks_exact(2)
+                    .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+                    .collect()),
+                Some((3 | 0xFFFE, 1, SAMPLE_RATE, 32)) => Ok(data
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect()),
+                _ => Err("Reference clip is not 16kHz mono audio".to_string()),
+            };
+        }
+
+        // Chunks are padded to an even length
+        offset = body + len + len % 2;
+    }
+
+    Err("Reference clip has no audio".to_string())
+}
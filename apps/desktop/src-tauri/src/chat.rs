@@ -0,0 +1,248 @@
+use serde::Serialize;
+use specta::Type;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_specta::Event;
+use tracing::error;
+
+use crate::llm::{self, ChatMessage, Provider};
+use crate::output::paste_text;
+use crate::redaction::redact_if_enabled;
+
+/// Give up on a reply after this long
+const REQUEST_TIMEOUT_SECS: u64 = 60;
+
+/// Turns of history sent with each message; older ones are kept but not sent
+const MAX_CONTEXT_TURNS: usize = 20;
+
+const SYSTEM_PROMPT: &str = "You are a voice assistant. The user speaks their messages, \
+    so they may contain transcription mistakes; read them charitably. Answer concisely \
+    in plain text that can be pasted into any app.";
+
+/// Open chat sessions by ID. They live in memory until ended or the app quits.
+static SESSIONS: OnceLock<Mutex<HashMap<String, ChatSession>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    User,
+    Assistant,
+}
+
+impl ChatRole {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Assistant => "assistant",
+        }
+    }
+}
+
+/// A message of a chat session
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ChatTurn {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+#[derive(Default)]
+struct ChatSession {
+    turns: Vec<ChatTurn>,
+    /// Set while a reply is streaming; a session answers one message at a time
+    replying: bool,
+}
+
+/// Payload of `chat-delta`, emitted for each piece of a streaming reply
+#[derive(Debug, Clone, Serialize, Type, Event)]
+pub struct ChatDelta {
+    pub session_id: String,
+    pub delta: String,
+}
+
+/// Payload of `chat-complete`, emitted with the whole reply once it has streamed
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ChatReply {
+    pub session_id: String,
+    pub reply: String,
+}
+
+impl tauri_specta::Event for ChatReply {
+    const NAME: &'static str = "chat-complete";
+}
+
+/// Payload of `chat-error`, emitted when a reply fails
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ChatFailed {
+    pub session_id: String,
+    pub error: String,
+}
+
+impl tauri_specta::Event for ChatFailed {
+    const NAME: &'static str = "chat-error";
+}
+
+fn sessions() -> &'static Mutex<HashMap<String, ChatSession>> {
+    SESSIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Start a chat session and return its ID
+#[tauri::command]
+#[specta::specta]
+pub fn chat_start() -> String {
+    let id = uuid::Uuid::new_v4().to_string();
+    sessions()
+        .lock()
+        .unwrap()
+        .insert(id.clone(), ChatSession::default());
+    id
+}
+
+/// Messages of a chat session, oldest first
+#[tauri::command]
+#[specta::specta]
+pub fn chat_history(session_id: String) -> Result<Vec<ChatTurn>, String> {
+    sessions()
+        .lock()
+        .unwrap()
+        .get(&session_id)
+        .map(|session| session.turns.clone())
+        .ok_or_else(|| format!("Chat session {} not found", session_id))
+}
+
+/// Send a message to a chat session. The reply streams back as `chat-delta`
+/// events followed by `chat-complete`, or `chat-error` if it fails.
+#[tauri::command]
+#[specta::specta]
+pub fn chat_send(app: AppHandle, session_id: String, text: String) -> Result<(), String> {
+    send(app, session_id, text)
+}
+
+/// Paste the last reply of a chat session into the frontmost app
+#[tauri::command]
+#[specta::specta]
+pub fn chat_insert_answer(app: AppHandle, session_id: String) -> Result<(), String> {
+    let answer = sessions()
+        .lock()
+        .unwrap()
+        .get(&session_id)
+        .ok_or_else(|| format!("Chat session {} not found", session_id))?
+        .turns
+        .iter()
+        .rev()
+        .find(|turn| turn.role == ChatRole::Assistant)
+        .map(|turn| turn.content.clone())
+        .ok_or("No answer to insert yet")?;
+
+    paste_text(app, answer)
+}
+
+/// End a chat session and forget its history
+#[tauri::command]
+#[specta::specta]
+pub fn chat_end(session_id: String) {
+    sessions().lock().unwrap().remove(&session_id);
+}
+
+/// Add a message to a session and stream the reply in the background
+pub fn send(app: AppHandle, session_id: String, text: String) -> Result<(), String> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err("Message is empty".to_string());
+    }
+
+    let turns = {
+        let mut sessions = sessions().lock().unwrap();
+        let session = sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| format!("Chat session {} not found", session_id))?;
+        if session.replying {
+            return Err("Still answering the previous message".to_string());
+        }
+        session.replying = true;
+        session.turns.push(ChatTurn {
+            role: ChatRole::User,
+            content: text,
+        });
+        session.turns.clone()
+    };
+
+    tauri::async_runtime::spawn(async move {
+        let result = reply(&app, &session_id, &turns).await;
+
+        let mut sessions = sessions().lock().unwrap();
+        // Ended while the reply was streaming
+        let Some(session) = sessions.get_mut(&session_id) else {
+            return;
+        };
+        session.replying = false;
+
+        match result {
+            Ok(reply) => {
+                session.turns.push(ChatTurn {
+                    role: ChatRole::Assistant,
+                    content: reply.clone(),
+                });
+                let _ = ChatReply { session_id, reply }.emit(&app);
+            }
+            Err(error) => {
+                // Drop the unanswered message so sending it again doesn't repeat it
+                session.turns.pop();
+                error!("Chat reply failed: {}", error);
+                let _ = ChatFailed { session_id, error }.emit(&app);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Ask the LLM for the reply to the last turn, emitting it as it streams
+async fn reply(app: &AppHandle, session_id: &str, turns: &[ChatTurn]) -> Result<String, String> {
+    let provider = Provider::from_setting();
+    let api_key = provider.api_key()?;
+
+    // Mask sensitive spans locally. Only the newest message's spans are restored
+    // in the reply; streamed deltas still show the placeholders.
+    let context = &turns[turns.len().saturating_sub(MAX_CONTEXT_TURNS)..];
+    let redacted: Vec<_> = context
+        .iter()
+        .map(|turn| redact_if_enabled(&turn.content))
+        .collect();
+
+    let mut messages = vec![ChatMessage {
+        role: "system",
+        content: SYSTEM_PROMPT,
+    }];
+    messages.extend(
+        context
+            .iter()
+            .zip(&redacted)
+            .map(|(turn, redacted)| ChatMessage {
+                role: turn.role.as_str(),
+                content: &redacted.text,
+            }),
+    );
+
+    let reply = llm::stream(
+        provider,
+        &api_key,
+        messages,
+        0.7,
+        Duration::from_secs(REQUEST_TIMEOUT_SECS),
+        |delta| {
+            let _ = ChatDelta {
+                session_id: session_id.to_string(),
+                delta: delta.to_string(),
+            }
+            .emit(app);
+        },
+    )
+    .await?;
+
+    Ok(match redacted.last() {
+        Some(last) => last.restore(&reply),
+        None => reply,
+    })
+}
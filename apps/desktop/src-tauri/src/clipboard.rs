@@ -0,0 +1,24 @@
+use crate::platform;
+
+/// Replace the system clipboard contents with plain text
+pub fn copy_text(text: &str) -> Result<(), String> {
+    platform::text_inserter().copy(text, None)
+}
+
+/// Replace the system clipboard contents with text in both plain and HTML
+/// flavors; each app pastes the richest one it understands
+pub fn copy_rich_text(text: &str, html: &str) -> Result<(), String> {
+    // Without a charset, some apps read the HTML flavor as Latin-1
+    let html = format!("<meta charset=\"utf-8\">{}", html);
+    platform::text_inserter().copy(text, Some(&html))
+}
+
+/// Plain text currently on the system clipboard, if any
+pub fn read_text() -> Option<String> {
+    platform::text_inserter().read_clipboard()
+}
+
+/// Empty the system clipboard
+pub fn clear() {
+    platform::text_inserter().clear_clipboard();
+}
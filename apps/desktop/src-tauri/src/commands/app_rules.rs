@@ -0,0 +1,294 @@
+use crate::commands::audit_log::{record_audit, AuditAction, AuditEntity};
+use crate::commands::error::CommandError;
+use crate::commands::validation::{self, MAX_APP_NAME_CHARS};
+use crate::db::pool::{get_connection, write_transaction};
+use crate::events::{emit_delete_event, emit_entity_event, names as event_names};
+use rusqlite::{params, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+/// Characters typed per second when a rule doesn't set a rate
+const DEFAULT_TYPING_RATE: u32 = 50;
+
+/// Slowest and fastest typing rates a rule can set, in characters per second
+const MIN_TYPING_RATE: u32 = 5;
+const MAX_TYPING_RATE: u32 = 500;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// How dictated text is inserted into an app
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+pub enum OutputMethod {
+    /// Put the text on the clipboard and press Cmd+V
+    #[serde(rename = "paste")]
+    Paste,
+    /// Type the text character by character, for terminals and remote desktop
+    /// apps that ignore a programmatic Cmd+V
+    #[serde(rename = "type")]
+    Type,
+}
+
+impl OutputMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Paste => "paste",
+            Self::Type => "type",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "paste" => Some(Self::Paste),
+            "type" => Some(Self::Type),
+            _ => None,
+        }
+    }
+}
+
+/// How dictated text is inserted into one app
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AppRule {
+    pub id: String,
+    /// App name as shown in the menu bar, e.g. `Terminal`; matched ignoring case
+    pub app_name: String,
+    pub output_method: OutputMethod,
+    /// Characters per second when typing; `None` uses the default rate
+    pub typing_rate: Option<u32>,
+    pub enabled: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CreateAppRuleInput {
+    pub app_name: String,
+    pub output_method: OutputMethod,
+    pub typing_rate: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UpdateAppRuleInput {
+    pub app_name: Option<String>,
+    pub output_method: Option<OutputMethod>,
+    /// Zero clears the rate, going back to the default
+    pub typing_rate: Option<u32>,
+    pub enabled: Option<bool>,
+}
+
+const SELECT_COLUMNS: &str =
+    "SELECT id, app_name, output_method, typing_rate, enabled, created_at, updated_at FROM app_rules";
+
+fn row_to_rule(row: &Row) -> rusqlite::Result<AppRule> {
+    let output_method: String = row.get(2)?;
+    Ok(AppRule {
+        id: row.get(0)?,
+        app_name: row.get(1)?,
+        output_method: OutputMethod::parse(&output_method).unwrap_or(OutputMethod::Paste),
+        typing_rate: row.get(3)?,
+        enabled: row.get(4)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// List all app rules, by app name
+#[tauri::command]
+#[specta::specta]
+pub fn app_rules_list() -> Result<Vec<AppRule>, CommandError> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "{} ORDER BY app_name COLLATE NOCASE ASC",
+        SELECT_COLUMNS
+    ))?;
+    let rows = stmt.query_map([], row_to_rule)?;
+
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Get a single app rule by ID
+#[tauri::command]
+#[specta::specta]
+pub fn app_rules_get(id: String) -> Result<AppRule, CommandError> {
+    let conn = get_connection()?;
+
+    conn.query_row(
+        &format!("{} WHERE id = ?", SELECT_COLUMNS),
+        params![id],
+        row_to_rule,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => CommandError::not_found("AppRule", &id),
+        _ => CommandError::database(e.to_string()),
+    })
+}
+
+/// Create a new app rule
+#[tauri::command]
+#[specta::specta]
+pub fn app_rules_create(
+    app: AppHandle,
+    input: CreateAppRuleInput,
+) -> Result<AppRule, CommandError> {
+    let app_name = validation::required("App name", &input.app_name, MAX_APP_NAME_CHARS)?;
+    let typing_rate = validate_typing_rate(input.typing_rate)?;
+
+    let mut conn = get_connection()?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::AppRule, &id, AuditAction::Create, now)?;
+        tx.execute(
+            "INSERT INTO app_rules (id, app_name, output_method, typing_rate, enabled, created_at, updated_at) VALUES (?, ?, ?, ?, 1, ?, ?)",
+            params![id, app_name, input.output_method.as_str(), typing_rate, now, now],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| CommandError::from_write(e, "AppRule", "app_name", &app_name))?;
+
+    let created = AppRule {
+        id: id.clone(),
+        app_name,
+        output_method: input.output_method,
+        typing_rate,
+        enabled: true,
+        created_at: now,
+        updated_at: now,
+    };
+
+    emit_entity_event(&app, event_names::APP_RULES_CREATED, created.clone())?;
+
+    Ok(created)
+}
+
+/// Update an existing app rule
+#[tauri::command]
+#[specta::specta]
+pub fn app_rules_update(
+    app: AppHandle,
+    id: String,
+    input: UpdateAppRuleInput,
+) -> Result<AppRule, CommandError> {
+    // First verify it exists
+    let existing = app_rules_get(id.clone())?;
+
+    let new_app_name = match input.app_name {
+        Some(app_name) => validation::required("App name", &app_name, MAX_APP_NAME_CHARS)?,
+        None => existing.app_name,
+    };
+    let new_output_method = input.output_method.unwrap_or(existing.output_method);
+    let new_typing_rate = match input.typing_rate {
+        Some(0) => None,
+        Some(rate) => validate_typing_rate(Some(rate))?,
+        None => existing.typing_rate,
+    };
+    let new_enabled = input.enabled.unwrap_or(existing.enabled);
+
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::AppRule, &id, AuditAction::Update, now)?;
+        tx.execute(
+            "UPDATE app_rules SET app_name = ?, output_method = ?, typing_rate = ?, enabled = ?, updated_at = ? WHERE id = ?",
+            params![new_app_name, new_output_method.as_str(), new_typing_rate, new_enabled, now, id],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| CommandError::from_write(e, "AppRule", "app_name", &new_app_name))?;
+
+    let updated = AppRule {
+        id: id.clone(),
+        app_name: new_app_name,
+        output_method: new_output_method,
+        typing_rate: new_typing_rate,
+        enabled: new_enabled,
+        created_at: existing.created_at,
+        updated_at: now,
+    };
+
+    emit_entity_event(&app, event_names::APP_RULES_UPDATED, updated.clone())?;
+
+    Ok(updated)
+}
+
+/// Delete an app rule
+#[tauri::command]
+#[specta::specta]
+pub fn app_rules_delete(app: AppHandle, id: String) -> Result<(), CommandError> {
+    // Verify it exists first
+    app_rules_get(id.clone())?;
+
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::AppRule, &id, AuditAction::Delete, now)?;
+        tx.execute("DELETE FROM app_rules WHERE id = ?", params![id])?;
+        Ok(())
+    })?;
+
+    emit_delete_event(&app, event_names::APP_RULES_DELETED, id)?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// The enabled rule for an app, if any
+pub fn rule_for_app(app_name: &str) -> Option<AppRule> {
+    let conn = get_connection().ok()?;
+    conn.query_row(
+        &format!(
+            "{} WHERE app_name = ? COLLATE NOCASE AND enabled = 1",
+            SELECT_COLUMNS
+        ),
+        params![app_name],
+        row_to_rule,
+    )
+    .optional()
+    .ok()
+    .flatten()
+}
+
+/// Characters per second to type into an app at, or `None` when it is pasted into
+pub fn typing_rate_for(app_name: &str) -> Option<u32> {
+    rule_for_app(app_name)
+        .filter(|rule| rule.output_method == OutputMethod::Type)
+        .map(|rule| rule.typing_rate.unwrap_or(DEFAULT_TYPING_RATE))
+}
+
+fn validate_typing_rate(rate: Option<u32>) -> Result<Option<u32>, CommandError> {
+    match rate {
+        Some(rate) if !(MIN_TYPING_RATE..=MAX_TYPING_RATE).contains(&rate) => {
+            Err(CommandError::validation(format!(
+                "Typing rate must be between {} and {} characters per second",
+                MIN_TYPING_RATE, MAX_TYPING_RATE
+            )))
+        }
+        _ => Ok(rate),
+    }
+}
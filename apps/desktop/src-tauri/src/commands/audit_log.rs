@@ -0,0 +1,148 @@
+use crate::commands::error::CommandError;
+use crate::db::pool::get_connection;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Oldest entries are pruned once the log grows past this many rows
+const MAX_AUDIT_ENTRIES: i64 = 5000;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum AuditEntity {
+    #[serde(rename = "keyterm")]
+    Keyterm,
+    #[serde(rename = "shortcut")]
+    Shortcut,
+    #[serde(rename = "macro")]
+    Macro,
+    #[serde(rename = "note")]
+    Note,
+    #[serde(rename = "setting")]
+    Setting,
+    #[serde(rename = "writing_style")]
+    WritingStyle,
+    #[serde(rename = "url_rule")]
+    UrlRule,
+    #[serde(rename = "app_rule")]
+    AppRule,
+}
+
+impl AuditEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keyterm => "keyterm",
+            Self::Shortcut => "shortcut",
+            Self::Macro => "macro",
+            Self::Note => "note",
+            Self::Setting => "setting",
+            Self::WritingStyle => "writing_style",
+            Self::UrlRule => "url_rule",
+            Self::AppRule => "app_rule",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum AuditAction {
+    #[serde(rename = "create")]
+    Create,
+    #[serde(rename = "update")]
+    Update,
+    #[serde(rename = "delete")]
+    Delete,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// One recorded change to an entity
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub entity: String,
+    /// ID of the changed row (the key for settings, the category for writing styles)
+    pub entity_id: String,
+    pub action: String,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct AuditLogParams {
+    #[serde(default)]
+    pub entity: Option<AuditEntity>,
+    #[serde(default)]
+    pub entity_id: Option<String>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// List recorded entity changes, newest first
+#[tauri::command]
+#[specta::specta]
+pub fn audit_log_list(params: Option<AuditLogParams>) -> Result<Vec<AuditLogEntry>, CommandError> {
+    let conn = get_connection()?;
+    let params = params.unwrap_or_default();
+    let entity = params.entity.as_ref().map(AuditEntity::as_str);
+    let limit = params.limit.unwrap_or(100);
+
+    let mut stmt = conn.prepare(
+        "SELECT id, entity, entity_id, action, created_at FROM audit_log
+         WHERE (?1 IS NULL OR entity = ?1) AND (?2 IS NULL OR entity_id = ?2)
+         ORDER BY id DESC LIMIT ?3",
+    )?;
+
+    let entries = stmt
+        .query_map(params![entity, params.entity_id, limit], |row| {
+            Ok(AuditLogEntry {
+                id: row.get(0)?,
+                entity: row.get(1)?,
+                entity_id: row.get(2)?,
+                action: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(entries)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Record a change in the audit log. Call it inside the transaction that makes
+/// the change, so a change is never committed without its entry.
+pub fn record_audit(
+    conn: &Connection,
+    entity: AuditEntity,
+    entity_id: &str,
+    action: AuditAction,
+    now: i64,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO audit_log (entity, entity_id, action, created_at) VALUES (?, ?, ?, ?)",
+        params![entity.as_str(), entity_id, action.as_str(), now],
+    )?;
+
+    conn.execute(
+        "DELETE FROM audit_log WHERE id <= last_insert_rowid() - ?",
+        params![MAX_AUDIT_ENTRIES],
+    )?;
+
+    Ok(())
+}
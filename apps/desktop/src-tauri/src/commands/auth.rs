@@ -0,0 +1,72 @@
+use crate::commands::error::CommandError;
+use crate::keychain::{delete_secret, get_secret, set_secret, AUTH_TOKEN_ACCOUNT};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+use tracing::info;
+
+/// Store file the auth token used to be kept in (plaintext)
+const LEGACY_AUTH_STORE: &str = "auth.json";
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Get the signed-in user's auth token from the Keychain
+#[tauri::command]
+#[specta::specta]
+pub fn auth_get_token() -> Result<Option<String>, CommandError> {
+    Ok(get_secret(AUTH_TOKEN_ACCOUNT)?)
+}
+
+/// Save the auth token in the Keychain
+#[tauri::command]
+#[specta::specta]
+pub fn auth_set_token(token: String) -> Result<(), CommandError> {
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(CommandError::validation("Token cannot be empty"));
+    }
+
+    set_secret(AUTH_TOKEN_ACCOUNT, token)?;
+
+    Ok(())
+}
+
+/// Remove the auth token from the Keychain (sign out)
+#[tauri::command]
+#[specta::specta]
+pub fn auth_clear_token() -> Result<(), CommandError> {
+    delete_secret(AUTH_TOKEN_ACCOUNT)?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Move a token left in the plaintext store by older versions into the Keychain
+pub fn migrate_legacy_token(app: &AppHandle) -> Result<(), String> {
+    let store = app
+        .store(LEGACY_AUTH_STORE)
+        .map_err(|e| format!("Failed to open auth store: {}", e))?;
+
+    let token = match store
+        .get("token")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+    {
+        Some(token) => token,
+        None => return Ok(()),
+    };
+
+    set_secret(AUTH_TOKEN_ACCOUNT, &token)?;
+
+    store.delete("token");
+    store
+        .save()
+        .map_err(|e| format!("Failed to save auth store: {}", e))?;
+
+    info!("🔐 Migrated auth token to the Keychain");
+
+    Ok(())
+}
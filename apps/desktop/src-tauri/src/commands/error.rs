@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
+use crate::db::pool::is_unique_violation;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct CommandError {
     pub code: ErrorCode,
@@ -14,6 +16,9 @@ pub enum ErrorCode {
     ValidationError,
     DuplicateEntry,
     InvalidInput,
+    InsufficientDiskSpace,
+    PermissionDenied,
+    RecordingFailed,
 }
 
 impl CommandError {
@@ -45,12 +50,47 @@ impl CommandError {
         }
     }
 
+    /// `duplicate` when a write hit a unique index, otherwise a database error
+    pub fn from_write(err: rusqlite::Error, entity: &str, field: &str, value: &str) -> Self {
+        if is_unique_violation(&err) {
+            Self::duplicate(entity, field, value)
+        } else {
+            Self::database(err.to_string())
+        }
+    }
+
     pub fn invalid_input(message: impl Into<String>) -> Self {
         Self {
             code: ErrorCode::InvalidInput,
             message: message.into(),
         }
     }
+
+    pub fn insufficient_disk_space(required_bytes: u64, available_bytes: u64) -> Self {
+        const MB: u64 = 1024 * 1024;
+        Self {
+            code: ErrorCode::InsufficientDiskSpace,
+            message: format!(
+                "Not enough disk space: {} MB needed, {} MB available",
+                required_bytes.div_ceil(MB),
+                available_bytes / MB
+            ),
+        }
+    }
+
+    pub fn permission_denied(message: impl Into<String>) -> Self {
+        Self {
+            code: ErrorCode::PermissionDenied,
+            message: message.into(),
+        }
+    }
+
+    pub fn recording_failed(message: impl Into<String>) -> Self {
+        Self {
+            code: ErrorCode::RecordingFailed,
+            message: message.into(),
+        }
+    }
 }
 
 impl From<rusqlite::Error> for CommandError {
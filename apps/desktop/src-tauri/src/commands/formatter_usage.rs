@@ -0,0 +1,94 @@
+use crate::commands::error::CommandError;
+use crate::db::pool::get_connection;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FormatterUsageStats {
+    pub total_requests: i64,
+    pub failed_requests: i64,
+    pub total_characters: i64,
+    /// Mean latency of successful requests in milliseconds
+    pub average_latency_ms: f64,
+    /// Slowest successful request in milliseconds
+    pub max_latency_ms: i64,
+    /// Requests made in the last 7 days
+    pub requests_last_7_days: i64,
+}
+
+// ============================================================================
+// Pipeline helpers
+// ============================================================================
+
+/// Record one request to the hosted formatter
+pub fn record_formatter_usage(
+    characters_sent: usize,
+    latency_ms: u64,
+    error: Option<&str>,
+) -> Result<(), CommandError> {
+    let conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    conn.execute(
+        "INSERT INTO formatter_usage (characters_sent, latency_ms, success, error, created_at)
+         VALUES (?, ?, ?, ?, ?)",
+        params![
+            characters_sent as i64,
+            latency_ms as i64,
+            error.is_none(),
+            error,
+            now
+        ],
+    )?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Get usage statistics for the hosted formatter
+#[tauri::command]
+#[specta::specta]
+pub fn formatter_usage_stats() -> Result<FormatterUsageStats, CommandError> {
+    let conn = get_connection()?;
+
+    let week_ago = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+        - 7 * 24 * 60 * 60;
+
+    conn.query_row(
+        "SELECT COUNT(*),
+                COALESCE(SUM(CASE WHEN success = 0 THEN 1 ELSE 0 END), 0),
+                COALESCE(SUM(characters_sent), 0),
+                COALESCE(AVG(CASE WHEN success = 1 THEN latency_ms END), 0.0),
+                COALESCE(MAX(CASE WHEN success = 1 THEN latency_ms END), 0),
+                COALESCE(SUM(CASE WHEN created_at >= ? THEN 1 ELSE 0 END), 0)
+         FROM formatter_usage",
+        params![week_ago],
+        |row| {
+            Ok(FormatterUsageStats {
+                total_requests: row.get(0)?,
+                failed_requests: row.get(1)?,
+                total_characters: row.get(2)?,
+                average_latency_ms: row.get(3)?,
+                max_latency_ms: row.get(4)?,
+                requests_last_7_days: row.get(5)?,
+            })
+        },
+    )
+    .map_err(CommandError::from)
+}
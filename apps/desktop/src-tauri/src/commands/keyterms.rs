@@ -1,5 +1,8 @@
+use crate::commands::audit_log::{record_audit, AuditAction, AuditEntity};
 use crate::commands::error::CommandError;
-use crate::db::pool::get_connection;
+use crate::commands::validation::{self, MAX_KEYTERM_CHARS};
+use crate::corrections::MIN_CORRECTION_COUNT;
+use crate::db::pool::{get_connection, write_transaction};
 use crate::events::{emit_delete_event, emit_entity_event, names as event_names};
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
@@ -44,6 +47,18 @@ pub struct Keyterm {
     pub category: String,
     pub created_at: i64,
     pub updated_at: i64,
+    /// Clashes with an older entry of the same category. Set when uniqueness
+    /// started being enforced; cleared once the entry is edited.
+    #[serde(default)]
+    pub is_duplicate: bool,
+}
+
+/// A word the user keeps correcting transcripts to, not yet in the dictionary
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct KeytermSuggestion {
+    pub text: String,
+    /// How many times the user has corrected a transcript to this text
+    pub count: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -71,7 +86,7 @@ pub fn keyterms_list(category: Option<KeytermCategory>) -> Result<Vec<Keyterm>,
     let keyterms = match &category {
         Some(cat) => {
             let mut stmt = conn.prepare(
-                "SELECT id, text, category, created_at, updated_at FROM keyterms WHERE category = ? ORDER BY created_at DESC",
+                "SELECT id, text, category, created_at, updated_at, is_duplicate FROM keyterms WHERE category = ? ORDER BY created_at DESC",
             )?;
             let cat_str = cat.as_str();
             let rows = stmt.query_map(params![cat_str], |row| {
@@ -81,13 +96,14 @@ pub fn keyterms_list(category: Option<KeytermCategory>) -> Result<Vec<Keyterm>,
                     category: row.get(2)?,
                     created_at: row.get(3)?,
                     updated_at: row.get(4)?,
+                    is_duplicate: row.get(5)?,
                 })
             })?;
             rows.collect::<Result<Vec<_>, _>>()?
         }
         None => {
             let mut stmt = conn.prepare(
-                "SELECT id, text, category, created_at, updated_at FROM keyterms ORDER BY created_at DESC",
+                "SELECT id, text, category, created_at, updated_at, is_duplicate FROM keyterms ORDER BY created_at DESC",
             )?;
             let rows = stmt.query_map([], |row| {
                 Ok(Keyterm {
@@ -96,6 +112,7 @@ pub fn keyterms_list(category: Option<KeytermCategory>) -> Result<Vec<Keyterm>,
                     category: row.get(2)?,
                     created_at: row.get(3)?,
                     updated_at: row.get(4)?,
+                    is_duplicate: row.get(5)?,
                 })
             })?;
             rows.collect::<Result<Vec<_>, _>>()?
@@ -112,7 +129,7 @@ pub fn keyterms_get(id: String) -> Result<Keyterm, CommandError> {
     let conn = get_connection()?;
 
     conn.query_row(
-        "SELECT id, text, category, created_at, updated_at FROM keyterms WHERE id = ?",
+        "SELECT id, text, category, created_at, updated_at, is_duplicate FROM keyterms WHERE id = ?",
         params![id],
         |row| {
             Ok(Keyterm {
@@ -121,6 +138,7 @@ pub fn keyterms_get(id: String) -> Result<Keyterm, CommandError> {
                 category: row.get(2)?,
                 created_at: row.get(3)?,
                 updated_at: row.get(4)?,
+                is_duplicate: row.get(5)?,
             })
         },
     )
@@ -134,7 +152,9 @@ pub fn keyterms_get(id: String) -> Result<Keyterm, CommandError> {
 #[tauri::command]
 #[specta::specta]
 pub fn keyterms_create(app: AppHandle, input: CreateKeytermInput) -> Result<Keyterm, CommandError> {
-    let conn = get_connection()?;
+    let text = validation::required("Keyterm", &input.text, MAX_KEYTERM_CHARS)?;
+
+    let mut conn = get_connection()?;
 
     let id = uuid::Uuid::new_v4().to_string();
     let now = SystemTime::now()
@@ -142,17 +162,23 @@ pub fn keyterms_create(app: AppHandle, input: CreateKeytermInput) -> Result<Keyt
         .unwrap()
         .as_secs() as i64;
 
-    conn.execute(
-        "INSERT INTO keyterms (id, text, category, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
-        params![id, input.text, input.category.as_str(), now, now],
-    )?;
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Keyterm, &id, AuditAction::Create, now)?;
+        tx.execute(
+            "INSERT INTO keyterms (id, text, category, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+            params![id, text, input.category.as_str(), now, now],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| CommandError::from_write(e, "Keyterm", "text", &text))?;
 
     let keyterm = Keyterm {
         id: id.clone(),
-        text: input.text,
+        text,
         category: input.category.as_str().to_string(),
         created_at: now,
         updated_at: now,
+        is_duplicate: false,
     };
 
     emit_entity_event(&app, event_names::KEYTERMS_CREATED, keyterm.clone())?;
@@ -171,23 +197,31 @@ pub fn keyterms_update(
     // First verify it exists
     let existing = keyterms_get(id.clone())?;
 
-    let conn = get_connection()?;
+    let mut conn = get_connection()?;
 
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs() as i64;
 
-    let new_text = input.text.unwrap_or(existing.text);
+    let new_text = match input.text {
+        Some(text) => validation::required("Keyterm", &text, MAX_KEYTERM_CHARS)?,
+        None => existing.text,
+    };
     let new_category = input
         .category
         .map(|c| c.as_str().to_string())
         .unwrap_or(existing.category);
 
-    conn.execute(
-        "UPDATE keyterms SET text = ?, category = ?, updated_at = ? WHERE id = ?",
-        params![new_text, new_category, now, id],
-    )?;
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Keyterm, &id, AuditAction::Update, now)?;
+        tx.execute(
+            "UPDATE keyterms SET text = ?, category = ?, updated_at = ?, is_duplicate = 0 WHERE id = ?",
+            params![new_text, new_category, now, id],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| CommandError::from_write(e, "Keyterm", "text", &new_text))?;
 
     let updated = Keyterm {
         id: id.clone(),
@@ -195,6 +229,7 @@ pub fn keyterms_update(
         category: new_category,
         created_at: existing.created_at,
         updated_at: now,
+        is_duplicate: false,
     };
 
     emit_entity_event(&app, event_names::KEYTERMS_UPDATED, updated.clone())?;
@@ -209,11 +244,127 @@ pub fn keyterms_delete(app: AppHandle, id: String) -> Result<(), CommandError> {
     // Verify it exists first
     keyterms_get(id.clone())?;
 
-    let conn = get_connection()?;
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
 
-    conn.execute("DELETE FROM keyterms WHERE id = ?", params![id])?;
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Keyterm, &id, AuditAction::Delete, now)?;
+        tx.execute("DELETE FROM keyterms WHERE id = ?", params![id])?;
+        Ok(())
+    })?;
 
     emit_delete_event(&app, event_names::KEYTERMS_DELETED, id)?;
 
     Ok(())
 }
+
+/// Suggest keyterms from correction history: words the user repeatedly fixes
+/// transcripts to that aren't keyterms yet, most corrected first
+#[tauri::command]
+#[specta::specta]
+pub fn keyterms_suggestions() -> Result<Vec<KeytermSuggestion>, CommandError> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT corrected, SUM(count) AS total FROM corrections
+         WHERE LOWER(corrected) NOT IN (SELECT LOWER(text) FROM keyterms)
+         GROUP BY corrected
+         HAVING total >= ?
+         ORDER BY total DESC, MAX(last_seen_at) DESC
+         LIMIT 20",
+    )?;
+
+    let suggestions = stmt
+        .query_map(params![MIN_CORRECTION_COUNT], |row| {
+            Ok(KeytermSuggestion {
+                text: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(suggestions)
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Keyterm texts that apply to an app category: the category's own terms plus
+/// the ones saved for "all". Without a category only the "all" terms apply.
+/// Most used terms come first, then the most recently used or added.
+pub fn keyterms_for_category(category: Option<&str>) -> Result<Vec<String>, CommandError> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT text FROM keyterms WHERE category = ? OR category = ?
+         ORDER BY usage_count DESC, COALESCE(last_used_at, created_at) DESC",
+    )?;
+    let all = KeytermCategory::All.as_str();
+    let texts = stmt
+        .query_map(params![all, category.unwrap_or(all)], |row| {
+            row.get::<_, String>(0)
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(texts)
+}
+
+/// Bump the usage count of every keyterm that appears in a transcript as whole
+/// words (case-insensitive), so frequently dictated terms rank first next time
+pub fn record_keyterm_usage(transcript: &str) -> Result<usize, CommandError> {
+    let mut conn = get_connection()?;
+
+    let transcript = transcript.to_lowercase();
+    let mut stmt = conn.prepare("SELECT id, text FROM keyterms")?;
+    let used: Vec<String> = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|(_, text)| contains_words(&transcript, &text.trim().to_lowercase()))
+        .map(|(id, _)| id)
+        .collect();
+    drop(stmt);
+
+    if used.is_empty() {
+        return Ok(0);
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        let mut update = tx.prepare_cached(
+            "UPDATE keyterms SET usage_count = usage_count + 1, last_used_at = ? WHERE id = ?",
+        )?;
+        for id in &used {
+            update.execute(params![now, id])?;
+        }
+        Ok(())
+    })?;
+
+    Ok(used.len())
+}
+
+/// Whether `term` occurs in `text` with no letter or digit right before or after
+/// it, so "go" isn't counted in "google". Both are expected in lowercase.
+fn contains_words(text: &str, term: &str) -> bool {
+    if term.is_empty() {
+        return false;
+    }
+
+    text.match_indices(term).any(|(start, _)| {
+        let end = start + term.len();
+        let before = text[..start].chars().next_back();
+        let after = text[end..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
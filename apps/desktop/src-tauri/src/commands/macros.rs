@@ -0,0 +1,233 @@
+use crate::commands::audit_log::{record_audit, AuditAction, AuditEntity};
+use crate::commands::error::CommandError;
+use crate::commands::validation::{self, MAX_REPLACEMENT_CHARS, MAX_TRIGGER_CHARS};
+use crate::db::pool::{get_connection, write_transaction};
+use crate::events::{emit_delete_event, emit_entity_event, names as event_names};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A spoken trigger phrase expanded into a template when dictated
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Macro {
+    pub id: String,
+    /// Phrase that triggers the macro, e.g. "insert signature"
+    pub trigger: String,
+    /// Text pasted in place of the trigger; may span several lines and contain
+    /// `{date}`, `{time}` and `{clipboard}` placeholders
+    pub template: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CreateMacroInput {
+    pub trigger: String,
+    pub template: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UpdateMacroInput {
+    pub trigger: Option<String>,
+    pub template: Option<String>,
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// List all macros
+#[tauri::command]
+#[specta::specta]
+pub fn macros_list() -> Result<Vec<Macro>, CommandError> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, trigger, template, created_at, updated_at FROM macros ORDER BY created_at DESC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(Macro {
+            id: row.get(0)?,
+            trigger: row.get(1)?,
+            template: row.get(2)?,
+            created_at: row.get(3)?,
+            updated_at: row.get(4)?,
+        })
+    })?;
+
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Get a single macro by ID
+#[tauri::command]
+#[specta::specta]
+pub fn macros_get(id: String) -> Result<Macro, CommandError> {
+    let conn = get_connection()?;
+
+    conn.query_row(
+        "SELECT id, trigger, template, created_at, updated_at FROM macros WHERE id = ?",
+        params![id],
+        |row| {
+            Ok(Macro {
+                id: row.get(0)?,
+                trigger: row.get(1)?,
+                template: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        },
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => CommandError::not_found("Macro", &id),
+        _ => CommandError::database(e.to_string()),
+    })
+}
+
+/// Create a new macro
+#[tauri::command]
+#[specta::specta]
+pub fn macros_create(app: AppHandle, input: CreateMacroInput) -> Result<Macro, CommandError> {
+    let trigger = validate_trigger(&input.trigger, None)?;
+    let template = validation::required("Template", &input.template, MAX_REPLACEMENT_CHARS)?;
+
+    let mut conn = get_connection()?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Macro, &id, AuditAction::Create, now)?;
+        tx.execute(
+            "INSERT INTO macros (id, trigger, template, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+            params![id, trigger, template, now, now],
+        )?;
+        Ok(())
+    })?;
+
+    let created = Macro {
+        id: id.clone(),
+        trigger,
+        template,
+        created_at: now,
+        updated_at: now,
+    };
+
+    emit_entity_event(&app, event_names::MACROS_CREATED, created.clone())?;
+
+    Ok(created)
+}
+
+/// Update an existing macro
+#[tauri::command]
+#[specta::specta]
+pub fn macros_update(
+    app: AppHandle,
+    id: String,
+    input: UpdateMacroInput,
+) -> Result<Macro, CommandError> {
+    // First verify it exists
+    let existing = macros_get(id.clone())?;
+
+    let new_trigger = match input.trigger {
+        Some(trigger) => validate_trigger(&trigger, Some(&id))?,
+        None => existing.trigger,
+    };
+    let new_template = match input.template {
+        Some(template) => validation::required("Template", &template, MAX_REPLACEMENT_CHARS)?,
+        None => existing.template,
+    };
+
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Macro, &id, AuditAction::Update, now)?;
+        tx.execute(
+            "UPDATE macros SET trigger = ?, template = ?, updated_at = ? WHERE id = ?",
+            params![new_trigger, new_template, now, id],
+        )?;
+        Ok(())
+    })?;
+
+    let updated = Macro {
+        id: id.clone(),
+        trigger: new_trigger,
+        template: new_template,
+        created_at: existing.created_at,
+        updated_at: now,
+    };
+
+    emit_entity_event(&app, event_names::MACROS_UPDATED, updated.clone())?;
+
+    Ok(updated)
+}
+
+/// Delete a macro
+#[tauri::command]
+#[specta::specta]
+pub fn macros_delete(app: AppHandle, id: String) -> Result<(), CommandError> {
+    // Verify it exists first
+    macros_get(id.clone())?;
+
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Macro, &id, AuditAction::Delete, now)?;
+        tx.execute("DELETE FROM macros WHERE id = ?", params![id])?;
+        Ok(())
+    })?;
+
+    emit_delete_event(&app, event_names::MACROS_DELETED, id)?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Trim a trigger and make sure no other macro already uses it (ignoring case,
+/// since spoken triggers are matched case-insensitively)
+fn validate_trigger(trigger: &str, id: Option<&str>) -> Result<String, CommandError> {
+    let trigger = trigger.trim();
+    validation::max_length("Trigger", trigger, MAX_TRIGGER_CHARS)?;
+    if !trigger.chars().any(char::is_alphanumeric) {
+        return Err(CommandError::validation(
+            "Macro trigger must contain at least one word",
+        ));
+    }
+
+    let conn = get_connection()?;
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT id FROM macros WHERE trigger = ? COLLATE NOCASE",
+            params![trigger],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    match existing {
+        Some(existing_id) if Some(existing_id.as_str()) != id => {
+            Err(CommandError::duplicate("Macro", "trigger", trigger))
+        }
+        _ => Ok(trigger.to_string()),
+    }
+}
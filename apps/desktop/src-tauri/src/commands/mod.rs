@@ -1,8 +1,20 @@
+pub mod app_rules;
+pub mod audit_log;
+pub mod auth;
 pub mod error;
+pub mod formatter_usage;
 pub mod keys_vault;
 pub mod keyterms;
+pub mod macros;
 pub mod notes;
+pub mod seed;
+pub mod sessions;
 pub mod settings;
 pub mod shortcuts;
+pub mod sync;
+#[cfg(any(test, feature = "test-harness"))]
+pub mod testing;
 pub mod transcriptions;
+pub mod url_rules;
+pub mod validation;
 pub mod writing_styles;
@@ -1,11 +1,20 @@
+use crate::commands::audit_log::{record_audit, AuditAction, AuditEntity};
 use crate::commands::error::CommandError;
-use crate::db::pool::get_connection;
+use crate::db::pool::{get_connection, write_transaction};
 use crate::events::{emit_delete_event, emit_entity_event, names as event_names};
-use rusqlite::params;
+use crate::mirror;
+use rusqlite::{params, Connection, Row};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
+use tracing::error;
+
+/// Folder in app data holding a folder of attached files per note
+const ATTACHMENTS_DIR: &str = "attachments";
 
 // ============================================================================
 // Types
@@ -18,6 +27,28 @@ pub struct Note {
     pub content: String,
     pub created_at: i64,
     pub updated_at: i64,
+    /// Pinned notes are listed first
+    #[serde(default)]
+    pub is_pinned: bool,
+    /// Archived notes are kept but hidden from the main list
+    #[serde(default)]
+    pub archived: bool,
+    /// Files attached to the note, oldest first. They stay on this device.
+    #[serde(default)]
+    pub attachments: Vec<NoteAttachment>,
+}
+
+/// A file attached to a note, such as the recording of a meeting
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NoteAttachment {
+    pub id: String,
+    pub note_id: String,
+    /// Name of the file as attached
+    pub file_name: String,
+    /// Where the copy in app data lives
+    pub path: String,
+    pub size_bytes: i64,
+    pub created_at: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -32,32 +63,128 @@ pub struct UpdateNoteInput {
     pub content: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum NoteSort {
+    /// Most recently edited first
+    #[serde(rename = "updated")]
+    Updated,
+    /// Newest first
+    #[serde(rename = "created")]
+    Created,
+    /// Alphabetical, ignoring case
+    #[serde(rename = "title")]
+    Title,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ListNotesParams {
+    /// Order within the pinned and unpinned groups; newest first when unset
+    #[serde(default)]
+    pub sort: Option<NoteSort>,
+    /// Only archived notes when true, only the others when false; both when unset
+    #[serde(default)]
+    pub archived: Option<bool>,
+}
+
+/// A previous version of a note
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NoteRevision {
+    pub note_id: String,
+    /// Counts up from 1 per note
+    pub revision: i64,
+    pub title: String,
+    pub content: String,
+    /// When this version was written, i.e. the note's `updated_at` at the time
+    pub saved_at: i64,
+}
+
+/// Revisions kept per note; the oldest are dropped beyond this
+const MAX_REVISIONS_PER_NOTE: i64 = 50;
+
+const SELECT_COLUMNS: &str =
+    "SELECT id, title, content, created_at, updated_at, is_pinned, archived FROM notes";
+
+fn row_to_note(row: &Row) -> rusqlite::Result<Note> {
+    Ok(Note {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        content: row.get(2)?,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+        is_pinned: row.get(5)?,
+        archived: row.get(6)?,
+        attachments: Vec::new(),
+    })
+}
+
+const SELECT_ATTACHMENT_COLUMNS: &str =
+    "SELECT id, note_id, file_name, path, size_bytes, created_at FROM note_attachments";
+
+fn row_to_attachment(row: &Row) -> rusqlite::Result<NoteAttachment> {
+    Ok(NoteAttachment {
+        id: row.get(0)?,
+        note_id: row.get(1)?,
+        file_name: row.get(2)?,
+        path: row.get(3)?,
+        size_bytes: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+fn row_to_revision(row: &Row) -> rusqlite::Result<NoteRevision> {
+    Ok(NoteRevision {
+        note_id: row.get(0)?,
+        revision: row.get(1)?,
+        title: row.get(2)?,
+        content: row.get(3)?,
+        saved_at: row.get(4)?,
+    })
+}
+
 // ============================================================================
 // Commands
 // ============================================================================
 
-/// List all notes
+/// List notes, pinned ones first
 #[tauri::command]
 #[specta::specta]
-pub fn notes_list() -> Result<Vec<Note>, CommandError> {
+pub fn notes_list(params: Option<ListNotesParams>) -> Result<Vec<Note>, CommandError> {
     let conn = get_connection()?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, title, content, created_at, updated_at FROM notes ORDER BY created_at DESC",
-    )?;
+    let params = params.unwrap_or_default();
+    let order_by = match params.sort {
+        Some(NoteSort::Updated) => "updated_at DESC",
+        Some(NoteSort::Title) => "title COLLATE NOCASE ASC, created_at DESC",
+        Some(NoteSort::Created) | None => "created_at DESC",
+    };
+
+    // An unset filter binds NULL and matches every row
+    let mut stmt = conn.prepare(&format!(
+        "{} WHERE (?1 IS NULL OR archived = ?1) ORDER BY is_pinned DESC, {}",
+        SELECT_COLUMNS, order_by
+    ))?;
 
-    let notes = stmt
-        .query_map([], |row| {
-            Ok(Note {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                content: row.get(2)?,
-                created_at: row.get(3)?,
-                updated_at: row.get(4)?,
-            })
-        })?
+    let mut notes = stmt
+        .query_map(params![params.archived], row_to_note)?
         .collect::<Result<Vec<_>, _>>()?;
 
+    // One query for every note's attachments rather than one per note
+    let mut stmt = conn.prepare(&format!(
+        "{} ORDER BY created_at ASC",
+        SELECT_ATTACHMENT_COLUMNS
+    ))?;
+    let mut attachments: HashMap<String, Vec<NoteAttachment>> = HashMap::new();
+    for attachment in stmt.query_map([], row_to_attachment)? {
+        let attachment = attachment?;
+        attachments
+            .entry(attachment.note_id.clone())
+            .or_default()
+            .push(attachment);
+    }
+    for note in &mut notes {
+        note.attachments = attachments.remove(&note.id).unwrap_or_default();
+    }
+
     Ok(notes)
 }
 
@@ -67,30 +194,26 @@ pub fn notes_list() -> Result<Vec<Note>, CommandError> {
 pub fn notes_get(id: String) -> Result<Note, CommandError> {
     let conn = get_connection()?;
 
-    conn.query_row(
-        "SELECT id, title, content, created_at, updated_at FROM notes WHERE id = ?",
-        params![id],
-        |row| {
-            Ok(Note {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                content: row.get(2)?,
-                created_at: row.get(3)?,
-                updated_at: row.get(4)?,
-            })
-        },
-    )
-    .map_err(|e| match e {
-        rusqlite::Error::QueryReturnedNoRows => CommandError::not_found("Note", &id),
-        _ => CommandError::database(e.to_string()),
-    })
+    let mut note = conn
+        .query_row(
+            &format!("{} WHERE id = ?", SELECT_COLUMNS),
+            params![id],
+            row_to_note,
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => CommandError::not_found("Note", &id),
+            _ => CommandError::database(e.to_string()),
+        })?;
+    note.attachments = attachments_of(&conn, &id)?;
+
+    Ok(note)
 }
 
 /// Create a new note
 #[tauri::command]
 #[specta::specta]
 pub fn notes_create(app: AppHandle, input: CreateNoteInput) -> Result<Note, CommandError> {
-    let conn = get_connection()?;
+    let mut conn = get_connection()?;
 
     let id = uuid::Uuid::new_v4().to_string();
     let now = SystemTime::now()
@@ -98,10 +221,14 @@ pub fn notes_create(app: AppHandle, input: CreateNoteInput) -> Result<Note, Comm
         .unwrap()
         .as_secs() as i64;
 
-    conn.execute(
-        "INSERT INTO notes (id, title, content, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
-        params![id, input.title, input.content, now, now],
-    )?;
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Note, &id, AuditAction::Create, now)?;
+        tx.execute(
+            "INSERT INTO notes (id, title, content, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+            params![id, input.title, input.content, now, now],
+        )?;
+        Ok(())
+    })?;
 
     let note = Note {
         id: id.clone(),
@@ -109,8 +236,12 @@ pub fn notes_create(app: AppHandle, input: CreateNoteInput) -> Result<Note, Comm
         content: input.content,
         created_at: now,
         updated_at: now,
+        is_pinned: false,
+        archived: false,
+        attachments: Vec::new(),
     };
 
+    mirror::mirror_note(&note, None);
     emit_entity_event(&app, event_names::NOTES_CREATED, note.clone())?;
 
     Ok(note)
@@ -127,20 +258,38 @@ pub fn notes_update(
     // First verify it exists
     let existing = notes_get(id.clone())?;
 
-    let conn = get_connection()?;
+    let mut conn = get_connection()?;
 
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs() as i64;
 
-    let new_title = input.title.unwrap_or(existing.title);
-    let new_content = input.content.unwrap_or(existing.content);
+    let new_title = input.title.unwrap_or_else(|| existing.title.clone());
+    let new_content = input.content.unwrap_or_else(|| existing.content.clone());
 
-    conn.execute(
-        "UPDATE notes SET title = ?, content = ?, updated_at = ? WHERE id = ?",
-        params![new_title, new_content, now, id],
-    )?;
+    let changed = new_title != existing.title || new_content != existing.content;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Note, &id, AuditAction::Update, now)?;
+        // Keep the version being overwritten
+        if changed {
+            tx.execute(
+                "INSERT INTO note_revisions (note_id, revision, title, content, saved_at)
+                 SELECT ?1, COALESCE(MAX(revision), 0) + 1, ?2, ?3, ?4 FROM note_revisions WHERE note_id = ?1",
+                params![id, existing.title, existing.content, existing.updated_at],
+            )?;
+            tx.execute(
+                "DELETE FROM note_revisions WHERE note_id = ?1 AND revision <= (SELECT MAX(revision) FROM note_revisions WHERE note_id = ?1) - ?2",
+                params![id, MAX_REVISIONS_PER_NOTE],
+            )?;
+        }
+        tx.execute(
+            "UPDATE notes SET title = ?, content = ?, updated_at = ? WHERE id = ?",
+            params![new_title, new_content, now, id],
+        )?;
+        Ok(())
+    })?;
 
     let updated = Note {
         id: id.clone(),
@@ -148,8 +297,12 @@ pub fn notes_update(
         content: new_content,
         created_at: existing.created_at,
         updated_at: now,
+        is_pinned: existing.is_pinned,
+        archived: existing.archived,
+        attachments: existing.attachments.clone(),
     };
 
+    mirror::mirror_note(&updated, Some(&existing));
     emit_entity_event(&app, event_names::NOTES_UPDATED, updated.clone())?;
 
     Ok(updated)
@@ -159,14 +312,364 @@ pub fn notes_update(
 #[tauri::command]
 #[specta::specta]
 pub fn notes_delete(app: AppHandle, id: String) -> Result<(), CommandError> {
+    // Verify it exists first
+    let existing = notes_get(id.clone())?;
+
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Note, &id, AuditAction::Delete, now)?;
+        delete_note_rows(tx, &id)
+    })?;
+
+    // The attachment files go with their rows
+    if !existing.attachments.is_empty() {
+        if let Ok(dir) = note_attachments_dir(&app, &id) {
+            if let Err(e) = fs::remove_dir_all(&dir) {
+                error!("Failed to remove attachments of note {}: {}", id, e);
+            }
+        }
+    }
+
+    mirror::remove_note(&existing);
+    emit_delete_event(&app, event_names::NOTES_DELETED, id)?;
+
+    Ok(())
+}
+
+/// Pin or unpin a note so it stays on top of the list
+#[tauri::command]
+#[specta::specta]
+pub fn notes_set_pinned(app: AppHandle, id: String, pinned: bool) -> Result<Note, CommandError> {
+    let existing = notes_get(id.clone())?;
+
+    set_flag(&id, "is_pinned", pinned)?;
+
+    let updated = Note {
+        is_pinned: pinned,
+        ..existing
+    };
+
+    emit_entity_event(&app, event_names::NOTES_UPDATED, updated.clone())?;
+
+    Ok(updated)
+}
+
+/// Archive a note to hide it from the main list, or bring it back
+#[tauri::command]
+#[specta::specta]
+pub fn notes_set_archived(
+    app: AppHandle,
+    id: String,
+    archived: bool,
+) -> Result<Note, CommandError> {
+    let existing = notes_get(id.clone())?;
+
+    set_flag(&id, "archived", archived)?;
+
+    let updated = Note {
+        archived,
+        ..existing
+    };
+
+    emit_entity_event(&app, event_names::NOTES_UPDATED, updated.clone())?;
+
+    Ok(updated)
+}
+
+/// Attach a file to a note. The file is copied into app data, so the original
+/// can be moved or deleted.
+#[tauri::command]
+#[specta::specta]
+pub fn notes_add_attachment(
+    app: AppHandle,
+    id: String,
+    source_path: String,
+) -> Result<NoteAttachment, CommandError> {
+    // Verify it exists first
+    notes_get(id.clone())?;
+
+    let source = Path::new(&source_path);
+    if !source.is_file() {
+        return Err(CommandError::invalid_input(format!(
+            "'{}' is not a file",
+            source_path
+        )));
+    }
+    let file_name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| CommandError::invalid_input("The file has no name"))?;
+
+    let attachment_id = uuid::Uuid::new_v4().to_string();
+    let dir = note_attachments_dir(&app, &id)?;
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create the attachments folder: {}", e))?;
+
+    // Stored under the attachment ID so two files with the same name don't collide
+    let path = match source.extension() {
+        Some(extension) => dir.join(format!("{}.{}", attachment_id, extension.to_string_lossy())),
+        None => dir.join(&attachment_id),
+    };
+    let size_bytes = fs::copy(source, &path)
+        .map_err(|e| format!("Failed to copy '{}': {}", file_name, e))? as i64;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    let attachment = NoteAttachment {
+        id: attachment_id,
+        note_id: id.clone(),
+        file_name,
+        path: path.to_string_lossy().to_string(),
+        size_bytes,
+        created_at: now,
+    };
+
+    let mut conn = get_connection()?;
+    let result = write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Note, &id, AuditAction::Update, now)?;
+        tx.execute(
+            "INSERT INTO note_attachments (id, note_id, file_name, path, size_bytes, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+            params![
+                attachment.id,
+                attachment.note_id,
+                attachment.file_name,
+                attachment.path,
+                attachment.size_bytes,
+                attachment.created_at
+            ],
+        )?;
+        Ok(())
+    });
+    if let Err(e) = result {
+        let _ = fs::remove_file(&path);
+        return Err(e);
+    }
+    drop(conn);
+
+    emit_entity_event(&app, event_names::NOTES_UPDATED, notes_get(id)?)?;
+
+    Ok(attachment)
+}
+
+/// Remove a file from its note and delete the copy in app data
+#[tauri::command]
+#[specta::specta]
+pub fn notes_remove_attachment(app: AppHandle, attachment_id: String) -> Result<(), CommandError> {
+    let mut conn = get_connection()?;
+
+    let attachment = conn
+        .query_row(
+            &format!("{} WHERE id = ?", SELECT_ATTACHMENT_COLUMNS),
+            params![attachment_id],
+            row_to_attachment,
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                CommandError::not_found("NoteAttachment", &attachment_id)
+            }
+            _ => CommandError::database(e.to_string()),
+        })?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(
+            tx,
+            AuditEntity::Note,
+            &attachment.note_id,
+            AuditAction::Update,
+            now,
+        )?;
+        tx.execute(
+            "DELETE FROM note_attachments WHERE id = ?",
+            params![attachment_id],
+        )?;
+        Ok(())
+    })?;
+    drop(conn);
+
+    if let Err(e) = fs::remove_file(&attachment.path) {
+        error!("Failed to remove attachment {}: {}", attachment.path, e);
+    }
+
+    emit_entity_event(
+        &app,
+        event_names::NOTES_UPDATED,
+        notes_get(attachment.note_id)?,
+    )?;
+
+    Ok(())
+}
+
+/// List the previous versions of a note, newest first
+#[tauri::command]
+#[specta::specta]
+pub fn notes_revisions_list(id: String) -> Result<Vec<NoteRevision>, CommandError> {
     // Verify it exists first
     notes_get(id.clone())?;
 
     let conn = get_connection()?;
 
+    let mut stmt = conn.prepare(
+        "SELECT note_id, revision, title, content, saved_at FROM note_revisions WHERE note_id = ? ORDER BY revision DESC",
+    )?;
+    let revisions = stmt
+        .query_map(params![id], row_to_revision)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(revisions)
+}
+
+/// Restore a previous version of a note. The current version is kept as a new
+/// revision, so a restore can be undone.
+#[tauri::command]
+#[specta::specta]
+pub fn notes_restore_revision(
+    app: AppHandle,
+    id: String,
+    revision: i64,
+) -> Result<Note, CommandError> {
+    let conn = get_connection()?;
+
+    let restored = conn
+        .query_row(
+            "SELECT note_id, revision, title, content, saved_at FROM note_revisions WHERE note_id = ? AND revision = ?",
+            params![id, revision],
+            row_to_revision,
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                CommandError::not_found("NoteRevision", &format!("{}#{}", id, revision))
+            }
+            _ => CommandError::database(e.to_string()),
+        })?;
+    drop(conn);
+
+    notes_update(
+        app,
+        id,
+        UpdateNoteInput {
+            title: Some(restored.title),
+            content: Some(restored.content),
+        },
+    )
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Attachments of one note, oldest first
+/// Delete a note and every row that belongs to it.
+/// Foreign keys are only enabled per-connection, so dependent rows are deleted explicitly.
+fn delete_note_rows(conn: &Connection, id: &str) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM note_revisions WHERE note_id = ?", params![id])?;
+    conn.execute(
+        "DELETE FROM note_attachments WHERE note_id = ?",
+        params![id],
+    )?;
     conn.execute("DELETE FROM notes WHERE id = ?", params![id])?;
+    Ok(())
+}
 
-    emit_delete_event(&app, event_names::NOTES_DELETED, id)?;
+fn attachments_of(conn: &Connection, note_id: &str) -> Result<Vec<NoteAttachment>, CommandError> {
+    let mut stmt = conn.prepare(&format!(
+        "{} WHERE note_id = ? ORDER BY created_at ASC",
+        SELECT_ATTACHMENT_COLUMNS
+    ))?;
+    let attachments = stmt
+        .query_map(params![note_id], row_to_attachment)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(attachments)
+}
+
+/// Folder holding the attached files of a note
+fn note_attachments_dir(app: &AppHandle, note_id: &str) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(ATTACHMENTS_DIR).join(note_id))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// Set a flag column of a note. `updated_at` is left alone so pinning or
+/// archiving doesn't count as an edit; clearing `synced_at` still queues the
+/// change for sync.
+fn set_flag(id: &str, column: &str, value: bool) -> Result<(), CommandError> {
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Note, id, AuditAction::Update, now)?;
+        tx.execute(
+            &format!(
+                "UPDATE notes SET {} = ?, synced_at = NULL WHERE id = ?",
+                column
+            ),
+            params![value, id],
+        )?;
+        Ok(())
+    })?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::get_migrations;
+
+    fn count(conn: &Connection, table: &str, note_id: &str) -> i64 {
+        conn.query_row(
+            &format!("SELECT COUNT(*) FROM {} WHERE note_id = ?", table),
+            params![note_id],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn deleting_a_note_deletes_its_child_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        for migration in get_migrations() {
+            conn.execute_batch(migration.sql).unwrap();
+        }
+        // Like the pooled connections, which never enable foreign keys
+        conn.execute_batch("PRAGMA foreign_keys=OFF;").unwrap();
+        conn.execute_batch(
+            "INSERT INTO notes (id, title, content, created_at, updated_at) VALUES ('a', 'A', '', 0, 0);
+             INSERT INTO notes (id, title, content, created_at, updated_at) VALUES ('b', 'B', '', 0, 0);
+             INSERT INTO note_revisions (note_id, revision, title, content, saved_at) VALUES ('a', 1, 'A', 'old', 0);
+             INSERT INTO note_revisions (note_id, revision, title, content, saved_at) VALUES ('b', 1, 'B', 'old', 0);
+             INSERT INTO note_attachments (id, note_id, file_name, path, size_bytes) VALUES ('x', 'a', 'x.png', 'x.png', 1);
+             INSERT INTO note_attachments (id, note_id, file_name, path, size_bytes) VALUES ('y', 'b', 'y.png', 'y.png', 1);",
+        )
+        .unwrap();
+
+        delete_note_rows(&conn, "a").unwrap();
+
+        assert_eq!(count(&conn, "note_revisions", "a"), 0);
+        assert_eq!(count(&conn, "note_attachments", "a"), 0);
+        assert_eq!(count(&conn, "note_revisions", "b"), 1);
+        assert_eq!(count(&conn, "note_attachments", "b"), 1);
+        let notes: i64 = conn
+            .query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(notes, 1);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::commands::audit_log::{record_audit, AuditAction, AuditEntity};
+use crate::commands::error::CommandError;
+use crate::commands::keyterms::{Keyterm, KeytermCategory};
+use crate::commands::settings::{keys as setting_keys, read_bool_setting};
+use crate::commands::shortcuts::{Shortcut, ShortcutCategory};
+use crate::commands::writing_styles::{WritingStyle, WritingStyleCategory};
+use crate::db::pool::{get_connection, write_transaction};
+use crate::events::{emit_entity_event, names as event_names};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+/// Example shortcuts as (trigger, replacement), applied in every category
+const DEFAULT_SHORTCUTS: &[(&str, &str)] = &[
+    ("brb", "be right back"),
+    ("omw", "on my way"),
+    ("lmk", "let me know"),
+];
+
+/// Starter keyterms, applied in every category
+const DEFAULT_KEYTERMS: &[&str] = &["Dicto", "GitHub", "JavaScript", "TypeScript"];
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// How many defaults `seed_defaults` added; all zero when they were seeded before
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct SeedReport {
+    pub writing_styles: usize,
+    pub shortcuts: usize,
+    pub keyterms: usize,
+}
+
+/// Rows added by one attempt of the seeding transaction
+#[derive(Default)]
+struct Seeded {
+    writing_styles: Vec<WritingStyle>,
+    shortcuts: Vec<Shortcut>,
+    keyterms: Vec<Keyterm>,
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Add the default writing styles, example shortcuts and starter keyterms on
+/// first run, all in one transaction. Styles are only added for categories
+/// without one, and examples only when the user has no shortcuts or keyterms
+/// yet. Does nothing once the defaults have been seeded, so deleting an
+/// example doesn't bring it back; databases in use before seeding existed are
+/// marked seeded by a migration.
+#[tauri::command]
+#[specta::specta]
+pub fn seed_defaults(app: AppHandle) -> Result<SeedReport, CommandError> {
+    if read_bool_setting(setting_keys::DEFAULTS_SEEDED, false) {
+        return Ok(SeedReport::default());
+    }
+
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    let seeded = write_transaction(&mut conn, |tx| {
+        let mut seeded = Seeded::default();
+
+        for category in [
+            WritingStyleCategory::Personal,
+            WritingStyleCategory::Work,
+            WritingStyleCategory::Email,
+            WritingStyleCategory::General,
+        ] {
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO writing_styles (category, selected_style, updated_at) VALUES (?, ?, ?)",
+                params![category.as_str(), category.default_style(), now],
+            )?;
+            if inserted == 0 {
+                continue;
+            }
+            record_audit(
+                tx,
+                AuditEntity::WritingStyle,
+                category.as_str(),
+                AuditAction::Create,
+                now,
+            )?;
+            seeded.writing_styles.push(WritingStyle {
+                category: category.as_str().to_string(),
+                selected_style: category.default_style().to_string(),
+                default_prompt: None,
+                custom_prompt: None,
+                updated_at: now,
+            });
+        }
+
+        let has_shortcuts: bool =
+            tx.query_row("SELECT EXISTS(SELECT 1 FROM shortcuts)", [], |row| {
+                row.get(0)
+            })?;
+        let shortcuts: &[(&str, &str)] = if has_shortcuts {
+            &[]
+        } else {
+            DEFAULT_SHORTCUTS
+        };
+        for (trigger, replacement) in shortcuts {
+            let shortcut = Shortcut {
+                id: uuid::Uuid::new_v4().to_string(),
+                trigger: trigger.to_string(),
+                replacement: replacement.to_string(),
+                category: ShortcutCategory::All.as_str().to_string(),
+                case_sensitive: false,
+                whole_word: true,
+                created_at: now,
+                updated_at: now,
+                is_duplicate: false,
+            };
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO shortcuts (id, trigger, replacement, category, case_sensitive, whole_word, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                params![shortcut.id, shortcut.trigger, shortcut.replacement, shortcut.category, shortcut.case_sensitive, shortcut.whole_word, now, now],
+            )?;
+            if inserted == 0 {
+                continue;
+            }
+            record_audit(
+                tx,
+                AuditEntity::Shortcut,
+                &shortcut.id,
+                AuditAction::Create,
+                now,
+            )?;
+            seeded.shortcuts.push(shortcut);
+        }
+
+        let has_keyterms: bool =
+            tx.query_row("SELECT EXISTS(SELECT 1 FROM keyterms)", [], |row| {
+                row.get(0)
+            })?;
+        let keyterms: &[&str] = if has_keyterms { &[] } else { DEFAULT_KEYTERMS };
+        for text in keyterms {
+            let keyterm = Keyterm {
+                id: uuid::Uuid::new_v4().to_string(),
+                text: text.to_string(),
+                category: KeytermCategory::All.as_str().to_string(),
+                created_at: now,
+                updated_at: now,
+                is_duplicate: false,
+            };
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO keyterms (id, text, category, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+                params![keyterm.id, keyterm.text, keyterm.category, now, now],
+            )?;
+            if inserted == 0 {
+                continue;
+            }
+            record_audit(
+                tx,
+                AuditEntity::Keyterm,
+                &keyterm.id,
+                AuditAction::Create,
+                now,
+            )?;
+            seeded.keyterms.push(keyterm);
+        }
+
+        tx.execute(
+            "INSERT INTO settings (key, value, created_at, updated_at) VALUES (?, 'true', ?, ?)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+            params![setting_keys::DEFAULTS_SEEDED, now, now],
+        )?;
+
+        Ok(seeded)
+    })?;
+
+    let report = SeedReport {
+        writing_styles: seeded.writing_styles.len(),
+        shortcuts: seeded.shortcuts.len(),
+        keyterms: seeded.keyterms.len(),
+    };
+
+    for style in seeded.writing_styles {
+        emit_entity_event(&app, event_names::WRITING_STYLES_UPDATED, style)?;
+    }
+    for shortcut in seeded.shortcuts {
+        emit_entity_event(&app, event_names::SHORTCUTS_CREATED, shortcut)?;
+    }
+    for keyterm in seeded.keyterms {
+        emit_entity_event(&app, event_names::KEYTERMS_CREATED, keyterm)?;
+    }
+
+    Ok(report)
+}
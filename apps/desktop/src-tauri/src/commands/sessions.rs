@@ -0,0 +1,237 @@
+use crate::commands::error::CommandError;
+use crate::db::pool::{get_connection, write_transaction};
+use crate::events::{emit_delete_event, names as event_names};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Session {
+    pub id: String,
+    pub title: String,
+    pub transcript: String,
+    pub chunk_count: i64,
+    pub duration_ms: i64,
+    pub started_at: i64,
+    pub ended_at: Option<i64>,
+    pub updated_at: i64,
+}
+
+/// A timestamped piece of a session transcript (one processed audio chunk)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionEntry {
+    pub id: i64,
+    pub session_id: String,
+    pub chunk_index: i64,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionDetail {
+    pub session: Session,
+    pub entries: Vec<SessionEntry>,
+}
+
+// ============================================================================
+// Pipeline helpers
+// ============================================================================
+
+/// Create an empty session record for a new meeting recording
+pub fn create_session(title: &str) -> Result<Session, CommandError> {
+    let conn = get_connection()?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    conn.execute(
+        "INSERT INTO sessions (id, title, transcript, chunk_count, duration_ms, started_at, updated_at)
+         VALUES (?, ?, '', 0, 0, ?, ?)",
+        params![id, title, now, now],
+    )?;
+
+    Ok(Session {
+        id,
+        title: title.to_string(),
+        transcript: String::new(),
+        chunk_count: 0,
+        duration_ms: 0,
+        started_at: now,
+        ended_at: None,
+        updated_at: now,
+    })
+}
+
+/// Append a transcribed chunk to a session and store the updated transcript
+pub fn append_session_entry(
+    session_id: &str,
+    chunk_index: i64,
+    start_ms: i64,
+    end_ms: i64,
+    text: &str,
+    transcript: &str,
+) -> Result<Session, CommandError> {
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        tx.execute(
+            "INSERT INTO session_entries (session_id, chunk_index, start_ms, end_ms, text, created_at)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            params![session_id, chunk_index, start_ms, end_ms, text, now],
+        )?;
+        tx.execute(
+            "UPDATE sessions SET transcript = ?, chunk_count = chunk_count + 1, duration_ms = MAX(duration_ms, ?), updated_at = ?
+             WHERE id = ?",
+            params![transcript, end_ms, now, session_id],
+        )?;
+        Ok(())
+    })?;
+
+    sessions_get_row(session_id)
+}
+
+/// Mark a session as ended with its final duration
+pub fn finish_session(session_id: &str, duration_ms: i64) -> Result<Session, CommandError> {
+    let conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    conn.execute(
+        "UPDATE sessions SET ended_at = ?, duration_ms = MAX(duration_ms, ?), updated_at = ? WHERE id = ?",
+        params![now, duration_ms, now, session_id],
+    )?;
+
+    sessions_get_row(session_id)
+}
+
+fn sessions_get_row(id: &str) -> Result<Session, CommandError> {
+    let conn = get_connection()?;
+
+    conn.query_row(
+        "SELECT id, title, transcript, chunk_count, duration_ms, started_at, ended_at, updated_at
+         FROM sessions WHERE id = ?",
+        params![id],
+        |row| {
+            Ok(Session {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                transcript: row.get(2)?,
+                chunk_count: row.get(3)?,
+                duration_ms: row.get(4)?,
+                started_at: row.get(5)?,
+                ended_at: row.get(6)?,
+                updated_at: row.get(7)?,
+            })
+        },
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => CommandError::not_found("Session", id),
+        _ => CommandError::database(e.to_string()),
+    })
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// List all sessions, most recent first
+#[tauri::command]
+#[specta::specta]
+pub fn sessions_list() -> Result<Vec<Session>, CommandError> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, title, transcript, chunk_count, duration_ms, started_at, ended_at, updated_at
+         FROM sessions ORDER BY started_at DESC",
+    )?;
+
+    let sessions = stmt
+        .query_map([], |row| {
+            Ok(Session {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                transcript: row.get(2)?,
+                chunk_count: row.get(3)?,
+                duration_ms: row.get(4)?,
+                started_at: row.get(5)?,
+                ended_at: row.get(6)?,
+                updated_at: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(sessions)
+}
+
+/// Get a session with its timestamped entries
+#[tauri::command]
+#[specta::specta]
+pub fn sessions_get(id: String) -> Result<SessionDetail, CommandError> {
+    let session = sessions_get_row(&id)?;
+
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, session_id, chunk_index, start_ms, end_ms, text, created_at
+         FROM session_entries WHERE session_id = ? ORDER BY chunk_index ASC",
+    )?;
+
+    let entries = stmt
+        .query_map(params![id], |row| {
+            Ok(SessionEntry {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                chunk_index: row.get(2)?,
+                start_ms: row.get(3)?,
+                end_ms: row.get(4)?,
+                text: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(SessionDetail { session, entries })
+}
+
+/// Delete a session and its entries
+#[tauri::command]
+#[specta::specta]
+pub fn sessions_delete(app: AppHandle, id: String) -> Result<(), CommandError> {
+    // Verify it exists first
+    sessions_get_row(&id)?;
+
+    let mut conn = get_connection()?;
+
+    // Foreign keys are only enabled per-connection, so delete entries explicitly
+    write_transaction(&mut conn, |tx| {
+        tx.execute(
+            "DELETE FROM session_entries WHERE session_id = ?",
+            params![id],
+        )?;
+        tx.execute("DELETE FROM sessions WHERE id = ?", params![id])?;
+        Ok(())
+    })?;
+
+    emit_delete_event(&app, event_names::SESSIONS_DELETED, id)?;
+
+    Ok(())
+}
@@ -1,12 +1,93 @@
+use crate::commands::audit_log::{record_audit, AuditAction, AuditEntity};
 use crate::commands::error::CommandError;
-use crate::db::pool::get_connection;
+use crate::db::pool::{get_connection, write_transaction};
 use crate::events::{emit_entity_event, names as event_names};
 use rusqlite::params;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::AppHandle;
 
+/// Setting keys read by the Rust side
+pub mod keys {
+    /// Bracket low-confidence words with `[?]` in pasted text
+    pub const MARK_LOW_CONFIDENCE: &str = "markLowConfidence";
+    /// Word probability below which a word counts as low confidence (0.0 - 1.0)
+    pub const LOW_CONFIDENCE_THRESHOLD: &str = "lowConfidenceThreshold";
+    /// Number of chunk worker threads transcribing in parallel
+    pub const CHUNK_WORKERS: &str = "chunkWorkers";
+    /// Whisper beam search width; 1 decodes greedily (fastest)
+    pub const WHISPER_BEAM_SIZE: &str = "whisperBeamSize";
+    /// Whisper sampling temperature (0.0 - 1.0); 0 is deterministic
+    pub const WHISPER_TEMPERATURE: &str = "whisperTemperature";
+    /// Probability above which Whisper treats a segment as silence (0.0 - 1.0)
+    pub const WHISPER_NO_SPEECH_THRESHOLD: &str = "whisperNoSpeechThreshold";
+    /// Maximum Whisper segment length in characters; 0 leaves segments unsplit
+    pub const WHISPER_MAX_SEGMENT_LENGTH: &str = "whisperMaxSegmentLength";
+    /// Threads each Whisper inference uses; 0 splits the cores between chunk workers
+    pub const WHISPER_THREADS: &str = "whisperThreads";
+    /// Transcribe with one worker, at most two threads and longer chunks to spare the battery
+    pub const LOW_POWER_MODE: &str = "lowPowerMode";
+    /// Turn low power mode on by itself while on battery below the threshold
+    pub const BATTERY_LOW_POWER: &str = "batteryLowPower";
+    /// Battery percentage at or below which low power mode turns on by itself
+    pub const BATTERY_LOW_POWER_THRESHOLD: &str = "batteryLowPowerThreshold";
+    /// Mask emails, phone numbers, card numbers and listed names before cloud formatting
+    pub const REDACT_PII: &str = "redactPii";
+    /// Send the focused window's title to the formatter as context
+    pub const CAPTURE_WINDOW_TITLE: &str = "captureWindowTitle";
+    /// JSON array of names to mask when PII redaction is enabled
+    pub const REDACT_NAMES: &str = "redactNames";
+    /// Name of the microphone to record from; unset uses the system default
+    pub const INPUT_DEVICE: &str = "inputDevice";
+    /// Record through the macOS voice processing unit (echo cancellation and noise
+    /// suppression); always uses the system default microphone
+    pub const VOICE_PROCESSING: &str = "voiceProcessing";
+    /// Manifest id of the local speech-to-text model, e.g. `whisper-small`
+    pub const LOCAL_MODEL: &str = "localModel";
+    /// JSON array of language codes to transcribe
+    pub const LANGUAGES: &str = "languages";
+    /// Let Whisper detect the spoken language
+    pub const AUTO_DETECT_LANGUAGE: &str = "autoDetectLanguage";
+    /// Language code dictations are translated into before formatting; unset keeps the spoken language
+    pub const OUTPUT_LANGUAGE: &str = "outputLanguage";
+    /// Provider whose vault key runs LLM requests such as translation: Groq, OpenAI or Gemini
+    pub const INTELLIGENCE_MODEL: &str = "intelligenceModel";
+    /// Stream audio to the cloud provider when a key is configured
+    pub const CLOUD_TRANSCRIPTION: &str = "cloudTranscription";
+    /// Cloud transcription provider: `Deepgram` streams while recording; `Groq` or
+    /// `OpenAI` upload on stop and fail over to each other
+    pub const CLOUD_MODEL: &str = "cloudModel";
+    /// Paste the local transcription right away, then re-transcribe the audio with
+    /// Groq or OpenAI in the background and update the saved transcription
+    pub const HYBRID_TRANSCRIPTION: &str = "hybridTranscription";
+    /// Left-clicking the tray icon toggles dictation instead of doing nothing
+    pub const TRAY_LEFT_CLICK_RECORDS: &str = "trayLeftClickRecords";
+    /// Run without a Dock icon while the main window is hidden (macOS)
+    pub const MENUBAR_ONLY: &str = "menubarOnly";
+    /// Pressing Esc while recording cancels without transcribing
+    pub const ESCAPE_CANCELS_RECORDING: &str = "escapeCancelsRecording";
+    /// Skip saving a transcription that nearly repeats one saved moments before
+    pub const SUPPRESS_DUPLICATE_TRANSCRIPTIONS: &str = "suppressDuplicateTranscriptions";
+    /// When the app dictated into lost focus, wait for the user to switch back to it
+    /// before pasting instead of bringing it to the front
+    pub const WAIT_FOR_TARGET_APP: &str = "waitForTargetApp";
+    /// Paste bold text, lists and headings as rich text too, for apps like Mail and Notes
+    pub const RICH_TEXT_PASTE: &str = "richTextPaste";
+    /// Base URL of the object store sync pushes encrypted records to; unset disables sync
+    pub const SYNC_ENDPOINT: &str = "syncEndpoint";
+    /// Mirror transcriptions and notes as Markdown files into iCloud Drive
+    pub const MIRROR_TO_ICLOUD: &str = "mirrorToICloud";
+    /// Unix time database maintenance last ran; set by the app, not the user
+    pub const DB_LAST_MAINTENANCE: &str = "dbLastMaintenance";
+    /// Stream the app's debug logs to the frontend as `pipeline-log` events
+    pub const PIPELINE_LOG: &str = "pipelineLog";
+    /// Whether the first-run defaults were added; set by the app, not the user
+    pub const DEFAULTS_SEEDED: &str = "defaultsSeeded";
+}
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -82,18 +163,27 @@ pub fn settings_get(key: String) -> Result<Option<Setting>, CommandError> {
 #[tauri::command]
 #[specta::specta]
 pub fn settings_set(app: AppHandle, input: SetSettingInput) -> Result<Setting, CommandError> {
-    let conn = get_connection()?;
+    let action = match settings_get(input.key.clone())? {
+        Some(_) => AuditAction::Update,
+        None => AuditAction::Create,
+    };
+
+    let mut conn = get_connection()?;
 
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs() as i64;
 
-    conn.execute(
-        "INSERT INTO settings (key, value, created_at, updated_at) VALUES (?, ?, ?, ?)
-         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-        params![input.key, input.value, now, now],
-    )?;
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Setting, &input.key, action, now)?;
+        tx.execute(
+            "INSERT INTO settings (key, value, created_at, updated_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+            params![input.key, input.value, now, now],
+        )?;
+        Ok(())
+    })?;
 
     let setting = Setting {
         key: input.key,
@@ -111,9 +201,70 @@ pub fn settings_set(app: AppHandle, input: SetSettingInput) -> Result<Setting, C
 #[tauri::command]
 #[specta::specta]
 pub fn settings_delete(key: String) -> Result<(), CommandError> {
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        // Deleting an unset key changes nothing worth recording
+        if tx.execute("DELETE FROM settings WHERE key = ?", params![key])? > 0 {
+            record_audit(tx, AuditEntity::Setting, &key, AuditAction::Delete, now)?;
+        }
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Read the raw value of a setting, if it has been set
+pub fn read_setting(key: &str) -> Option<String> {
+    settings_get(key.to_string())
+        .ok()
+        .flatten()
+        .map(|setting| setting.value)
+}
+
+/// Read a boolean setting stored as "true"/"false"
+pub fn read_bool_setting(key: &str, default: bool) -> bool {
+    read_setting(key).map(|v| v == "true").unwrap_or(default)
+}
+
+/// Read a setting parsed from its string value, falling back to `default`
+pub fn read_parsed_setting<T: FromStr>(key: &str, default: T) -> T {
+    read_setting(key)
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(default)
+}
+
+/// Read a setting stored as JSON (e.g. lists), falling back to `default`
+pub fn read_json_setting<T: DeserializeOwned>(key: &str, default: T) -> T {
+    read_setting(key)
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or(default)
+}
+
+/// Store a value the app keeps for itself. Unlike `settings_set` this emits no event,
+/// since nothing in the UI shows it.
+pub fn write_setting(key: &str, value: &str) -> Result<(), CommandError> {
     let conn = get_connection()?;
 
-    conn.execute("DELETE FROM settings WHERE key = ?", params![key])?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    conn.execute(
+        "INSERT INTO settings (key, value, created_at, updated_at) VALUES (?, ?, ?, ?)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![key, value, now, now],
+    )?;
 
     Ok(())
 }
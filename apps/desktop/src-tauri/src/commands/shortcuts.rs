@@ -1,6 +1,9 @@
+use crate::commands::audit_log::{record_audit, AuditAction, AuditEntity};
 use crate::commands::error::CommandError;
-use crate::db::pool::get_connection;
+use crate::commands::validation::{self, MAX_REPLACEMENT_CHARS, MAX_TRIGGER_CHARS};
+use crate::db::pool::{get_connection, write_transaction};
 use crate::events::{emit_delete_event, emit_entity_event, names as event_names};
+use crate::expansion;
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -43,8 +46,17 @@ pub struct Shortcut {
     pub trigger: String,
     pub replacement: String,
     pub category: String,
+    /// Only match the trigger with exactly this capitalization. Otherwise any
+    /// capitalization matches and the replacement follows it ("BRB" -> "BE RIGHT BACK").
+    pub case_sensitive: bool,
+    /// Only match the trigger as whole words, not inside other words
+    pub whole_word: bool,
     pub created_at: i64,
     pub updated_at: i64,
+    /// Clashes with an older entry of the same category. Set when uniqueness
+    /// started being enforced; cleared once the entry is edited.
+    #[serde(default)]
+    pub is_duplicate: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -52,6 +64,12 @@ pub struct CreateShortcutInput {
     pub trigger: String,
     pub replacement: String,
     pub category: ShortcutCategory,
+    /// Defaults to false
+    #[serde(default)]
+    pub case_sensitive: Option<bool>,
+    /// Defaults to true
+    #[serde(default)]
+    pub whole_word: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -59,6 +77,10 @@ pub struct UpdateShortcutInput {
     pub trigger: Option<String>,
     pub replacement: Option<String>,
     pub category: Option<ShortcutCategory>,
+    #[serde(default)]
+    pub case_sensitive: Option<bool>,
+    #[serde(default)]
+    pub whole_word: Option<bool>,
 }
 
 // ============================================================================
@@ -74,7 +96,7 @@ pub fn shortcuts_list(category: Option<ShortcutCategory>) -> Result<Vec<Shortcut
     let shortcuts = match &category {
         Some(cat) => {
             let mut stmt = conn.prepare(
-                "SELECT id, trigger, replacement, category, created_at, updated_at FROM shortcuts WHERE category = ? ORDER BY created_at DESC",
+                "SELECT id, trigger, replacement, category, case_sensitive, whole_word, created_at, updated_at, is_duplicate FROM shortcuts WHERE category = ? ORDER BY created_at DESC",
             )?;
             let cat_str = cat.as_str();
             let rows = stmt.query_map(params![cat_str], |row| {
@@ -83,15 +105,18 @@ pub fn shortcuts_list(category: Option<ShortcutCategory>) -> Result<Vec<Shortcut
                     trigger: row.get(1)?,
                     replacement: row.get(2)?,
                     category: row.get(3)?,
-                    created_at: row.get(4)?,
-                    updated_at: row.get(5)?,
+                    case_sensitive: row.get(4)?,
+                    whole_word: row.get(5)?,
+                    created_at: row.get(6)?,
+                    updated_at: row.get(7)?,
+                    is_duplicate: row.get(8)?,
                 })
             })?;
             rows.collect::<Result<Vec<_>, _>>()?
         }
         None => {
             let mut stmt = conn.prepare(
-                "SELECT id, trigger, replacement, category, created_at, updated_at FROM shortcuts ORDER BY created_at DESC",
+                "SELECT id, trigger, replacement, category, case_sensitive, whole_word, created_at, updated_at, is_duplicate FROM shortcuts ORDER BY created_at DESC",
             )?;
             let rows = stmt.query_map([], |row| {
                 Ok(Shortcut {
@@ -99,8 +124,11 @@ pub fn shortcuts_list(category: Option<ShortcutCategory>) -> Result<Vec<Shortcut
                     trigger: row.get(1)?,
                     replacement: row.get(2)?,
                     category: row.get(3)?,
-                    created_at: row.get(4)?,
-                    updated_at: row.get(5)?,
+                    case_sensitive: row.get(4)?,
+                    whole_word: row.get(5)?,
+                    created_at: row.get(6)?,
+                    updated_at: row.get(7)?,
+                    is_duplicate: row.get(8)?,
                 })
             })?;
             rows.collect::<Result<Vec<_>, _>>()?
@@ -117,7 +145,7 @@ pub fn shortcuts_get(id: String) -> Result<Shortcut, CommandError> {
     let conn = get_connection()?;
 
     conn.query_row(
-        "SELECT id, trigger, replacement, category, created_at, updated_at FROM shortcuts WHERE id = ?",
+        "SELECT id, trigger, replacement, category, case_sensitive, whole_word, created_at, updated_at, is_duplicate FROM shortcuts WHERE id = ?",
         params![id],
         |row| {
             Ok(Shortcut {
@@ -125,8 +153,11 @@ pub fn shortcuts_get(id: String) -> Result<Shortcut, CommandError> {
                 trigger: row.get(1)?,
                 replacement: row.get(2)?,
                 category: row.get(3)?,
-                created_at: row.get(4)?,
-                updated_at: row.get(5)?,
+                case_sensitive: row.get(4)?,
+                whole_word: row.get(5)?,
+                created_at: row.get(6)?,
+                updated_at: row.get(7)?,
+                is_duplicate: row.get(8)?,
             })
         },
     )
@@ -143,7 +174,7 @@ pub fn shortcuts_create(
     app: AppHandle,
     input: CreateShortcutInput,
 ) -> Result<Shortcut, CommandError> {
-    let conn = get_connection()?;
+    let mut conn = get_connection()?;
 
     let id = uuid::Uuid::new_v4().to_string();
     let now = SystemTime::now()
@@ -151,19 +182,32 @@ pub fn shortcuts_create(
         .unwrap()
         .as_secs() as i64;
 
-    conn.execute(
-        "INSERT INTO shortcuts (id, trigger, replacement, category, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
-        params![id, input.trigger, input.replacement, input.category.as_str(), now, now],
-    )?;
-
     let shortcut = Shortcut {
         id: id.clone(),
-        trigger: input.trigger,
-        replacement: input.replacement,
+        trigger: validate_trigger(&input.trigger)?,
+        replacement: validation::required(
+            "Replacement",
+            &input.replacement,
+            MAX_REPLACEMENT_CHARS,
+        )?,
         category: input.category.as_str().to_string(),
+        case_sensitive: input.case_sensitive.unwrap_or(false),
+        whole_word: input.whole_word.unwrap_or(true),
         created_at: now,
         updated_at: now,
+        is_duplicate: false,
     };
+    validate_no_loop(&shortcut)?;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Shortcut, &id, AuditAction::Create, now)?;
+        tx.execute(
+            "INSERT INTO shortcuts (id, trigger, replacement, category, case_sensitive, whole_word, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![id, shortcut.trigger, shortcut.replacement, shortcut.category, shortcut.case_sensitive, shortcut.whole_word, now, now],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| CommandError::from_write(e, "Shortcut", "trigger", &shortcut.trigger))?;
 
     emit_entity_event(&app, event_names::SHORTCUTS_CREATED, shortcut.clone())?;
 
@@ -181,33 +225,52 @@ pub fn shortcuts_update(
     // First verify it exists
     let existing = shortcuts_get(id.clone())?;
 
-    let conn = get_connection()?;
+    let mut conn = get_connection()?;
 
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs() as i64;
 
-    let new_trigger = input.trigger.unwrap_or(existing.trigger);
-    let new_replacement = input.replacement.unwrap_or(existing.replacement);
+    let new_trigger = match input.trigger {
+        Some(trigger) => validate_trigger(&trigger)?,
+        None => existing.trigger,
+    };
+    let new_replacement = match input.replacement {
+        Some(replacement) => {
+            validation::required("Replacement", &replacement, MAX_REPLACEMENT_CHARS)?
+        }
+        None => existing.replacement,
+    };
     let new_category = input
         .category
         .map(|c| c.as_str().to_string())
         .unwrap_or(existing.category);
-
-    conn.execute(
-        "UPDATE shortcuts SET trigger = ?, replacement = ?, category = ?, updated_at = ? WHERE id = ?",
-        params![new_trigger, new_replacement, new_category, now, id],
-    )?;
+    let new_case_sensitive = input.case_sensitive.unwrap_or(existing.case_sensitive);
+    let new_whole_word = input.whole_word.unwrap_or(existing.whole_word);
 
     let updated = Shortcut {
         id: id.clone(),
         trigger: new_trigger,
         replacement: new_replacement,
         category: new_category,
+        case_sensitive: new_case_sensitive,
+        whole_word: new_whole_word,
         created_at: existing.created_at,
         updated_at: now,
+        is_duplicate: false,
     };
+    validate_no_loop(&updated)?;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Shortcut, &id, AuditAction::Update, now)?;
+        tx.execute(
+            "UPDATE shortcuts SET trigger = ?, replacement = ?, category = ?, case_sensitive = ?, whole_word = ?, updated_at = ?, is_duplicate = 0 WHERE id = ?",
+            params![updated.trigger, updated.replacement, updated.category, updated.case_sensitive, updated.whole_word, now, id],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| CommandError::from_write(e, "Shortcut", "trigger", &updated.trigger))?;
 
     emit_entity_event(&app, event_names::SHORTCUTS_UPDATED, updated.clone())?;
 
@@ -221,11 +284,50 @@ pub fn shortcuts_delete(app: AppHandle, id: String) -> Result<(), CommandError>
     // Verify it exists first
     shortcuts_get(id.clone())?;
 
-    let conn = get_connection()?;
+    let mut conn = get_connection()?;
 
-    conn.execute("DELETE FROM shortcuts WHERE id = ?", params![id])?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::Shortcut, &id, AuditAction::Delete, now)?;
+        tx.execute("DELETE FROM shortcuts WHERE id = ?", params![id])?;
+        Ok(())
+    })?;
 
     emit_delete_event(&app, event_names::SHORTCUTS_DELETED, id)?;
 
     Ok(())
 }
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Trim a trigger and make sure it has a word to match; expansion skips
+/// triggers made only of punctuation
+fn validate_trigger(trigger: &str) -> Result<String, CommandError> {
+    let trigger = validation::required("Trigger", trigger, MAX_TRIGGER_CHARS)?;
+    if !trigger.chars().any(char::is_alphanumeric) {
+        return Err(CommandError::validation(
+            "Shortcut trigger must contain at least one word",
+        ));
+    }
+
+    Ok(trigger)
+}
+
+/// Reject a replacement that contains its own trigger, which would expand again
+/// whenever the text passes through expansion another time
+fn validate_no_loop(shortcut: &Shortcut) -> Result<(), CommandError> {
+    if expansion::contains_trigger(&shortcut.replacement, shortcut) {
+        return Err(CommandError::validation(format!(
+            "Replacement cannot contain its own trigger '{}'",
+            shortcut.trigger
+        )));
+    }
+
+    Ok(())
+}
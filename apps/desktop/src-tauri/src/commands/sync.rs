@@ -0,0 +1,100 @@
+use crate::commands::error::CommandError;
+use crate::commands::settings::{keys, settings_delete, settings_set, SetSettingInput};
+use crate::keychain::{
+    delete_secret, get_secret, set_secret, SYNC_KEY_ACCOUNT, SYNC_TOKEN_ACCOUNT,
+};
+use crate::sync::{self, SyncStatus, SyncSummary};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SyncConfigInput {
+    /// Base URL of an object store that takes plain GET, PUT and DELETE requests with
+    /// bearer auth and ETags, e.g. a self-hosted server
+    pub endpoint: String,
+    /// Bearer token sent with every request, if the backend needs one
+    pub token: Option<String>,
+    /// Base64 key from another device; a new key is generated when omitted
+    pub encryption_key: Option<String>,
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Turn on sync with the given backend. Returns the encryption key, which must be
+/// entered on every other device that syncs with this backend.
+#[tauri::command]
+#[specta::specta]
+pub fn sync_configure(app: AppHandle, input: SyncConfigInput) -> Result<String, CommandError> {
+    let endpoint = input.endpoint.trim().trim_end_matches('/').to_string();
+    if !endpoint.starts_with("https://") && !endpoint.starts_with("http://localhost") {
+        return Err(CommandError::validation(
+            "Sync endpoint must be an https:// URL",
+        ));
+    }
+
+    let key = match input.encryption_key.as_deref().map(str::trim) {
+        Some(key) if !key.is_empty() => {
+            sync::validate_key(key).map_err(CommandError::validation)?;
+            key.to_string()
+        }
+        // Keep the existing key so reconfiguring doesn't orphan what was already pushed
+        _ => match get_secret(SYNC_KEY_ACCOUNT)? {
+            Some(key) => key,
+            None => sync::generate_key(),
+        },
+    };
+
+    set_secret(SYNC_KEY_ACCOUNT, &key)?;
+    match input.token.as_deref().map(str::trim) {
+        Some(token) if !token.is_empty() => set_secret(SYNC_TOKEN_ACCOUNT, token)?,
+        _ => delete_secret(SYNC_TOKEN_ACCOUNT)?,
+    }
+
+    settings_set(
+        app,
+        SetSettingInput {
+            key: keys::SYNC_ENDPOINT.to_string(),
+            value: endpoint,
+        },
+    )?;
+
+    Ok(key)
+}
+
+/// Turn off sync. The encryption key is kept so sync can be turned back on.
+#[tauri::command]
+#[specta::specta]
+pub fn sync_disable() -> Result<(), CommandError> {
+    settings_delete(keys::SYNC_ENDPOINT.to_string())?;
+    delete_secret(SYNC_TOKEN_ACCOUNT)?;
+
+    Ok(())
+}
+
+/// Sync with the backend right away
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_now(app: AppHandle) -> Result<SyncSummary, CommandError> {
+    Ok(sync::sync_now(&app).await?)
+}
+
+/// Whether sync is configured, running, or has changes waiting
+#[tauri::command]
+#[specta::specta]
+pub fn sync_status() -> Result<SyncStatus, CommandError> {
+    Ok(sync::status()?)
+}
+
+/// The encryption key, to set up sync on another device
+#[tauri::command]
+#[specta::specta]
+pub fn sync_get_key() -> Result<Option<String>, CommandError> {
+    Ok(get_secret(SYNC_KEY_ACCOUNT)?)
+}
@@ -0,0 +1,49 @@
+use tauri::ipc::Invoke;
+
+use crate::commands::error::CommandError;
+use crate::platform;
+use crate::stt::mock;
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Feed 16kHz mono audio into the recording in progress, in place of the
+/// microphone. The mock transcriber hears `transcript` in it.
+#[tauri::command]
+pub fn test_inject_audio(samples: Vec<f32>, transcript: String) -> Result<(), CommandError> {
+    mock::inject_audio(samples, &transcript)
+        .map_err(|e| CommandError::recording_failed(e.to_string()))
+}
+
+/// Text pasted or typed since the last call, oldest first
+#[tauri::command]
+pub fn test_take_pasted_text() -> Vec<String> {
+    platform::mock::take_pasted_text()
+}
+
+/// Plain text on the mock clipboard, if any
+#[tauri::command]
+pub fn test_read_clipboard() -> Option<String> {
+    platform::text_inserter().read_clipboard()
+}
+
+/// Answer the `test_*` commands, handing every other command to `handler`.
+/// They are left out of the TypeScript bindings, which ship with every build.
+pub fn with_test_commands(
+    handler: impl Fn(Invoke) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke) -> bool + Send + Sync + 'static {
+    let test_handler = tauri::generate_handler![
+        test_inject_audio,
+        test_take_pasted_text,
+        test_read_clipboard
+    ];
+
+    move |invoke| {
+        if invoke.message.command().starts_with("test_") {
+            test_handler(invoke)
+        } else {
+            handler(invoke)
+        }
+    }
+}
@@ -1,9 +1,17 @@
+use crate::clipboard;
 use crate::commands::error::CommandError;
-use crate::db::pool::get_connection;
+use crate::corrections::{diff_words, word_corrections, DiffOp, MIN_CORRECTION_COUNT};
+use crate::db::pool::{get_connection, write_transaction};
 use crate::events::{emit_delete_event, emit_entity_event, names as event_names};
-use rusqlite::params;
+use crate::mirror;
+use crate::output::{paste_text, replace_pasted_text};
+use crate::persistence::{save_transcription, SaveOutcome};
+use crate::recordings;
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashSet;
+use std::io::Write;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::AppHandle;
 
@@ -17,6 +25,28 @@ pub struct Transcription {
     pub text: String,
     pub formatted_text: Option<String>,
     pub created_at: i64,
+    pub is_pinned: bool,
+    /// App the text was dictated into, when known
+    pub app_name: Option<String>,
+}
+
+/// Which version of a transcription to use
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum TranscriptionVersion {
+    #[serde(rename = "raw")]
+    Raw,
+    #[serde(rename = "formatted")]
+    Formatted,
+}
+
+impl Transcription {
+    /// Text of the given version; the formatted version falls back to the raw text
+    pub fn text_for(&self, version: &TranscriptionVersion) -> &str {
+        match version {
+            TranscriptionVersion::Raw => &self.text,
+            TranscriptionVersion::Formatted => self.formatted_text.as_deref().unwrap_or(&self.text),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -32,9 +62,48 @@ pub struct UpdateTranscriptionInput {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum TranscriptionSort {
+    #[serde(rename = "newest")]
+    Newest,
+    /// Pinned transcriptions first, each group newest first
+    #[serde(rename = "pinned_first")]
+    PinnedFirst,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
 pub struct ListTranscriptionsParams {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    #[serde(default)]
+    pub sort: Option<TranscriptionSort>,
+    /// Only transcriptions created at or after this Unix time
+    #[serde(default)]
+    pub from: Option<i64>,
+    /// Only transcriptions created at or before this Unix time
+    #[serde(default)]
+    pub to: Option<i64>,
+    /// Only transcriptions dictated into this app
+    #[serde(default)]
+    pub app_name: Option<String>,
+    /// Only transcriptions whose formatted text differs from the raw text (or, when false, doesn't)
+    #[serde(default)]
+    pub has_formatted: Option<bool>,
+}
+
+/// Inclusive range of creation times in Unix seconds; open ends are unbounded
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct DateRange {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+/// Transcriptions created on one local calendar day
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptionDay {
+    /// Local date as `YYYY-MM-DD`
+    pub date: String,
+    pub word_count: i64,
+    pub items: Vec<Transcription>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -44,12 +113,100 @@ pub struct PaginatedTranscriptions {
     pub has_more: bool,
 }
 
+/// A timestamped segment of a transcription, offsets relative to the recording start
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptionSegment {
+    pub id: i64,
+    pub transcription_id: String,
+    pub segment_index: i64,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    pub confidence: Option<f64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct TranscriptionAnalytics {
     pub total_count: i64,
     pub total_words: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum TranscriptionRating {
+    #[serde(rename = "good")]
+    Good,
+    #[serde(rename = "bad")]
+    Bad,
+}
+
+impl TranscriptionRating {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Good => "good",
+            Self::Bad => "bad",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptionFeedback {
+    pub transcription_id: String,
+    pub rating: TranscriptionRating,
+    /// What the user actually said, when they corrected the transcript
+    pub corrected_text: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// A phrase the user keeps correcting, offered as a replacement or keyterm
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CorrectionSuggestion {
+    pub original: String,
+    pub corrected: String,
+    pub count: i64,
+    pub last_seen_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum DiffKind {
+    #[serde(rename = "equal")]
+    Equal,
+    #[serde(rename = "removed")]
+    Removed,
+    #[serde(rename = "added")]
+    Added,
+}
+
+/// A run of consecutive words that changed the same way
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DiffSpan {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// Word-level diff of the raw transcript against the formatted text
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptionDiff {
+    pub transcription_id: String,
+    pub spans: Vec<DiffSpan>,
+    pub words_removed: i64,
+    pub words_added: i64,
+}
+
+/// One line of the feedback export
+#[derive(Debug, Clone, Serialize)]
+struct FeedbackExportRecord {
+    transcription_id: String,
+    created_at: i64,
+    rating: String,
+    text: String,
+    corrected_text: Option<String>,
+    /// The recording as a 16kHz mono WAV file; `None` when the audio was not kept,
+    /// e.g. for transcriptions rated more than a day after they were made
+    audio_path: Option<String>,
+    segments: Vec<TranscriptionSegment>,
+}
+
 // ============================================================================
 // Commands
 // ============================================================================
@@ -62,27 +219,60 @@ pub fn transcriptions_list(
 ) -> Result<PaginatedTranscriptions, CommandError> {
     let conn = get_connection()?;
 
-    let limit = params.as_ref().and_then(|p| p.limit).unwrap_or(20);
-    let offset = params.as_ref().and_then(|p| p.offset).unwrap_or(0);
+    let params = params.unwrap_or_default();
+    let limit = params.limit.unwrap_or(20);
+    let offset = params.offset.unwrap_or(0);
+    let order_by = match params.sort {
+        Some(TranscriptionSort::PinnedFirst) => "is_pinned DESC, created_at DESC",
+        Some(TranscriptionSort::Newest) | None => "created_at DESC",
+    };
+
+    // Unset filters bind NULL and match every row
+    let filter = "WHERE (?1 IS NULL OR created_at >= ?1)
+           AND (?2 IS NULL OR created_at <= ?2)
+           AND (?3 IS NULL OR app_name = ?3)
+           AND (?4 IS NULL OR (formatted_text IS NOT NULL AND formatted_text <> text) = ?4)";
 
     // Get total count
-    let total: i64 = conn.query_row("SELECT COUNT(*) FROM transcriptions", [], |row| row.get(0))?;
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM transcriptions {}", filter),
+        params![
+            params.from,
+            params.to,
+            params.app_name,
+            params.has_formatted
+        ],
+        |row| row.get(0),
+    )?;
 
     // Get paginated items
-    let mut stmt = conn.prepare(
-        "SELECT id, text, formatted_text, created_at FROM transcriptions
-         ORDER BY created_at DESC LIMIT ? OFFSET ?",
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, text, formatted_text, created_at, is_pinned, app_name FROM transcriptions
+         {} ORDER BY {} LIMIT ?5 OFFSET ?6",
+        filter, order_by
+    ))?;
 
     let items = stmt
-        .query_map(params![limit, offset], |row| {
-            Ok(Transcription {
-                id: row.get(0)?,
-                text: row.get(1)?,
-                formatted_text: row.get(2)?,
-                created_at: row.get(3)?,
-            })
-        })?
+        .query_map(
+            params![
+                params.from,
+                params.to,
+                params.app_name,
+                params.has_formatted,
+                limit,
+                offset
+            ],
+            |row| {
+                Ok(Transcription {
+                    id: row.get(0)?,
+                    text: row.get(1)?,
+                    formatted_text: row.get(2)?,
+                    created_at: row.get(3)?,
+                    is_pinned: row.get(4)?,
+                    app_name: row.get(5)?,
+                })
+            },
+        )?
         .collect::<Result<Vec<_>, _>>()?;
 
     let has_more = offset + (items.len() as i64) < total;
@@ -94,6 +284,60 @@ pub fn transcriptions_list(
     })
 }
 
+/// List transcriptions in a date range grouped by local day, newest day first
+#[tauri::command]
+#[specta::specta]
+pub fn transcriptions_list_grouped(
+    range: Option<DateRange>,
+) -> Result<Vec<TranscriptionDay>, CommandError> {
+    let conn = get_connection()?;
+    let range = range.unwrap_or_default();
+
+    let mut stmt = conn.prepare(
+        "SELECT id, text, formatted_text, created_at, is_pinned, app_name,
+                date(created_at, 'unixepoch', 'localtime') AS day,
+                LENGTH(text) - LENGTH(REPLACE(text, ' ', '')) + 1 AS words
+         FROM transcriptions
+         WHERE (?1 IS NULL OR created_at >= ?1) AND (?2 IS NULL OR created_at <= ?2)
+         ORDER BY created_at DESC",
+    )?;
+
+    let rows = stmt
+        .query_map(params![range.from, range.to], |row| {
+            Ok((
+                Transcription {
+                    id: row.get(0)?,
+                    text: row.get(1)?,
+                    formatted_text: row.get(2)?,
+                    created_at: row.get(3)?,
+                    is_pinned: row.get(4)?,
+                    app_name: row.get(5)?,
+                },
+                row.get::<_, String>(6)?,
+                row.get::<_, i64>(7)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Rows are newest first, so each day's rows are contiguous
+    let mut days: Vec<TranscriptionDay> = Vec::new();
+    for (transcription, date, words) in rows {
+        match days.last_mut() {
+            Some(day) if day.date == date => {
+                day.word_count += words;
+                day.items.push(transcription);
+            }
+            _ => days.push(TranscriptionDay {
+                date,
+                word_count: words,
+                items: vec![transcription],
+            }),
+        }
+    }
+
+    Ok(days)
+}
+
 /// Get a single transcription by ID
 #[tauri::command]
 #[specta::specta]
@@ -101,7 +345,8 @@ pub fn transcriptions_get(id: String) -> Result<Transcription, CommandError> {
     let conn = get_connection()?;
 
     conn.query_row(
-        "SELECT id, text, formatted_text, created_at FROM transcriptions WHERE id = ?",
+        "SELECT id, text, formatted_text, created_at, is_pinned, app_name
+         FROM transcriptions WHERE id = ?",
         params![id],
         |row| {
             Ok(Transcription {
@@ -109,6 +354,8 @@ pub fn transcriptions_get(id: String) -> Result<Transcription, CommandError> {
                 text: row.get(1)?,
                 formatted_text: row.get(2)?,
                 created_at: row.get(3)?,
+                is_pinned: row.get(4)?,
+                app_name: row.get(5)?,
             })
         },
     )
@@ -118,33 +365,22 @@ pub fn transcriptions_get(id: String) -> Result<Transcription, CommandError> {
     })
 }
 
-/// Create a new transcription
+/// Create a new transcription. Saved the same way as a dictation, so a near-copy
+/// of one saved moments earlier returns that one instead.
 #[tauri::command]
 #[specta::specta]
 pub fn transcriptions_create(
     app: AppHandle,
     input: CreateTranscriptionInput,
 ) -> Result<Transcription, CommandError> {
-    let conn = get_connection()?;
-
-    let id = uuid::Uuid::new_v4().to_string();
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as i64;
-
-    conn.execute(
-        "INSERT INTO transcriptions (id, text, formatted_text, created_at) VALUES (?, ?, ?, ?)",
-        params![id, input.text, input.formatted_text, now],
-    )?;
-
-    let transcription = Transcription {
-        id: id.clone(),
-        text: input.text,
-        formatted_text: input.formatted_text,
-        created_at: now,
+    let id = match save_transcription(&input.text, input.formatted_text.as_deref(), &[], None)? {
+        SaveOutcome::Saved(id) => id,
+        SaveOutcome::Duplicate(id) => return transcriptions_get(id),
     };
 
+    let transcription = transcriptions_get(id)?;
+
+    mirror::mirror_transcription(&transcription);
     emit_entity_event(
         &app,
         event_names::TRANSCRIPTIONS_CREATED,
@@ -165,42 +401,203 @@ pub fn transcriptions_update(
     // First verify it exists
     let existing = transcriptions_get(id.clone())?;
 
-    let conn = get_connection()?;
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    // Hand edits are corrections too; count what changed in each version
+    let mut corrections = Vec::new();
+    if let Some(text) = &input.text {
+        corrections.extend(word_corrections(&existing.text, text));
+    }
+    if let Some(formatted_text) = &input.formatted_text {
+        let previous = existing.formatted_text.as_deref().unwrap_or(&existing.text);
+        corrections.extend(word_corrections(previous, formatted_text));
+    }
+    // Editing both versions the same way is still one correction
+    corrections.sort();
+    corrections.dedup();
 
     let new_text = input.text.unwrap_or(existing.text);
     let new_formatted_text = input.formatted_text.or(existing.formatted_text);
 
-    conn.execute(
-        "UPDATE transcriptions SET text = ?, formatted_text = ? WHERE id = ?",
-        params![new_text, new_formatted_text, id],
-    )?;
+    write_transaction(&mut conn, |tx| {
+        tx.execute(
+            "UPDATE transcriptions SET text = ?, formatted_text = ? WHERE id = ?",
+            params![new_text, new_formatted_text, id],
+        )?;
+        record_corrections(tx, &corrections, now)?;
+        Ok(())
+    })?;
 
     let updated = Transcription {
         id: id.clone(),
         text: new_text,
         formatted_text: new_formatted_text,
         created_at: existing.created_at,
+        is_pinned: existing.is_pinned,
+        app_name: existing.app_name,
     };
 
+    mirror::mirror_transcription(&updated);
     emit_entity_event(&app, event_names::TRANSCRIPTIONS_UPDATED, updated.clone())?;
 
     Ok(updated)
 }
 
+/// Pin or unpin a transcription so it stays on top of the pinned-first list
+#[tauri::command]
+#[specta::specta]
+pub fn transcriptions_set_pinned(
+    app: AppHandle,
+    id: String,
+    pinned: bool,
+) -> Result<Transcription, CommandError> {
+    let existing = transcriptions_get(id.clone())?;
+
+    let conn = get_connection()?;
+
+    conn.execute(
+        "UPDATE transcriptions SET is_pinned = ? WHERE id = ?",
+        params![pinned, id],
+    )?;
+
+    let updated = Transcription {
+        is_pinned: pinned,
+        ..existing
+    };
+
+    emit_entity_event(&app, event_names::TRANSCRIPTIONS_UPDATED, updated.clone())?;
+
+    Ok(updated)
+}
+
+/// Copy the raw or formatted text of a transcription to the clipboard
+#[tauri::command]
+#[specta::specta]
+pub fn transcriptions_copy_to_clipboard(
+    id: String,
+    which: TranscriptionVersion,
+) -> Result<(), CommandError> {
+    let transcription = transcriptions_get(id)?;
+
+    clipboard::copy_text(transcription.text_for(&which))?;
+
+    Ok(())
+}
+
+/// Paste the most recent transcription into the focused app again, for when
+/// the original paste was missed
+#[tauri::command]
+#[specta::specta]
+pub fn paste_last_transcription(app: AppHandle) -> Result<(), CommandError> {
+    let latest = transcriptions_list(Some(ListTranscriptionsParams {
+        limit: Some(1),
+        ..Default::default()
+    }))?
+    .items
+    .into_iter()
+    .next()
+    .ok_or_else(|| CommandError::validation("There is no transcription to paste yet"))?;
+
+    paste_text(
+        app,
+        latest
+            .text_for(&TranscriptionVersion::Formatted)
+            .to_string(),
+    )?;
+
+    Ok(())
+}
+
+/// Swap the draft pasted by a hybrid-mode dictation for its refined text. The
+/// draft is selected back from the caret, so this only works until the caret moves.
+#[tauri::command]
+#[specta::specta]
+pub fn transcriptions_replace_pasted(
+    app: AppHandle,
+    id: String,
+    draft: String,
+) -> Result<(), CommandError> {
+    let transcription = transcriptions_get(id)?;
+
+    replace_pasted_text(
+        app,
+        &draft,
+        transcription
+            .text_for(&TranscriptionVersion::Formatted)
+            .to_string(),
+    )?;
+
+    Ok(())
+}
+
 /// Delete a transcription
 #[tauri::command]
 #[specta::specta]
 pub fn transcriptions_delete(app: AppHandle, id: String) -> Result<(), CommandError> {
+    // Verify it exists first
+    let existing = transcriptions_get(id.clone())?;
+
+    let mut conn = get_connection()?;
+
+    // Foreign keys are only enabled per-connection, so delete dependent rows explicitly
+    write_transaction(&mut conn, |tx| {
+        tx.execute(
+            "DELETE FROM transcription_segments WHERE transcription_id = ?",
+            params![id],
+        )?;
+        tx.execute(
+            "DELETE FROM pending_formatting WHERE transcription_id = ?",
+            params![id],
+        )?;
+        tx.execute(
+            "DELETE FROM transcription_feedback WHERE transcription_id = ?",
+            params![id],
+        )?;
+        tx.execute("DELETE FROM transcriptions WHERE id = ?", params![id])?;
+        Ok(())
+    })?;
+
+    mirror::remove_transcription(&existing);
+    recordings::remove(&app, &id);
+    emit_delete_event(&app, event_names::TRANSCRIPTIONS_DELETED, id)?;
+
+    Ok(())
+}
+
+/// Get the timestamped segments of a transcription
+#[tauri::command]
+#[specta::specta]
+pub fn transcriptions_segments(id: String) -> Result<Vec<TranscriptionSegment>, CommandError> {
     // Verify it exists first
     transcriptions_get(id.clone())?;
 
     let conn = get_connection()?;
 
-    conn.execute("DELETE FROM transcriptions WHERE id = ?", params![id])?;
+    let mut stmt = conn.prepare(
+        "SELECT id, transcription_id, segment_index, start_ms, end_ms, text, confidence
+         FROM transcription_segments WHERE transcription_id = ? ORDER BY segment_index ASC",
+    )?;
 
-    emit_delete_event(&app, event_names::TRANSCRIPTIONS_DELETED, id)?;
+    let segments = stmt
+        .query_map(params![id], |row| {
+            Ok(TranscriptionSegment {
+                id: row.get(0)?,
+                transcription_id: row.get(1)?,
+                segment_index: row.get(2)?,
+                start_ms: row.get(3)?,
+                end_ms: row.get(4)?,
+                text: row.get(5)?,
+                confidence: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
 
-    Ok(())
+    Ok(segments)
 }
 
 /// Get analytics for transcriptions
@@ -223,3 +620,239 @@ pub fn transcriptions_analytics() -> Result<TranscriptionAnalytics, CommandError
     )
     .map_err(CommandError::from)
 }
+
+/// Rate a transcription and optionally store the corrected text.
+/// Phrases that differ from the raw transcript are counted towards suggestions.
+#[tauri::command]
+#[specta::specta]
+pub fn transcriptions_rate(
+    app: AppHandle,
+    id: String,
+    rating: TranscriptionRating,
+    corrected_text: Option<String>,
+) -> Result<TranscriptionFeedback, CommandError> {
+    let transcription = transcriptions_get(id.clone())?;
+
+    let corrected_text = corrected_text
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty());
+
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    let previous: Option<(Option<String>, i64)> = conn
+        .query_row(
+            "SELECT corrected_text, created_at FROM transcription_feedback WHERE transcription_id = ?",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let (previous_correction, created_at) = match previous {
+        Some((correction, created_at)) => (correction, created_at),
+        None => (None, now),
+    };
+
+    write_transaction(&mut conn, |tx| {
+        tx.execute(
+            "INSERT INTO transcription_feedback (transcription_id, rating, corrected_text, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(transcription_id) DO UPDATE SET
+               rating = excluded.rating,
+               corrected_text = excluded.corrected_text,
+               updated_at = excluded.updated_at",
+            params![id, rating.as_str(), corrected_text, created_at, now],
+        )?;
+
+        // Re-rating the same transcription only counts corrections it didn't have before
+        if let Some(corrected) = &corrected_text {
+            let already_counted: HashSet<(String, String)> = previous_correction
+                .as_deref()
+                .map(|previous| {
+                    word_corrections(&transcription.text, previous)
+                        .into_iter()
+                        .collect()
+                })
+                .unwrap_or_default();
+            let new_corrections: Vec<(String, String)> =
+                word_corrections(&transcription.text, corrected)
+                    .into_iter()
+                    .filter(|pair| !already_counted.contains(pair))
+                    .collect();
+            record_corrections(tx, &new_corrections, now)?;
+        }
+        Ok(())
+    })?;
+
+    let feedback = TranscriptionFeedback {
+        transcription_id: id,
+        rating,
+        corrected_text,
+        created_at,
+        updated_at: now,
+    };
+
+    emit_entity_event(&app, event_names::TRANSCRIPTIONS_RATED, feedback.clone())?;
+
+    Ok(feedback)
+}
+
+/// Export rated transcriptions as JSON lines (one audio/correction pair per line)
+/// for evaluating or fine-tuning models. Returns the number of records.
+#[tauri::command]
+#[specta::specta]
+pub fn transcriptions_feedback_export(app: AppHandle, path: String) -> Result<usize, CommandError> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT t.id, t.created_at, f.rating, t.text, f.corrected_text
+         FROM transcription_feedback f
+         JOIN transcriptions t ON t.id = f.transcription_id
+         ORDER BY t.created_at ASC",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut file = std::fs::File::create(&path)
+        .map_err(|e| CommandError::invalid_input(format!("Failed to create {}: {}", path, e)))?;
+
+    for (transcription_id, created_at, rating, text, corrected_text) in &rows {
+        let record = FeedbackExportRecord {
+            transcription_id: transcription_id.clone(),
+            created_at: *created_at,
+            rating: rating.clone(),
+            text: text.clone(),
+            corrected_text: corrected_text.clone(),
+            audio_path: recordings::path(&app, transcription_id)
+                .map(|path| path.to_string_lossy().into_owned()),
+            segments: transcriptions_segments(transcription_id.clone())?,
+        };
+        let line = serde_json::to_string(&record)
+            .map_err(|e| CommandError::database(format!("Failed to serialize feedback: {}", e)))?;
+        writeln!(file, "{}", line)
+            .map_err(|e| CommandError::database(format!("Failed to write {}: {}", path, e)))?;
+    }
+
+    Ok(rows.len())
+}
+
+/// Corrections the user has made repeatedly, most frequent first
+#[tauri::command]
+#[specta::specta]
+pub fn transcriptions_correction_suggestions() -> Result<Vec<CorrectionSuggestion>, CommandError> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT original, corrected, count, last_seen_at FROM corrections
+         WHERE count >= ? ORDER BY count DESC, last_seen_at DESC",
+    )?;
+
+    let suggestions = stmt
+        .query_map(params![MIN_CORRECTION_COUNT], |row| {
+            Ok(CorrectionSuggestion {
+                original: row.get(0)?,
+                corrected: row.get(1)?,
+                count: row.get(2)?,
+                last_seen_at: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(suggestions)
+}
+
+/// Diff the raw transcript against the formatted text word by word, so the
+/// changes made by the formatter can be reviewed. Punctuation counts as part of a word.
+#[tauri::command]
+#[specta::specta]
+pub fn transcriptions_diff(id: String) -> Result<TranscriptionDiff, CommandError> {
+    let transcription = transcriptions_get(id.clone())?;
+
+    let raw: Vec<&str> = transcription.text.split_whitespace().collect();
+    let formatted: Vec<&str> = transcription
+        .formatted_text
+        .as_deref()
+        .unwrap_or(&transcription.text)
+        .split_whitespace()
+        .collect();
+
+    let mut spans: Vec<DiffSpan> = Vec::new();
+    let mut words_removed = 0;
+    let mut words_added = 0;
+    let mut current: Option<(DiffOp, Vec<&str>)> = None;
+
+    for (op, word) in diff_words(&raw, &formatted) {
+        match op {
+            DiffOp::Removed => words_removed += 1,
+            DiffOp::Added => words_added += 1,
+            DiffOp::Equal => {}
+        }
+        if let Some((current_op, words)) = current.as_mut() {
+            if *current_op == op {
+                words.push(word);
+                continue;
+            }
+        }
+        if let Some((op, words)) = current.replace((op, vec![word])) {
+            spans.push(diff_span(op, &words));
+        }
+    }
+    if let Some((op, words)) = current {
+        spans.push(diff_span(op, &words));
+    }
+
+    Ok(TranscriptionDiff {
+        transcription_id: id,
+        spans,
+        words_removed,
+        words_added,
+    })
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn diff_span(op: DiffOp, words: &[&str]) -> DiffSpan {
+    let kind = match op {
+        DiffOp::Equal => DiffKind::Equal,
+        DiffOp::Removed => DiffKind::Removed,
+        DiffOp::Added => DiffKind::Added,
+    };
+
+    DiffSpan {
+        kind,
+        text: words.join(" "),
+    }
+}
+
+/// Count `(original, corrected)` phrase pairs towards correction suggestions
+fn record_corrections(
+    conn: &Connection,
+    corrections: &[(String, String)],
+    now: i64,
+) -> rusqlite::Result<()> {
+    for (original, corrected) in corrections {
+        conn.execute(
+            "INSERT INTO corrections (original, corrected, count, last_seen_at) VALUES (?, ?, 1, ?)
+             ON CONFLICT(original, corrected) DO UPDATE SET
+               count = count + 1,
+               last_seen_at = excluded.last_seen_at",
+            params![original, corrected, now],
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,352 @@
+use crate::commands::audit_log::{record_audit, AuditAction, AuditEntity};
+use crate::commands::error::CommandError;
+use crate::commands::validation;
+use crate::commands::writing_styles::{writing_styles_get, WritingStyleCategory};
+use crate::db::pool::{get_connection, write_transaction};
+use crate::events::{emit_delete_event, emit_entity_event, names as event_names};
+use rusqlite::{params, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Writing style applied when dictating into a browser tab whose URL matches
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UrlRule {
+    pub id: String,
+    /// Host with an optional path, without the scheme. `*` matches any run of
+    /// characters, e.g. `mail.google.com` or `*.atlassian.net/browse`. A host also
+    /// matches its subdomains and a path matches everything below it.
+    pub pattern: String,
+    pub category: WritingStyleCategory,
+    /// Style to format with; `None` uses the category's selected style
+    pub style: Option<String>,
+    pub enabled: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CreateUrlRuleInput {
+    pub pattern: String,
+    pub category: WritingStyleCategory,
+    pub style: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UpdateUrlRuleInput {
+    pub pattern: Option<String>,
+    pub category: Option<WritingStyleCategory>,
+    /// An empty string clears the style
+    pub style: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+const SELECT_COLUMNS: &str =
+    "SELECT id, pattern, category, style, enabled, created_at, updated_at FROM url_rules";
+
+fn row_to_rule(row: &Row) -> rusqlite::Result<UrlRule> {
+    let category: String = row.get(2)?;
+    Ok(UrlRule {
+        id: row.get(0)?,
+        pattern: row.get(1)?,
+        category: WritingStyleCategory::parse(&category).unwrap_or(WritingStyleCategory::General),
+        style: row.get(3)?,
+        enabled: row.get(4)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// List all URL rules, most specific pattern first
+#[tauri::command]
+#[specta::specta]
+pub fn url_rules_list() -> Result<Vec<UrlRule>, CommandError> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "{} ORDER BY length(pattern) DESC, created_at ASC",
+        SELECT_COLUMNS
+    ))?;
+    let rows = stmt.query_map([], row_to_rule)?;
+
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Get a single URL rule by ID
+#[tauri::command]
+#[specta::specta]
+pub fn url_rules_get(id: String) -> Result<UrlRule, CommandError> {
+    let conn = get_connection()?;
+
+    conn.query_row(
+        &format!("{} WHERE id = ?", SELECT_COLUMNS),
+        params![id],
+        row_to_rule,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => CommandError::not_found("UrlRule", &id),
+        _ => CommandError::database(e.to_string()),
+    })
+}
+
+/// Create a new URL rule
+#[tauri::command]
+#[specta::specta]
+pub fn url_rules_create(
+    app: AppHandle,
+    input: CreateUrlRuleInput,
+) -> Result<UrlRule, CommandError> {
+    let pattern = validate_pattern(&input.pattern, None)?;
+    let style = normalize_style(input.style);
+    if let Some(style) = &style {
+        validation::style_for_category(&input.category, style)?;
+    }
+
+    let mut conn = get_connection()?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::UrlRule, &id, AuditAction::Create, now)?;
+        tx.execute(
+            "INSERT INTO url_rules (id, pattern, category, style, enabled, created_at, updated_at) VALUES (?, ?, ?, ?, 1, ?, ?)",
+            params![id, pattern, input.category.as_str(), style, now, now],
+        )?;
+        Ok(())
+    })?;
+
+    let created = UrlRule {
+        id: id.clone(),
+        pattern,
+        category: input.category,
+        style,
+        enabled: true,
+        created_at: now,
+        updated_at: now,
+    };
+
+    emit_entity_event(&app, event_names::URL_RULES_CREATED, created.clone())?;
+
+    Ok(created)
+}
+
+/// Update an existing URL rule
+#[tauri::command]
+#[specta::specta]
+pub fn url_rules_update(
+    app: AppHandle,
+    id: String,
+    input: UpdateUrlRuleInput,
+) -> Result<UrlRule, CommandError> {
+    // First verify it exists
+    let existing = url_rules_get(id.clone())?;
+
+    let new_pattern = match input.pattern {
+        Some(pattern) => validate_pattern(&pattern, Some(&id))?,
+        None => existing.pattern,
+    };
+    let new_category = input.category.unwrap_or(existing.category);
+    let new_style = match input.style {
+        Some(style) => normalize_style(Some(style)),
+        None => existing.style,
+    };
+    if let Some(style) = &new_style {
+        validation::style_for_category(&new_category, style)?;
+    }
+    let new_enabled = input.enabled.unwrap_or(existing.enabled);
+
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::UrlRule, &id, AuditAction::Update, now)?;
+        tx.execute(
+            "UPDATE url_rules SET pattern = ?, category = ?, style = ?, enabled = ?, updated_at = ? WHERE id = ?",
+            params![new_pattern, new_category.as_str(), new_style, new_enabled, now, id],
+        )?;
+        Ok(())
+    })?;
+
+    let updated = UrlRule {
+        id: id.clone(),
+        pattern: new_pattern,
+        category: new_category,
+        style: new_style,
+        enabled: new_enabled,
+        created_at: existing.created_at,
+        updated_at: now,
+    };
+
+    emit_entity_event(&app, event_names::URL_RULES_UPDATED, updated.clone())?;
+
+    Ok(updated)
+}
+
+/// Delete a URL rule
+#[tauri::command]
+#[specta::specta]
+pub fn url_rules_delete(app: AppHandle, id: String) -> Result<(), CommandError> {
+    // Verify it exists first
+    url_rules_get(id.clone())?;
+
+    let mut conn = get_connection()?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    write_transaction(&mut conn, |tx| {
+        record_audit(tx, AuditEntity::UrlRule, &id, AuditAction::Delete, now)?;
+        tx.execute("DELETE FROM url_rules WHERE id = ?", params![id])?;
+        Ok(())
+    })?;
+
+    emit_delete_event(&app, event_names::URL_RULES_DELETED, id)?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Whether any enabled rule exists, so the URL is only looked up when it matters
+pub fn has_enabled_rules() -> bool {
+    get_connection()
+        .ok()
+        .and_then(|conn| {
+            conn.query_row(
+                "SELECT 1 FROM url_rules WHERE enabled = 1 LIMIT 1",
+                [],
+                |_| Ok(()),
+            )
+            .optional()
+            .ok()
+            .flatten()
+        })
+        .is_some()
+}
+
+/// The most specific enabled rule matching a URL
+pub fn rule_for_url(url: &str) -> Option<UrlRule> {
+    let rules = url_rules_list().ok()?;
+    rules
+        .into_iter()
+        .find(|rule| rule.enabled && pattern_matches(&rule.pattern, url))
+}
+
+/// Category and style of the rule matching a URL, falling back to the
+/// category's selected style when the rule names none
+pub fn style_for_url(url: &str) -> Option<(WritingStyleCategory, String)> {
+    let rule = rule_for_url(url)?;
+    let style = rule.style.clone().unwrap_or_else(|| {
+        writing_styles_get(rule.category.clone())
+            .map(|style| style.selected_style)
+            .unwrap_or_else(|_| rule.category.default_style().to_string())
+    });
+    Some((rule.category, style))
+}
+
+/// Match a URL against a rule pattern. The scheme, query and fragment are
+/// ignored, and so is case.
+fn pattern_matches(pattern: &str, url: &str) -> bool {
+    let url = url.to_lowercase();
+    let url = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let (host, path) = url.split_once('/').unwrap_or((url, ""));
+    // Credentials and port aren't part of what users write in a pattern
+    let host = host.rsplit('@').next().unwrap_or(host);
+    let host = host.split(':').next().unwrap_or(host);
+
+    let pattern = pattern.to_lowercase();
+    let (pattern_host, pattern_path) = pattern.split_once('/').unwrap_or((&pattern, ""));
+
+    let host_matches = glob_matches(pattern_host, host, false)
+        || host
+            .char_indices()
+            .filter(|&(_, c)| c == '.')
+            .any(|(i, _)| glob_matches(pattern_host, &host[i + 1..], false));
+    host_matches && glob_matches(pattern_path.trim_end_matches('/'), path, true)
+}
+
+/// Match text against a pattern where `*` stands for any run of characters.
+/// With `prefix`, the pattern only has to match the start of the text, up to a
+/// `/` boundary.
+fn glob_matches(pattern: &str, text: &str, prefix: bool) -> bool {
+    match pattern.split_once('*') {
+        None => {
+            if prefix {
+                pattern.is_empty()
+                    || text == pattern
+                    || text
+                        .strip_prefix(pattern)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            } else {
+                text == pattern
+            }
+        }
+        Some((head, tail)) => {
+            let Some(rest) = text.strip_prefix(head) else {
+                return false;
+            };
+            (0..=rest.len())
+                .filter(|&i| rest.is_char_boundary(i))
+                .any(|i| glob_matches(tail, &rest[i..], prefix))
+        }
+    }
+}
+
+/// Trim a pattern, drop a pasted scheme and make sure no other rule uses it
+fn validate_pattern(pattern: &str, id: Option<&str>) -> Result<String, CommandError> {
+    let pattern = pattern.trim().to_lowercase();
+    let pattern = pattern
+        .split_once("://")
+        .map_or(pattern.as_str(), |(_, rest)| rest)
+        .trim_end_matches('/');
+    let host = pattern.split('/').next().unwrap_or_default();
+    if !host.chars().any(char::is_alphanumeric) {
+        return Err(CommandError::validation(
+            "URL pattern must start with a domain, e.g. mail.google.com",
+        ));
+    }
+
+    let conn = get_connection()?;
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT id FROM url_rules WHERE pattern = ?",
+            params![pattern],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    match existing {
+        Some(existing_id) if Some(existing_id.as_str()) != id => {
+            Err(CommandError::duplicate("UrlRule", "pattern", pattern))
+        }
+        _ => Ok(pattern.to_string()),
+    }
+}
+
+fn normalize_style(style: Option<String>) -> Option<String> {
+    style
+        .map(|style| style.trim().to_string())
+        .filter(|style| !style.is_empty())
+}
@@ -0,0 +1,68 @@
+use crate::commands::error::CommandError;
+use crate::commands::writing_styles::WritingStyleCategory;
+
+// ============================================================================
+// Limits
+// ============================================================================
+
+/// Longest keyterm; the STT providers cap individual terms well below this
+pub const MAX_KEYTERM_CHARS: usize = 100;
+
+/// Longest spoken trigger of a shortcut or macro
+pub const MAX_TRIGGER_CHARS: usize = 100;
+
+/// Longest shortcut replacement or macro template
+pub const MAX_REPLACEMENT_CHARS: usize = 5_000;
+
+/// Longest custom prompt of a writing style
+pub const MAX_PROMPT_CHARS: usize = 4_000;
+
+/// Longest app name of an app rule
+pub const MAX_APP_NAME_CHARS: usize = 200;
+
+// ============================================================================
+// Checks
+// ============================================================================
+
+/// `value` trimmed, rejected when it is empty or longer than `max_chars`.
+/// `field` names the value in the error, e.g. "Keyterm".
+pub fn required(field: &str, value: &str, max_chars: usize) -> Result<String, CommandError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(CommandError::validation(format!(
+            "{} cannot be empty",
+            field
+        )));
+    }
+    max_length(field, value, max_chars)?;
+
+    Ok(value.to_string())
+}
+
+/// Reject `value` when it is longer than `max_chars`, for fields that may be empty
+pub fn max_length(field: &str, value: &str, max_chars: usize) -> Result<(), CommandError> {
+    if value.chars().count() > max_chars {
+        return Err(CommandError::validation(format!(
+            "{} cannot be longer than {} characters",
+            field, max_chars
+        )));
+    }
+
+    Ok(())
+}
+
+/// Reject a style the category doesn't offer
+pub fn style_for_category(
+    category: &WritingStyleCategory,
+    style: &str,
+) -> Result<(), CommandError> {
+    if category.styles().iter().any(|(key, _)| *key == style) {
+        return Ok(());
+    }
+
+    Err(CommandError::validation(format!(
+        "'{}' is not a {} writing style",
+        style,
+        category.as_str()
+    )))
+}
@@ -1,5 +1,7 @@
+use crate::commands::audit_log::{record_audit, AuditAction, AuditEntity};
 use crate::commands::error::CommandError;
-use crate::db::pool::get_connection;
+use crate::commands::validation::{self, MAX_PROMPT_CHARS};
+use crate::db::pool::{get_connection, write_transaction};
 use crate::events::{emit_entity_event, names as event_names};
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
@@ -11,6 +13,10 @@ use tauri::AppHandle;
 // Types
 // ============================================================================
 
+/// Style the formatter structures text as Markdown with. Applied to dictation into
+/// notes and Markdown editors in any category rather than picked by the user.
+pub const MARKDOWN_STYLE: &str = "markdown";
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub enum WritingStyleCategory {
     #[serde(rename = "Personal")]
@@ -32,6 +38,93 @@ impl WritingStyleCategory {
             Self::General => "General",
         }
     }
+
+    /// Category stored under a name returned by `as_str`
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "Personal" => Some(Self::Personal),
+            "Work" => Some(Self::Work),
+            "Email" => Some(Self::Email),
+            "General" => Some(Self::General),
+            _ => None,
+        }
+    }
+
+    /// Category for a native app name, mirroring APP_TO_CATEGORY in the frontend
+    pub fn for_app(app_name: &str) -> Self {
+        match app_name {
+            "Messages" | "WhatsApp" | "Telegram" => Self::Personal,
+            "Slack" | "Microsoft Teams" | "Discord" | "Linkedin" | "LinkedIn" => Self::Work,
+            "Mail" | "Microsoft Outlook" => Self::Email,
+            _ => Self::General,
+        }
+    }
+
+    /// Category for a web app's URL, mirroring DOMAIN_TO_CATEGORY in the frontend.
+    /// `None` when the domain isn't a known web app.
+    pub fn for_url(url: &str) -> Option<Self> {
+        const DOMAINS: &[(&str, WritingStyleCategory)] = &[
+            ("web.whatsapp.com", WritingStyleCategory::Personal),
+            ("web.telegram.org", WritingStyleCategory::Personal),
+            ("app.slack.com", WritingStyleCategory::Work),
+            ("teams.microsoft.com", WritingStyleCategory::Work),
+            ("discord.com", WritingStyleCategory::Work),
+            ("linkedin.com", WritingStyleCategory::Work),
+            ("mail.google.com", WritingStyleCategory::Email),
+            ("outlook.live.com", WritingStyleCategory::Email),
+            ("outlook.office.com", WritingStyleCategory::Email),
+        ];
+
+        let host = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let host = host.split(['/', '?', '#', ':']).next().unwrap_or_default();
+        let host = host.to_lowercase();
+
+        DOMAINS
+            .iter()
+            .find(|(domain, _)| {
+                host == *domain
+                    || host
+                        .strip_suffix(domain)
+                        .is_some_and(|sub| sub.ends_with('.'))
+            })
+            .map(|(_, category)| category.clone())
+    }
+
+    /// Style keys and labels offered for this category, mirroring STYLES in the frontend
+    pub fn styles(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Personal => &[
+                ("casual", "Casual"),
+                ("brief", "Brief"),
+                ("expressive", "Expressive"),
+            ],
+            Self::Work => &[
+                ("professional", "Professional"),
+                ("casual", "Casual"),
+                ("excited", "Excited"),
+            ],
+            Self::Email => &[
+                ("formal", "Formal"),
+                ("professional", "Professional"),
+                ("friendly", "Friendly"),
+            ],
+            Self::General => &[
+                ("formal", "Formal."),
+                ("casual", "Casual"),
+                ("excited", "Excited!"),
+            ],
+        }
+    }
+
+    /// Style used when the category has no row yet
+    pub fn default_style(&self) -> &'static str {
+        match self {
+            Self::Personal => "casual",
+            Self::Work => "professional",
+            Self::Email => "formal",
+            Self::General => "casual",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -106,6 +199,20 @@ pub fn writing_styles_get(category: WritingStyleCategory) -> Result<WritingStyle
     })
 }
 
+/// Custom prompt of a category's writing style, if one is set
+pub fn custom_prompt_for(category: &str) -> Option<String> {
+    let conn = get_connection().ok()?;
+
+    conn.query_row(
+        "SELECT custom_prompt FROM writing_styles WHERE category = ?",
+        params![category],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .ok()
+    .flatten()
+    .filter(|prompt| !prompt.trim().is_empty())
+}
+
 /// Update a writing style (upsert)
 #[tauri::command]
 #[specta::specta]
@@ -114,7 +221,14 @@ pub fn writing_styles_update(
     category: WritingStyleCategory,
     input: UpdateWritingStyleInput,
 ) -> Result<WritingStyle, CommandError> {
-    let conn = get_connection()?;
+    if let Some(style) = &input.selected_style {
+        validation::style_for_category(&category, style)?;
+    }
+    if let Some(prompt) = &input.custom_prompt {
+        validation::max_length("Custom prompt", prompt, MAX_PROMPT_CHARS)?;
+    }
+
+    let mut conn = get_connection()?;
 
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -137,22 +251,32 @@ pub fn writing_styles_update(
         .custom_prompt
         .or_else(|| existing.as_ref().and_then(|e| e.custom_prompt.clone()));
 
-    conn.execute(
-        "INSERT INTO writing_styles (category, selected_style, default_prompt, custom_prompt, updated_at)
-         VALUES (?, ?, ?, ?, ?)
-         ON CONFLICT(category) DO UPDATE SET
-           selected_style = excluded.selected_style,
-           default_prompt = excluded.default_prompt,
-           custom_prompt = excluded.custom_prompt,
-           updated_at = excluded.updated_at",
-        params![
+    write_transaction(&mut conn, |tx| {
+        record_audit(
+            tx,
+            AuditEntity::WritingStyle,
             category.as_str(),
-            selected_style,
-            default_prompt,
-            custom_prompt,
-            now
-        ],
-    )?;
+            AuditAction::Update,
+            now,
+        )?;
+        tx.execute(
+            "INSERT INTO writing_styles (category, selected_style, default_prompt, custom_prompt, updated_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(category) DO UPDATE SET
+               selected_style = excluded.selected_style,
+               default_prompt = excluded.default_prompt,
+               custom_prompt = excluded.custom_prompt,
+               updated_at = excluded.updated_at",
+            params![
+                category.as_str(),
+                selected_style,
+                default_prompt,
+                custom_prompt,
+                now
+            ],
+        )?;
+        Ok(())
+    })?;
 
     let style = WritingStyle {
         category: category.as_str().to_string(),
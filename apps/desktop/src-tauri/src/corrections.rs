@@ -0,0 +1,157 @@
+/// A correction must be seen this many times before it is suggested
+pub const MIN_CORRECTION_COUNT: i64 = 2;
+
+/// Longest phrase (in words) on either side of a correction worth remembering.
+/// Longer edits are rewrites rather than misheard words.
+const MAX_CORRECTION_WORDS: usize = 3;
+
+/// How a word changed between two versions of a text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    Equal,
+    Removed,
+    Added,
+}
+
+/// Largest LCS table `diff_words` builds, in cells. Past it, the differing
+/// middle of the texts is reported as removed and added wholesale.
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+/// Word-level diff (longest common subsequence) of two word lists.
+/// Where a word was replaced, removals come before additions.
+pub fn diff_words<'a>(from: &[&'a str], to: &[&'a str]) -> Vec<(DiffOp, &'a str)> {
+    // Corrections touch a few words; the shared start and end need no table
+    let prefix = from.iter().zip(to).take_while(|(a, b)| a == b).count();
+    let suffix = from[prefix..]
+        .iter()
+        .rev()
+        .zip(to[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (from_mid, to_mid) = (
+        &from[prefix..from.len() - suffix],
+        &to[prefix..to.len() - suffix],
+    );
+
+    let mut ops = Vec::with_capacity(from.len().max(to.len()));
+    ops.extend(from[..prefix].iter().map(|&word| (DiffOp::Equal, word)));
+    if (from_mid.len() + 1).saturating_mul(to_mid.len() + 1) > MAX_DIFF_CELLS {
+        ops.extend(from_mid.iter().map(|&word| (DiffOp::Removed, word)));
+        ops.extend(to_mid.iter().map(|&word| (DiffOp::Added, word)));
+    } else {
+        diff_middle(from_mid, to_mid, &mut ops);
+    }
+    ops.extend(
+        from[from.len() - suffix..]
+            .iter()
+            .map(|&word| (DiffOp::Equal, word)),
+    );
+
+    ops
+}
+
+/// LCS diff of the part of two word lists that differs
+fn diff_middle<'a>(from: &[&'a str], to: &[&'a str], ops: &mut Vec<(DiffOp, &'a str)>) {
+    // Longest common subsequence table, filled from the end
+    let width = to.len() + 1;
+    let mut lcs = vec![0u32; (from.len() + 1) * width];
+    for i in (0..from.len()).rev() {
+        for j in (0..to.len()).rev() {
+            lcs[i * width + j] = if from[i] == to[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < from.len() || j < to.len() {
+        if i < from.len() && j < to.len() && from[i] == to[j] {
+            ops.push((DiffOp::Equal, from[i]));
+            i += 1;
+            j += 1;
+        } else if i < from.len()
+            && (j == to.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+        {
+            ops.push((DiffOp::Removed, from[i]));
+            i += 1;
+        } else {
+            ops.push((DiffOp::Added, to[j]));
+            j += 1;
+        }
+    }
+}
+
+/// How alike two texts are, from 0.0 (nothing shared) to 1.0 (same words).
+/// Case and punctuation are ignored.
+pub fn similarity(a: &str, b: &str) -> f32 {
+    let normalize = |text: &str| -> Vec<String> {
+        text.split_whitespace()
+            .map(|w| clean_word(w).to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect()
+    };
+    let a = normalize(a);
+    let b = normalize(b);
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    let a: Vec<&str> = a.iter().map(String::as_str).collect();
+    let b: Vec<&str> = b.iter().map(String::as_str).collect();
+    let shared = diff_words(&a, &b)
+        .iter()
+        .filter(|(op, _)| *op == DiffOp::Equal)
+        .count();
+
+    (2 * shared) as f32 / (a.len() + b.len()) as f32
+}
+
+/// Strip the punctuation Whisper attaches to a word, keeping its case
+fn clean_word(word: &str) -> &str {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+/// Phrases the user replaced when correcting a transcription, as
+/// `(original, corrected)` pairs in the order they appear.
+/// Pure insertions and deletions are skipped; only substitutions say what was misheard.
+pub fn word_corrections(original: &str, corrected: &str) -> Vec<(String, String)> {
+    let from: Vec<&str> = original
+        .split_whitespace()
+        .map(clean_word)
+        .filter(|w| !w.is_empty())
+        .collect();
+    let to: Vec<&str> = corrected
+        .split_whitespace()
+        .map(clean_word)
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut pairs = Vec::new();
+    let mut removed: Vec<&str> = Vec::new();
+    let mut added: Vec<&str> = Vec::new();
+
+    let mut flush = |removed: &mut Vec<&str>, added: &mut Vec<&str>| {
+        if !removed.is_empty()
+            && !added.is_empty()
+            && removed.len() <= MAX_CORRECTION_WORDS
+            && added.len() <= MAX_CORRECTION_WORDS
+        {
+            pairs.push((removed.join(" "), added.join(" ")));
+        }
+        removed.clear();
+        added.clear();
+    };
+
+    for (op, word) in diff_words(&from, &to) {
+        match op {
+            DiffOp::Equal => flush(&mut removed, &mut added),
+            DiffOp::Removed => removed.push(word),
+            DiffOp::Added => added.push(word),
+        }
+    }
+    flush(&mut removed, &mut added);
+
+    pairs
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+
+use crate::commands::error::CommandError;
+use crate::commands::settings::{keys, read_parsed_setting, write_setting};
+use crate::db::pool::get_connection;
+use crate::jobs;
+
+/// Maintenance runs automatically once this much time has passed since the last run
+const MAINTENANCE_INTERVAL_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// How often the background task checks whether maintenance is due
+const CHECK_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+/// Wait after launch before the first check, so startup isn't slowed down
+const STARTUP_DELAY_SECS: u64 = 120;
+
+/// Result of a maintenance run
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DbMaintenanceReport {
+    /// Whether `PRAGMA integrity_check` found no problems
+    pub integrity_ok: bool,
+    /// Problems reported by the integrity check (empty when it passed)
+    pub integrity_errors: Vec<String>,
+    /// Size of the database and its WAL file in bytes, before and after
+    pub size_before: u64,
+    pub size_after: u64,
+    pub ran_at: i64,
+}
+
+/// Check integrity, then checkpoint the WAL and vacuum. A database that fails
+/// the integrity check is left untouched.
+pub fn run_maintenance() -> Result<DbMaintenanceReport, String> {
+    let conn = get_connection()?;
+    let path = conn
+        .path()
+        .map(|path| path.to_string())
+        .ok_or_else(|| "Database has no file path".to_string())?;
+
+    let size_before = database_size(&path);
+
+    let mut stmt = conn
+        .prepare("PRAGMA integrity_check")
+        .map_err(|e| format!("Failed to check database integrity: {}", e))?;
+    let results = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to check database integrity: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to check database integrity: {}", e))?;
+    drop(stmt);
+
+    let integrity_ok = results.len() == 1 && results[0] == "ok";
+    let integrity_errors = if integrity_ok { Vec::new() } else { results };
+
+    if integrity_ok {
+        // VACUUM writes the rebuilt database through the WAL, so checkpoint afterwards too
+        conn.execute_batch(
+            "PRAGMA wal_checkpoint(TRUNCATE); VACUUM; PRAGMA wal_checkpoint(TRUNCATE);",
+        )
+        .map_err(|e| format!("Failed to vacuum database: {}", e))?;
+    } else {
+        error!("Database integrity check failed: {:?}", integrity_errors);
+    }
+
+    let ran_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    // Release the connection before writing through another one
+    drop(conn);
+    write_setting(keys::DB_LAST_MAINTENANCE, &ran_at.to_string()).map_err(|e| e.message)?;
+
+    let report = DbMaintenanceReport {
+        integrity_ok,
+        integrity_errors,
+        size_before,
+        size_after: database_size(&path),
+        ran_at,
+    };
+
+    info!(
+        "🧹 Database maintenance finished: {} -> {} bytes",
+        report.size_before, report.size_after
+    );
+
+    Ok(report)
+}
+
+/// Register the `db_maintenance` job, which runs maintenance whenever a month
+/// has passed since the last run
+pub fn register_maintenance_job() {
+    jobs::register(
+        "db_maintenance",
+        Duration::from_secs(CHECK_INTERVAL_SECS),
+        Duration::from_secs(STARTUP_DELAY_SECS),
+        || async {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64;
+            let last_run = read_parsed_setting(keys::DB_LAST_MAINTENANCE, 0i64);
+            if now - last_run < MAINTENANCE_INTERVAL_SECS {
+                return Ok(());
+            }
+
+            tauri::async_runtime::spawn_blocking(run_maintenance)
+                .await
+                .map_err(|e| format!("Database maintenance task panicked: {}", e))?
+                .map(|_| ())
+        },
+    );
+}
+
+/// Bytes on disk for the database file and its WAL
+pub fn database_size(path: &str) -> u64 {
+    let file_size = |path: &Path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    file_size(Path::new(path)) + file_size(Path::new(&format!("{}-wal", path)))
+}
+
+/// Check the database for corruption and compact it
+#[tauri::command]
+#[specta::specta]
+pub async fn db_maintenance() -> Result<DbMaintenanceReport, CommandError> {
+    // VACUUM can take a while on a large history
+    let report = tauri::async_runtime::spawn_blocking(run_maintenance)
+        .await
+        .map_err(|e| CommandError::database(format!("Maintenance task failed: {}", e)))??;
+
+    Ok(report)
+}
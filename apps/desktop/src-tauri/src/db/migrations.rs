@@ -57,5 +57,241 @@ pub fn get_migrations() -> Vec<Migration> {
             sql: include_str!("../migrations/009_rename_notes_to_general.sql"),
             kind: MigrationKind::Up,
         },
+        Migration {
+            version: 10,
+            description: "create_sessions_table",
+            sql: include_str!("../migrations/010_create_sessions.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 11,
+            description: "create_transcription_segments_table",
+            sql: include_str!("../migrations/011_create_transcription_segments.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 12,
+            description: "create_pending_formatting_table",
+            sql: include_str!("../migrations/012_create_pending_formatting.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 13,
+            description: "create_formatter_usage_table",
+            sql: include_str!("../migrations/013_create_formatter_usage.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 14,
+            description: "add_keyterm_usage",
+            sql: include_str!("../migrations/014_add_keyterm_usage.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 15,
+            description: "create_transcription_feedback_table",
+            sql: include_str!("../migrations/015_create_transcription_feedback.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 16,
+            description: "add_transcription_pinned",
+            sql: include_str!("../migrations/016_add_transcription_pinned.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 17,
+            description: "add_transcription_app_name",
+            sql: include_str!("../migrations/017_add_transcription_app_name.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 18,
+            description: "create_audit_log",
+            sql: include_str!("../migrations/018_create_audit_log.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 19,
+            description: "add_sync_tracking",
+            sql: include_str!("../migrations/019_add_sync_tracking.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 20,
+            description: "create_macros_table",
+            sql: include_str!("../migrations/020_create_macros.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 21,
+            description: "add_shortcut_match_options",
+            sql: include_str!("../migrations/021_add_shortcut_match_options.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 22,
+            description: "add_pending_formatting_custom_prompt",
+            sql: include_str!("../migrations/022_add_pending_formatting_custom_prompt.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 23,
+            description: "create_url_rules_table",
+            sql: include_str!("../migrations/023_create_url_rules.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 24,
+            description: "add_pending_formatting_context",
+            sql: include_str!("../migrations/024_add_pending_formatting_context.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 25,
+            description: "create_note_revisions_table",
+            sql: include_str!("../migrations/025_create_note_revisions.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 26,
+            description: "add_note_pinned_archived",
+            sql: include_str!("../migrations/026_add_note_pinned_archived.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 27,
+            description: "create_note_attachments_table",
+            sql: include_str!("../migrations/027_create_note_attachments.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 28,
+            description: "add_keyterm_shortcut_unique_indexes",
+            sql: include_str!("../migrations/028_add_keyterm_shortcut_unique_indexes.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 29,
+            description: "create_app_rules_table",
+            sql: include_str!("../migrations/029_create_app_rules.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 30,
+            description: "store_local_model_as_manifest_id",
+            sql: include_str!("../migrations/030_store_local_model_as_manifest_id.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 31,
+            description: "mark_existing_databases_seeded",
+            sql: include_str!("../migrations/031_mark_existing_databases_seeded.sql"),
+            kind: MigrationKind::Up,
+        },
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    /// Run the migrations in `versions` against `conn`, in order
+    fn migrate(conn: &Connection, versions: impl Fn(i64) -> bool) {
+        for migration in get_migrations().into_iter().filter(|m| versions(m.version)) {
+            conn.execute_batch(migration.sql).unwrap();
+        }
+    }
+
+    fn insert_shortcut(conn: &Connection, id: &str, trigger: &str, case_sensitive: bool) -> bool {
+        conn.execute(
+            "INSERT INTO shortcuts (id, trigger, replacement, category, case_sensitive, whole_word, created_at, updated_at)
+             VALUES (?1, ?2, 'replacement', 'all', ?3, 1, 0, 0)",
+            rusqlite::params![id, trigger, case_sensitive],
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn existing_duplicates_are_flagged_not_deleted() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn, |version| version < 28);
+        insert_shortcut(&conn, "a", "brb", false);
+        insert_shortcut(&conn, "b", "BRB", false);
+        insert_shortcut(&conn, "c", "BRB", true);
+        conn.execute_batch(
+            "INSERT INTO keyterms (id, text, category, created_at, updated_at) VALUES ('x', 'Dicto', 'all', 0, 0);
+             INSERT INTO keyterms (id, text, category, created_at, updated_at) VALUES ('y', 'dicto', 'all', 0, 0);",
+        )
+        .unwrap();
+        migrate(&conn, |version| version >= 28);
+
+        let flagged = |table: &str| -> Vec<(String, bool)> {
+            let mut stmt = conn
+                .prepare(&format!(
+                    "SELECT id, is_duplicate FROM {} ORDER BY id",
+                    table
+                ))
+                .unwrap();
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .unwrap();
+            rows.collect::<Result<_, _>>().unwrap()
+        };
+        assert_eq!(
+            flagged("shortcuts"),
+            vec![
+                ("a".to_string(), false),
+                ("b".to_string(), true),
+                ("c".to_string(), false)
+            ]
+        );
+        assert_eq!(
+            flagged("keyterms"),
+            vec![("x".to_string(), false), ("y".to_string(), true)]
+        );
+
+        // Nothing was deleted, so nothing is pushed to other devices as deleted
+        let tombstones: i64 = conn
+            .query_row("SELECT COUNT(*) FROM sync_tombstones", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(tombstones, 0);
+    }
+
+    #[test]
+    fn only_databases_in_use_are_marked_seeded() {
+        let seeded = |conn: &Connection| -> bool {
+            conn.query_row(
+                "SELECT COUNT(*) FROM settings WHERE key = 'defaultsSeeded'",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .unwrap()
+                > 0
+        };
+
+        let fresh = Connection::open_in_memory().unwrap();
+        migrate(&fresh, |_| true);
+        assert!(!seeded(&fresh));
+
+        let existing = Connection::open_in_memory().unwrap();
+        migrate(&existing, |version| version < 31);
+        insert_shortcut(&existing, "a", "brb", false);
+        migrate(&existing, |version| version >= 31);
+        assert!(seeded(&existing));
+    }
+
+    #[test]
+    fn case_sensitive_triggers_only_clash_with_the_same_capitalization() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn, |_| true);
+
+        assert!(insert_shortcut(&conn, "a", "BRB", true));
+        assert!(insert_shortcut(&conn, "b", "brb", true));
+        assert!(!insert_shortcut(&conn, "c", "BRB", true));
+
+        assert!(insert_shortcut(&conn, "d", "omw", false));
+        assert!(!insert_shortcut(&conn, "e", "OMW", false));
+    }
+}
@@ -1,4 +1,5 @@
-mod migrations;
+pub mod maintenance;
+pub(crate) mod migrations;
 pub mod pool;
 
 use tauri::Manager;
@@ -1,16 +1,51 @@
 use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{Connection, ErrorCode, Transaction, TransactionBehavior};
+use serde::{Deserialize, Serialize};
+use specta::Type;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::OnceLock;
+use std::time::Duration;
 
 pub type DbPool = Pool<SqliteConnectionManager>;
 pub type DbConnection = PooledConnection<SqliteConnectionManager>;
 
 static DB_POOL: OnceLock<DbPool> = OnceLock::new();
 
+/// How long a statement waits for another connection's lock before failing with SQLITE_BUSY
+const BUSY_TIMEOUT_MS: u64 = 5000;
+
+/// Write transactions still busy after the timeout are retried this many times
+const MAX_WRITE_RETRIES: u32 = 3;
+
+/// Backoff before the first retry, doubled for each one after
+const RETRY_BACKOFF_MS: u64 = 50;
+
+/// Contention counters since launch, reported by `db_diagnostics`
+static BUSY_RETRIES: AtomicU64 = AtomicU64::new(0);
+static BUSY_FAILURES: AtomicU64 = AtomicU64::new(0);
+static WRITE_TRANSACTIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Database contention since launch
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DbDiagnostics {
+    pub busy_timeout_ms: u64,
+    /// Write transactions run through `write_transaction`
+    pub write_transactions: u64,
+    /// Times a write transaction hit SQLITE_BUSY and was retried
+    pub busy_retries: u64,
+    /// Write transactions that were still busy after every retry
+    pub busy_failures: u64,
+    pub pool_connections: u32,
+    pub pool_idle_connections: u32,
+}
+
 /// Initialize the database connection pool
 pub fn init_pool(db_path: PathBuf) -> Result<(), String> {
-    let manager = SqliteConnectionManager::file(&db_path);
+    // Every pooled connection waits for locks instead of failing immediately
+    let manager = SqliteConnectionManager::file(&db_path)
+        .with_init(|conn| conn.busy_timeout(Duration::from_millis(BUSY_TIMEOUT_MS)));
 
     let pool = Pool::builder()
         .max_size(10)
@@ -40,3 +75,76 @@ pub fn get_connection() -> Result<DbConnection, String> {
         .get()
         .map_err(|e| format!("Failed to get database connection: {}", e))
 }
+
+/// Run `f` in an immediate write transaction, retrying when the database stays busy.
+/// Taking the write lock up front avoids the SQLITE_BUSY that a deferred transaction
+/// gets when it upgrades from reading to writing while another connection writes.
+pub fn write_transaction<T>(
+    conn: &mut Connection,
+    mut f: impl FnMut(&Transaction) -> rusqlite::Result<T>,
+) -> rusqlite::Result<T> {
+    WRITE_TRANSACTIONS.fetch_add(1, Ordering::Relaxed);
+
+    let mut attempt = 0;
+    loop {
+        let result = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .and_then(|tx| {
+                let value = f(&tx)?;
+                tx.commit()?;
+                Ok(value)
+            });
+
+        match result {
+            Err(e) if is_busy(&e) && attempt < MAX_WRITE_RETRIES => {
+                BUSY_RETRIES.fetch_add(1, Ordering::Relaxed);
+                std::thread::sleep(Duration::from_millis(RETRY_BACKOFF_MS << attempt));
+                attempt += 1;
+            }
+            Err(e) => {
+                if is_busy(&e) {
+                    BUSY_FAILURES.fetch_add(1, Ordering::Relaxed);
+                }
+                return Err(e);
+            }
+            Ok(value) => return Ok(value),
+        }
+    }
+}
+
+fn is_busy(error: &rusqlite::Error) -> bool {
+    matches!(
+        error.sqlite_error_code(),
+        Some(ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+    )
+}
+
+/// Whether a write failed because it would duplicate a row of a unique index
+pub fn is_unique_violation(error: &rusqlite::Error) -> bool {
+    matches!(
+        error,
+        rusqlite::Error::SqliteFailure(e, _)
+            if e.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE
+    )
+}
+
+/// Snapshot of the pool and contention counters
+pub fn diagnostics() -> DbDiagnostics {
+    let state = DB_POOL.get().map(|pool| pool.state());
+
+    DbDiagnostics {
+        busy_timeout_ms: BUSY_TIMEOUT_MS,
+        write_transactions: WRITE_TRANSACTIONS.load(Ordering::Relaxed),
+        busy_retries: BUSY_RETRIES.load(Ordering::Relaxed),
+        busy_failures: BUSY_FAILURES.load(Ordering::Relaxed),
+        pool_connections: state.as_ref().map_or(0, |s| s.connections),
+        pool_idle_connections: state.as_ref().map_or(0, |s| s.idle_connections),
+    }
+}
+
+/// Connection pool state and SQLITE_BUSY counts since launch
+#[tauri::command]
+#[specta::specta]
+pub fn db_diagnostics() -> DbDiagnostics {
+    diagnostics()
+}
@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tracing::error;
+
+use crate::commands::error::CommandError;
+use crate::db::maintenance::database_size;
+use crate::db::pool::{db_diagnostics, get_connection, DbDiagnostics};
+use crate::model_download::{model_disk_size, model_installed, model_path, selected_stt_model};
+use crate::performance::{performance_stats, PerformanceStats};
+use crate::transcription::{RecordingMemory, TranscriptionServiceHandle};
+
+/// Errors kept for the diagnostics report; older ones are dropped
+const MAX_RECENT_ERRORS: usize = 50;
+
+static RECENT_ERRORS: Mutex<VecDeque<RecentError>> = Mutex::new(VecDeque::new());
+
+/// An error logged while the app was running
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RecentError {
+    /// Unix time the error happened
+    pub at: i64,
+    /// Part of the app that failed, e.g. `transcription` or `paste`
+    pub source: String,
+    pub message: String,
+}
+
+/// Local speech model on disk and in memory
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ModelFootprint {
+    pub file_name: String,
+    /// `None` when the model hasn't been downloaded
+    pub file_bytes: Option<u64>,
+    /// Rough memory held by loaded models: every chunk worker loads its own copy
+    pub loaded_bytes: u64,
+}
+
+/// State of the app worth attaching to a bug report
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DiagnosticsReport {
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub generated_at: i64,
+    /// Resident memory of the whole process; `None` where it can't be read
+    pub resident_memory_bytes: Option<u64>,
+    pub model: ModelFootprint,
+    pub recording: bool,
+    /// Audio buffered by the current recording
+    pub recording_memory: RecordingMemory,
+    /// Database file plus its WAL
+    pub database_bytes: u64,
+    pub database: DbDiagnostics,
+    /// Chunk timings of the current or most recent local recording
+    pub performance: PerformanceStats,
+    /// Most recent first
+    pub recent_errors: Vec<RecentError>,
+}
+
+/// Log an error and keep it for the diagnostics report
+pub fn record_error(source: &str, message: impl Into<String>) {
+    let message = message.into();
+    error!("{}", message);
+
+    let at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    if let Ok(mut errors) = RECENT_ERRORS.lock() {
+        if errors.len() >= MAX_RECENT_ERRORS {
+            errors.pop_front();
+        }
+        errors.push_back(RecentError {
+            at,
+            source: source.to_string(),
+            message,
+        });
+    }
+}
+
+fn recent_errors() -> Vec<RecentError> {
+    RECENT_ERRORS
+        .lock()
+        .map(|errors| errors.iter().rev().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn model_footprint(app: &AppHandle, loaded_contexts: usize) -> ModelFootprint {
+    // The model recordings transcribe with, loaded once per chunk worker
+    let Ok(model) = selected_stt_model() else {
+        return ModelFootprint::default();
+    };
+    let file_bytes = model_installed(app, model)
+        .then(|| model_path(app, model).ok())
+        .flatten()
+        .map(|path| model_disk_size(&path));
+
+    ModelFootprint {
+        file_name: model.filename.clone(),
+        file_bytes,
+        loaded_bytes: file_bytes.unwrap_or(0) * loaded_contexts as u64,
+    }
+}
+
+fn database_bytes() -> u64 {
+    get_connection()
+        .ok()
+        .and_then(|conn| conn.path().map(database_size))
+        .unwrap_or(0)
+}
+
+/// Resident memory of this process from the Mach task info
+#[cfg(target_os = "macos")]
+fn resident_memory_bytes() -> Option<u64> {
+    /// `MACH_TASK_BASIC_INFO` flavor of `task_info`
+    const MACH_TASK_BASIC_INFO: i32 = 20;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct MachTaskBasicInfo {
+        virtual_size: u64,
+        resident_size: u64,
+        resident_size_max: u64,
+        user_time: [i32; 2],
+        system_time: [i32; 2],
+        policy: i32,
+        suspend_count: i32,
+    }
+
+    extern "C" {
+        static mach_task_self_: u32;
+        fn task_info(task: u32, flavor: i32, info: *mut MachTaskBasicInfo, count: *mut u32) -> i32;
+    }
+
+    let mut info = MachTaskBasicInfo::default();
+    let mut count = (std::mem::size_of::<MachTaskBasicInfo>() / std::mem::size_of::<u32>()) as u32;
+    let result = unsafe { task_info(mach_task_self_, MACH_TASK_BASIC_INFO, &mut info, &mut count) };
+
+    (result == 0).then_some(info.resident_size)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn resident_memory_bytes() -> Option<u64> {
+    None
+}
+
+/// Memory, database and recent error state for bug reports
+#[tauri::command]
+#[specta::specta]
+pub async fn diagnostics_report(
+    app: AppHandle,
+    service: tauri::State<'_, TranscriptionServiceHandle>,
+) -> Result<DiagnosticsReport, CommandError> {
+    let (recording, recording_memory) = {
+        let service = service.lock().await;
+        (service.is_recording(), service.memory_usage())
+    };
+
+    let generated_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    Ok(DiagnosticsReport {
+        app_version: app.package_info().version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        generated_at,
+        resident_memory_bytes: resident_memory_bytes(),
+        model: model_footprint(&app, recording_memory.loaded_model_contexts),
+        recording,
+        recording_memory,
+        database_bytes: database_bytes(),
+        database: db_diagnostics(),
+        performance: performance_stats(),
+        recent_errors: recent_errors(),
+    })
+}
@@ -1,5 +1,9 @@
 use serde::Serialize;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tracing::warn;
+
+/// Label of the floating widget window (a panel on macOS)
+const WIDGET_LABEL: &str = "widget";
 
 /// Event names for reactive updates
 pub mod names {
@@ -7,6 +11,7 @@ pub mod names {
     pub const TRANSCRIPTIONS_CREATED: &str = "transcriptions:created";
     pub const TRANSCRIPTIONS_UPDATED: &str = "transcriptions:updated";
     pub const TRANSCRIPTIONS_DELETED: &str = "transcriptions:deleted";
+    pub const TRANSCRIPTIONS_RATED: &str = "transcriptions:rated";
 
     // Keyterms
     pub const KEYTERMS_CREATED: &str = "keyterms:created";
@@ -18,9 +23,24 @@ pub mod names {
     pub const SHORTCUTS_UPDATED: &str = "shortcuts:updated";
     pub const SHORTCUTS_DELETED: &str = "shortcuts:deleted";
 
+    // Macros
+    pub const MACROS_CREATED: &str = "macros:created";
+    pub const MACROS_UPDATED: &str = "macros:updated";
+    pub const MACROS_DELETED: &str = "macros:deleted";
+
     // Writing styles
     pub const WRITING_STYLES_UPDATED: &str = "writing_styles:updated";
 
+    // URL rules
+    pub const URL_RULES_CREATED: &str = "url_rules:created";
+    pub const URL_RULES_UPDATED: &str = "url_rules:updated";
+    pub const URL_RULES_DELETED: &str = "url_rules:deleted";
+
+    // App rules
+    pub const APP_RULES_CREATED: &str = "app_rules:created";
+    pub const APP_RULES_UPDATED: &str = "app_rules:updated";
+    pub const APP_RULES_DELETED: &str = "app_rules:deleted";
+
     // Keys vault
     pub const KEYS_VAULT_CREATED: &str = "keys_vault:created";
     pub const KEYS_VAULT_UPDATED: &str = "keys_vault:updated";
@@ -33,6 +53,14 @@ pub mod names {
     pub const NOTES_CREATED: &str = "notes:created";
     pub const NOTES_UPDATED: &str = "notes:updated";
     pub const NOTES_DELETED: &str = "notes:deleted";
+
+    // Sessions
+    pub const SESSIONS_CREATED: &str = "sessions:created";
+    pub const SESSIONS_UPDATED: &str = "sessions:updated";
+    pub const SESSIONS_DELETED: &str = "sessions:deleted";
+
+    // Sync
+    pub const SYNC_COMPLETED: &str = "sync:completed";
 }
 
 /// Emit an entity event with full entity data
@@ -50,3 +78,101 @@ pub fn emit_delete_event(app: &AppHandle, event_name: &str, id: String) -> Resul
     app.emit(event_name, id)
         .map_err(|e| format!("Failed to emit event '{}': {}", event_name, e))
 }
+
+/// Emit an event to the widget only. Falls back to a broadcast when the widget
+/// doesn't exist or can't be reached, so the event is never dropped.
+pub fn emit_to_widget<R: Runtime, T: Serialize + Clone>(
+    app: &AppHandle<R>,
+    event_name: &str,
+    payload: T,
+) -> Result<(), String> {
+    if app.get_webview_window(WIDGET_LABEL).is_some() {
+        match app.emit_to(WIDGET_LABEL, event_name, payload.clone()) {
+            Ok(()) => return Ok(()),
+            Err(e) => warn!(
+                "Failed to emit event '{}' to the widget, broadcasting: {}",
+                event_name, e
+            ),
+        }
+    }
+
+    emit_broadcast(app, event_name, payload)
+}
+
+/// Emit an event to every window and panel, for events both the widget and the
+/// main window react to
+pub fn emit_broadcast<R: Runtime, T: Serialize + Clone>(
+    app: &AppHandle<R>,
+    event_name: &str,
+    payload: T,
+) -> Result<(), String> {
+    app.emit(event_name, payload)
+        .map_err(|e| format!("Failed to emit event '{}': {}", event_name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use tauri::test::{mock_app, MockRuntime};
+    use tauri::{App, Listener, WebviewUrl, WebviewWindowBuilder};
+
+    /// Payloads of `event` heard by the app itself, i.e. the main window's side
+    fn listen_on_app(app: &App<MockRuntime>, event: &str) -> mpsc::Receiver<String> {
+        let (tx, rx) = mpsc::channel();
+        app.listen(event, move |event| {
+            let _ = tx.send(event.payload().to_string());
+        });
+        rx
+    }
+
+    fn open_widget(app: &App<MockRuntime>) -> mpsc::Receiver<String> {
+        let widget = WebviewWindowBuilder::new(app, WIDGET_LABEL, WebviewUrl::default())
+            .build()
+            .unwrap();
+        let (tx, rx) = mpsc::channel();
+        widget.listen("level", move |event| {
+            let _ = tx.send(event.payload().to_string());
+        });
+        rx
+    }
+
+    fn received(rx: &mpsc::Receiver<String>) -> Option<String> {
+        rx.recv_timeout(Duration::from_millis(500)).ok()
+    }
+
+    #[test]
+    fn widget_events_reach_only_the_widget() {
+        let app = mock_app();
+        let widget = open_widget(&app);
+        let main = listen_on_app(&app, "level");
+
+        emit_to_widget(app.handle(), "level", 0.5).unwrap();
+
+        assert_eq!(received(&widget).as_deref(), Some("0.5"));
+        assert_eq!(received(&main), None);
+    }
+
+    #[test]
+    fn widget_events_are_broadcast_without_a_widget() {
+        let app = mock_app();
+        let main = listen_on_app(&app, "level");
+
+        emit_to_widget(app.handle(), "level", 0.5).unwrap();
+
+        assert_eq!(received(&main).as_deref(), Some("0.5"));
+    }
+
+    #[test]
+    fn broadcasts_reach_every_window() {
+        let app = mock_app();
+        let widget = open_widget(&app);
+        let main = listen_on_app(&app, "level");
+
+        emit_broadcast(app.handle(), "level", 1.0).unwrap();
+
+        assert_eq!(received(&widget).as_deref(), Some("1.0"));
+        assert_eq!(received(&main).as_deref(), Some("1.0"));
+    }
+}
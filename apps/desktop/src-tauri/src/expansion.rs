@@ -0,0 +1,254 @@
+use tracing::error;
+
+use crate::commands::shortcuts::{shortcuts_list, Shortcut, ShortcutCategory};
+use crate::placeholders;
+
+/// Capitalization of the spoken trigger, carried over to the replacement
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CaseStyle {
+    /// Lowercase or mixed case; the replacement is used as written
+    AsWritten,
+    /// First letter uppercase ("Brb" at the start of a sentence)
+    Capitalized,
+    /// Every letter uppercase ("BRB")
+    Upper,
+}
+
+/// A trigger found in the text, as a byte range to replace
+struct Expansion<'a> {
+    start: usize,
+    end: usize,
+    shortcut: &'a Shortcut,
+}
+
+/// Replace shortcut triggers ("brb") in dictated text with their replacements.
+/// Shortcuts in the `all` category apply everywhere, the others only in their own
+/// category. Whitespace and the punctuation around a trigger are kept, so
+/// formatted paragraphs survive.
+pub fn expand_shortcuts(text: &str, category: &str) -> String {
+    let shortcuts = match shortcuts_list(None) {
+        Ok(shortcuts) => shortcuts,
+        Err(e) => {
+            error!("Failed to load shortcuts: {}", e.message);
+            return text.to_string();
+        }
+    };
+
+    let shortcuts: Vec<&Shortcut> = shortcuts
+        .iter()
+        .filter(|s| s.category == ShortcutCategory::All.as_str() || s.category == category)
+        .filter(|s| s.trigger.chars().any(char::is_alphanumeric))
+        .collect();
+    if shortcuts.is_empty() {
+        return text.to_string();
+    }
+
+    let spans = word_spans(text);
+    let mut expansions: Vec<Expansion> = Vec::new();
+    for shortcut in &shortcuts {
+        if shortcut.whole_word {
+            find_whole_words(text, &spans, shortcut, &mut expansions);
+        } else {
+            find_anywhere(text, shortcut, &mut expansions);
+        }
+    }
+
+    // Earliest first and, at the same position, the longest trigger wins
+    expansions.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+    let mut output = String::with_capacity(text.len());
+    let mut copied = 0;
+    for expansion in expansions {
+        if expansion.start < copied {
+            // Overlaps a trigger that was already expanded
+            continue;
+        }
+
+        let replacement = if expansion.shortcut.case_sensitive {
+            expansion.shortcut.replacement.clone()
+        } else {
+            let style = case_style(&text[expansion.start..expansion.end]);
+            apply_case(&expansion.shortcut.replacement, style)
+        };
+
+        output.push_str(&text[copied..expansion.start]);
+        output.push_str(&placeholders::render(&replacement));
+        copied = expansion.end;
+    }
+    output.push_str(&text[copied..]);
+
+    output
+}
+
+/// Whether `text` contains the shortcut's trigger as expansion would match it,
+/// e.g. to catch a replacement that would trigger its own shortcut
+pub fn contains_trigger(text: &str, shortcut: &Shortcut) -> bool {
+    let mut expansions: Vec<Expansion> = Vec::new();
+    if shortcut.whole_word {
+        find_whole_words(text, &word_spans(text), shortcut, &mut expansions);
+    } else {
+        find_anywhere(text, shortcut, &mut expansions);
+    }
+
+    !expansions.is_empty()
+}
+
+/// Find the trigger as a sequence of whole words, ignoring the punctuation
+/// Whisper attaches to them
+fn find_whole_words<'a>(
+    text: &str,
+    spans: &[(usize, usize)],
+    shortcut: &'a Shortcut,
+    expansions: &mut Vec<Expansion<'a>>,
+) {
+    let trigger: Vec<&str> = shortcut
+        .trigger
+        .split_whitespace()
+        .map(strip_punctuation)
+        .filter(|w| !w.is_empty())
+        .collect();
+    if trigger.is_empty() || trigger.len() > spans.len() {
+        return;
+    }
+
+    for idx in 0..=spans.len() - trigger.len() {
+        let matches = trigger.iter().enumerate().all(|(offset, expected)| {
+            let (start, end) = spans[idx + offset];
+            words_equal(
+                strip_punctuation(&text[start..end]),
+                expected,
+                shortcut.case_sensitive,
+            )
+        });
+        if !matches {
+            continue;
+        }
+
+        let (first_start, first_end) = spans[idx];
+        let (last_start, last_end) = spans[idx + trigger.len() - 1];
+        expansions.push(Expansion {
+            start: first_start + leading_punctuation(&text[first_start..first_end]),
+            end: last_end - trailing_punctuation(&text[last_start..last_end]),
+            shortcut,
+        });
+    }
+}
+
+/// Find the trigger anywhere, including inside longer words
+fn find_anywhere<'a>(text: &str, shortcut: &'a Shortcut, expansions: &mut Vec<Expansion<'a>>) {
+    let trigger = shortcut.trigger.trim();
+
+    for (start, _) in text.char_indices() {
+        if let Some(len) = prefix_len(&text[start..], trigger, shortcut.case_sensitive) {
+            expansions.push(Expansion {
+                start,
+                end: start + len,
+                shortcut,
+            });
+        }
+    }
+}
+
+/// Byte length of `trigger` at the start of `text`, if it starts with it
+fn prefix_len(text: &str, trigger: &str, case_sensitive: bool) -> Option<usize> {
+    if case_sensitive {
+        return text.starts_with(trigger).then_some(trigger.len());
+    }
+
+    let mut text_chars = text.char_indices();
+    for expected in trigger.chars() {
+        let (_, c) = text_chars.next()?;
+        if !c.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(text_chars.next().map_or(text.len(), |(i, _)| i))
+}
+
+fn words_equal(word: &str, expected: &str, case_sensitive: bool) -> bool {
+    if case_sensitive {
+        word == expected
+    } else {
+        word.to_lowercase() == expected.to_lowercase()
+    }
+}
+
+fn case_style(matched: &str) -> CaseStyle {
+    let letters: Vec<char> = matched.chars().filter(|c| c.is_alphabetic()).collect();
+    match letters.first() {
+        Some(_) if letters.len() > 1 && letters.iter().all(|c| c.is_uppercase()) => {
+            CaseStyle::Upper
+        }
+        Some(first) if first.is_uppercase() => CaseStyle::Capitalized,
+        _ => CaseStyle::AsWritten,
+    }
+}
+
+/// Follow the trigger's capitalization, leaving placeholders like `{date}` intact
+fn apply_case(replacement: &str, style: CaseStyle) -> String {
+    match style {
+        CaseStyle::AsWritten => replacement.to_string(),
+        CaseStyle::Capitalized => {
+            let mut chars = replacement.chars();
+            match chars.next() {
+                Some(first) if first.is_alphabetic() => first.to_uppercase().chain(chars).collect(),
+                _ => replacement.to_string(),
+            }
+        }
+        CaseStyle::Upper => {
+            let mut output = String::with_capacity(replacement.len());
+            let mut rest = replacement;
+            while let Some(c) = rest.chars().next() {
+                match placeholders::ALL.iter().find(|p| rest.starts_with(*p)) {
+                    Some(placeholder) => {
+                        output.push_str(placeholder);
+                        rest = &rest[placeholder.len()..];
+                    }
+                    None => {
+                        output.extend(c.to_uppercase());
+                        rest = &rest[c.len_utf8()..];
+                    }
+                }
+            }
+            output
+        }
+    }
+}
+
+/// Byte ranges of the whitespace-separated words in `text`
+pub fn word_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+
+    spans
+}
+
+fn strip_punctuation(word: &str) -> &str {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+/// Bytes of punctuation before the first letter or digit of a word
+pub fn leading_punctuation(word: &str) -> usize {
+    let trimmed = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+    word.len() - trimmed.len()
+}
+
+/// Bytes of punctuation after the last letter or digit of a word
+pub fn trailing_punctuation(word: &str) -> usize {
+    let trimmed = word.trim_end_matches(|c: char| !c.is_alphanumeric());
+    word.len() - trimmed.len()
+}
@@ -1,16 +1,46 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json;
+use std::time::Instant;
+use tracing::{debug, error, warn};
+
+use crate::commands::formatter_usage::record_formatter_usage;
+use crate::keychain::{get_secret, AUTH_TOKEN_ACCOUNT};
+use crate::redaction::{redact_if_enabled, redact_url_if_enabled};
 
 const API_URL: &str = "https://dicto-ai-server.vercel.app/llm/formatting";
 
+/// Where dictated text is going, sent as `context` so the server can tailor the
+/// formatting. Servers that predate it ignore the object.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatContext {
+    /// App dictated into, e.g. "Mail"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+    /// Origin and path of the browser tab dictated into, never its query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Title of the window dictated into, e.g. "Re: Q3 budget"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_title: Option<String>,
+    /// Characters that were selected in the app when dictation started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected_text_length: Option<usize>,
+}
+
 #[derive(Serialize)]
 struct FormatRequest {
     category: String,
     style: String,
+    /// Holds the category, as servers that predate `context` expect
     #[serde(rename = "appName")]
     app_name: String,
     text: String,
+    context: FormatContext,
+    /// The category's custom writing style prompt, with its variables filled in
+    #[serde(rename = "customPrompt", skip_serializing_if = "Option::is_none")]
+    custom_prompt: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -19,24 +49,113 @@ struct FormatResponse {
     formatted_text: String,
 }
 
+/// Why a formatting request failed
+#[derive(Debug)]
+pub enum FormatError {
+    /// The server could not be reached (offline, DNS, timeout); worth retrying later
+    Network(String),
+    /// Any other failure (auth, bad response)
+    Other(String),
+}
+
+impl FormatError {
+    pub fn is_network(&self) -> bool {
+        matches!(self, Self::Network(_))
+    }
+}
+
+impl std::fmt::Display for FormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Network(e) => write!(f, "Request failed: {}", e),
+            Self::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// Read the signed-in user's auth token from the Keychain
+pub fn read_auth_token() -> Option<String> {
+    match get_secret(AUTH_TOKEN_ACCOUNT) {
+        Ok(token) => token,
+        Err(e) => {
+            error!("{}", e);
+            None
+        }
+    }
+}
+
 /// Format text by calling the server API
 pub async fn format_text(
     auth_token: &str,
     category: &str,
     style: &str,
-    app_name: &str,
     text: &str,
-) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    context: &FormatContext,
+    custom_prompt: Option<&str>,
+) -> Result<String, FormatError> {
+    // Mask sensitive spans locally; they are restored in the formatted result
+    let redacted = redact_if_enabled(text);
+    // Titles and URLs often hold names and addresses too; they're only context,
+    // so the masked spans are never restored. Queued requests from older versions
+    // may still carry a full URL.
+    let context = FormatContext {
+        url: context.url.as_deref().and_then(redact_url_if_enabled),
+        window_title: context
+            .window_title
+            .as_deref()
+            .map(|title| redact_if_enabled(title).text),
+        ..context.clone()
+    };
+
+    let started = Instant::now();
+    let result = send_format_request(
+        auth_token,
+        category,
+        style,
+        &redacted.text,
+        context,
+        custom_prompt,
+    )
+    .await;
+
+    // Meter every request so users can see how often (and how slowly) formatting runs
+    let error = result.as_ref().err().map(|e| e.to_string());
+    if let Err(e) = record_formatter_usage(
+        redacted.text.chars().count(),
+        started.elapsed().as_millis() as u64,
+        error.as_deref(),
+    ) {
+        error!("Failed to record formatter usage: {}", e.message);
+    }
+
+    result.map(|formatted| redacted.restore(&formatted))
+}
+
+async fn send_format_request(
+    auth_token: &str,
+    category: &str,
+    style: &str,
+    text: &str,
+    context: FormatContext,
+    custom_prompt: Option<&str>,
+) -> Result<String, FormatError> {
     let client = Client::new();
 
     let request_body = FormatRequest {
         category: category.to_string(),
         style: style.to_string(),
-        app_name: app_name.to_string(),
+        app_name: category.to_string(),
         text: text.to_string(),
+        context,
+        custom_prompt: custom_prompt.map(str::to_string),
     };
 
-    println!("Sending format request: category={}, style={}, app_name={}", category, style, app_name);
+    debug!(
+        "Sending format request: category={}, style={}",
+        category, style
+    );
 
     let response = match client
         .post(API_URL)
@@ -48,20 +167,25 @@ pub async fn format_text(
     {
         Ok(resp) => resp,
         Err(e) => {
-            eprintln!("Failed to send request: {:?}", e);
-            return Err(format!("Request failed: {}", e).into());
+            warn!("Failed to send format request: {}", e);
+            return Err(FormatError::Network(e.to_string()));
         }
     };
 
-    println!("Response status: {}", response.status());
+    debug!("Format response status: {}", response.status());
 
     if response.status() == 401 {
-        return Err("Unauthorized - please sign in".into());
+        return Err(FormatError::Other(
+            "Unauthorized - please sign in".to_string(),
+        ));
     }
 
-    let response_text = response.text().await?;
-    println!("Response body: {}", response_text);
+    let response_text = response
+        .text()
+        .await
+        .map_err(|e| FormatError::Network(e.to_string()))?;
 
-    let result: FormatResponse = serde_json::from_str(&response_text)?;
+    let result: FormatResponse = serde_json::from_str(&response_text)
+        .map_err(|e| FormatError::Other(format!("Invalid response: {}", e)))?;
     Ok(result.formatted_text)
 }
@@ -0,0 +1,243 @@
+use rusqlite::params;
+use serde::Serialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tauri_specta::Event;
+use tracing::{error, info, warn};
+
+use crate::db::pool::{get_connection, write_transaction};
+use crate::events::{emit_entity_event, names as event_names};
+use crate::formatter::{format_text, read_auth_token, FormatContext};
+use crate::jobs;
+use crate::mirror;
+use crate::translator;
+
+/// How often the queue is retried while it has pending entries
+const RETRY_INTERVAL_SECS: u64 = 30;
+
+/// Entries failing for a non-network reason are dropped after this many attempts
+const MAX_ATTEMPTS: i64 = 5;
+
+/// Payload emitted when a queued transcription is formatted after the fact
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct FormattingCompletedLate {
+    pub transcription_id: String,
+    pub formatted_text: String,
+}
+
+/// A transcription waiting to be formatted once the server is reachable again
+struct PendingFormatting {
+    id: i64,
+    transcription_id: String,
+    text: Option<String>,
+    category: String,
+    style: String,
+    context: FormatContext,
+    custom_prompt: Option<String>,
+}
+
+/// Queue a saved transcription for formatting when connectivity returns
+pub fn enqueue(
+    transcription_id: &str,
+    category: &str,
+    style: &str,
+    context: &FormatContext,
+    custom_prompt: Option<&str>,
+) -> Result<(), String> {
+    let conn = get_connection()?;
+    let context = serde_json::to_string(context).map_err(|e| e.to_string())?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO pending_formatting (transcription_id, category, style, app_name, context, custom_prompt, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        params![transcription_id, category, style, category, context, custom_prompt, now],
+    )
+    .map_err(|e| format!("Failed to queue formatting: {}", e))?;
+
+    info!(
+        "📥 Queued transcription {} for formatting",
+        transcription_id
+    );
+
+    Ok(())
+}
+
+/// Oldest pending entries first, joined with the raw transcription text. The
+/// saved formatted text already has shortcuts expanded, so it isn't sent again.
+fn pending_entries() -> Result<Vec<PendingFormatting>, String> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT p.id, p.transcription_id, t.text, p.category, p.style, p.context, p.custom_prompt
+             FROM pending_formatting p
+             LEFT JOIN transcriptions t ON t.id = p.transcription_id
+             ORDER BY p.created_at ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let entries = stmt
+        .query_map([], |row| {
+            Ok(PendingFormatting {
+                id: row.get(0)?,
+                transcription_id: row.get(1)?,
+                text: row.get(2)?,
+                category: row.get(3)?,
+                style: row.get(4)?,
+                // Entries queued before the context was stored have none
+                context: row
+                    .get::<_, Option<String>>(5)?
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
+                custom_prompt: row.get(6)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(entries)
+}
+
+fn remove_entry(id: i64) -> Result<(), String> {
+    let conn = get_connection()?;
+    conn.execute("DELETE FROM pending_formatting WHERE id = ?", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Record a failed attempt; returns true once the entry has exhausted its attempts
+fn record_failure(id: i64, error: &str) -> Result<bool, String> {
+    let conn = get_connection()?;
+    conn.execute(
+        "UPDATE pending_formatting SET attempts = attempts + 1, last_error = ? WHERE id = ?",
+        params![error, id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let attempts: i64 = conn
+        .query_row(
+            "SELECT attempts FROM pending_formatting WHERE id = ?",
+            params![id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(attempts >= MAX_ATTEMPTS)
+}
+
+/// Store the late formatted text and notify the frontend
+fn complete_entry(
+    app: &AppHandle,
+    entry: &PendingFormatting,
+    formatted: &str,
+) -> Result<(), String> {
+    let mut conn = get_connection()?;
+
+    write_transaction(&mut conn, |tx| {
+        tx.execute(
+            "UPDATE transcriptions SET formatted_text = ? WHERE id = ?",
+            params![formatted, entry.transcription_id],
+        )?;
+        tx.execute(
+            "DELETE FROM pending_formatting WHERE id = ?",
+            params![entry.id],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    if let Ok(transcription) =
+        crate::commands::transcriptions::transcriptions_get(entry.transcription_id.clone())
+    {
+        mirror::mirror_transcription(&transcription);
+        let _ = emit_entity_event(app, event_names::TRANSCRIPTIONS_UPDATED, transcription);
+    }
+
+    let _ = FormattingCompletedLate {
+        transcription_id: entry.transcription_id.clone(),
+        formatted_text: formatted.to_string(),
+    }
+    .emit(app);
+
+    info!(
+        "✅ Late formatting completed for {}",
+        entry.transcription_id
+    );
+
+    Ok(())
+}
+
+/// Try to format every queued transcription. Stops at the first network failure
+/// since the rest would fail the same way.
+async fn drain(app: &AppHandle) -> Result<(), String> {
+    let entries = pending_entries()?;
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let token = match read_auth_token() {
+        Some(token) => token,
+        None => return Ok(()),
+    };
+
+    for entry in entries {
+        // The transcription was deleted while waiting
+        let text = match &entry.text {
+            Some(text) => text.clone(),
+            None => {
+                remove_entry(entry.id)?;
+                continue;
+            }
+        };
+        // Format the output language, as the dictation itself would have been
+        let text = translator::translate_if_enabled(&text, None).await;
+
+        match format_text(
+            &token,
+            &entry.category,
+            &entry.style,
+            &text,
+            &entry.context,
+            entry.custom_prompt.as_deref(),
+        )
+        .await
+        {
+            Ok(formatted) => complete_entry(app, &entry, &formatted)?,
+            Err(e) if e.is_network() => {
+                warn!("Formatting server still unreachable, retrying later");
+                return Ok(());
+            }
+            Err(e) => {
+                error!(
+                    "Late formatting failed for {}: {}",
+                    entry.transcription_id, e
+                );
+                if record_failure(entry.id, &e.to_string())? {
+                    remove_entry(entry.id)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Register the `formatting_retry` job, which retries queued formatting until
+/// it succeeds
+pub fn register_retry_job(app: AppHandle) {
+    jobs::register(
+        "formatting_retry",
+        Duration::from_secs(RETRY_INTERVAL_SECS),
+        Duration::ZERO,
+        move || {
+            let app = app.clone();
+            async move { drain(&app).await }
+        },
+    );
+}
@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::model_registry::DEFAULT_STT_MODEL;
+
+/// Bytes in a gibibyte, the unit Macs are sold with
+const GIB: u64 = 1024 * 1024 * 1024;
+
+/// Model suggested to machines that only suit base-sized models
+const LIGHT_STT_MODEL: &str = "moonshine-base";
+
+/// Largest model class this machine transcribes comfortably while dictating
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ModelSize {
+    /// Under 100 MB, e.g. Moonshine Base or Whisper Base
+    Base,
+    /// About 500 MB, e.g. Whisper Small
+    Small,
+    /// About 1.5 GB, e.g. Whisper Medium
+    Medium,
+    /// About 3 GB, e.g. Whisper Large
+    Large,
+}
+
+/// What this machine offers for on-device inference
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HardwareCapabilities {
+    /// Apple Silicon rather than Intel, even when the app runs under Rosetta
+    pub apple_silicon: bool,
+    /// The app runs translated by Rosetta, which makes inference much slower
+    pub rosetta: bool,
+    /// A Metal GPU is available for acceleration
+    pub metal: bool,
+    /// Physical memory, shared with the GPU on Apple Silicon; `None` when it
+    /// can't be read
+    pub memory_bytes: Option<u64>,
+    pub cpu_brand: Option<String>,
+    pub cpu_cores: usize,
+    pub suggested_model_size: ModelSize,
+    /// Manifest id of the local model to pre-select in settings
+    pub suggested_model: String,
+}
+
+impl HardwareCapabilities {
+    pub fn detect() -> Self {
+        let apple_silicon = sysctl_i32("hw.optional.arm64") == Some(1);
+        let rosetta = sysctl_i32("sysctl.proc_translated") == Some(1);
+        let metal = metal_available();
+        let memory_bytes = physical_memory_bytes();
+        let cpu_cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        let suggested_model_size = match memory_bytes {
+            _ if rosetta => ModelSize::Base,
+            // Without a reading, stay on the default Whisper model
+            None => ModelSize::Small,
+            Some(memory) if memory < 8 * GIB => ModelSize::Base,
+            _ if !apple_silicon || !metal => ModelSize::Small,
+            Some(memory) if memory < 16 * GIB => ModelSize::Medium,
+            Some(_) => ModelSize::Large,
+        };
+        let suggested_model = if suggested_model_size == ModelSize::Base {
+            LIGHT_STT_MODEL
+        } else {
+            DEFAULT_STT_MODEL
+        };
+
+        Self {
+            apple_silicon,
+            rosetta,
+            metal,
+            memory_bytes,
+            cpu_brand: sysctl_string("machdep.cpu.brand_string"),
+            cpu_cores,
+            suggested_model_size,
+            suggested_model: suggested_model.to_string(),
+        }
+    }
+}
+
+/// Read a sysctl value into `buffer`, returning the number of bytes written
+#[cfg(target_os = "macos")]
+fn sysctl_raw(name: &str, buffer: &mut [u8]) -> Option<usize> {
+    use std::ffi::{c_char, c_void, CString};
+
+    extern "C" {
+        fn sysctlbyname(
+            name: *const c_char,
+            oldp: *mut c_void,
+            oldlenp: *mut usize,
+            newp: *mut c_void,
+            newlen: usize,
+        ) -> i32;
+    }
+
+    let name = CString::new(name).ok()?;
+    let mut len = buffer.len();
+    let result = unsafe {
+        sysctlbyname(
+            name.as_ptr(),
+            buffer.as_mut_ptr() as *mut c_void,
+            &mut len,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+
+    (result == 0).then_some(len)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn sysctl_raw(_name: &str, _buffer: &mut [u8]) -> Option<usize> {
+    None
+}
+
+fn sysctl_i32(name: &str) -> Option<i32> {
+    let mut buffer = [0u8; 4];
+    match sysctl_raw(name, &mut buffer)? {
+        4 => Some(i32::from_ne_bytes(buffer)),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn sysctl_u64(name: &str) -> Option<u64> {
+    let mut buffer = [0u8; 8];
+    match sysctl_raw(name, &mut buffer)? {
+        8 => Some(u64::from_ne_bytes(buffer)),
+        _ => None,
+    }
+}
+
+fn sysctl_string(name: &str) -> Option<String> {
+    let mut buffer = [0u8; 256];
+    let len = sysctl_raw(name, &mut buffer)?;
+    let text = String::from_utf8_lossy(&buffer[..len]);
+    let text = text.trim_end_matches('\0').trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Installed physical memory
+#[cfg(target_os = "macos")]
+fn physical_memory_bytes() -> Option<u64> {
+    sysctl_u64("hw.memsize")
+}
+
+/// `MemTotal` from `/proc/meminfo`, which is given in KiB
+#[cfg(target_os = "linux")]
+fn physical_memory_bytes() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let total = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemTotal:"))?;
+    let kib: u64 = total.trim().trim_end_matches("kB").trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(target_os = "windows")]
+fn physical_memory_bytes() -> Option<u64> {
+    use windows_sys::Win32::System::SystemInformation::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+
+    let mut status: MEMORYSTATUSEX = unsafe { std::mem::zeroed() };
+    status.dwLength = std::mem::size_of::<MEMORYSTATUSEX>() as u32;
+    let result = unsafe { GlobalMemoryStatusEx(&mut status) };
+
+    (result != 0).then_some(status.ullTotalPhys)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+fn physical_memory_bytes() -> Option<u64> {
+    None
+}
+
+/// Whether Metal has a default GPU device
+#[cfg(target_os = "macos")]
+fn metal_available() -> bool {
+    use cocoa::base::{id, nil};
+    use objc::{msg_send, sel, sel_impl};
+
+    #[link(name = "Metal", kind = "framework")]
+    extern "C" {
+        fn MTLCreateSystemDefaultDevice() -> id;
+    }
+
+    unsafe {
+        let device = MTLCreateSystemDefaultDevice();
+        if device == nil {
+            return false;
+        }
+        let _: () = msg_send![device, release];
+        true
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn metal_available() -> bool {
+    false
+}
+
+/// Processor, GPU and memory of this machine with the model size it suits,
+/// for pre-selecting defaults and warning before large downloads
+#[tauri::command]
+#[specta::specta]
+pub fn hardware_capabilities() -> HardwareCapabilities {
+    HardwareCapabilities::detect()
+}
@@ -0,0 +1,397 @@
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Listener, Runtime};
+use tracing::error;
+
+use crate::commands::error::CommandError;
+use crate::commands::macros::macros_list;
+use crate::events::names as event_names;
+use crate::expansion::{leading_punctuation, trailing_punctuation, word_spans};
+use crate::placeholders;
+
+/// Phrase that switches letter-by-letter spelling on
+const SPELL_MODE_ON: [&str; 3] = ["spell", "mode", "on"];
+
+/// Phrase that switches letter-by-letter spelling off
+const SPELL_MODE_OFF: [&str; 3] = ["spell", "mode", "off"];
+
+/// Phrase that starts a Markdown list item
+const BULLET_POINT: [&str; 2] = ["bullet", "point"];
+
+/// Word that starts a Markdown heading, optionally followed by its level
+const HEADING: [&str; 1] = ["heading"];
+
+/// Run the command-interpreter stage over a finished transcription.
+/// Spoken commands are rewritten into their literal output before formatting and pasting.
+pub fn interpret(text: &str) -> String {
+    expand_macros(&apply_spell_mode(text))
+}
+
+/// Lowercase a word and strip the punctuation Whisper attaches to it
+fn normalize_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Check whether the normalized words at `idx` match a command phrase
+fn matches_phrase<S: AsRef<str>>(words: &[String], idx: usize, phrase: &[S]) -> bool {
+    words.len() >= idx + phrase.len()
+        && phrase
+            .iter()
+            .enumerate()
+            .all(|(offset, p)| words[idx + offset] == p.as_ref())
+}
+
+/// Map a spoken NATO code word to its letter
+fn nato_to_letter(word: &str) -> Option<char> {
+    let letter = match word {
+        "alpha" | "alfa" => 'a',
+        "bravo" => 'b',
+        "charlie" => 'c',
+        "delta" => 'd',
+        "echo" => 'e',
+        "foxtrot" => 'f',
+        "golf" => 'g',
+        "hotel" => 'h',
+        "india" => 'i',
+        "juliet" | "juliett" => 'j',
+        "kilo" => 'k',
+        "lima" => 'l',
+        "mike" => 'm',
+        "november" => 'n',
+        "oscar" => 'o',
+        "papa" => 'p',
+        "quebec" => 'q',
+        "romeo" => 'r',
+        "sierra" => 's',
+        "tango" => 't',
+        "uniform" => 'u',
+        "victor" => 'v',
+        "whiskey" | "whisky" => 'w',
+        "x-ray" | "xray" => 'x',
+        "yankee" => 'y',
+        "zulu" => 'z',
+        _ => return None,
+    };
+    Some(letter)
+}
+
+/// Map a spoken digit or symbol name to its character
+fn spoken_symbol(word: &str) -> Option<char> {
+    let symbol = match word {
+        "zero" => '0',
+        "one" => '1',
+        "two" => '2',
+        "three" => '3',
+        "four" => '4',
+        "five" => '5',
+        "six" => '6',
+        "seven" => '7',
+        "eight" => '8',
+        "nine" => '9',
+        "dash" | "hyphen" | "minus" => '-',
+        "underscore" => '_',
+        "dot" | "period" | "point" => '.',
+        "at" => '@',
+        "slash" => '/',
+        "plus" => '+',
+        "space" => ' ',
+        _ => return None,
+    };
+    Some(symbol)
+}
+
+/// Convert one spoken word inside spell mode into the characters it stands for
+fn spell_word(raw: &str, normalized: &str) -> String {
+    if let Some(letter) = nato_to_letter(normalized) {
+        return letter.to_string();
+    }
+    if let Some(symbol) = spoken_symbol(normalized) {
+        return symbol.to_string();
+    }
+
+    // Whisper often transcribes spelled letters and digits directly ("A", "B", "7")
+    let chars: Vec<char> = normalized.chars().collect();
+    if chars.len() == 1 {
+        return chars[0].to_string();
+    }
+
+    // Anything else is kept literally, without surrounding punctuation
+    raw.trim_matches(|c: char| !c.is_alphanumeric()).to_string()
+}
+
+/// Convert NATO/letter dictation between "spell mode on" and "spell mode off" into literal
+/// characters. Spell mode left open at the end of the text is closed implicitly.
+pub fn apply_spell_mode(text: &str) -> String {
+    let raw_words: Vec<&str> = text.split_whitespace().collect();
+    let words: Vec<String> = raw_words.iter().map(|w| normalize_word(w)).collect();
+
+    let mut output: Vec<String> = Vec::with_capacity(raw_words.len());
+    let mut spelled = String::new();
+    let mut spelling = false;
+    let mut capitalize_next = false;
+    let mut idx = 0;
+
+    while idx < raw_words.len() {
+        if !spelling && matches_phrase(&words, idx, &SPELL_MODE_ON) {
+            spelling = true;
+            idx += SPELL_MODE_ON.len();
+            continue;
+        }
+
+        if spelling && matches_phrase(&words, idx, &SPELL_MODE_OFF) {
+            if !spelled.is_empty() {
+                output.push(std::mem::take(&mut spelled));
+            }
+            spelling = false;
+            capitalize_next = false;
+            idx += SPELL_MODE_OFF.len();
+            continue;
+        }
+
+        if !spelling {
+            output.push(raw_words[idx].to_string());
+            idx += 1;
+            continue;
+        }
+
+        let normalized = &words[idx];
+        match normalized.as_str() {
+            "" => {}
+            "capital" | "uppercase" | "cap" => capitalize_next = true,
+            _ => {
+                let chars = spell_word(raw_words[idx], normalized);
+                let mut chars_iter = chars.chars();
+                if capitalize_next {
+                    if let Some(first) = chars_iter.next() {
+                        spelled.extend(first.to_uppercase());
+                    }
+                    capitalize_next = false;
+                }
+                spelled.extend(chars_iter);
+            }
+        }
+        idx += 1;
+    }
+
+    if !spelled.is_empty() {
+        output.push(spelled);
+    }
+
+    output.join(" ")
+}
+
+/// A macro's trigger as normalized words, with the template it expands to
+struct Trigger {
+    words: Vec<String>,
+    template: String,
+}
+
+/// Macro triggers, longest first, as last read from the database. Cleared
+/// whenever a macro changes.
+static TRIGGERS: Mutex<Option<Arc<Vec<Trigger>>>> = Mutex::new(None);
+
+fn load_triggers() -> Result<Arc<Vec<Trigger>>, CommandError> {
+    let mut cached = TRIGGERS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(triggers) = cached.as_ref() {
+        return Ok(triggers.clone());
+    }
+
+    let mut triggers: Vec<Trigger> = macros_list()?
+        .into_iter()
+        .map(|m| Trigger {
+            words: m
+                .trigger
+                .split_whitespace()
+                .map(normalize_word)
+                .filter(|w| !w.is_empty())
+                .collect(),
+            template: m.template,
+        })
+        .filter(|trigger| !trigger.words.is_empty())
+        .collect();
+    // Longest first, so "insert work signature" wins over "insert work"
+    triggers.sort_by(|a, b| b.words.len().cmp(&a.words.len()));
+
+    let triggers = Arc::new(triggers);
+    *cached = Some(triggers.clone());
+    Ok(triggers)
+}
+
+/// Drop the cached macro triggers whenever a macro is created, updated or deleted
+pub fn watch_macros<R: Runtime>(app: &AppHandle<R>) {
+    for event in [
+        event_names::MACROS_CREATED,
+        event_names::MACROS_UPDATED,
+        event_names::MACROS_DELETED,
+    ] {
+        app.listen(event, |_| {
+            *TRIGGERS.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        });
+    }
+}
+
+/// Replace spoken macro triggers ("insert signature") with their rendered templates.
+/// Triggers match case-insensitively and ignore the punctuation Whisper adds.
+/// Whitespace and the punctuation around a trigger are kept, like shortcut expansion.
+pub fn expand_macros(text: &str) -> String {
+    let triggers = match load_triggers() {
+        Ok(triggers) => triggers,
+        Err(e) => {
+            error!("Failed to load macros: {}", e.message);
+            return text.to_string();
+        }
+    };
+    if triggers.is_empty() {
+        return text.to_string();
+    }
+
+    let spans = word_spans(text);
+    let words: Vec<String> = spans
+        .iter()
+        .map(|&(start, end)| normalize_word(&text[start..end]))
+        .collect();
+
+    let mut output = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut idx = 0;
+
+    while idx < spans.len() {
+        let Some(trigger) = triggers
+            .iter()
+            .find(|trigger| matches_phrase(&words, idx, &trigger.words))
+        else {
+            idx += 1;
+            continue;
+        };
+
+        let (first_start, first_end) = spans[idx];
+        let (last_start, last_end) = spans[idx + trigger.words.len() - 1];
+        let start = first_start + leading_punctuation(&text[first_start..first_end]);
+        let end = last_end - trailing_punctuation(&text[last_start..last_end]);
+
+        output.push_str(&text[copied..start]);
+        output.push_str(&placeholders::render(&trigger.template));
+        copied = end;
+        idx += trigger.words.len();
+    }
+    output.push_str(&text[copied..]);
+
+    output
+}
+
+/// A block of Markdown built from dictation
+enum MarkdownBlock {
+    Paragraph(String),
+    Bullet(String),
+    /// Level and text
+    Heading(usize, String),
+}
+
+/// Heading level spoken after "heading", e.g. "heading two"
+fn heading_level(word: &str) -> Option<usize> {
+    match word {
+        "one" | "1" => Some(1),
+        "two" | "2" => Some(2),
+        "three" | "3" => Some(3),
+        _ => None,
+    }
+}
+
+/// Whether the word at `idx` begins a sentence, so "heading" in the middle of
+/// one is left alone
+fn starts_sentence(raw_words: &[&str], idx: usize) -> bool {
+    idx == 0 || raw_words[idx - 1].ends_with(['.', '!', '?', ':'])
+}
+
+/// Turn spoken structure into Markdown for notes and Markdown editors:
+/// "bullet point milk" becomes `- milk` and "heading two groceries" becomes
+/// `## groceries`. A list item or heading runs to the end of its sentence, and
+/// "heading" only counts at the start of one.
+pub fn apply_markdown_commands(text: &str) -> String {
+    let raw_words: Vec<&str> = text.split_whitespace().collect();
+    let words: Vec<String> = raw_words.iter().map(|w| normalize_word(w)).collect();
+
+    let mut blocks: Vec<MarkdownBlock> = Vec::new();
+    let mut current = MarkdownBlock::Paragraph(String::new());
+    let mut idx = 0;
+
+    while idx < raw_words.len() {
+        let next = if matches_phrase(&words, idx, &BULLET_POINT) {
+            idx += BULLET_POINT.len();
+            Some(MarkdownBlock::Bullet(String::new()))
+        } else if starts_sentence(&raw_words, idx) && matches_phrase(&words, idx, &HEADING) {
+            idx += HEADING.len();
+            let level = words.get(idx).and_then(|w| heading_level(w));
+            if level.is_some() {
+                idx += 1;
+            }
+            Some(MarkdownBlock::Heading(level.unwrap_or(1), String::new()))
+        } else {
+            None
+        };
+
+        if let Some(next) = next {
+            blocks.push(std::mem::replace(&mut current, next));
+            continue;
+        }
+
+        let word = raw_words[idx];
+        let text = match &mut current {
+            MarkdownBlock::Paragraph(text)
+            | MarkdownBlock::Bullet(text)
+            | MarkdownBlock::Heading(_, text) => text,
+        };
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(word);
+        idx += 1;
+
+        // The sentence ends the item; what follows is a paragraph again
+        let ends_sentence = word.ends_with(['.', '!', '?']);
+        if ends_sentence && !matches!(current, MarkdownBlock::Paragraph(_)) {
+            blocks.push(std::mem::replace(
+                &mut current,
+                MarkdownBlock::Paragraph(String::new()),
+            ));
+        }
+    }
+    blocks.push(current);
+
+    let mut output = String::new();
+    let mut previous_bullet = false;
+    for block in blocks {
+        let (line, bullet) = match block {
+            MarkdownBlock::Paragraph(text) => (text.trim().to_string(), false),
+            MarkdownBlock::Bullet(text) => (
+                format!("- {}", text.trim().trim_end_matches([',', ';', '.'])),
+                true,
+            ),
+            MarkdownBlock::Heading(level, text) => (
+                format!(
+                    "{} {}",
+                    "#".repeat(level),
+                    text.trim().trim_end_matches([',', ';', '.', ':'])
+                ),
+                false,
+            ),
+        };
+        // Drop commands spoken with nothing after them, and empty paragraphs
+        if line.trim_start_matches(['#', '-']).trim().is_empty() {
+            continue;
+        }
+
+        if !output.is_empty() {
+            let separator = if bullet && previous_bullet {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            output.push_str(separator);
+        }
+        output.push_str(&line);
+        previous_bullet = bullet;
+    }
+
+    output
+}
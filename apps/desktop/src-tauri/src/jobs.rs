@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::time::MissedTickBehavior;
+use tracing::warn;
+
+/// Status of every registered job, in registration order
+static JOBS: Mutex<Vec<JobStatus>> = Mutex::new(Vec::new());
+
+/// A periodic background job and the result of its last run
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JobStatus {
+    /// Name the job was registered under, e.g. `sync`
+    pub name: String,
+    pub interval_secs: u64,
+    /// Whether a run is in progress
+    pub running: bool,
+    /// Runs finished since launch
+    pub runs: u64,
+    /// When the last run finished, unset until the first run
+    pub last_run_at: Option<i64>,
+    pub last_duration_ms: Option<u64>,
+    /// Error of the last run, unset when it succeeded
+    pub last_error: Option<String>,
+}
+
+/// Run `job` every `interval`, the first time after `delay`. Runs never overlap:
+/// a run that takes longer than the interval pushes the next one back. Must be
+/// called from Tauri setup or later, once the async runtime is up.
+pub fn register<F, Fut>(name: &'static str, interval: Duration, delay: Duration, mut job: F)
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), String>> + Send + 'static,
+{
+    if let Ok(mut jobs) = JOBS.lock() {
+        jobs.push(JobStatus {
+            name: name.to_string(),
+            interval_secs: interval.as_secs(),
+            running: false,
+            runs: 0,
+            last_run_at: None,
+            last_duration_ms: None,
+            last_error: None,
+        });
+    }
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(delay).await;
+
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            update(name, |status| status.running = true);
+            let started = Instant::now();
+            let result = job().await;
+            let duration_ms = started.elapsed().as_millis() as u64;
+
+            if let Err(e) = &result {
+                warn!("Job {} failed: {}", name, e);
+            }
+
+            let finished_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64;
+            update(name, |status| {
+                status.running = false;
+                status.runs += 1;
+                status.last_run_at = Some(finished_at);
+                status.last_duration_ms = Some(duration_ms);
+                status.last_error = result.err();
+            });
+        }
+    });
+}
+
+fn update(name: &str, apply: impl FnOnce(&mut JobStatus)) {
+    if let Ok(mut jobs) = JOBS.lock() {
+        if let Some(status) = jobs.iter_mut().find(|status| status.name == name) {
+            apply(status);
+        }
+    }
+}
+
+/// List the background jobs with the result of their last run
+#[tauri::command]
+#[specta::specta]
+pub fn jobs_status() -> Vec<JobStatus> {
+    match JOBS.lock() {
+        Ok(jobs) => jobs.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
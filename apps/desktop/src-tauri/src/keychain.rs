@@ -0,0 +1,90 @@
+/// Keychain service all of the app's secrets are stored under (generic passwords
+/// on macOS, Secret Service items on Linux, Credential Manager entries on Windows)
+const SERVICE: &str = "com.mac.dicto";
+
+/// Keychain account holding the signed-in user's auth token
+pub const AUTH_TOKEN_ACCOUNT: &str = "auth-token";
+
+/// Keychain account holding the bearer token for the sync backend
+pub const SYNC_TOKEN_ACCOUNT: &str = "sync-token";
+
+/// Keychain account holding the key sync data is encrypted with (base64)
+pub const SYNC_KEY_ACCOUNT: &str = "sync-key";
+
+/// `errSecItemNotFound`
+#[cfg(target_os = "macos")]
+const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+
+/// Read a secret, `None` if it was never stored
+#[cfg(target_os = "macos")]
+pub fn get_secret(account: &str) -> Result<Option<String>, String> {
+    use security_framework::passwords::get_generic_password;
+
+    match get_generic_password(SERVICE, account) {
+        Ok(bytes) => String::from_utf8(bytes)
+            .map(Some)
+            .map_err(|e| format!("Keychain item '{}' is not valid UTF-8: {}", account, e)),
+        Err(e) if e.code() == ERR_SEC_ITEM_NOT_FOUND => Ok(None),
+        Err(e) => Err(format!("Failed to read keychain item '{}': {}", account, e)),
+    }
+}
+
+/// Store a secret, replacing any existing value
+#[cfg(target_os = "macos")]
+pub fn set_secret(account: &str, secret: &str) -> Result<(), String> {
+    use security_framework::passwords::set_generic_password;
+
+    set_generic_password(SERVICE, account, secret.as_bytes())
+        .map_err(|e| format!("Failed to write keychain item '{}': {}", account, e))
+}
+
+/// Remove a secret; removing a missing secret is not an error
+#[cfg(target_os = "macos")]
+pub fn delete_secret(account: &str) -> Result<(), String> {
+    use security_framework::passwords::delete_generic_password;
+
+    match delete_generic_password(SERVICE, account) {
+        Ok(()) => Ok(()),
+        Err(e) if e.code() == ERR_SEC_ITEM_NOT_FOUND => Ok(()),
+        Err(e) => Err(format!(
+            "Failed to delete keychain item '{}': {}",
+            account, e
+        )),
+    }
+}
+
+/// Read a secret, `None` if it was never stored
+#[cfg(not(target_os = "macos"))]
+pub fn get_secret(account: &str) -> Result<Option<String>, String> {
+    match entry(account)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read keychain item '{}': {}", account, e)),
+    }
+}
+
+/// Store a secret, replacing any existing value
+#[cfg(not(target_os = "macos"))]
+pub fn set_secret(account: &str, secret: &str) -> Result<(), String> {
+    entry(account)?
+        .set_password(secret)
+        .map_err(|e| format!("Failed to write keychain item '{}': {}", account, e))
+}
+
+/// Remove a secret; removing a missing secret is not an error
+#[cfg(not(target_os = "macos"))]
+pub fn delete_secret(account: &str) -> Result<(), String> {
+    match entry(account)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!(
+            "Failed to delete keychain item '{}': {}",
+            account, e
+        )),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn entry(account: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(SERVICE, account)
+        .map_err(|e| format!("Secure storage is unavailable: {}", e))
+}
@@ -1,151 +1,80 @@
+mod app_context;
+mod audio_level;
+mod benchmark;
+mod chat;
+mod clipboard;
 mod commands;
+mod corrections;
 mod db;
+mod diagnostics;
 mod events;
+mod expansion;
 mod formatter;
+mod formatting_queue;
+mod hardware;
+mod interpreter;
+mod jobs;
+mod keychain;
+mod llm;
+mod microphone;
+mod mirror;
 mod model_download;
+mod model_registry;
+mod output;
+mod performance;
+mod persistence;
+mod pipeline_log;
+mod placeholders;
+mod platform;
+mod power;
+mod recordings;
+mod redaction;
+mod rewriter;
+mod rich_text;
+mod session;
 mod shortcut;
+mod spill;
+mod stt;
+mod sync;
+mod text;
 mod transcription;
+mod translator;
 mod tray;
+mod voice_processing;
 mod window;
+mod workspace;
 
+use commands::error::CommandError;
+use microphone::MicrophonePermissionDenied;
 use specta_typescript::Typescript;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::{Emitter, Manager, WebviewUrl};
 use tauri::menu::{MenuBuilder, SubmenuBuilder};
-use tauri_nspanel::{
-    tauri_panel, CollectionBehavior, ManagerExt, PanelBuilder, PanelLevel, StyleMask,
-    WebviewPanelManager,
-};
-use tauri_specta::{collect_commands, Builder};
+use tauri::{Emitter, Listener, Manager};
+#[cfg(target_os = "macos")]
+use tauri_nspanel::WebviewPanelManager;
+use tauri_specta::{collect_commands, collect_events, Builder};
+use tracing::{debug, error};
 use transcription::{create_transcription_service, TranscriptionServiceHandle};
 
-tauri_panel! {
-    panel!(WidgetPanel {
-        config: {
-            can_become_key_window: true,
-            is_floating_panel: true
-        }
-    })
-}
-
-#[derive(serde::Serialize, specta::Type)]
-struct AppInfo {
-    app_name: String,
-    url: Option<String>,
-}
-
-#[tauri::command]
-#[specta::specta]
-async fn get_frontmost_app() -> Result<AppInfo, String> {
-    #[cfg(target_os = "macos")]
-    {
-        use std::process::Command;
-
-        // Get frontmost app name
-        let app_output = Command::new("osascript")
-            .arg("-e")
-            .arg("tell application \"System Events\" to get name of first application process whose frontmost is true")
-            .output()
-            .map_err(|e| e.to_string())?;
-
-        let app_name = String::from_utf8_lossy(&app_output.stdout)
-            .trim()
-            .to_string();
-
-        // Check if it's a supported browser and get URL
-        let url = match app_name.as_str() {
-            "Google Chrome" | "Arc" => {
-                let script = format!(
-                    "tell application \"{}\" to get URL of active tab of front window",
-                    app_name
-                );
-                Command::new("osascript")
-                    .arg("-e")
-                    .arg(&script)
-                    .output()
-                    .ok()
-                    .and_then(|o| {
-                        let url = String::from_utf8_lossy(&o.stdout).trim().to_string();
-                        if url.is_empty() {
-                            None
-                        } else {
-                            Some(url)
-                        }
-                    })
-            }
-            "Safari" => Command::new("osascript")
-                .arg("-e")
-                .arg("tell application \"Safari\" to get URL of current tab of front window")
-                .output()
-                .ok()
-                .and_then(|o| {
-                    let url = String::from_utf8_lossy(&o.stdout).trim().to_string();
-                    if url.is_empty() {
-                        None
-                    } else {
-                        Some(url)
-                    }
-                }),
-            _ => None,
-        };
-
-        Ok(AppInfo { app_name, url })
-    }
-
-    #[cfg(not(target_os = "macos"))]
-    Err("Platform detection only available on macOS".to_string())
-}
-
-/// Saves a transcription to the local SQLite database.
-/// Returns the generated UUID on success.
-fn save_transcription(
-    app: &tauri::AppHandle,
-    text: &str,
-    formatted_text: Option<&str>,
-) -> Result<String, String> {
-    use tauri::Manager;
-
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-
-    let db_path = app_data_dir.join("dicto.db");
-
-    println!("Opening database at: {:?}", db_path);
-
-    let conn = rusqlite::Connection::open(&db_path)
-        .map_err(|e| format!("Failed to open database: {:?}: {}", db_path, e))?;
-
-    let id = uuid::Uuid::new_v4().to_string();
-    let created_at = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| format!("Failed to get timestamp: {}", e))?
-        .as_secs() as i64;
-
-    conn.execute(
-        "INSERT INTO transcriptions (id, text, formatted_text, created_at) VALUES (?1, ?2, ?3, ?4)",
-        rusqlite::params![id, text, formatted_text, created_at],
-    )
-    .map_err(|e| format!("Failed to insert transcription: {}", e))?;
-
-    println!("✅ Saved transcription with id: {}", id);
-
-    Ok(id)
-}
-
 #[tauri::command]
 #[specta::specta]
 async fn start_recording(
     app: tauri::AppHandle,
     service: tauri::State<'_, TranscriptionServiceHandle>,
     settings: crate::transcription::TranscriptionSettings,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let mut service = service.lock().await;
     service
-        .start_recording(app, settings)
+        .start_recording(app.clone(), settings)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| match e.downcast_ref::<MicrophonePermissionDenied>() {
+            Some(denied) => CommandError::permission_denied(denied.to_string()),
+            None => CommandError::recording_failed(e.to_string()),
+        })?;
+    drop(service);
+
+    refresh_recording_menus(&app, true);
+
+    Ok(())
 }
 
 #[tauri::command]
@@ -157,10 +86,36 @@ async fn stop_recording(
     style: String,
 ) -> Result<(), String> {
     let mut service = service.lock().await;
-    println!("{:?}", style);
+    debug!("Stopping recording with style {:?}", style);
     service
-        .stop_recording(app, app_name, style)
-        .map_err(|e| e.to_string())
+        .stop_recording(app.clone(), app_name, style)
+        .map_err(|e| e.to_string())?;
+    drop(service);
+
+    refresh_recording_menus(&app, false);
+
+    Ok(())
+}
+
+/// Flip the File menu and tray recording items to match the recording state
+fn refresh_recording_menus(app: &tauri::AppHandle, recording: bool) {
+    let title = if recording {
+        "Stop Recording"
+    } else {
+        "New Recording"
+    };
+
+    if let Some(item) = app
+        .menu()
+        .and_then(|menu| menu.get("file"))
+        .and_then(|file| file.as_submenu().and_then(|file| file.get("new")))
+    {
+        if let Some(item) = item.as_menuitem() {
+            let _ = item.set_text(title);
+        }
+    }
+
+    let _ = tray::update_tray_menu(app);
 }
 
 #[tauri::command]
@@ -175,174 +130,189 @@ async fn is_recording(
 #[tauri::command]
 #[specta::specta]
 async fn paste_text(app: tauri::AppHandle, text: String) -> Result<(), String> {
-    #[cfg(target_os = "macos")]
-    {
-        use cocoa::base::{id, nil};
-        use cocoa::foundation::NSString;
-        use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation};
-        use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
-        use objc::{class, msg_send, sel, sel_impl};
-        use std::thread;
-        // use std::time::Duration;
-
-        // Clone app handle and text for the thread
-        let app_clone = app.clone();
-        let text_clone = text.clone();
-
-        thread::spawn(move || {
-            unsafe {
-                let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
-                if pasteboard == nil {
-                    eprintln!("Failed to get pasteboard");
-                    if let Some(widget_window) = app_clone
-                        .get_webview_panel("widget")
-                        .ok()
-                        .and_then(|p| p.to_window())
-                    {
-                        let _ = widget_window.emit("paste-complete", ());
-                    }
-                    return;
-                }
-
-                // Clear contents
-                let _: () = msg_send![pasteboard, clearContents];
-
-                // Create NSString for the text
-                let ns_string = NSString::alloc(nil).init_str(&text_clone);
-                if ns_string == nil {
-                    eprintln!("Failed to create NSString");
-                    if let Some(widget_window) = app_clone
-                        .get_webview_panel("widget")
-                        .ok()
-                        .and_then(|p| p.to_window())
-                    {
-                        let _ = widget_window.emit("paste-complete", ());
-                    }
-                    return;
-                }
-
-                let ns_string_type = NSString::alloc(nil).init_str("public.utf8-plain-text");
-                let success: bool =
-                    msg_send![pasteboard, setString:ns_string forType:ns_string_type];
-
-                if !success {
-                    eprintln!("Failed to set string to pasteboard");
-                    if let Some(widget_window) = app_clone
-                        .get_webview_panel("widget")
-                        .ok()
-                        .and_then(|p| p.to_window())
-                    {
-                        let _ = widget_window.emit("paste-complete", ());
-                    }
-                    return;
-                }
-
-                // Small delay before sending keyboard events
-                // thread::sleep(Duration::from_millis(50));
-
-                // Send Cmd+V keyboard events
-                if let Ok(event_source) = CGEventSource::new(CGEventSourceStateID::HIDSystemState) {
-                    if let Ok(key_down) = CGEvent::new_keyboard_event(event_source.clone(), 9, true)
-                    {
-                        key_down.set_flags(CGEventFlags::CGEventFlagCommand);
-                        let _ = key_down.post(CGEventTapLocation::HID);
-                    }
-
-                    // thread::sleep(Duration::from_millis(50));
-
-                    if let Ok(key_up) = CGEvent::new_keyboard_event(event_source, 9, false) {
-                        key_up.set_flags(CGEventFlags::CGEventFlagCommand);
-                        let _ = key_up.post(CGEventTapLocation::HID);
-                    }
-                }
-
-                // // Wait a bit for paste to complete
-                // thread::sleep(Duration::from_millis(100));
-
-                // Clear clipboard after pasting (don't restore old contents to avoid exceptions)
-                let _: () = msg_send![pasteboard, clearContents];
-
-                println!("✅ Pasted successfully.");
-            }
-
-            // Always emit paste-complete event
-            if let Some(widget_window) = app_clone
-                .get_webview_panel("widget")
-                .ok()
-                .and_then(|p| p.to_window())
-            {
-                let _ = widget_window.emit("paste-complete", ());
-            }
-        });
-    }
-
-    #[cfg(not(target_os = "macos"))]
-    {
-        return Err("Paste functionality is only available on macOS".to_string());
-    }
-
-    Ok(())
+    output::paste_text(app, text)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let builder = Builder::<tauri::Wry>::new().commands(collect_commands![
-        // Core app commands
-        start_recording,
-        stop_recording,
-        is_recording,
-        paste_text,
-        get_frontmost_app,
-        // Model download - STT
-        model_download::check_stt_model_status,
-        model_download::download_stt_model,
-        // Model download - LLM
-        model_download::check_llm_model_status,
-        model_download::download_llm_model,
-        // Shortcut
-        shortcut::get_current_shortcut,
-        shortcut::change_shortcut,
-        shortcut::unregister_shortcut,
-        // Keyterms
-        commands::keyterms::keyterms_list,
-        commands::keyterms::keyterms_get,
-        commands::keyterms::keyterms_create,
-        commands::keyterms::keyterms_update,
-        commands::keyterms::keyterms_delete,
-        // Settings
-        commands::settings::settings_list,
-        commands::settings::settings_get,
-        commands::settings::settings_set,
-        commands::settings::settings_delete,
-        // Keys Vault
-        commands::keys_vault::keys_vault_list,
-        commands::keys_vault::keys_vault_get,
-        commands::keys_vault::keys_vault_set,
-        commands::keys_vault::keys_vault_delete,
-        // Transcriptions
-        commands::transcriptions::transcriptions_list,
-        commands::transcriptions::transcriptions_get,
-        commands::transcriptions::transcriptions_create,
-        commands::transcriptions::transcriptions_update,
-        commands::transcriptions::transcriptions_delete,
-        commands::transcriptions::transcriptions_analytics,
-        // Notes
-        commands::notes::notes_list,
-        commands::notes::notes_get,
-        commands::notes::notes_create,
-        commands::notes::notes_update,
-        commands::notes::notes_delete,
-        // Shortcuts
-        commands::shortcuts::shortcuts_list,
-        commands::shortcuts::shortcuts_get,
-        commands::shortcuts::shortcuts_create,
-        commands::shortcuts::shortcuts_update,
-        commands::shortcuts::shortcuts_delete,
-        // Writing Styles
-        commands::writing_styles::writing_styles_list,
-        commands::writing_styles::writing_styles_get,
-        commands::writing_styles::writing_styles_update
-    ]);
+    pipeline_log::init();
+
+    let builder = Builder::<tauri::Wry>::new()
+        .commands(collect_commands![
+            // Core app commands
+            start_recording,
+            stop_recording,
+            is_recording,
+            paste_text,
+            app_context::get_frontmost_app,
+            // Audio level
+            audio_level::audio_level_subscribe,
+            audio_level::audio_level_unsubscribe,
+            // Model registry
+            model_registry::models_catalog,
+            model_registry::models_installed,
+            model_download::check_model_status,
+            model_download::download_model,
+            benchmark::benchmark_models,
+            hardware::hardware_capabilities,
+            // Shortcut
+            shortcut::get_current_shortcut,
+            shortcut::change_shortcut,
+            shortcut::unregister_shortcut,
+            shortcut::get_repaste_shortcut,
+            shortcut::change_repaste_shortcut,
+            shortcut::get_rewrite_shortcut,
+            shortcut::change_rewrite_shortcut,
+            shortcut::get_note_shortcut,
+            shortcut::change_note_shortcut,
+            shortcut::shortcut_permission_status,
+            shortcut::shortcut_request_permission,
+            // Microphone
+            microphone::microphone_permission_status,
+            // Keyterms
+            commands::keyterms::keyterms_list,
+            commands::keyterms::keyterms_get,
+            commands::keyterms::keyterms_create,
+            commands::keyterms::keyterms_update,
+            commands::keyterms::keyterms_delete,
+            commands::keyterms::keyterms_suggestions,
+            // Settings
+            commands::settings::settings_list,
+            commands::settings::settings_get,
+            commands::settings::settings_set,
+            commands::settings::settings_delete,
+            // Onboarding
+            commands::seed::seed_defaults,
+            // Keys Vault
+            commands::keys_vault::keys_vault_list,
+            commands::keys_vault::keys_vault_get,
+            commands::keys_vault::keys_vault_set,
+            commands::keys_vault::keys_vault_delete,
+            // Audit log
+            commands::audit_log::audit_log_list,
+            // Auth
+            commands::auth::auth_get_token,
+            commands::auth::auth_set_token,
+            commands::auth::auth_clear_token,
+            // Transcriptions
+            commands::transcriptions::transcriptions_list,
+            commands::transcriptions::transcriptions_list_grouped,
+            commands::transcriptions::transcriptions_get,
+            commands::transcriptions::transcriptions_create,
+            commands::transcriptions::transcriptions_update,
+            commands::transcriptions::transcriptions_delete,
+            commands::transcriptions::transcriptions_segments,
+            commands::transcriptions::transcriptions_analytics,
+            commands::transcriptions::transcriptions_rate,
+            commands::transcriptions::transcriptions_feedback_export,
+            commands::transcriptions::transcriptions_correction_suggestions,
+            commands::transcriptions::transcriptions_diff,
+            commands::transcriptions::transcriptions_set_pinned,
+            commands::transcriptions::transcriptions_copy_to_clipboard,
+            commands::transcriptions::paste_last_transcription,
+            commands::transcriptions::transcriptions_replace_pasted,
+            // Formatter usage
+            commands::formatter_usage::formatter_usage_stats,
+            // Notes
+            commands::notes::notes_list,
+            commands::notes::notes_get,
+            commands::notes::notes_create,
+            commands::notes::notes_update,
+            commands::notes::notes_delete,
+            commands::notes::notes_set_pinned,
+            commands::notes::notes_set_archived,
+            commands::notes::notes_add_attachment,
+            commands::notes::notes_remove_attachment,
+            commands::notes::notes_revisions_list,
+            commands::notes::notes_restore_revision,
+            // Sessions
+            commands::sessions::sessions_list,
+            commands::sessions::sessions_get,
+            commands::sessions::sessions_delete,
+            // Chat
+            chat::chat_start,
+            chat::chat_history,
+            chat::chat_send,
+            chat::chat_insert_answer,
+            chat::chat_end,
+            // Shortcuts
+            commands::shortcuts::shortcuts_list,
+            commands::shortcuts::shortcuts_get,
+            commands::shortcuts::shortcuts_create,
+            commands::shortcuts::shortcuts_update,
+            commands::shortcuts::shortcuts_delete,
+            // Macros
+            commands::macros::macros_list,
+            commands::macros::macros_get,
+            commands::macros::macros_create,
+            commands::macros::macros_update,
+            commands::macros::macros_delete,
+            // iCloud Drive mirror
+            mirror::mirror_icloud_available,
+            mirror::mirror_export_all,
+            // Power
+            power::power_get_status,
+            // Diagnostics
+            diagnostics::diagnostics_report,
+            performance::performance_stats,
+            workspace::cleanup_workspaces,
+            jobs::jobs_status,
+            // Database
+            db::maintenance::db_maintenance,
+            db::pool::db_diagnostics,
+            // Sync
+            commands::sync::sync_configure,
+            commands::sync::sync_disable,
+            commands::sync::sync_now,
+            commands::sync::sync_status,
+            commands::sync::sync_get_key,
+            // Writing Styles
+            commands::writing_styles::writing_styles_list,
+            commands::writing_styles::writing_styles_get,
+            commands::writing_styles::writing_styles_update,
+            // URL Rules
+            commands::url_rules::url_rules_list,
+            commands::url_rules::url_rules_get,
+            commands::url_rules::url_rules_create,
+            commands::url_rules::url_rules_update,
+            commands::url_rules::url_rules_delete,
+            // App Rules
+            commands::app_rules::app_rules_list,
+            commands::app_rules::app_rules_get,
+            commands::app_rules::app_rules_create,
+            commands::app_rules::app_rules_update,
+            commands::app_rules::app_rules_delete
+        ])
+        .events(collect_events![
+            transcription::TranscriptionProcessing,
+            transcription::TranscriptionProgress,
+            transcription::TranscriptionPartial,
+            transcription::TranscriptionConfidence,
+            transcription::TranscriptionResult,
+            transcription::TranscriptionRefined,
+            transcription::SessionStarted,
+            transcription::SessionFinalizing,
+            transcription::SessionEnded,
+            transcription::RecordingCancelled,
+            transcription::TranscriptionError,
+            transcription::FirstWordsDetected,
+            transcription::RecordingElapsed,
+            transcription::RecordingRetry,
+            transcription::ModelLoadFailed,
+            model_download::DownloadProgress,
+            model_download::DownloadComplete,
+            model_download::DownloadError,
+            formatting_queue::FormattingCompletedLate,
+            output::PasteTargetGone,
+            output::PasteCountdown,
+            chat::ChatDelta,
+            chat::ChatReply,
+            chat::ChatFailed,
+            microphone::MicrophonePermissionDenied,
+            shortcut::ShortcutListenerFailed,
+            power::LowPowerChanged,
+            pipeline_log::PipelineLog
+        ]);
 
     #[cfg(debug_assertions)]
     builder
@@ -354,23 +324,57 @@ pub fn run() {
 
     let transcription_service = create_transcription_service();
 
-    tauri::Builder::default()
-        .plugin(tauri_plugin_http::init())
+    // Test builds also answer the `test_*` commands end-to-end tests drive the app with
+    #[cfg(not(any(test, feature = "test-harness")))]
+    let invoke_handler = builder.invoke_handler();
+    #[cfg(any(test, feature = "test-harness"))]
+    let invoke_handler = commands::testing::with_test_commands(builder.invoke_handler());
+
+    let app_builder = tauri::Builder::default().plugin(tauri_plugin_http::init());
+
+    // The widget is an NSPanel on macOS, so it can float over full-screen apps
+    #[cfg(target_os = "macos")]
+    let app_builder = app_builder
         .manage(WebviewPanelManager::<tauri::Wry>::default())
-        .plugin(tauri_nspanel::init::<tauri::Wry>())
+        .plugin(tauri_nspanel::init::<tauri::Wry>());
+
+    app_builder
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_macos_permissions::init())
         .plugin(db::init_database())
         .manage(transcription_service)
-        .invoke_handler(builder.invoke_handler())
+        .invoke_handler(invoke_handler)
         .setup(move |app| {
             builder.mount_events(app.handle());
 
             // Initialize database connection pool after migrations
             db::setup_pool(app)?;
 
+            // Mirror logs to the frontend while the pipeline log setting is on
+            pipeline_log::attach(app.handle());
+
+            // Older versions kept the auth token in a plaintext store
+            if let Err(e) = commands::auth::migrate_legacy_token(app.handle()) {
+                error!("Failed to migrate auth token: {}", e);
+            }
+
+            // Background jobs, listed by `jobs_status`
+            // Retry formatting that failed while offline
+            formatting_queue::register_retry_job(app.handle().clone());
+            // Push and pull changes while sync is configured
+            sync::register_sync_job(app.handle().clone());
+            // Integrity check and VACUUM once a month
+            db::maintenance::register_maintenance_job();
+            // Delete week-old workspaces kept from failed recordings
+            workspace::register_cleanup_job(app.handle().clone());
+            // Delete the audio of transcriptions not rated within a day
+            recordings::register_cleanup_job(app.handle().clone());
+
+            // Switch to low power mode while the battery runs low
+            power::register_battery_job(app.handle().clone());
+
             // Create menubar
             let app_menu = SubmenuBuilder::new(app, "Dicto")
                 .about(None)
@@ -382,7 +386,7 @@ pub fn run() {
                 .quit()
                 .build()?;
 
-            let file_menu = SubmenuBuilder::new(app, "File")
+            let file_menu = SubmenuBuilder::with_id(app, "file", "File")
                 .text("new", "New Recording")
                 .separator()
                 .text("settings", "Settings")
@@ -425,47 +429,48 @@ pub fn run() {
             app.set_menu(menu)?;
 
             // Handle menu events
-            app.on_menu_event(move |app_handle, event| {
-                match event.id().as_ref() {
-                    "new" => {
-                        println!("New Recording clicked");
-                    }
-                    "settings" => {
-                        println!("Settings clicked");
-                        let _ = app_handle.emit("open-settings", ());
-                    }
-                    "transcriptions" => {
-                        println!("Transcriptions clicked");
-                        let _ = app_handle.emit("navigate-transcriptions", ());
-                    }
-                    "notes" => {
-                        println!("Notes clicked");
-                        let _ = app_handle.emit("navigate-notes", ());
-                    }
-                    "keyterms" => {
-                        println!("Keyterms clicked");
-                        let _ = app_handle.emit("navigate-keyterms", ());
-                    }
-                    "writing-styles" => {
-                        println!("Writing Styles clicked");
-                        let _ = app_handle.emit("navigate-writing-styles", ());
-                    }
-                    "reload" => {
-                        println!("Reload clicked");
-                        if let Some(window) = app_handle.get_webview_window("main") {
-                            let _ = window.eval("location.reload()");
-                        }
-                    }
-                    "docs" => {
-                        println!("Documentation clicked");
-                        let _ = app_handle.emit("open-docs", ());
-                    }
-                    "report" => {
-                        println!("Report Issue clicked");
-                        let _ = app_handle.emit("open-report-issue", ());
+            app.on_menu_event(move |app_handle, event| match event.id().as_ref() {
+                "new" => {
+                    debug!("New Recording clicked");
+                    // The tray's dictation item; the dashboard in front is the paste target
+                    tray::remember_frontmost_app();
+                    tray::toggle_dictation(app_handle);
+                }
+                "settings" => {
+                    debug!("Settings clicked");
+                    let _ = app_handle.emit("open-settings", ());
+                }
+                "transcriptions" => {
+                    debug!("Transcriptions clicked");
+                    let _ = app_handle.emit("navigate-transcriptions", ());
+                }
+                "notes" => {
+                    debug!("Notes clicked");
+                    let _ = app_handle.emit("navigate-notes", ());
+                }
+                "keyterms" => {
+                    debug!("Keyterms clicked");
+                    let _ = app_handle.emit("navigate-keyterms", ());
+                }
+                "writing-styles" => {
+                    debug!("Writing Styles clicked");
+                    let _ = app_handle.emit("navigate-writing-styles", ());
+                }
+                "reload" => {
+                    debug!("Reload clicked");
+                    if let Some(window) = app_handle.get_webview_window("main") {
+                        let _ = window.eval("location.reload()");
                     }
-                    _ => {}
                 }
+                "docs" => {
+                    debug!("Documentation clicked");
+                    let _ = app_handle.emit("open-docs", ());
+                }
+                "report" => {
+                    debug!("Report Issue clicked");
+                    let _ = app_handle.emit("open-report-issue", ());
+                }
+                _ => {}
             });
 
             let main_window = window::build_main_window(app.handle(), "main", "index.html")
@@ -478,84 +483,38 @@ pub fn run() {
                 if let tauri::WindowEvent::CloseRequested { api, .. } = event {
                     window_clone.hide().unwrap();
                     api.prevent_close();
+                    window::sync_dock_icon(window_clone.app_handle());
                 }
             });
 
-                let panel = PanelBuilder::<tauri::Wry, WidgetPanel>::new(app.handle(), "widget")
-                .url(WebviewUrl::App("widget.html".into()))
-                .size(tauri::Size::Logical(tauri::LogicalSize {
-                    width: 80.0, // When chat 535
-                    height: 32.0, // When chat 370
-                }))
-                .level(PanelLevel::Floating)
-                .has_shadow(false)
-                .collection_behavior(
-                    CollectionBehavior::new()
-                        .can_join_all_spaces()
-                        .full_screen_auxiliary()
-                        .into(),
-                )
-                .hides_on_deactivate(false)
-                .works_when_modal(true)
-                .with_window(|w| w.decorations(false).transparent(true))
-                .style_mask(StyleMask::empty().nonactivating_panel().into())
-                .build()
-                .map_err(|e| e.to_string())?;
+            // Reload macro triggers when macros change
+            interpreter::watch_macros(app.handle());
+
+            // Apply menubar-only mode now and whenever a setting changes
+            window::sync_dock_icon(app.handle());
+            let app_handle = app.handle().clone();
+            app.listen(events::names::SETTINGS_UPDATED, move |_| {
+                window::sync_dock_icon(&app_handle);
+            });
 
-            panel.show();
+            let widget_window = platform::overlay_window().build_widget(app.handle())?;
 
             // Initialize global shortcut from stored settings
             shortcut::enable_shortcut(app);
 
             // Position widget window at top center
-            if let Some(widget_window) = panel.to_window() {
-                // Set window to be visible on all workspaces/desktops (macOS)
-                #[cfg(target_os = "macos")]
-                {
-                    use cocoa::appkit::{NSWindow, NSWindowCollectionBehavior, NSColor};
-                    use cocoa::base::{id, nil};
-                    
-                    unsafe {
-                        if let Ok(ns_window_ptr) = widget_window.ns_window() {
-                            let ns_window = ns_window_ptr as id;
-
-                            // Combine both behaviors for all spaces + fullscreen visibility
-                            let behavior = NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces
-                                | NSWindowCollectionBehavior::NSWindowCollectionBehaviorFullScreenAuxiliary;
-                            
-                            ns_window.setCollectionBehavior_(behavior);
-                            
-                            // Set window level to ensure it stays on top
-                            ns_window.setLevel_(cocoa::appkit::NSMainMenuWindowLevel as i64 + 1);
-                            
-                            // Make window background fully transparent
-                            ns_window.setOpaque_(false);
-                            ns_window.setBackgroundColor_(NSColor::clearColor(nil));
-                            ns_window.setHasShadow_(false); 
-
-                            // Disable window dragging by background
-                            ns_window.setMovableByWindowBackground_(cocoa::base::NO);
-
-                            ns_window.setAlphaValue_(0.9); 
-                        }
-                    }
-                }
-                
-                // Position the window
-                if let Ok(primary_monitor) = widget_window.primary_monitor() {
-                    if let Some(monitor) = primary_monitor {
-                        let size = monitor.size();
-                        let scale_factor = monitor.scale_factor();
-                        
-                        let widget_width = 50.0;
-                        let widget_height = 20.0; // Add your widget's height
-
-                        let x = (size.width as f64 / scale_factor - widget_width) / 2.0;
-                        let y = (size.height as f64 / scale_factor - widget_height) - 15.0; // 50.0 is bottom margin
-
-                        
-                        let _ = widget_window.set_position(tauri::LogicalPosition::new(x, y));
-                    }
+            if let Ok(primary_monitor) = widget_window.primary_monitor() {
+                if let Some(monitor) = primary_monitor {
+                    let size = monitor.size();
+                    let scale_factor = monitor.scale_factor();
+
+                    let widget_width = 50.0;
+                    let widget_height = 20.0; // Add your widget's height
+
+                    let x = (size.width as f64 / scale_factor - widget_width) / 2.0;
+                    let y = (size.height as f64 / scale_factor - widget_height) - 15.0; // 50.0 is bottom margin
+
+                    let _ = widget_window.set_position(tauri::LogicalPosition::new(x, y));
                 }
             }
 
@@ -0,0 +1,245 @@
+use futures::StreamExt;
+use reqwest::{Client, Response};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::commands::keys_vault::{keys_vault_get, VaultService};
+use crate::commands::settings::{keys as setting_keys, read_setting};
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    temperature: f32,
+    /// Ask for the reply as server-sent events, one delta at a time
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Serialize)]
+pub struct ChatMessage<'a> {
+    pub role: &'a str,
+    pub content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatResponseMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatResponseMessage {
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct ChatStreamChunk {
+    choices: Vec<ChatStreamChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatStreamChoice {
+    delta: ChatStreamDelta,
+}
+
+#[derive(Deserialize)]
+struct ChatStreamDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// LLM provider picked with the `intelligenceModel` setting. Each exposes an
+/// OpenAI-compatible chat completions endpoint.
+#[derive(Debug, Clone, Copy)]
+pub enum Provider {
+    Groq,
+    OpenAI,
+    Gemini,
+}
+
+impl Provider {
+    pub fn from_setting() -> Self {
+        match read_setting(setting_keys::INTELLIGENCE_MODEL).as_deref() {
+            Some("OpenAI") => Self::OpenAI,
+            Some("Gemini") => Self::Gemini,
+            _ => Self::Groq,
+        }
+    }
+
+    pub fn vault_service(&self) -> VaultService {
+        match self {
+            Self::Groq => VaultService::Groq,
+            Self::OpenAI => VaultService::OpenAI,
+            Self::Gemini => VaultService::Gemini,
+        }
+    }
+
+    fn url(&self) -> &'static str {
+        match self {
+            Self::Groq => "https://api.groq.com/openai/v1/chat/completions",
+            Self::OpenAI => "https://api.openai.com/v1/chat/completions",
+            Self::Gemini => {
+                "https://generativelanguage.googleapis.com/v1beta/openai/chat/completions"
+            }
+        }
+    }
+
+    fn model(&self) -> &'static str {
+        match self {
+            Self::Groq => "llama-3.3-70b-versatile",
+            Self::OpenAI => "gpt-4o-mini",
+            Self::Gemini => "gemini-2.0-flash",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Groq => "Groq",
+            Self::OpenAI => "OpenAI",
+            Self::Gemini => "Gemini",
+        }
+    }
+
+    /// API key of the provider from the vault
+    pub fn api_key(&self) -> Result<String, String> {
+        match keys_vault_get(self.vault_service()) {
+            Ok(Some(key)) => Ok(key),
+            Ok(None) => Err(format!(
+                "No {} key in the vault",
+                self.vault_service().as_str()
+            )),
+            Err(e) => Err(format!("Failed to read the API key: {}", e.message)),
+        }
+    }
+}
+
+/// Send a conversation to the provider and return the reply, giving up after
+/// `timeout`
+pub async fn complete(
+    provider: Provider,
+    api_key: &str,
+    messages: Vec<ChatMessage<'_>>,
+    temperature: f32,
+    timeout: Duration,
+) -> Result<String, String> {
+    let request = ChatRequest {
+        model: provider.model(),
+        messages,
+        temperature,
+        stream: false,
+    };
+    let response = send(provider, api_key, &request, timeout).await?;
+
+    let response: ChatResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response: {}", e))?;
+
+    response
+        .choices
+        .into_iter()
+        .next()
+        .map(|choice| choice.message.content.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .ok_or_else(|| "Empty response".to_string())
+}
+
+/// Like [`complete`], but the reply is streamed: `on_delta` is called with each
+/// piece as it arrives, and the whole reply is returned at the end
+pub async fn stream(
+    provider: Provider,
+    api_key: &str,
+    messages: Vec<ChatMessage<'_>>,
+    temperature: f32,
+    timeout: Duration,
+    mut on_delta: impl FnMut(&str),
+) -> Result<String, String> {
+    let request = ChatRequest {
+        model: provider.model(),
+        messages,
+        temperature,
+        stream: true,
+    };
+    let response = send(provider, api_key, &request, timeout).await?;
+
+    let mut body = response.bytes_stream();
+    // Events can be split across chunks, even inside a UTF-8 character
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut reply = String::new();
+
+    'events: while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|e| format!("Stream failed: {}", e))?;
+        buffer.extend_from_slice(&chunk);
+
+        while let Some(end) = buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let Some(data) = line.trim().strip_prefix("data:") else {
+                continue;
+            };
+            let data = data.trim();
+            if data == "[DONE]" {
+                break 'events;
+            }
+
+            let Ok(chunk) = serde_json::from_str::<ChatStreamChunk>(data) else {
+                continue;
+            };
+            if let Some(delta) = chunk
+                .choices
+                .into_iter()
+                .next()
+                .and_then(|choice| choice.delta.content)
+                .filter(|delta| !delta.is_empty())
+            {
+                on_delta(&delta);
+                reply.push_str(&delta);
+            }
+        }
+    }
+
+    let reply = reply.trim();
+    if reply.is_empty() {
+        return Err("Empty response".to_string());
+    }
+    Ok(reply.to_string())
+}
+
+/// Post a chat request, turning an error status into an error with its body
+async fn send(
+    provider: Provider,
+    api_key: &str,
+    request: &ChatRequest<'_>,
+    timeout: Duration,
+) -> Result<Response, String> {
+    let client = Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let response = client
+        .post(provider.url())
+        .bearer_auth(api_key)
+        .json(request)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!(
+            "{} request failed ({}): {}",
+            provider.name(),
+            status,
+            body
+        ));
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,101 @@
+use serde::Serialize;
+use specta::Type;
+use std::fmt;
+use tauri::AppHandle;
+use tauri_specta::Event;
+use tracing::warn;
+
+/// Shown to the user when recording is refused for lack of microphone access
+#[cfg(target_os = "macos")]
+const PERMISSION_HINT: &str =
+    "Allow Dicto to use the microphone in System Settings > Privacy & Security > Microphone";
+
+#[cfg(not(target_os = "macos"))]
+const PERMISSION_HINT: &str = "Allow Dicto to use the microphone";
+
+/// Whether the app may record from the microphone
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MicrophonePermission {
+    /// Not asked yet; the system prompts when recording first starts
+    NotDetermined,
+    /// Blocked by a device policy, so the user can't grant it
+    Restricted,
+    Denied,
+    Granted,
+}
+
+/// Payload of `microphone-permission-denied`, emitted when a recording can't
+/// start because microphone access was refused. Also the error `start_recording`
+/// fails with in that case.
+#[derive(Debug, Clone, Serialize, Type, Event)]
+pub struct MicrophonePermissionDenied {
+    pub permission: MicrophonePermission,
+    pub permission_hint: String,
+}
+
+impl fmt::Display for MicrophonePermissionDenied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Microphone access denied. {}", self.permission_hint)
+    }
+}
+
+impl std::error::Error for MicrophonePermissionDenied {}
+
+/// Current microphone permission, read without prompting
+#[cfg(target_os = "macos")]
+pub fn permission() -> MicrophonePermission {
+    use cocoa::base::id;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    #[link(name = "AVFoundation", kind = "framework")]
+    extern "C" {
+        static AVMediaTypeAudio: id;
+    }
+
+    // AVAuthorizationStatus
+    let status: isize = unsafe {
+        msg_send![class!(AVCaptureDevice), authorizationStatusForMediaType: AVMediaTypeAudio]
+    };
+
+    match status {
+        1 => MicrophonePermission::Restricted,
+        2 => MicrophonePermission::Denied,
+        3 => MicrophonePermission::Granted,
+        _ => MicrophonePermission::NotDetermined,
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn permission() -> MicrophonePermission {
+    MicrophonePermission::Granted
+}
+
+/// Check microphone access before opening the input stream, which otherwise
+/// fails on its own thread with nothing shown to the user. Emits
+/// `microphone-permission-denied` when access was refused.
+pub fn ensure_permission(app: &AppHandle) -> Result<(), MicrophonePermissionDenied> {
+    let permission = permission();
+    if !matches!(
+        permission,
+        MicrophonePermission::Denied | MicrophonePermission::Restricted
+    ) {
+        return Ok(());
+    }
+
+    let denied = MicrophonePermissionDenied {
+        permission,
+        permission_hint: PERMISSION_HINT.to_string(),
+    };
+    warn!("{}", denied);
+    let _ = denied.emit(app);
+
+    Err(denied)
+}
+
+/// Whether the app may record from the microphone
+#[tauri::command]
+#[specta::specta]
+pub fn microphone_permission_status() -> MicrophonePermission {
+    permission()
+}
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS pending_formatting (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    transcription_id TEXT NOT NULL UNIQUE REFERENCES transcriptions(id) ON DELETE CASCADE,
+    category TEXT NOT NULL,
+    style TEXT NOT NULL,
+    app_name TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_pending_formatting_created_at ON pending_formatting(created_at);
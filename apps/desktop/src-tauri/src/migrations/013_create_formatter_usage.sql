@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS formatter_usage (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    characters_sent INTEGER NOT NULL,
+    latency_ms INTEGER NOT NULL,
+    success INTEGER NOT NULL CHECK(success IN (0, 1)),
+    error TEXT,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_formatter_usage_created_at ON formatter_usage(created_at);
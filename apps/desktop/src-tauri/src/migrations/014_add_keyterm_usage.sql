@@ -0,0 +1,6 @@
+-- Track how often each keyterm shows up in transcripts so the Whisper prompt
+-- can favour the terms that are actually used
+ALTER TABLE keyterms ADD COLUMN usage_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE keyterms ADD COLUMN last_used_at INTEGER;
+
+CREATE INDEX idx_keyterms_usage ON keyterms(usage_count DESC, last_used_at DESC);
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS transcription_feedback (
+    transcription_id TEXT PRIMARY KEY NOT NULL REFERENCES transcriptions(id) ON DELETE CASCADE,
+    rating TEXT NOT NULL CHECK(rating IN ('good', 'bad')),
+    corrected_text TEXT,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+-- Phrases users keep correcting (what was transcribed -> what they meant)
+CREATE TABLE IF NOT EXISTS corrections (
+    original TEXT NOT NULL,
+    corrected TEXT NOT NULL,
+    count INTEGER NOT NULL DEFAULT 1,
+    last_seen_at INTEGER NOT NULL,
+    PRIMARY KEY (original, corrected)
+);
+
+CREATE INDEX idx_corrections_count ON corrections(count DESC);
@@ -0,0 +1,3 @@
+ALTER TABLE transcriptions ADD COLUMN is_pinned INTEGER NOT NULL DEFAULT 0 CHECK(is_pinned IN (0, 1));
+
+CREATE INDEX idx_transcriptions_pinned ON transcriptions(is_pinned DESC, created_at DESC);
@@ -0,0 +1,4 @@
+-- Name of the app the transcription was dictated into
+ALTER TABLE transcriptions ADD COLUMN app_name TEXT;
+
+CREATE INDEX idx_transcriptions_app_name ON transcriptions(app_name);
@@ -0,0 +1,52 @@
+-- Change tracking for cloud sync: a row needs pushing when updated_at > synced_at
+ALTER TABLE transcriptions ADD COLUMN updated_at INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE transcriptions ADD COLUMN synced_at INTEGER;
+ALTER TABLE notes ADD COLUMN synced_at INTEGER;
+ALTER TABLE keyterms ADD COLUMN synced_at INTEGER;
+
+UPDATE transcriptions SET updated_at = created_at;
+
+-- Transcriptions are written from several places; keep updated_at current here
+-- unless the statement sets it itself (as sync does when applying remote changes)
+CREATE TRIGGER transcriptions_set_updated_at_on_insert
+AFTER INSERT ON transcriptions
+WHEN NEW.updated_at = 0
+BEGIN
+    UPDATE transcriptions SET updated_at = NEW.created_at WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER transcriptions_set_updated_at_on_update
+AFTER UPDATE OF text, formatted_text, is_pinned ON transcriptions
+WHEN NEW.updated_at = OLD.updated_at
+BEGIN
+    UPDATE transcriptions SET updated_at = CAST(strftime('%s', 'now') AS INTEGER) WHERE id = NEW.id;
+END;
+
+-- Deletions waiting to be pushed
+CREATE TABLE IF NOT EXISTS sync_tombstones (
+    entity TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    deleted_at INTEGER NOT NULL,
+    PRIMARY KEY (entity, entity_id)
+);
+
+CREATE TRIGGER transcriptions_sync_tombstone
+AFTER DELETE ON transcriptions
+BEGIN
+    INSERT OR REPLACE INTO sync_tombstones (entity, entity_id, deleted_at)
+    VALUES ('transcription', OLD.id, CAST(strftime('%s', 'now') AS INTEGER));
+END;
+
+CREATE TRIGGER notes_sync_tombstone
+AFTER DELETE ON notes
+BEGIN
+    INSERT OR REPLACE INTO sync_tombstones (entity, entity_id, deleted_at)
+    VALUES ('note', OLD.id, CAST(strftime('%s', 'now') AS INTEGER));
+END;
+
+CREATE TRIGGER keyterms_sync_tombstone
+AFTER DELETE ON keyterms
+BEGIN
+    INSERT OR REPLACE INTO sync_tombstones (entity, entity_id, deleted_at)
+    VALUES ('keyterm', OLD.id, CAST(strftime('%s', 'now') AS INTEGER));
+END;
@@ -0,0 +1,10 @@
+-- Spoken trigger phrases expanded into a template by the command interpreter
+CREATE TABLE IF NOT EXISTS macros (
+    id TEXT PRIMARY KEY NOT NULL,
+    trigger TEXT NOT NULL,
+    template TEXT NOT NULL,
+    created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
+
+CREATE UNIQUE INDEX idx_macros_trigger ON macros(trigger COLLATE NOCASE);
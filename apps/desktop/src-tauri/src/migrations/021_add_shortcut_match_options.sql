@@ -0,0 +1,3 @@
+-- Per-shortcut trigger matching: exact case, and whole words vs. anywhere in a word
+ALTER TABLE shortcuts ADD COLUMN case_sensitive INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE shortcuts ADD COLUMN whole_word INTEGER NOT NULL DEFAULT 1;
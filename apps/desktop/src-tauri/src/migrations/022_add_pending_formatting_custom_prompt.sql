@@ -0,0 +1,2 @@
+-- Custom writing style prompt resolved when the transcription was dictated
+ALTER TABLE pending_formatting ADD COLUMN custom_prompt TEXT;
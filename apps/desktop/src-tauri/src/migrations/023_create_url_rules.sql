@@ -0,0 +1,12 @@
+-- Writing style applied when dictating into a browser tab whose URL matches
+CREATE TABLE IF NOT EXISTS url_rules (
+    id TEXT PRIMARY KEY NOT NULL,
+    pattern TEXT NOT NULL,
+    category TEXT NOT NULL CHECK(category IN ('Personal', 'Work', 'Email', 'General')),
+    style TEXT,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
+
+CREATE UNIQUE INDEX idx_url_rules_pattern ON url_rules(pattern);
@@ -0,0 +1,2 @@
+-- JSON context (app, URL, window title) the transcription was dictated with
+ALTER TABLE pending_formatting ADD COLUMN context TEXT;
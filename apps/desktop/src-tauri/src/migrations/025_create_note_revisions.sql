@@ -0,0 +1,10 @@
+-- Previous versions of a note, saved each time it is updated
+CREATE TABLE IF NOT EXISTS note_revisions (
+    note_id TEXT NOT NULL REFERENCES notes(id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    -- When this version was written, i.e. the note's updated_at at the time
+    saved_at INTEGER NOT NULL,
+    PRIMARY KEY (note_id, revision)
+);
@@ -0,0 +1,4 @@
+ALTER TABLE notes ADD COLUMN is_pinned INTEGER NOT NULL DEFAULT 0 CHECK(is_pinned IN (0, 1));
+ALTER TABLE notes ADD COLUMN archived INTEGER NOT NULL DEFAULT 0 CHECK(archived IN (0, 1));
+
+CREATE INDEX idx_notes_archived_pinned ON notes(archived, is_pinned DESC);
@@ -0,0 +1,12 @@
+-- Files attached to a note, copied into the app data folder
+CREATE TABLE IF NOT EXISTS note_attachments (
+    id TEXT PRIMARY KEY NOT NULL,
+    note_id TEXT NOT NULL REFERENCES notes(id) ON DELETE CASCADE,
+    -- Name of the file as attached, shown to the user
+    file_name TEXT NOT NULL,
+    path TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
+
+CREATE INDEX idx_note_attachments_note_id ON note_attachments(note_id, created_at);
@@ -0,0 +1,32 @@
+-- Keyterms are unique within a category, ignoring case. So are shortcut
+-- triggers, except case-sensitive ones, which only clash with the exact same
+-- trigger. Existing duplicates are flagged rather than deleted, keeping the
+-- oldest of each unflagged, and listed to the user to edit or delete.
+ALTER TABLE keyterms ADD COLUMN is_duplicate INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE shortcuts ADD COLUMN is_duplicate INTEGER NOT NULL DEFAULT 0;
+
+UPDATE keyterms SET is_duplicate = 1
+WHERE rowid NOT IN (
+    SELECT MIN(rowid) FROM keyterms GROUP BY text COLLATE NOCASE, category
+);
+
+UPDATE shortcuts SET is_duplicate = 1
+WHERE case_sensitive = 0 AND rowid NOT IN (
+    SELECT MIN(rowid) FROM shortcuts WHERE case_sensitive = 0
+    GROUP BY trigger COLLATE NOCASE, category
+);
+
+UPDATE shortcuts SET is_duplicate = 1
+WHERE case_sensitive = 1 AND rowid NOT IN (
+    SELECT MIN(rowid) FROM shortcuts WHERE case_sensitive = 1
+    GROUP BY trigger, category
+);
+
+CREATE UNIQUE INDEX idx_keyterms_text_category
+ON keyterms(text COLLATE NOCASE, category) WHERE is_duplicate = 0;
+
+CREATE UNIQUE INDEX idx_shortcuts_trigger_category
+ON shortcuts(trigger COLLATE NOCASE, category) WHERE case_sensitive = 0 AND is_duplicate = 0;
+
+CREATE UNIQUE INDEX idx_shortcuts_trigger_category_exact
+ON shortcuts(trigger, category) WHERE case_sensitive = 1 AND is_duplicate = 0;
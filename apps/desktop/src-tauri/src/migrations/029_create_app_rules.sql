@@ -0,0 +1,13 @@
+-- How dictated text is inserted into an app, for apps that ignore a pasted Cmd+V
+CREATE TABLE IF NOT EXISTS app_rules (
+    id TEXT PRIMARY KEY NOT NULL,
+    app_name TEXT NOT NULL,
+    output_method TEXT NOT NULL DEFAULT 'paste' CHECK(output_method IN ('paste', 'type')),
+    -- Characters per second when typing; NULL uses the default rate
+    typing_rate INTEGER,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
+
+CREATE UNIQUE INDEX idx_app_rules_app_name ON app_rules(app_name COLLATE NOCASE);
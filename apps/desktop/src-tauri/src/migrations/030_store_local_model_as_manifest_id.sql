@@ -0,0 +1,2 @@
+-- The local model setting names a model of the manifest by id instead of by engine
+UPDATE settings SET value = 'whisper-small' WHERE key = 'localModel' AND value = 'Whisper';
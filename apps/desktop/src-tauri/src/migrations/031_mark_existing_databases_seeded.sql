@@ -0,0 +1,10 @@
+-- Databases already in use when first-run defaults were added count as seeded, so
+-- upgrading doesn't add example shortcuts and keyterms the user never asked for.
+-- Fresh databases are empty here and still get the defaults.
+INSERT OR IGNORE INTO settings (key, value)
+SELECT 'defaultsSeeded', 'true'
+WHERE EXISTS (SELECT 1 FROM transcriptions)
+   OR EXISTS (SELECT 1 FROM notes)
+   OR EXISTS (SELECT 1 FROM shortcuts)
+   OR EXISTS (SELECT 1 FROM keyterms)
+   OR EXISTS (SELECT 1 FROM settings);
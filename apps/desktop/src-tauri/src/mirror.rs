@@ -0,0 +1,271 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::error;
+
+use crate::commands::error::CommandError;
+use crate::commands::notes::{notes_list, Note};
+use crate::commands::settings::{keys, read_bool_setting};
+use crate::commands::transcriptions::{transcriptions_get, Transcription};
+use crate::db::pool::get_connection;
+
+/// iCloud Drive's local folder, relative to the home directory
+const ICLOUD_DRIVE: &str = "Library/Mobile Documents/com~apple~CloudDocs";
+
+/// Folder inside iCloud Drive the Markdown files are written to
+const MIRROR_FOLDER: &str = "Dicto";
+
+const TRANSCRIPTIONS_DIR: &str = "Transcriptions";
+const NOTES_DIR: &str = "Notes";
+
+/// Characters of a note title kept in its file name
+const MAX_TITLE_CHARS: usize = 60;
+
+// ============================================================================
+// Mirroring
+// ============================================================================
+
+/// Folder the Markdown files are mirrored into; `None` if iCloud Drive is off
+pub fn icloud_folder() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")?;
+    let drive = PathBuf::from(home).join(ICLOUD_DRIVE);
+    drive.is_dir().then(|| drive.join(MIRROR_FOLDER))
+}
+
+/// Write a transcription's Markdown file, if mirroring is on
+pub fn mirror_transcription(transcription: &Transcription) {
+    if let Some(root) = mirror_root() {
+        if let Err(e) = write_transcription(&root, transcription) {
+            error!("Failed to mirror transcription {}: {}", transcription.id, e);
+        }
+    }
+}
+
+/// Write the Markdown file of a transcription saved elsewhere, if mirroring is on
+pub fn mirror_transcription_by_id(id: &str) {
+    if mirror_root().is_none() {
+        return;
+    }
+    match transcriptions_get(id.to_string()) {
+        Ok(transcription) => mirror_transcription(&transcription),
+        Err(e) => error!("Failed to mirror transcription {}: {}", id, e.message),
+    }
+}
+
+/// Remove a deleted transcription's Markdown file
+pub fn remove_transcription(transcription: &Transcription) {
+    if let Some(root) = mirror_root() {
+        remove_file(
+            &root
+                .join(TRANSCRIPTIONS_DIR)
+                .join(transcription_file_name(transcription)),
+        );
+    }
+}
+
+/// Write a note's Markdown file, replacing the one under its old title
+pub fn mirror_note(note: &Note, previous: Option<&Note>) {
+    let Some(root) = mirror_root() else {
+        return;
+    };
+
+    if let Some(previous) = previous {
+        if note_file_name(previous) != note_file_name(note) {
+            remove_note_file(&root, previous);
+        }
+    }
+    if let Err(e) = write_note(&root, note) {
+        error!("Failed to mirror note {}: {}", note.id, e);
+    }
+}
+
+/// Remove a deleted note's Markdown file
+pub fn remove_note(note: &Note) {
+    if let Some(root) = mirror_root() {
+        remove_note_file(&root, note);
+    }
+}
+
+/// Write every transcription and note, e.g. right after mirroring is turned on.
+/// Returns the number of files written.
+pub fn export_all() -> Result<usize, String> {
+    let root = icloud_folder().ok_or_else(|| "iCloud Drive is not available".to_string())?;
+
+    let conn = get_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, text, formatted_text, created_at, is_pinned, app_name FROM transcriptions",
+        )
+        .map_err(|e| e.to_string())?;
+    let transcriptions = stmt
+        .query_map([], |row| {
+            Ok(Transcription {
+                id: row.get(0)?,
+                text: row.get(1)?,
+                formatted_text: row.get(2)?,
+                created_at: row.get(3)?,
+                is_pinned: row.get(4)?,
+                app_name: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    drop(stmt);
+    drop(conn);
+
+    let notes = notes_list(None).map_err(|e| e.message)?;
+
+    for transcription in &transcriptions {
+        write_transcription(&root, transcription)?;
+    }
+    for note in &notes {
+        write_note(&root, note)?;
+    }
+
+    Ok(transcriptions.len() + notes.len())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn mirror_root() -> Option<PathBuf> {
+    if !read_bool_setting(keys::MIRROR_TO_ICLOUD, false) {
+        return None;
+    }
+    icloud_folder()
+}
+
+fn write_transcription(root: &Path, transcription: &Transcription) -> Result<(), String> {
+    let text = transcription
+        .formatted_text
+        .as_deref()
+        .filter(|text| !text.trim().is_empty())
+        .unwrap_or(&transcription.text);
+
+    let mut markdown = format!(
+        "---\nid: {}\ncreated: {}\n",
+        transcription.id,
+        local_datetime(transcription.created_at)
+    );
+    if let Some(app_name) = &transcription.app_name {
+        markdown.push_str(&format!("app: {}\n", yaml_string(app_name)));
+    }
+    markdown.push_str("---\n\n");
+    markdown.push_str(text.trim());
+    markdown.push('\n');
+
+    write_file(
+        &root.join(TRANSCRIPTIONS_DIR),
+        &transcription_file_name(transcription),
+        &markdown,
+    )
+}
+
+fn write_note(root: &Path, note: &Note) -> Result<(), String> {
+    let markdown = format!(
+        "---\nid: {}\ntitle: {}\ncreated: {}\nupdated: {}\n---\n\n# {}\n\n{}\n",
+        note.id,
+        yaml_string(&note.title),
+        local_datetime(note.created_at),
+        local_datetime(note.updated_at),
+        note.title,
+        note.content.trim()
+    );
+
+    write_file(&root.join(NOTES_DIR), &note_file_name(note), &markdown)
+}
+
+fn remove_note_file(root: &Path, note: &Note) {
+    remove_file(&root.join(NOTES_DIR).join(note_file_name(note)));
+}
+
+/// `2026-01-31 14.05.09 (1a2b3c4d).md`; the short id keeps same-second files apart
+fn transcription_file_name(transcription: &Transcription) -> String {
+    format!(
+        "{} ({}).md",
+        local_datetime(transcription.created_at).replace(':', "."),
+        short_id(&transcription.id)
+    )
+}
+
+/// `Title (1a2b3c4d).md`
+fn note_file_name(note: &Note) -> String {
+    let title: String = note
+        .title
+        .chars()
+        .map(|c| {
+            if matches!(c, '/' | ':' | '\\') {
+                '-'
+            } else {
+                c
+            }
+        })
+        .filter(|c| !c.is_control())
+        .take(MAX_TITLE_CHARS)
+        .collect();
+    let title = title.trim();
+    let title = if title.is_empty() { "Untitled" } else { title };
+
+    format!("{} ({}).md", title, short_id(&note.id))
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+/// Write through a temporary file so iCloud never uploads a half-written file
+fn write_file(dir: &Path, name: &str, contents: &str) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+
+    let path = dir.join(name);
+    let temp = dir.join(format!(".{}.tmp", name));
+    fs::write(&temp, contents).map_err(|e| format!("Failed to write {:?}: {}", temp, e))?;
+    fs::rename(&temp, &path).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+fn remove_file(path: &Path) {
+    match fs::remove_file(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => error!("Failed to remove mirrored file {:?}: {}", path, e),
+    }
+}
+
+/// `YYYY-MM-DD HH:MM:SS` in local time
+fn local_datetime(timestamp: i64) -> String {
+    get_connection()
+        .ok()
+        .and_then(|conn| {
+            conn.query_row(
+                "SELECT datetime(?, 'unixepoch', 'localtime')",
+                [timestamp],
+                |row| row.get(0),
+            )
+            .ok()
+        })
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// Quote a value for YAML front matter (JSON strings are valid YAML)
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| String::from("\"\""))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Whether iCloud Drive is available to mirror into
+#[tauri::command]
+#[specta::specta]
+pub fn mirror_icloud_available() -> bool {
+    icloud_folder().is_some()
+}
+
+/// Write every existing transcription and note to the iCloud Drive folder
+#[tauri::command]
+#[specta::specta]
+pub fn mirror_export_all() -> Result<usize, CommandError> {
+    Ok(export_all()?)
+}
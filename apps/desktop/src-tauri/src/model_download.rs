@@ -1,8 +1,19 @@
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_specta::Event;
+use tracing::{error, info};
+
+use crate::commands::error::CommandError;
+use crate::commands::settings::{keys, read_setting};
+use crate::model_registry::{ModelEntry, ModelKind, ModelRegistry, DEFAULT_STT_MODEL};
+use crate::platform;
+
+/// Free space kept beyond what a download needs, so the disk isn't left full
+const DISK_SPACE_MARGIN_BYTES: u64 = 200 * 1024 * 1024;
 
 /// If a directory contains only a single subdirectory, move its contents up.
 /// This handles tars that contain a top-level folder.
@@ -14,7 +25,7 @@ fn flatten_nested_folder(dir: &Path) -> std::io::Result<()> {
         let entry = &entries[0];
         if entry.file_type()?.is_dir() {
             let nested_dir = entry.path();
-            println!("Flattening nested folder: {:?}", nested_dir);
+            info!("Flattening nested folder: {:?}", nested_dir);
 
             // Move all contents from nested folder to parent
             for nested_entry in fs::read_dir(&nested_dir)? {
@@ -28,118 +39,35 @@ fn flatten_nested_folder(dir: &Path) -> std::io::Result<()> {
 
             // Remove the now-empty nested directory
             fs::remove_dir(&nested_dir)?;
-            println!("✅ Flattened nested folder structure");
+            info!("✅ Flattened nested folder structure");
         }
     }
 
     Ok(())
 }
 
-/// Speech-to-Text models
-#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, PartialEq)]
-pub enum SttModel {
-    Whisper,
-}
-
-/// Large Language Models (Text-to-Text)
-#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, PartialEq)]
-pub enum LlmModel {
-    Qwen,
-}
-
-/// Common trait for downloadable models
-trait DownloadableModel {
-    fn url(&self) -> &'static str;
-    fn filename(&self) -> &'static str;
-    fn display_name(&self) -> &'static str;
-    fn extracted_folder(&self) -> Option<&'static str>;
-    fn model_dir(&self) -> &'static str;
-}
-
-impl DownloadableModel for SttModel {
-    fn url(&self) -> &'static str {
-        match self {
-            SttModel::Whisper => {
-                "https://bikhwis00a.ufs.sh/f/h7fo4nF4JUG5sUZCah8euX3BLg9ApnPrdlmKHOkNh84zboSi"
-            }
-        }
-    }
-
-    fn filename(&self) -> &'static str {
-        match self {
-            SttModel::Whisper => "ggml-small-q8_0.bin",
-        }
-    }
-
-    fn display_name(&self) -> &'static str {
-        match self {
-            SttModel::Whisper => "Whisper Small",
-        }
-    }
-
-    fn extracted_folder(&self) -> Option<&'static str> {
-        match self {
-            SttModel::Whisper => None,
-        }
-    }
-
-    fn model_dir(&self) -> &'static str {
-        "stt"
-    }
+/// Local speech model selected in settings, by manifest id. Falls back to the
+/// default model when none is set or the id is not an STT model of the manifest.
+pub fn selected_stt_model() -> Result<&'static ModelEntry, CommandError> {
+    let registry = ModelRegistry::global()?;
+    read_setting(keys::LOCAL_MODEL)
+        .and_then(|id| registry.get_of_kind(&id, ModelKind::Stt))
+        .or_else(|| registry.get_of_kind(DEFAULT_STT_MODEL, ModelKind::Stt))
+        .ok_or_else(|| CommandError::not_found("Model", DEFAULT_STT_MODEL))
 }
 
-impl DownloadableModel for LlmModel {
-    fn url(&self) -> &'static str {
-        match self {
-            LlmModel::Qwen => {
-                "https://bikhwis00a.ufs.sh/f/h7fo4nF4JUG5J0IwOJo81CvgA5JmjP0WpaT6RHNGnyStrZde"
-            }
-        }
-    }
-
-    fn filename(&self) -> &'static str {
-        match self {
-            LlmModel::Qwen => "qwen-0.5b-q8_0.gguf",
-        }
-    }
-
-    fn display_name(&self) -> &'static str {
-        match self {
-            LlmModel::Qwen => "Qwen 0.5B",
-        }
-    }
-
-    fn extracted_folder(&self) -> Option<&'static str> {
-        match self {
-            LlmModel::Qwen => None,
-        }
-    }
-
-    fn model_dir(&self) -> &'static str {
-        "llm"
-    }
-}
-
-/// Status of an STT model
+/// Download status of a model
 #[derive(Debug, Clone, Serialize, specta::Type)]
-pub struct SttModelStatus {
-    pub model: SttModel,
+pub struct ModelStatus {
+    /// Manifest id of the model
+    pub id: String,
     pub downloaded: bool,
     pub file_size: Option<f64>,
     pub path: Option<String>,
 }
 
-/// Status of an LLM model
-#[derive(Debug, Clone, Serialize, specta::Type)]
-pub struct LlmModelStatus {
-    pub model: LlmModel,
-    pub downloaded: bool,
-    pub file_size: Option<f64>,
-    pub path: Option<String>,
-}
-
-/// Progress event payload
-#[derive(Debug, Clone, Serialize, specta::Type)]
+/// Payload of `model-download-progress`
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct DownloadProgress {
     pub model: String,
     pub downloaded: f64,
@@ -147,21 +75,33 @@ pub struct DownloadProgress {
     pub percentage: f32,
 }
 
-/// Completion event payload
+impl tauri_specta::Event for DownloadProgress {
+    const NAME: &'static str = "model-download-progress";
+}
+
+/// Payload of `model-download-complete`
 #[derive(Debug, Clone, Serialize, specta::Type)]
 pub struct DownloadComplete {
     pub model: String,
 }
 
-/// Error event payload
+impl tauri_specta::Event for DownloadComplete {
+    const NAME: &'static str = "model-download-complete";
+}
+
+/// Payload of `model-download-error`
 #[derive(Debug, Clone, Serialize, specta::Type)]
 pub struct DownloadError {
     pub model: String,
     pub error: String,
 }
 
+impl tauri_specta::Event for DownloadError {
+    const NAME: &'static str = "model-download-error";
+}
+
 /// Get the models directory path for a specific model type
-fn get_model_dir_for<M: DownloadableModel>(app: &AppHandle, model: &M) -> Result<PathBuf, String> {
+fn get_model_dir_for(app: &AppHandle, model: &ModelEntry) -> Result<PathBuf, String> {
     let app_data_dir = app
         .path()
         .app_data_dir()
@@ -178,122 +118,166 @@ fn get_model_dir_for<M: DownloadableModel>(app: &AppHandle, model: &M) -> Result
     Ok(model_dir)
 }
 
-/// Get the path for a specific model
-fn get_model_path_for<M: DownloadableModel>(app: &AppHandle, model: &M) -> Result<PathBuf, String> {
+/// Path of a model's file, or of its folder for archive-based models
+pub fn model_path(app: &AppHandle, model: &ModelEntry) -> Result<PathBuf, String> {
     let model_dir = get_model_dir_for(app, model)?;
 
     // For archive-based models, check for extracted folder; for others, check the file
-    if let Some(folder) = model.extracted_folder() {
+    if let Some(folder) = &model.extracted_folder {
         Ok(model_dir.join(folder))
     } else {
-        Ok(model_dir.join(model.filename()))
+        Ok(model_dir.join(&model.filename))
+    }
+}
+
+/// Whether a model is present on disk, without creating its directory
+pub fn model_installed<R: Runtime>(app: &AppHandle<R>, model: &ModelEntry) -> bool {
+    let Ok(app_data_dir) = app.path().app_data_dir() else {
+        return false;
+    };
+
+    let model_dir = app_data_dir.join(model.model_dir());
+    match &model.extracted_folder {
+        Some(folder) => model_dir.join(folder).exists(),
+        None => model_dir.join(&model.filename).exists(),
+    }
+}
+
+/// Smallest downloaded speech model, by download size
+pub fn smallest_installed_stt_model<R: Runtime>(app: &AppHandle<R>) -> Option<&'static ModelEntry> {
+    ModelRegistry::global()
+        .ok()?
+        .of_kind(ModelKind::Stt)
+        .filter(|model| model_installed(app, model))
+        .min_by_key(|model| model.size_bytes)
+}
+
+/// Bytes a downloaded model takes on disk
+pub fn model_disk_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        fs_extra::dir::get_size(path).unwrap_or(0)
+    } else {
+        fs::metadata(path).map(|m| m.len()).unwrap_or(0)
     }
 }
 
+/// Display name of the first installed model of a kind, if any
+fn installed_model_name<R: Runtime>(app: &AppHandle<R>, kind: ModelKind) -> Option<&'static str> {
+    ModelRegistry::global()
+        .ok()?
+        .of_kind(kind)
+        .find(|model| model_installed(app, model))
+        .map(|model| model.name.as_str())
+}
+
+/// Display name of the installed STT model, if any
+pub fn installed_stt_model<R: Runtime>(app: &AppHandle<R>) -> Option<&'static str> {
+    installed_model_name(app, ModelKind::Stt)
+}
+
+/// Display name of the installed LLM model, if any
+pub fn installed_llm_model<R: Runtime>(app: &AppHandle<R>) -> Option<&'static str> {
+    installed_model_name(app, ModelKind::Llm)
+}
+
 /// Check model status helper
-fn check_model_status_impl<M: DownloadableModel>(
+fn check_model_status_impl(
     app: &AppHandle,
-    model: &M,
+    model: &ModelEntry,
 ) -> Result<(bool, Option<f64>, Option<String>), String> {
-    let model_path = get_model_path_for(app, model)?;
+    let model_path = model_path(app, model)?;
 
-    let (downloaded, file_size) = if model_path.exists() {
-        if model_path.is_dir() {
-            let size = fs_extra::dir::get_size(&model_path).unwrap_or(0);
-            (true, Some(size as f64))
-        } else {
-            let metadata = fs::metadata(&model_path)
-                .map_err(|e| format!("Failed to read file metadata: {}", e))?;
-            (true, Some(metadata.len() as f64))
-        }
-    } else {
-        (false, None)
-    };
-
-    let path = if downloaded {
-        Some(model_path.to_string_lossy().to_string())
-    } else {
-        None
-    };
+    if !model_path.exists() {
+        return Ok((false, None, None));
+    }
 
-    Ok((downloaded, file_size, path))
+    Ok((
+        true,
+        Some(model_disk_size(&model_path) as f64),
+        Some(model_path.to_string_lossy().to_string()),
+    ))
 }
 
-/// Check if an STT model is downloaded and get its status
+/// Check if a model of the catalog is downloaded and get its status
 #[tauri::command]
 #[specta::specta]
-pub async fn check_stt_model_status(
-    app: AppHandle,
-    model: SttModel,
-) -> Result<SttModelStatus, String> {
-    let (downloaded, file_size, path) = check_model_status_impl(&app, &model)?;
-    Ok(SttModelStatus {
-        model,
+pub async fn check_model_status(app: AppHandle, id: String) -> Result<ModelStatus, CommandError> {
+    let model = ModelRegistry::global()?
+        .get(&id)
+        .ok_or_else(|| CommandError::not_found("Model", &id))?;
+    let (downloaded, file_size, path) = check_model_status_impl(&app, model)?;
+    Ok(ModelStatus {
+        id,
         downloaded,
         file_size,
         path,
     })
 }
 
-/// Check if an LLM model is downloaded and get its status
-#[tauri::command]
-#[specta::specta]
-pub async fn check_llm_model_status(
-    app: AppHandle,
-    model: LlmModel,
-) -> Result<LlmModelStatus, String> {
-    let (downloaded, file_size, path) = check_model_status_impl(&app, &model)?;
-    Ok(LlmModelStatus {
-        model,
-        downloaded,
-        file_size,
-        path,
-    })
+/// Fail up front when the model won't fit on disk, instead of with a write
+/// error near the end of the download. Archives need room for the archive and
+/// its extracted files at the same time.
+async fn check_disk_space(model: &ModelEntry, model_dir: &Path) -> Result<(), CommandError> {
+    let Some(available) = platform::available_disk_space(model_dir) else {
+        // Let the download try rather than block it on a failed check
+        return Ok(());
+    };
+
+    // `content_length()` reports the body, which a HEAD response doesn't have,
+    // so read the header itself
+    let content_length = match reqwest::Client::new().head(&model.url).send().await {
+        Ok(response) if response.status().is_success() => response
+            .headers()
+            .get(reqwest::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok()),
+        _ => None,
+    };
+    let size = content_length
+        .filter(|&len| len > 0)
+        .unwrap_or(model.size_bytes);
+    let copies = if model.extracted_folder.is_some() {
+        2
+    } else {
+        1
+    };
+    let required = size * copies + DISK_SPACE_MARGIN_BYTES;
+
+    if available < required {
+        return Err(CommandError::insufficient_disk_space(required, available));
+    }
+
+    Ok(())
 }
 
 /// Download model helper
-async fn download_model_helper<M: DownloadableModel + Send + 'static>(
+async fn download_model_helper(
     app: AppHandle,
-    model: M,
-) -> Result<(), String> {
-    let model_dir = get_model_dir_for(&app, &model)?;
-    let model_name = model.display_name().to_string();
-    let url = model.url().to_string();
-    let filename = model.filename().to_string();
-    let extracted_folder = model.extracted_folder().map(|s| s.to_string());
+    model: &'static ModelEntry,
+) -> Result<(), CommandError> {
+    let model_dir = get_model_dir_for(&app, model)?;
+    check_disk_space(model, &model_dir).await?;
+    let model_name = model.name.clone();
 
     // Spawn background download task
     tokio::spawn(async move {
-        let result = download_model_impl(
-            &app,
-            &model_name,
-            &url,
-            &model_dir,
-            &filename,
-            extracted_folder.as_deref(),
-            false, // plain .tar, not .tar.gz
-        )
-        .await;
+        let result = download_model_impl(&app, model, &model_dir).await;
 
         match result {
             Ok(_) => {
-                let _ = app.emit(
-                    "model-download-complete",
-                    DownloadComplete {
-                        model: model_name.clone(),
-                    },
-                );
-                println!("✅ Model {} downloaded successfully", model_name);
+                let _ = DownloadComplete {
+                    model: model_name.clone(),
+                }
+                .emit(&app);
+                info!("✅ Model {} downloaded successfully", model_name);
             }
             Err(e) => {
-                let _ = app.emit(
-                    "model-download-error",
-                    DownloadError {
-                        model: model_name.clone(),
-                        error: e.clone(),
-                    },
-                );
-                eprintln!("❌ Failed to download model {}: {}", model_name, e);
+                let _ = DownloadError {
+                    model: model_name.clone(),
+                    error: e.clone(),
+                }
+                .emit(&app);
+                error!("❌ Failed to download model {}: {}", model_name, e);
             }
         }
     });
@@ -301,30 +285,25 @@ async fn download_model_helper<M: DownloadableModel + Send + 'static>(
     Ok(())
 }
 
-/// Download an STT model in the background
+/// Download any model of the catalog in the background, by its manifest id
 #[tauri::command]
 #[specta::specta]
-pub async fn download_stt_model(app: AppHandle, model: SttModel) -> Result<(), String> {
-    download_model_helper(app, model).await
-}
-
-/// Download an LLM model in the background
-#[tauri::command]
-#[specta::specta]
-pub async fn download_llm_model(app: AppHandle, model: LlmModel) -> Result<(), String> {
+pub async fn download_model(app: AppHandle, id: String) -> Result<(), CommandError> {
+    let model = ModelRegistry::global()?
+        .get(&id)
+        .ok_or_else(|| CommandError::not_found("Model", &id))?;
     download_model_helper(app, model).await
 }
 
 /// Internal implementation of model download with streaming
 async fn download_model_impl(
     app: &AppHandle,
-    model_name: &str,
-    url: &str,
-    stt_dir: &PathBuf,
-    filename: &str,
-    extracted_folder: Option<&str>,
-    is_tar_gz: bool,
+    model: &ModelEntry,
+    model_dir: &Path,
 ) -> Result<(), String> {
+    let model_name = model.name.as_str();
+    let filename = model.filename.as_str();
+
     // Disable automatic decompression to get raw bytes for large binary files
     let client = reqwest::Client::builder()
         .no_gzip()
@@ -335,7 +314,7 @@ async fn download_model_impl(
 
     // Start the download request
     let response = client
-        .get(url)
+        .get(&model.url)
         .send()
         .await
         .map_err(|e| format!("Failed to start download: {}", e))?;
@@ -348,13 +327,14 @@ async fn download_model_impl(
     }
 
     let total_size = response.content_length().unwrap_or(0);
-    let download_path = stt_dir.join(format!("{}.part", filename));
-    let final_path = stt_dir.join(filename);
+    let download_path = model_dir.join(format!("{}.part", filename));
+    let final_path = model_dir.join(filename);
 
     // Create the file for writing
     let mut file = fs::File::create(&download_path)
         .map_err(|e| format!("Failed to create download file: {}", e))?;
 
+    let mut hasher = Sha256::new();
     let mut downloaded: u64 = 0;
     let mut last_emit_percentage: f32 = 0.0;
 
@@ -367,6 +347,7 @@ async fn download_model_impl(
 
         file.write_all(&chunk)
             .map_err(|e| format!("Failed to write chunk: {}", e))?;
+        hasher.update(&chunk);
 
         downloaded += chunk.len() as u64;
 
@@ -381,15 +362,13 @@ async fn download_model_impl(
             || downloaded % (10 * 1024 * 1024) < chunk.len() as u64
         {
             last_emit_percentage = percentage;
-            let _ = app.emit(
-                "model-download-progress",
-                DownloadProgress {
-                    model: model_name.to_string(),
-                    downloaded: downloaded as f64,
-                    total: total_size as f64,
-                    percentage,
-                },
-            );
+            let _ = DownloadProgress {
+                model: model_name.to_string(),
+                downloaded: downloaded as f64,
+                total: total_size as f64,
+                percentage,
+            }
+            .emit(app);
         }
     }
 
@@ -398,13 +377,29 @@ async fn download_model_impl(
         .map_err(|e| format!("Failed to flush file: {}", e))?;
     drop(file);
 
+    // Reject a corrupted or tampered download before it replaces anything
+    if let Some(expected) = model.sha256.as_deref() {
+        let actual: String = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        if !actual.eq_ignore_ascii_case(expected) {
+            let _ = fs::remove_file(&download_path);
+            return Err(format!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                filename, expected, actual
+            ));
+        }
+    }
+
     // Rename .part file to final filename
     fs::rename(&download_path, &final_path)
         .map_err(|e| format!("Failed to rename downloaded file: {}", e))?;
 
     // Extract archive-based models
-    if let Some(folder_name) = extracted_folder {
-        let extract_dir = stt_dir.join(folder_name);
+    if let Some(folder_name) = model.extracted_folder.as_deref() {
+        let extract_dir = model_dir.join(folder_name);
 
         // Create extraction directory
         if extract_dir.exists() {
@@ -414,31 +409,18 @@ async fn download_model_impl(
         fs::create_dir_all(&extract_dir)
             .map_err(|e| format!("Failed to create extraction dir: {}", e))?;
 
-        if is_tar_gz {
-            // Extract the tar.gz
-            let tar_gz = fs::File::open(&final_path)
-                .map_err(|e| format!("Failed to open tar.gz file: {}", e))?;
-
-            let tar = flate2::read::GzDecoder::new(tar_gz);
-            let mut archive = tar::Archive::new(tar);
-
-            archive
-                .unpack(&extract_dir)
-                .map_err(|e| format!("Failed to extract tar.gz: {}", e))?;
-        } else {
-            // Use system tar command for better compatibility with macOS-created tars
-            let output = std::process::Command::new("tar")
-                .arg("-xf")
-                .arg(&final_path)
-                .arg("-C")
-                .arg(&extract_dir)
-                .output()
-                .map_err(|e| format!("Failed to run tar command: {}", e))?;
-
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(format!("tar extraction failed: {}", stderr));
-            }
+        // System tar, which also unpacks .tar.bz2 and handles macOS-created tars
+        let output = std::process::Command::new("tar")
+            .arg("-xf")
+            .arg(&final_path)
+            .arg("-C")
+            .arg(&extract_dir)
+            .output()
+            .map_err(|e| format!("Failed to run tar command: {}", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("tar extraction failed: {}", stderr));
         }
 
         // Flatten nested folder if present (e.g., tar contains a single top-level folder)
@@ -448,19 +430,17 @@ async fn download_model_impl(
         // Remove archive after extraction
         let _ = fs::remove_file(&final_path);
 
-        println!("✅ Extracted {} to {:?}", folder_name, extract_dir);
+        info!("✅ Extracted {} to {:?}", folder_name, extract_dir);
     }
 
     // Emit final 100% progress
-    let _ = app.emit(
-        "model-download-progress",
-        DownloadProgress {
-            model: model_name.to_string(),
-            downloaded: total_size as f64,
-            total: total_size as f64,
-            percentage: 100.0,
-        },
-    );
+    let _ = DownloadProgress {
+        model: model_name.to_string(),
+        downloaded: total_size as f64,
+        total: total_size as f64,
+        percentage: 100.0,
+    }
+    .emit(app);
 
     Ok(())
 }
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+use crate::commands::error::CommandError;
+use crate::model_download::{model_disk_size, model_installed, model_path};
+
+/// Downloadable models, bundled with the app. A new model of a supported
+/// engine is added here without touching the code.
+const MANIFEST: &str = include_str!("models.json");
+
+/// Local speech model used when none is selected, or the selected one is missing
+pub const DEFAULT_STT_MODEL: &str = "whisper-small";
+
+static REGISTRY: OnceLock<Result<ModelRegistry, String>> = OnceLock::new();
+
+/// What a model is used for
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelKind {
+    /// Speech to text
+    Stt,
+    /// Text to text, for formatting
+    Llm,
+}
+
+/// Runtime that loads a model's files
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelEngine {
+    /// ggml file through whisper.cpp
+    Whisper,
+    /// sherpa-onnx Moonshine export through ONNX Runtime
+    Moonshine,
+    /// GGUF file through llama.cpp
+    Llama,
+}
+
+/// A model in the manifest
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelEntry {
+    /// Stable identifier used by `download_model`
+    pub id: String,
+    /// Display name, also the `model` of download events
+    pub name: String,
+    pub kind: ModelKind,
+    pub engine: ModelEngine,
+    pub url: String,
+    /// Name the download is saved under
+    pub filename: String,
+    /// Hex SHA-256 of the download; checked when present
+    pub sha256: Option<String>,
+    /// Download size, for the disk space check when the server sends no Content-Length
+    pub size_bytes: u64,
+    /// Folder an archive is extracted to; `None` for single-file models
+    pub extracted_folder: Option<String>,
+}
+
+impl ModelEntry {
+    /// Folder under app data holding models of this kind
+    pub fn model_dir(&self) -> &'static str {
+        match self.kind {
+            ModelKind::Stt => "stt",
+            ModelKind::Llm => "llm",
+        }
+    }
+}
+
+/// The models that can be downloaded, read from the bundled manifest
+pub struct ModelRegistry {
+    models: Vec<ModelEntry>,
+}
+
+impl ModelRegistry {
+    /// The registry of the bundled manifest
+    pub fn global() -> Result<&'static ModelRegistry, CommandError> {
+        REGISTRY
+            .get_or_init(|| Self::parse(MANIFEST))
+            .as_ref()
+            .map_err(|e| CommandError::validation(format!("models.json is invalid: {}", e)))
+    }
+
+    /// Read a manifest, rejecting duplicate ids and malformed hashes
+    fn parse(manifest: &str) -> Result<ModelRegistry, String> {
+        let models: Vec<ModelEntry> = serde_json::from_str(manifest).map_err(|e| e.to_string())?;
+
+        for (i, model) in models.iter().enumerate() {
+            if models[..i].iter().any(|other| other.id == model.id) {
+                return Err(format!("`{}` is listed twice", model.id));
+            }
+            if let Some(sha256) = &model.sha256 {
+                if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(format!("`{}` has a malformed sha256", model.id));
+                }
+            }
+        }
+
+        Ok(ModelRegistry { models })
+    }
+
+    pub fn models(&self) -> &[ModelEntry] {
+        &self.models
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ModelEntry> {
+        self.models.iter().find(|model| model.id == id)
+    }
+
+    /// Model of one kind by its id
+    pub fn get_of_kind(&self, id: &str, kind: ModelKind) -> Option<&ModelEntry> {
+        self.get(id).filter(|model| model.kind == kind)
+    }
+
+    /// Models of one kind, in manifest order
+    pub fn of_kind(&self, kind: ModelKind) -> impl Iterator<Item = &ModelEntry> {
+        self.models.iter().filter(move |model| model.kind == kind)
+    }
+}
+
+/// A downloaded model
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct InstalledModel {
+    pub id: String,
+    pub name: String,
+    pub kind: ModelKind,
+    /// File, or folder for archive-based models
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Every model that can be downloaded
+#[tauri::command]
+#[specta::specta]
+pub fn models_catalog() -> Result<Vec<ModelEntry>, CommandError> {
+    Ok(ModelRegistry::global()?.models().to_vec())
+}
+
+/// Models of the catalog that are downloaded
+#[tauri::command]
+#[specta::specta]
+pub fn models_installed(app: AppHandle) -> Result<Vec<InstalledModel>, CommandError> {
+    let mut installed = Vec::new();
+
+    for model in ModelRegistry::global()?.models() {
+        if !model_installed(&app, model) {
+            continue;
+        }
+        let path = model_path(&app, model)?;
+        installed.push(InstalledModel {
+            id: model.id.clone(),
+            name: model.name.clone(),
+            kind: model.kind,
+            size_bytes: model_disk_size(&path),
+            path: path.to_string_lossy().to_string(),
+        });
+    }
+
+    Ok(installed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_manifest_is_valid() {
+        let registry = ModelRegistry::parse(MANIFEST).unwrap();
+        assert!(registry
+            .get_of_kind(DEFAULT_STT_MODEL, ModelKind::Stt)
+            .is_some());
+    }
+
+    #[test]
+    fn malformed_hashes_are_rejected() {
+        let manifest = r#"[{"id": "a", "name": "A", "kind": "stt", "engine": "whisper",
+            "url": "https://example.com/a.bin", "filename": "a.bin", "sha256": "abc",
+            "size_bytes": 1, "extracted_folder": null}]"#;
+        assert!(ModelRegistry::parse(manifest).is_err());
+    }
+}
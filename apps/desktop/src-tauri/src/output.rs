@@ -0,0 +1,272 @@
+use serde::Serialize;
+use specta::Type;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+use tauri_specta::Event;
+use tracing::{info, warn};
+
+use crate::app_context::{self, RunningApp};
+use crate::clipboard;
+use crate::commands::app_rules;
+use crate::commands::settings::{keys as setting_keys, read_bool_setting};
+use crate::events;
+use crate::placeholders;
+use crate::platform;
+use crate::rich_text;
+use crate::window;
+
+/// Tell the widget the paste has finished, whether or not it succeeded, and shrink
+/// it back to the pill
+pub fn emit_paste_complete<R: Runtime>(app: &AppHandle<R>) {
+    window::set_widget_expanded(app, false);
+    let _ = events::emit_to_widget(app, "paste-complete", ());
+}
+
+/// Payload of `paste-target-gone`, emitted when the app dictated into quit before
+/// the paste, or never came back to the front; the text was left on the
+/// clipboard instead
+#[derive(Debug, Clone, Serialize, Type, Event)]
+pub struct PasteTargetGone {
+    pub app_name: String,
+    /// The app is still running but the wait for it to come back timed out
+    pub timed_out: bool,
+}
+
+/// Payload of `paste-countdown`, emitted every second while waiting for the app
+/// dictated into to come back to the front
+#[derive(Debug, Clone, Serialize, Type, Event)]
+pub struct PasteCountdown {
+    pub app_name: String,
+    /// Seconds left before giving up and leaving the text on the clipboard
+    pub remaining_secs: u32,
+}
+
+/// Whether the app dictated into can take the paste
+#[derive(PartialEq)]
+enum TargetFocus {
+    Ready,
+    Gone,
+    TimedOut,
+}
+
+/// Paste text into the frontmost app: put it on the clipboard, press Cmd+V (Ctrl+V
+/// elsewhere), then clear the clipboard again. A `{cursor}` marker in the text is
+/// removed and the caret moved back to it. Runs on its own thread and emits
+/// `paste-complete` when done.
+pub fn paste_text<R: Runtime>(app: AppHandle<R>, text: String) -> Result<(), String> {
+    paste_into(app, None, text)
+}
+
+/// Paste text like `paste_text`, into `target` when set: it is brought back to
+/// the front first in case another app took focus meanwhile, or with the wait
+/// for target app setting on, the paste waits until the user switches back to
+/// it. When it has quit or the wait times out, the text is only copied and
+/// `paste-target-gone` emitted. Apps with a typing rule get the text typed
+/// instead.
+pub fn paste_into<R: Runtime>(
+    app: AppHandle<R>,
+    target: Option<RunningApp>,
+    text: String,
+) -> Result<(), String> {
+    let (text, cursor_back) = placeholders::take_cursor(&text);
+
+    thread::spawn(move || {
+        if let Some(target) = &target {
+            let focus = if read_bool_setting(setting_keys::WAIT_FOR_TARGET_APP, false) {
+                wait_for_target(&app, &target)
+            } else if app_context::activate_app(target.pid) {
+                TargetFocus::Ready
+            } else {
+                TargetFocus::Gone
+            };
+
+            if focus != TargetFocus::Ready {
+                warn!(
+                    "{} can't take the paste, leaving the text on the clipboard",
+                    target.name
+                );
+                if let Err(e) = copy_for_paste(&text) {
+                    warn!("{}", e);
+                }
+                let _ = PasteTargetGone {
+                    app_name: target.name.clone(),
+                    timed_out: focus == TargetFocus::TimedOut,
+                }
+                .emit(&app);
+                emit_paste_complete(&app);
+                return;
+            }
+        }
+
+        // Apps that ignore a programmatic paste are typed into
+        let typing_rate = target
+            .map(|target| target.name)
+            .or_else(|| app_context::frontmost_running_app().map(|app| app.name))
+            .and_then(|name| app_rules::typing_rate_for(&name));
+
+        match typing_rate {
+            Some(chars_per_sec) => type_text(&text, chars_per_sec),
+            None => {
+                if let Err(e) = copy_for_paste(&text) {
+                    warn!("{}", e);
+                    emit_paste_complete(&app);
+                    return;
+                }
+
+                // Small delay before sending keyboard events
+                thread::sleep(Duration::from_millis(50));
+
+                press_paste();
+
+                // Wait a bit for paste to complete
+                thread::sleep(Duration::from_millis(100));
+            }
+        }
+
+        // Walk the caret back to where the {cursor} marker was
+        press_left(cursor_back, false);
+
+        // Clear clipboard after pasting (don't restore old contents to avoid exceptions)
+        if typing_rate.is_none() {
+            clipboard::clear();
+        }
+
+        info!("✅ Pasted successfully.");
+
+        emit_paste_complete(&app);
+    });
+
+    Ok(())
+}
+
+/// Put text to be pasted on the clipboard, with an HTML flavor as well when rich
+/// text paste is on and the text has formatting worth keeping
+fn copy_for_paste(text: &str) -> Result<(), String> {
+    if read_bool_setting(setting_keys::RICH_TEXT_PASTE, false) {
+        if let Some(html) = rich_text::to_html(text) {
+            return clipboard::copy_rich_text(text, &html);
+        }
+    }
+    clipboard::copy_text(text)
+}
+
+/// Wait for `target` to be frontmost again, emitting `paste-countdown` every
+/// second until it is, it quits, or the wait times out
+fn wait_for_target<R: Runtime>(app: &AppHandle<R>, target: &RunningApp) -> TargetFocus {
+    /// How long to wait before giving up
+    const TARGET_WAIT_SECS: u32 = 10;
+
+    /// How often focus is checked while waiting
+    const POLLS_PER_SEC: u32 = 10;
+
+    for remaining_secs in (1..=TARGET_WAIT_SECS).rev() {
+        for poll in 0..POLLS_PER_SEC {
+            if !app_context::is_running(target.pid) {
+                return TargetFocus::Gone;
+            }
+            if app_context::is_frontmost(target.pid) {
+                return TargetFocus::Ready;
+            }
+            if poll == 0 {
+                let _ = PasteCountdown {
+                    app_name: target.name.clone(),
+                    remaining_secs,
+                }
+                .emit(app);
+            }
+            thread::sleep(Duration::from_millis(1000 / POLLS_PER_SEC as u64));
+        }
+    }
+
+    TargetFocus::TimedOut
+}
+
+/// Replace text pasted a moment ago: select it back from the caret with
+/// Shift+Left, then paste the new text over it. Only works while the caret is
+/// still right after the pasted text.
+pub fn replace_pasted_text<R: Runtime>(
+    app: AppHandle<R>,
+    pasted: &str,
+    text: String,
+) -> Result<(), String> {
+    let count = pasted.chars().count();
+
+    thread::spawn(move || {
+        press_left(count, true);
+
+        if let Err(e) = paste_text(app, text) {
+            warn!("{}", e);
+        }
+    });
+
+    Ok(())
+}
+
+/// Copy the selection of the frontmost app without accessibility, for apps that
+/// don't expose it: Cmd+C (Ctrl+C on Windows), or the primary selection on
+/// Linux. Blocks briefly while the app copies; the clipboard is cleared
+/// afterwards.
+pub fn copy_selection() -> Option<String> {
+    platform::text_inserter().copy_selection()
+}
+
+fn press_paste() {
+    if let Err(e) = platform::text_inserter().press_paste() {
+        warn!("Failed to press paste: {}", e);
+    }
+}
+
+/// Press the left arrow `count` times, with Shift held to select when `select`
+/// is set
+fn press_left(count: usize, select: bool) {
+    if let Err(e) = platform::text_inserter().press_left(count, select) {
+        warn!("Failed to move the caret: {}", e);
+    }
+}
+
+fn type_text(text: &str, chars_per_sec: u32) {
+    if let Err(e) = platform::text_inserter().type_text(text, chars_per_sec) {
+        warn!("Failed to type text: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::mock::{take_pasted_text, PASTE_TURN};
+    use std::sync::mpsc;
+    use tauri::Listener;
+
+    /// Paste through the mock platform and wait for `paste-complete`
+    fn paste(text: &str) -> Vec<String> {
+        let app = tauri::test::mock_app();
+        let (done_tx, done_rx) = mpsc::channel();
+        app.listen_any("paste-complete", move |_| {
+            let _ = done_tx.send(());
+        });
+
+        paste_into(app.handle().clone(), None, text.to_string()).unwrap();
+        done_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("paste-complete was not emitted");
+        take_pasted_text()
+    }
+
+    #[test]
+    fn text_is_pasted_and_the_clipboard_cleared() {
+        let _turn = PASTE_TURN.lock().unwrap_or_else(|e| e.into_inner());
+        take_pasted_text();
+
+        assert_eq!(paste("Hello world"), vec!["Hello world"]);
+        assert_eq!(clipboard::read_text(), None);
+    }
+
+    #[test]
+    fn the_cursor_marker_is_never_pasted() {
+        let _turn = PASTE_TURN.lock().unwrap_or_else(|e| e.into_inner());
+        take_pasted_text();
+
+        assert_eq!(paste("Dear {cursor}, thanks"), vec!["Dear , thanks"]);
+    }
+}
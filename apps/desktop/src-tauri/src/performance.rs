@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// Consecutive chunks that must wait longer than their own audio before the
+/// workers are considered to be falling behind
+const FALLING_BEHIND_CHUNKS: usize = 3;
+
+/// Chunk timings kept for `performance_stats`
+const MAX_RECENT_CHUNKS: usize = 20;
+
+static METRICS: Mutex<RecordingMetrics> = Mutex::new(RecordingMetrics::new());
+
+/// Timing of a single transcribed chunk
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ChunkTiming {
+    pub chunk_id: usize,
+    /// Length of the chunk audio
+    pub audio_ms: i64,
+    /// Time the chunk waited for a free worker
+    pub queue_wait_ms: i64,
+    /// Time Whisper took to transcribe the chunk, retries included
+    pub inference_ms: i64,
+    /// Inference time divided by audio length; above 1 is slower than real time
+    pub realtime_factor: f32,
+}
+
+/// Transcription speed of the current or most recent local recording
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PerformanceStats {
+    pub worker_count: usize,
+    pub chunks: usize,
+    pub total_audio_ms: i64,
+    pub total_inference_ms: i64,
+    pub average_queue_wait_ms: i64,
+    pub max_queue_wait_ms: i64,
+    /// Total inference time divided by total audio length
+    pub realtime_factor: f32,
+    /// The last chunks waited longer than their own audio, so the backlog grows
+    pub falling_behind: bool,
+    /// Most recent first
+    pub recent_chunks: Vec<ChunkTiming>,
+}
+
+struct RecordingMetrics {
+    worker_count: usize,
+    timings: Vec<ChunkTiming>,
+    falling_behind: bool,
+}
+
+impl RecordingMetrics {
+    const fn new() -> Self {
+        Self {
+            worker_count: 0,
+            timings: Vec::new(),
+            falling_behind: false,
+        }
+    }
+}
+
+/// Clear the metrics of the previous recording
+pub fn start_recording(worker_count: usize) {
+    if let Ok(mut metrics) = METRICS.lock() {
+        *metrics = RecordingMetrics {
+            worker_count,
+            ..RecordingMetrics::new()
+        };
+    }
+}
+
+/// Record how long a chunk waited and took to transcribe, warning once when
+/// the workers start falling behind the audio
+pub fn record_chunk(chunk_id: usize, audio_ms: i64, queue_wait_ms: i64, inference_ms: i64) {
+    let timing = ChunkTiming {
+        chunk_id,
+        audio_ms,
+        queue_wait_ms,
+        inference_ms,
+        realtime_factor: inference_ms as f32 / audio_ms.max(1) as f32,
+    };
+    info!(
+        "ChunkProcessor: Chunk {} took {}ms for {}ms of audio (waited {}ms, {:.2}x real time)",
+        chunk_id, inference_ms, audio_ms, queue_wait_ms, timing.realtime_factor
+    );
+
+    let Ok(mut metrics) = METRICS.lock() else {
+        return;
+    };
+    metrics.timings.push(timing);
+
+    let behind = metrics.timings.len() >= FALLING_BEHIND_CHUNKS
+        && metrics.timings[metrics.timings.len() - FALLING_BEHIND_CHUNKS..]
+            .iter()
+            .all(|t| t.queue_wait_ms > t.audio_ms);
+    if behind && !metrics.falling_behind {
+        warn!(
+            "⚠️ Transcription is falling behind the audio: the last {} chunks waited longer than their own length ({} workers)",
+            FALLING_BEHIND_CHUNKS, metrics.worker_count
+        );
+    }
+    metrics.falling_behind = behind;
+}
+
+/// Whether the workers of the current recording are falling behind the audio
+pub fn falling_behind() -> bool {
+    METRICS.lock().map(|m| m.falling_behind).unwrap_or(false)
+}
+
+/// Chunk timings of the current or most recent local recording
+#[tauri::command]
+#[specta::specta]
+pub fn performance_stats() -> PerformanceStats {
+    let metrics = match METRICS.lock() {
+        Ok(metrics) => metrics,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let timings = &metrics.timings;
+
+    let total_audio_ms: i64 = timings.iter().map(|t| t.audio_ms).sum();
+    let total_inference_ms: i64 = timings.iter().map(|t| t.inference_ms).sum();
+    let total_queue_wait_ms: i64 = timings.iter().map(|t| t.queue_wait_ms).sum();
+
+    PerformanceStats {
+        worker_count: metrics.worker_count,
+        chunks: timings.len(),
+        total_audio_ms,
+        total_inference_ms,
+        average_queue_wait_ms: total_queue_wait_ms / timings.len().max(1) as i64,
+        max_queue_wait_ms: timings.iter().map(|t| t.queue_wait_ms).max().unwrap_or(0),
+        realtime_factor: total_inference_ms as f32 / total_audio_ms.max(1) as f32,
+        falling_behind: metrics.falling_behind,
+        recent_chunks: timings
+            .iter()
+            .rev()
+            .take(MAX_RECENT_CHUNKS)
+            .cloned()
+            .collect(),
+    }
+}
@@ -0,0 +1,300 @@
+use rusqlite::{Connection, OptionalExtension};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+use crate::commands::settings::{keys as setting_keys, read_bool_setting};
+use crate::corrections::similarity;
+use crate::db::pool::{get_connection, write_transaction};
+use crate::transcription::TranscriptSegment;
+
+/// Transcriptions saved this close together may be accidental double presses
+const DUPLICATE_WINDOW_SECS: i64 = 15;
+
+/// Word similarity at which a recent transcription counts as a duplicate
+const DUPLICATE_SIMILARITY: f32 = 0.9;
+
+/// What `save_transcription` did with a transcription
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveOutcome {
+    /// Saved under a new id
+    Saved(String),
+    /// Not saved: it nearly repeats the transcription with this id, saved moments earlier
+    Duplicate(String),
+}
+
+/// Saves a transcription and its timestamped segments to the local SQLite database.
+/// A near-copy of a transcription saved moments earlier is not saved again.
+/// Both dictations and the `transcriptions_create` command save through here.
+pub fn save_transcription(
+    text: &str,
+    formatted_text: Option<&str>,
+    segments: &[TranscriptSegment],
+    app_name: Option<&str>,
+) -> Result<SaveOutcome, String> {
+    // Pooled connections wait out locks held by the command layer instead of failing
+    let mut conn = get_connection()?;
+
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Failed to get timestamp: {}", e))?
+        .as_secs() as i64;
+
+    insert_transcription(
+        &mut conn,
+        text,
+        formatted_text,
+        segments,
+        app_name,
+        created_at,
+        read_bool_setting(setting_keys::SUPPRESS_DUPLICATE_TRANSCRIPTIONS, true),
+    )
+}
+
+/// `save_transcription` on a given connection and clock
+fn insert_transcription(
+    conn: &mut Connection,
+    text: &str,
+    formatted_text: Option<&str>,
+    segments: &[TranscriptSegment],
+    app_name: Option<&str>,
+    created_at: i64,
+    suppress_duplicates: bool,
+) -> Result<SaveOutcome, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+
+    // Accidental double presses produce the same dictation twice
+    if suppress_duplicates {
+        let recent: Option<(String, String)> = conn
+            .query_row(
+                "SELECT id, text FROM transcriptions WHERE created_at >= ?1
+                 ORDER BY created_at DESC LIMIT 1",
+                rusqlite::params![created_at - DUPLICATE_WINDOW_SECS],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to check for duplicate transcription: {}", e))?;
+
+        if let Some((recent_id, recent_text)) = recent {
+            if similarity(text, &recent_text) >= DUPLICATE_SIMILARITY {
+                info!("Skipping duplicate of transcription {}", recent_id);
+                return Ok(SaveOutcome::Duplicate(recent_id));
+            }
+        }
+    }
+
+    // Use formatted_text if provided, otherwise use raw text
+    let formatted = formatted_text.unwrap_or(text);
+    write_transaction(conn, |tx| {
+        tx.execute(
+            "INSERT INTO transcriptions (id, text, formatted_text, created_at, app_name)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![id, text, formatted, created_at, app_name],
+        )?;
+
+        for (index, segment) in segments.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO transcription_segments (transcription_id, segment_index, start_ms, end_ms, text, confidence)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                rusqlite::params![
+                    id,
+                    index as i64,
+                    segment.start_ms,
+                    segment.end_ms,
+                    segment.text,
+                    segment.confidence
+                ],
+            )?;
+        }
+
+        Ok(())
+    })
+    .map_err(|e| format!("Failed to save transcription: {}", e))?;
+
+    info!("✅ Saved local transcription with id: {}", id);
+
+    Ok(SaveOutcome::Saved(id))
+}
+
+/// Replaces the text and segments of a saved transcription with a refined
+/// transcription of the same recording. The formatted text is only replaced when
+/// given, so formatting applied to the draft isn't lost.
+pub fn update_refined_transcription(
+    id: &str,
+    text: &str,
+    formatted_text: Option<&str>,
+    segments: &[TranscriptSegment],
+) -> Result<(), String> {
+    let mut conn = get_connection()?;
+
+    write_transaction(&mut conn, |tx| {
+        tx.execute(
+            "UPDATE transcriptions SET text = ?1, formatted_text = COALESCE(?2, formatted_text)
+             WHERE id = ?3",
+            rusqlite::params![text, formatted_text, id],
+        )?;
+
+        tx.execute(
+            "DELETE FROM transcription_segments WHERE transcription_id = ?1",
+            rusqlite::params![id],
+        )?;
+        for (index, segment) in segments.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO transcription_segments (transcription_id, segment_index, start_ms, end_ms, text, confidence)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                rusqlite::params![
+                    id,
+                    index as i64,
+                    segment.start_ms,
+                    segment.end_ms,
+                    segment.text,
+                    segment.confidence
+                ],
+            )?;
+        }
+
+        Ok(())
+    })
+    .map_err(|e| format!("Failed to update transcription: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations::get_migrations;
+
+    fn database() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        for migration in get_migrations() {
+            conn.execute_batch(migration.sql).unwrap();
+        }
+        conn
+    }
+
+    fn segment(start_ms: i64, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start_ms,
+            end_ms: start_ms + 1000,
+            text: text.to_string(),
+            confidence: 0.9,
+            words: Vec::new(),
+        }
+    }
+
+    fn save(conn: &mut Connection, text: &str, created_at: i64) -> SaveOutcome {
+        insert_transcription(conn, text, None, &[], None, created_at, true).unwrap()
+    }
+
+    #[test]
+    fn saves_the_transcription_and_its_segments() {
+        let mut conn = database();
+        let segments = [segment(0, "Hello"), segment(1000, "world")];
+
+        let outcome = insert_transcription(
+            &mut conn,
+            "hello world",
+            Some("Hello world."),
+            &segments,
+            Some("Notes"),
+            100,
+            true,
+        )
+        .unwrap();
+        let SaveOutcome::Saved(id) = outcome else {
+            panic!("expected the transcription to be saved");
+        };
+
+        let saved: (String, String, i64, Option<String>) = conn
+            .query_row(
+                "SELECT text, formatted_text, created_at, app_name FROM transcriptions WHERE id = ?",
+                [&id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            saved,
+            (
+                "hello world".to_string(),
+                "Hello world.".to_string(),
+                100,
+                Some("Notes".to_string())
+            )
+        );
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT segment_index, start_ms, text FROM transcription_segments
+                 WHERE transcription_id = ? ORDER BY segment_index",
+            )
+            .unwrap();
+        let rows: Vec<(i64, i64, String)> = stmt
+            .query_map([&id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![(0, 0, "Hello".to_string()), (1, 1000, "world".to_string())]
+        );
+    }
+
+    #[test]
+    fn unformatted_transcriptions_keep_the_raw_text() {
+        let mut conn = database();
+        let SaveOutcome::Saved(id) = save(&mut conn, "just text", 100) else {
+            panic!("expected the transcription to be saved");
+        };
+
+        let formatted: String = conn
+            .query_row(
+                "SELECT formatted_text FROM transcriptions WHERE id = ?",
+                [&id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(formatted, "just text");
+    }
+
+    #[test]
+    fn near_repeats_inside_the_window_are_not_saved() {
+        let mut conn = database();
+        let SaveOutcome::Saved(first) = save(&mut conn, "send the report to Anna today", 100)
+        else {
+            panic!("expected the transcription to be saved");
+        };
+
+        // The same dictation moments later, give or take punctuation
+        assert_eq!(
+            save(&mut conn, "Send the report to Anna today.", 105),
+            SaveOutcome::Duplicate(first)
+        );
+        // Different words are saved
+        assert!(matches!(
+            save(&mut conn, "book a table for friday", 106),
+            SaveOutcome::Saved(_)
+        ));
+        // So is a repeat once the window has passed
+        assert!(matches!(
+            save(
+                &mut conn,
+                "book a table for friday",
+                106 + DUPLICATE_WINDOW_SECS + 1
+            ),
+            SaveOutcome::Saved(_)
+        ));
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM transcriptions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn repeats_are_saved_when_suppression_is_off() {
+        let mut conn = database();
+        save(&mut conn, "hello there", 100);
+
+        let outcome =
+            insert_transcription(&mut conn, "hello there", None, &[], None, 101, false).unwrap();
+        assert!(matches!(outcome, SaveOutcome::Saved(_)));
+    }
+}
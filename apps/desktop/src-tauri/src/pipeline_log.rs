@@ -0,0 +1,130 @@
+use serde::Serialize;
+use specta::Type;
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Listener};
+use tauri_specta::Event as _;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, Layer};
+
+use crate::commands::settings::{keys as setting_keys, read_bool_setting, Setting};
+use crate::events::names as event_names;
+
+/// Target of this crate's log events; stripped from the module sent to the UI
+const CRATE_TARGET: &str = "desktop_lib";
+
+/// App to emit `pipeline-log` on, set once the app is running
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+/// Mirrors the pipeline log setting so logging doesn't read the database
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Payload of `pipeline-log`, emitted for each log line of the app while the
+/// pipeline log setting is on
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct PipelineLog {
+    /// `error`, `warn`, `info` or `debug`
+    pub level: String,
+    /// Module that logged, e.g. `transcription` or `stt::whisper`
+    pub module: String,
+    pub message: String,
+}
+
+/// Install the global log subscriber: the console gets this crate's logs (debug
+/// builds include debug logs) and warnings from dependencies, and the pipeline
+/// log layer gets this crate's debug logs. Call before anything logs.
+pub fn init() {
+    let console_level = if cfg!(debug_assertions) {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let console = fmt::layer().with_filter(
+        Targets::new()
+            .with_default(LevelFilter::WARN)
+            .with_target(CRATE_TARGET, console_level),
+    );
+    let pipeline =
+        PipelineLogLayer.with_filter(Targets::new().with_target(CRATE_TARGET, Level::DEBUG));
+
+    if let Err(e) = tracing_subscriber::registry()
+        .with(console)
+        .with(pipeline)
+        .try_init()
+    {
+        eprintln!("Failed to install the log subscriber: {}", e);
+    }
+}
+
+/// Start emitting `pipeline-log` once the setting is on, following the setting
+/// as it changes
+pub fn attach(app: &AppHandle) {
+    ENABLED.store(
+        read_bool_setting(setting_keys::PIPELINE_LOG, false),
+        Ordering::Relaxed,
+    );
+    let _ = APP.set(app.clone());
+
+    app.listen(event_names::SETTINGS_UPDATED, |event| {
+        let Ok(setting) = serde_json::from_str::<Setting>(event.payload()) else {
+            return;
+        };
+        if setting.key == setting_keys::PIPELINE_LOG {
+            ENABLED.store(setting.value == "true", Ordering::Relaxed);
+        }
+    });
+}
+
+/// Forwards log events to the frontend as `pipeline-log`
+struct PipelineLogLayer;
+
+impl<S: Subscriber> Layer<S> for PipelineLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let Some(app) = APP.get() else {
+            return;
+        };
+
+        let mut message = MessageVisitor::default();
+        event.record(&mut message);
+
+        let metadata = event.metadata();
+        let module = metadata.target();
+        let module = module
+            .strip_prefix(CRATE_TARGET)
+            .map(|rest| rest.trim_start_matches("::"))
+            .filter(|rest| !rest.is_empty())
+            .unwrap_or(module);
+
+        let _ = PipelineLog {
+            level: metadata.level().as_str().to_lowercase(),
+            module: module.to_string(),
+            message: message.0,
+        }
+        .emit(app);
+    }
+}
+
+/// Formats an event's message followed by any other fields as `name=value`
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        if field.name() == "message" {
+            let _ = write!(self.0, "{:?}", value);
+        } else {
+            let _ = write!(self.0, "{}={:?}", field.name(), value);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::clipboard;
+use crate::db::pool::get_connection;
+
+const DATE: &str = "{date}";
+const TIME: &str = "{time}";
+const CLIPBOARD: &str = "{clipboard}";
+const APP_NAME: &str = "{app_name}";
+const URL: &str = "{url}";
+const RECIPIENT: &str = "{recipient}";
+
+/// Marks where the caret should end up after pasting; never pasted itself
+pub const CURSOR: &str = "{cursor}";
+
+/// Every placeholder a template may contain
+pub const ALL: [&str; 4] = [DATE, TIME, CLIPBOARD, CURSOR];
+
+/// Every variable a custom writing style prompt may contain
+pub const PROMPT_VARIABLES: [&str; 4] = [APP_NAME, URL, DATE, RECIPIENT];
+
+/// Where a dictation is going, for filling writing style prompts
+#[derive(Debug, Clone, Default)]
+pub struct PromptContext {
+    pub app_name: Option<String>,
+    /// Origin and path of the browser tab being dictated into
+    pub url: Option<String>,
+    pub recipient: Option<String>,
+}
+
+/// Fill the `{date}`, `{time}` and `{clipboard}` placeholders of a template.
+/// A placeholder whose value can't be read is left as typed. `{cursor}` is kept
+/// for the paste step.
+pub fn render(template: &str) -> String {
+    let mut rendered = template.to_string();
+
+    if rendered.contains(DATE) {
+        if let Some(date) = local_now("%Y-%m-%d") {
+            rendered = rendered.replace(DATE, &date);
+        }
+    }
+    if rendered.contains(TIME) {
+        if let Some(time) = local_now("%H:%M") {
+            rendered = rendered.replace(TIME, &time);
+        }
+    }
+    if rendered.contains(CLIPBOARD) {
+        if let Some(contents) = clipboard::read_text() {
+            rendered = rendered.replace(CLIPBOARD, &contents);
+        }
+    }
+
+    rendered
+}
+
+/// Whether a writing style prompt contains any variable
+pub fn has_prompt_variables(template: &str) -> bool {
+    PROMPT_VARIABLES
+        .iter()
+        .any(|variable| template.contains(variable))
+}
+
+/// Fill the `{app_name}`, `{url}`, `{date}` and `{recipient}` variables of a
+/// writing style prompt. Unlike snippet placeholders, a variable without a value
+/// is left empty so the formatter never sees the braces.
+pub fn render_prompt(template: &str, context: &PromptContext) -> String {
+    let date = if template.contains(DATE) {
+        local_now("%Y-%m-%d")
+    } else {
+        None
+    };
+
+    [
+        (APP_NAME, &context.app_name),
+        (URL, &context.url),
+        (DATE, &date),
+        (RECIPIENT, &context.recipient),
+    ]
+    .into_iter()
+    .fold(template.to_string(), |rendered, (variable, value)| {
+        rendered.replace(variable, value.as_deref().unwrap_or(""))
+    })
+}
+
+/// Remove the cursor markers from text. Returns the text and the number of
+/// characters after the first marker, i.e. how far to move the caret back.
+pub fn take_cursor(text: &str) -> (String, usize) {
+    let Some(position) = text.find(CURSOR) else {
+        return (text.to_string(), 0);
+    };
+
+    let after = text[position + CURSOR.len()..].replace(CURSOR, "");
+    let chars_after = after.chars().count();
+    (format!("{}{}", &text[..position], after), chars_after)
+}
+
+/// The current local time formatted with SQLite's `strftime`
+fn local_now(format: &str) -> Option<String> {
+    let conn = get_connection().ok()?;
+    conn.query_row("SELECT strftime(?, 'now', 'localtime')", [format], |row| {
+        row.get(0)
+    })
+    .ok()
+}
@@ -0,0 +1,376 @@
+use rdev::Key;
+use tracing::warn;
+
+use super::KeyEvent;
+
+/// Whether the app may observe global key events (Input Monitoring on macOS)
+#[cfg(target_os = "macos")]
+pub fn has_permission() -> bool {
+    unsafe { CGPreflightListenEventAccess() }
+}
+
+/// Ask for Input Monitoring access. Shows the system prompt the first time and
+/// returns the current state; later calls only report it.
+#[cfg(target_os = "macos")]
+pub fn request_permission() -> bool {
+    unsafe { CGRequestListenEventAccess() }
+}
+
+/// Listen to global key events on the current thread until the run loop stops.
+/// The tap is listen-only, so every event still reaches the focused app.
+#[cfg(target_os = "macos")]
+pub fn listen<F: Fn(KeyEvent) + 'static>(callback: F) -> Result<(), String> {
+    use core_foundation::mach_port::CFMachPortRef;
+    use core_foundation::runloop::{kCFRunLoopCommonModes, CFRunLoop};
+    use core_graphics::event::{
+        CGEventTap, CGEventTapLocation, CGEventTapOptions, CGEventTapPlacement, CGEventType,
+    };
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    if !has_permission() {
+        return Err("Input Monitoring permission not granted".to_string());
+    }
+
+    // The callback needs the tap's port to re-enable it, but the port only exists
+    // once the tap is created
+    let tap_port: Rc<Cell<Option<CFMachPortRef>>> = Rc::new(Cell::new(None));
+    let callback_port = tap_port.clone();
+
+    let tap = CGEventTap::new(
+        CGEventTapLocation::HID,
+        CGEventTapPlacement::HeadInsertEventTap,
+        CGEventTapOptions::ListenOnly,
+        vec![
+            CGEventType::KeyDown,
+            CGEventType::KeyUp,
+            CGEventType::FlagsChanged,
+        ],
+        move |_proxy, event_type, event| {
+            match event_type {
+                // macOS turns the tap off when a callback is slow or on secure input
+                CGEventType::TapDisabledByTimeout | CGEventType::TapDisabledByUserInput => {
+                    if let Some(port) = callback_port.get() {
+                        warn!("⚠️ Event tap was disabled ({:?}), re-enabling", event_type);
+                        unsafe { CGEventTapEnable(port, true) };
+                    }
+                }
+                _ => {
+                    if let Some(key_event) = to_key_event(event_type, event) {
+                        callback(key_event);
+                    }
+                }
+            }
+            None
+        },
+    )
+    .map_err(|_| "Failed to create keyboard event tap".to_string())?;
+
+    unsafe {
+        use core_foundation::base::TCFType;
+
+        tap_port.set(Some(tap.mach_port.as_concrete_TypeRef()));
+
+        let source = tap
+            .mach_port
+            .create_runloop_source(0)
+            .map_err(|_| "Failed to create run loop source for event tap".to_string())?;
+        CFRunLoop::get_current().add_source(&source, kCFRunLoopCommonModes);
+        tap.enable();
+        CFRunLoop::run_current();
+    }
+
+    Err("Keyboard event tap run loop stopped".to_string())
+}
+
+/// Translate a tap event into a key press/release. Modifiers (including Globe/FN)
+/// only arrive as `FlagsChanged`, so whether they went down is read from the flags.
+/// The left and right keys of a pair share a flag, so the device-specific bit of
+/// the key is read instead, so releasing one while the other is held is seen.
+#[cfg(target_os = "macos")]
+fn to_key_event(
+    event_type: core_graphics::event::CGEventType,
+    event: &core_graphics::event::CGEvent,
+) -> Option<KeyEvent> {
+    use core_graphics::event::{CGEventFlags, CGEventType, EventField};
+
+    // Device-specific modifier bits (`NX_DEVICE*KEYMASK` in IOKit's `IOLLEvent.h`)
+    const NX_DEVICELCTLKEYMASK: u64 = 0x0000_0001;
+    const NX_DEVICELSHIFTKEYMASK: u64 = 0x0000_0002;
+    const NX_DEVICERSHIFTKEYMASK: u64 = 0x0000_0004;
+    const NX_DEVICELCMDKEYMASK: u64 = 0x0000_0008;
+    const NX_DEVICERCMDKEYMASK: u64 = 0x0000_0010;
+    const NX_DEVICELALTKEYMASK: u64 = 0x0000_0020;
+    const NX_DEVICERALTKEYMASK: u64 = 0x0000_0040;
+    const NX_DEVICERCTLKEYMASK: u64 = 0x0000_2000;
+
+    let keycode = event.get_integer_value_field(EventField::KEYBOARD_EVENT_KEYCODE) as u16;
+    let key = key_from_keycode(keycode)?;
+
+    match event_type {
+        CGEventType::KeyDown => Some(KeyEvent::Press(key)),
+        CGEventType::KeyUp => Some(KeyEvent::Release(key)),
+        CGEventType::FlagsChanged => {
+            let flag = match key {
+                Key::Function => CGEventFlags::CGEventFlagSecondaryFn.bits(),
+                Key::CapsLock => CGEventFlags::CGEventFlagAlphaShift.bits(),
+                Key::ControlLeft => NX_DEVICELCTLKEYMASK,
+                Key::ControlRight => NX_DEVICERCTLKEYMASK,
+                Key::ShiftLeft => NX_DEVICELSHIFTKEYMASK,
+                Key::ShiftRight => NX_DEVICERSHIFTKEYMASK,
+                Key::MetaLeft => NX_DEVICELCMDKEYMASK,
+                Key::MetaRight => NX_DEVICERCMDKEYMASK,
+                Key::Alt => NX_DEVICELALTKEYMASK,
+                Key::AltGr => NX_DEVICERALTKEYMASK,
+                _ => return None,
+            };
+
+            if event.get_flags().bits() & flag != 0 {
+                Some(KeyEvent::Press(key))
+            } else {
+                Some(KeyEvent::Release(key))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Map a macOS virtual keycode (`kVK_*`) to the key names shortcuts are parsed into
+#[cfg(target_os = "macos")]
+fn key_from_keycode(keycode: u16) -> Option<Key> {
+    let key = match keycode {
+        0 => Key::KeyA,
+        1 => Key::KeyS,
+        2 => Key::KeyD,
+        3 => Key::KeyF,
+        4 => Key::KeyH,
+        5 => Key::KeyG,
+        6 => Key::KeyZ,
+        7 => Key::KeyX,
+        8 => Key::KeyC,
+        9 => Key::KeyV,
+        11 => Key::KeyB,
+        12 => Key::KeyQ,
+        13 => Key::KeyW,
+        14 => Key::KeyE,
+        15 => Key::KeyR,
+        16 => Key::KeyY,
+        17 => Key::KeyT,
+        18 => Key::Num1,
+        19 => Key::Num2,
+        20 => Key::Num3,
+        21 => Key::Num4,
+        22 => Key::Num6,
+        23 => Key::Num5,
+        25 => Key::Num9,
+        26 => Key::Num7,
+        28 => Key::Num8,
+        29 => Key::Num0,
+        31 => Key::KeyO,
+        32 => Key::KeyU,
+        34 => Key::KeyI,
+        35 => Key::KeyP,
+        36 => Key::Return,
+        37 => Key::KeyL,
+        38 => Key::KeyJ,
+        40 => Key::KeyK,
+        45 => Key::KeyN,
+        46 => Key::KeyM,
+        48 => Key::Tab,
+        49 => Key::Space,
+        51 => Key::Backspace,
+        53 => Key::Escape,
+        54 => Key::MetaRight,
+        55 => Key::MetaLeft,
+        56 => Key::ShiftLeft,
+        57 => Key::CapsLock,
+        58 => Key::Alt,
+        59 => Key::ControlLeft,
+        60 => Key::ShiftRight,
+        61 => Key::AltGr,
+        62 => Key::ControlRight,
+        // Globe/FN
+        63 => Key::Function,
+        96 => Key::F5,
+        97 => Key::F6,
+        98 => Key::F7,
+        99 => Key::F3,
+        100 => Key::F8,
+        101 => Key::F9,
+        103 => Key::F11,
+        109 => Key::F10,
+        111 => Key::F12,
+        117 => Key::Delete,
+        118 => Key::F4,
+        120 => Key::F2,
+        122 => Key::F1,
+        123 => Key::LeftArrow,
+        124 => Key::RightArrow,
+        125 => Key::DownArrow,
+        126 => Key::UpArrow,
+        _ => return None,
+    };
+
+    Some(key)
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGPreflightListenEventAccess() -> bool;
+    fn CGRequestListenEventAccess() -> bool;
+    fn CGEventTapEnable(tap: core_foundation::mach_port::CFMachPortRef, enable: bool);
+}
+
+/// Whether any keyboard device can be read, which needs read access to
+/// /dev/input, usually through the `input` group
+#[cfg(target_os = "linux")]
+pub fn has_permission() -> bool {
+    !keyboards().is_empty()
+}
+
+/// Listen to key events from every keyboard's evdev device until they are all
+/// gone. For Wayland, which hides other apps' key events from X11 clients.
+/// Devices are only read, so every event still reaches the focused app.
+#[cfg(target_os = "linux")]
+pub fn listen<F: Fn(KeyEvent) + Send + Sync + 'static>(callback: F) -> Result<(), String> {
+    use evdev::InputEventKind;
+    use std::sync::Arc;
+    use std::thread;
+
+    let keyboards = keyboards();
+    if keyboards.is_empty() {
+        return Err("No readable keyboard in /dev/input".to_string());
+    }
+
+    let callback = Arc::new(callback);
+    let readers: Vec<_> = keyboards
+        .into_iter()
+        .map(|mut device| {
+            let callback = callback.clone();
+            thread::spawn(move || loop {
+                let events = match device.fetch_events() {
+                    Ok(events) => events,
+                    Err(e) => return e.to_string(),
+                };
+                for event in events {
+                    let InputEventKind::Key(code) = event.kind() else {
+                        continue;
+                    };
+                    let Some(key) = key_from_code(code.code()) else {
+                        continue;
+                    };
+                    // 1 is a press and 2 an autorepeat, both presses as on macOS
+                    if event.value() == 0 {
+                        callback(KeyEvent::Release(key));
+                    } else {
+                        callback(KeyEvent::Press(key));
+                    }
+                }
+            })
+        })
+        .collect();
+
+    // Unplugging a keyboard only ends its own reader
+    let mut error = "Keyboard devices closed".to_string();
+    for reader in readers {
+        if let Ok(e) = reader.join() {
+            error = e;
+        }
+    }
+    Err(format!("Keyboard listener stopped: {}", error))
+}
+
+/// Input devices with letter keys, leaving out mice, power buttons and the like
+#[cfg(target_os = "linux")]
+fn keyboards() -> Vec<evdev::Device> {
+    use evdev::Key as EvdevKey;
+
+    evdev::enumerate()
+        .map(|(_, device)| device)
+        .filter(|device| {
+            device.supported_keys().is_some_and(|keys| {
+                keys.contains(EvdevKey::KEY_A) && keys.contains(EvdevKey::KEY_ENTER)
+            })
+        })
+        .collect()
+}
+
+/// Map a Linux input event code (`KEY_*`) to the key names shortcuts are parsed into
+#[cfg(target_os = "linux")]
+fn key_from_code(code: u16) -> Option<Key> {
+    let key = match code {
+        1 => Key::Escape,
+        2 => Key::Num1,
+        3 => Key::Num2,
+        4 => Key::Num3,
+        5 => Key::Num4,
+        6 => Key::Num5,
+        7 => Key::Num6,
+        8 => Key::Num7,
+        9 => Key::Num8,
+        10 => Key::Num9,
+        11 => Key::Num0,
+        14 => Key::Backspace,
+        15 => Key::Tab,
+        16 => Key::KeyQ,
+        17 => Key::KeyW,
+        18 => Key::KeyE,
+        19 => Key::KeyR,
+        20 => Key::KeyT,
+        21 => Key::KeyY,
+        22 => Key::KeyU,
+        23 => Key::KeyI,
+        24 => Key::KeyO,
+        25 => Key::KeyP,
+        28 => Key::Return,
+        29 => Key::ControlLeft,
+        30 => Key::KeyA,
+        31 => Key::KeyS,
+        32 => Key::KeyD,
+        33 => Key::KeyF,
+        34 => Key::KeyG,
+        35 => Key::KeyH,
+        36 => Key::KeyJ,
+        37 => Key::KeyK,
+        38 => Key::KeyL,
+        42 => Key::ShiftLeft,
+        44 => Key::KeyZ,
+        45 => Key::KeyX,
+        46 => Key::KeyC,
+        47 => Key::KeyV,
+        48 => Key::KeyB,
+        49 => Key::KeyN,
+        50 => Key::KeyM,
+        54 => Key::ShiftRight,
+        56 => Key::Alt,
+        57 => Key::Space,
+        58 => Key::CapsLock,
+        59 => Key::F1,
+        60 => Key::F2,
+        61 => Key::F3,
+        62 => Key::F4,
+        63 => Key::F5,
+        64 => Key::F6,
+        65 => Key::F7,
+        66 => Key::F8,
+        67 => Key::F9,
+        68 => Key::F10,
+        87 => Key::F11,
+        88 => Key::F12,
+        97 => Key::ControlRight,
+        100 => Key::AltGr,
+        103 => Key::UpArrow,
+        105 => Key::LeftArrow,
+        106 => Key::RightArrow,
+        108 => Key::DownArrow,
+        111 => Key::Delete,
+        125 => Key::MetaLeft,
+        126 => Key::MetaRight,
+        // KEY_FN, only reported by some laptop keyboards
+        464 => Key::Function,
+        _ => return None,
+    };
+
+    Some(key)
+}
@@ -0,0 +1,373 @@
+// Desktop integration on Linux through the usual command line tools: `xdotool`
+// and `xclip` on X11, `ydotool` and `wl-clipboard` on Wayland. Wayland has no
+// protocol for reading or focusing other apps' windows, so the frontmost app
+// is only known on X11.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tauri::{AppHandle, WebviewWindow};
+
+use super::{
+    event_tap, listen_rdev, FocusedApp, FrontmostAppProvider, GlobalKeyListener, KeyEvent,
+    OverlayWindow, TextInserter,
+};
+use crate::app_context::RunningApp;
+use crate::window::build_widget_window;
+
+/// Linux input event codes of the keys sent through `ydotool`
+const KEY_LEFTCTRL: u16 = 29;
+const KEY_LEFTSHIFT: u16 = 42;
+const KEY_V: u16 = 47;
+const KEY_LEFT: u16 = 105;
+
+/// Whether the session is Wayland rather than X11
+fn is_wayland() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some()
+        || std::env::var("XDG_SESSION_TYPE").is_ok_and(|session| session == "wayland")
+}
+
+/// Output of a command, `None` when it fails or prints nothing
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Run a command to completion, reporting a missing tool by name
+fn run_status(program: &str, args: &[&str]) -> Result<(), String> {
+    let status = Command::new(program)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| format!("Failed to run {} (is it installed?): {}", program, e))?;
+    if !status.success() {
+        return Err(format!("{} exited with {}", program, status));
+    }
+    Ok(())
+}
+
+/// Run a command with `input` on stdin. Output is discarded: clipboard tools
+/// keep running in the background to serve the selection, so waiting on their
+/// stdout would hang.
+fn run_with_input(program: &str, args: &[&str], input: &str) -> Result<(), String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run {} (is it installed?): {}", program, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for {}: {}", program, e))?;
+    if !status.success() {
+        return Err(format!("{} exited with {}", program, status));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Clipboard
+// ============================================================================
+
+/// Replace the clipboard contents with plain text
+fn set_clipboard(text: &str) -> Result<(), String> {
+    if is_wayland() {
+        run_with_input("wl-copy", &["--type", "text/plain;charset=utf-8"], text)
+    } else {
+        run_with_input("xclip", &["-selection", "clipboard"], text)
+    }
+}
+
+/// Plain text on the clipboard, or on the primary selection (the text selected
+/// in the focused app) when `primary` is set
+fn get_clipboard(primary: bool) -> Option<String> {
+    let selection = if primary { "primary" } else { "clipboard" };
+    if is_wayland() {
+        let mut args = vec!["--no-newline", "--type", "text/plain"];
+        if primary {
+            args.push("--primary");
+        }
+        run("wl-paste", &args)
+    } else {
+        run("xclip", &["-selection", selection, "-o"])
+    }
+}
+
+/// Empty the clipboard
+fn clear_clipboard() {
+    let result = if is_wayland() {
+        run_status("wl-copy", &["--clear"])
+    } else {
+        run_with_input("xclip", &["-selection", "clipboard"], "")
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to clear the clipboard: {}", e);
+    }
+}
+
+// ============================================================================
+// Keyboard
+// ============================================================================
+
+/// `ydotool key` arguments pressing `keys` down in order and releasing them in
+/// reverse
+fn ydotool_chord(keys: &[u16]) -> Vec<String> {
+    let mut args = vec!["key".to_string()];
+    args.extend(keys.iter().map(|key| format!("{}:1", key)));
+    args.extend(keys.iter().rev().map(|key| format!("{}:0", key)));
+    args
+}
+
+fn ydotool(args: &[String]) -> Result<(), String> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    run_status("ydotool", &args)
+}
+
+/// Press Ctrl+V
+fn press_paste() -> Result<(), String> {
+    if is_wayland() {
+        ydotool(&ydotool_chord(&[KEY_LEFTCTRL, KEY_V]))
+    } else {
+        run_status("xdotool", &["key", "--clearmodifiers", "ctrl+v"])
+    }
+}
+
+/// Press the left arrow `count` times, with Shift held to select when `select`
+/// is set
+fn press_left(count: usize, select: bool) -> Result<(), String> {
+    if count == 0 {
+        return Ok(());
+    }
+
+    if is_wayland() {
+        let chord = if select {
+            vec![KEY_LEFTSHIFT, KEY_LEFT]
+        } else {
+            vec![KEY_LEFT]
+        };
+        let mut args = ydotool_chord(&chord);
+        for _ in 1..count {
+            args.extend(ydotool_chord(&chord).into_iter().skip(1));
+        }
+        ydotool(&args)
+    } else {
+        let count = count.to_string();
+        let key = if select { "shift+Left" } else { "Left" };
+        run_status(
+            "xdotool",
+            &[
+                "key",
+                "--clearmodifiers",
+                "--repeat",
+                &count,
+                "--delay",
+                "5",
+                key,
+            ],
+        )
+    }
+}
+
+/// Type text at `chars_per_sec`, for apps that ignore a programmatic paste.
+/// Newlines are sent as Return.
+fn type_text(text: &str, chars_per_sec: u32) -> Result<(), String> {
+    let delay_ms = (1000 / chars_per_sec.max(1)).to_string();
+    let text = text.replace('\r', "");
+
+    if is_wayland() {
+        run_status("ydotool", &["type", "--key-delay", &delay_ms, "--", &text])
+    } else {
+        run_status(
+            "xdotool",
+            &[
+                "type",
+                "--clearmodifiers",
+                "--delay",
+                &delay_ms,
+                "--",
+                &text,
+            ],
+        )
+    }
+}
+
+// ============================================================================
+// Windows
+// ============================================================================
+
+/// Process id of the focused window, only known on X11
+fn active_window_pid() -> Option<i32> {
+    if is_wayland() {
+        return None;
+    }
+    run("xdotool", &["getactivewindow", "getwindowpid"])?
+        .parse()
+        .ok()
+}
+
+/// Title of the focused window, only known on X11
+fn active_window_title() -> Option<String> {
+    if is_wayland() {
+        return None;
+    }
+    run("xdotool", &["getactivewindow", "getwindowname"])
+}
+
+/// Name of a running process, e.g. `firefox`
+fn process_name(pid: i32) -> Option<String> {
+    let name = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Whether a process is still running
+fn is_running(pid: i32) -> bool {
+    std::path::Path::new(&format!("/proc/{}", pid)).exists()
+}
+
+/// Focus the first visible window of a process. Only possible on X11; on
+/// Wayland the compositor decides, so this reports success and the paste lands
+/// wherever focus is.
+fn activate_window(pid: i32) -> bool {
+    if is_wayland() {
+        return true;
+    }
+    let pid = pid.to_string();
+    run_status(
+        "xdotool",
+        &[
+            "search",
+            "--onlyvisible",
+            "--pid",
+            &pid,
+            "windowactivate",
+            "--sync",
+        ],
+    )
+    .is_ok()
+}
+
+// ============================================================================
+// Platform
+// ============================================================================
+
+/// Command line tools on X11 and Wayland, evdev for keys on Wayland
+pub struct Native;
+
+impl TextInserter for Native {
+    /// Only plain text: the clipboard tools offer one type at a time
+    fn copy(&self, text: &str, _html: Option<&str>) -> Result<(), String> {
+        set_clipboard(text)
+    }
+
+    fn read_clipboard(&self) -> Option<String> {
+        get_clipboard(false)
+    }
+
+    fn clear_clipboard(&self) {
+        clear_clipboard();
+    }
+
+    fn press_paste(&self) -> Result<(), String> {
+        press_paste()
+    }
+
+    fn press_left(&self, count: usize, select: bool) -> Result<(), String> {
+        press_left(count, select)
+    }
+
+    fn type_text(&self, text: &str, chars_per_sec: u32) -> Result<(), String> {
+        type_text(text, chars_per_sec)
+    }
+
+    /// Read from the primary selection, without touching the clipboard
+    fn copy_selection(&self) -> Option<String> {
+        get_clipboard(true)
+    }
+}
+
+impl FrontmostAppProvider for Native {
+    fn focused_app(&self) -> Option<FocusedApp> {
+        let name = active_window_pid().and_then(process_name)?;
+        Some(FocusedApp {
+            process_name: name,
+            bundle_id: None,
+        })
+    }
+
+    fn running_app(&self) -> Option<RunningApp> {
+        let pid = active_window_pid()?;
+        if pid as u32 == std::process::id() {
+            return None;
+        }
+        let name = process_name(pid).unwrap_or_default();
+
+        Some(RunningApp { pid, name })
+    }
+
+    fn activate(&self, pid: i32) -> bool {
+        if self.running_app().is_some_and(|app| app.pid == pid) {
+            return true;
+        }
+        is_running(pid) && activate_window(pid)
+    }
+
+    fn is_running(&self, pid: i32) -> bool {
+        is_running(pid)
+    }
+
+    fn window_title(&self) -> Option<String> {
+        active_window_title()
+    }
+
+    /// There is no accessibility API to read it through; `copy_selection`
+    /// reads the primary selection instead
+    fn selected_text(&self) -> Option<String> {
+        None
+    }
+}
+
+impl OverlayWindow for Native {
+    fn build_widget(&self, app: &AppHandle) -> Result<WebviewWindow, String> {
+        build_widget_window(app).map_err(|e| e.to_string())
+    }
+}
+
+impl GlobalKeyListener for Native {
+    /// X11 lets any client see key events; on Wayland they are read from the
+    /// keyboard devices
+    fn has_permission(&self) -> bool {
+        !is_wayland() || event_tap::has_permission()
+    }
+
+    /// There is no prompt for /dev/input access, so this only reports it
+    fn request_permission(&self) -> bool {
+        self.has_permission()
+    }
+
+    fn permission_hint(&self) -> &'static str {
+        "On Wayland, add your user to the input group (sudo usermod -aG input $USER), then log out and back in"
+    }
+
+    /// rdev on X11, and the keyboards' evdev devices on Wayland, where X11
+    /// clients don't see other apps' key events
+    fn listen(&self, callback: Box<dyn Fn(KeyEvent) + Send + Sync>) -> Result<(), String> {
+        if is_wayland() {
+            event_tap::listen(callback)
+        } else {
+            listen_rdev(callback)
+        }
+    }
+}
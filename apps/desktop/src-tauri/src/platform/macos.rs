@@ -0,0 +1,443 @@
+use cocoa::base::{id, nil};
+use cocoa::foundation::NSString;
+use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation};
+use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+use objc::{class, msg_send, sel, sel_impl};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, WebviewWindow};
+use tauri_nspanel::{tauri_panel, CollectionBehavior, PanelBuilder, PanelLevel, StyleMask};
+
+use super::event_tap;
+use super::{
+    FocusedApp, FrontmostAppProvider, GlobalKeyListener, KeyEvent, OverlayWindow, TextInserter,
+};
+use crate::app_context::{run_osascript, RunningApp};
+use crate::window::WIDGET_PILL_SIZE;
+
+/// Pasteboard type of plain text
+const PLAIN_TEXT_TYPE: &str = "public.utf8-plain-text";
+
+/// Pasteboard type of HTML, read by rich text apps like Mail and Notes
+const HTML_TYPE: &str = "public.html";
+
+/// Virtual key codes (`kVK_*`) of the keys pressed
+const C_KEY_CODE: u16 = 8;
+const V_KEY_CODE: u16 = 9;
+const RETURN_KEY_CODE: u16 = 36;
+const LEFT_ARROW_KEY_CODE: u16 = 123;
+
+tauri_panel! {
+    panel!(WidgetPanel {
+        config: {
+            can_become_key_window: true,
+            is_floating_panel: true
+        }
+    })
+}
+
+/// Cocoa, CoreGraphics and accessibility
+pub struct Native;
+
+fn event_source() -> Result<CGEventSource, String> {
+    CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+        .map_err(|_| "Failed to create a keyboard event source".to_string())
+}
+
+/// Press and release a key with exactly `flags` held, so a modifier the user
+/// still holds down doesn't change what it does
+fn press_key(source: &CGEventSource, key_code: u16, flags: CGEventFlags) {
+    for key_down in [true, false] {
+        if let Ok(event) = CGEvent::new_keyboard_event(source.clone(), key_code, key_down) {
+            event.set_flags(flags);
+            event.post(CGEventTapLocation::HID);
+        }
+    }
+}
+
+impl Native {
+    /// Clear the pasteboard, then write each `(type, contents)` pair
+    fn write_flavors(&self, flavors: &[(&str, &str)]) -> Result<(), String> {
+        unsafe {
+            let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
+            if pasteboard == nil {
+                return Err("Failed to get pasteboard".to_string());
+            }
+
+            let _: () = msg_send![pasteboard, clearContents];
+
+            for (flavor, contents) in flavors {
+                let ns_string = NSString::alloc(nil).init_str(contents);
+                if ns_string == nil {
+                    return Err("Failed to create NSString".to_string());
+                }
+
+                let ns_string_type = NSString::alloc(nil).init_str(flavor);
+                let success: bool =
+                    msg_send![pasteboard, setString:ns_string forType:ns_string_type];
+                if !success {
+                    return Err("Failed to set string to pasteboard".to_string());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TextInserter for Native {
+    fn copy(&self, text: &str, html: Option<&str>) -> Result<(), String> {
+        match html {
+            Some(html) => self.write_flavors(&[(PLAIN_TEXT_TYPE, text), (HTML_TYPE, html)]),
+            None => self.write_flavors(&[(PLAIN_TEXT_TYPE, text)]),
+        }
+    }
+
+    fn read_clipboard(&self) -> Option<String> {
+        unsafe {
+            let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
+            if pasteboard == nil {
+                return None;
+            }
+
+            let ns_string_type = NSString::alloc(nil).init_str(PLAIN_TEXT_TYPE);
+            let contents: id = msg_send![pasteboard, stringForType: ns_string_type];
+            if contents == nil {
+                return None;
+            }
+
+            let utf8: *const c_char = msg_send![contents, UTF8String];
+            if utf8.is_null() {
+                return None;
+            }
+            Some(CStr::from_ptr(utf8).to_string_lossy().into_owned())
+        }
+    }
+
+    fn clear_clipboard(&self) {
+        unsafe {
+            let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
+            if pasteboard != nil {
+                let _: () = msg_send![pasteboard, clearContents];
+            }
+        }
+    }
+
+    fn press_paste(&self) -> Result<(), String> {
+        let source = event_source()?;
+
+        if let Ok(key_down) = CGEvent::new_keyboard_event(source.clone(), V_KEY_CODE, true) {
+            key_down.set_flags(CGEventFlags::CGEventFlagCommand);
+            key_down.post(CGEventTapLocation::HID);
+        }
+
+        thread::sleep(Duration::from_millis(50));
+
+        if let Ok(key_up) = CGEvent::new_keyboard_event(source, V_KEY_CODE, false) {
+            key_up.set_flags(CGEventFlags::CGEventFlagCommand);
+            key_up.post(CGEventTapLocation::HID);
+        }
+
+        Ok(())
+    }
+
+    fn press_left(&self, count: usize, select: bool) -> Result<(), String> {
+        if count == 0 {
+            return Ok(());
+        }
+
+        let source = event_source()?;
+        let flags = if select {
+            CGEventFlags::CGEventFlagShift
+        } else {
+            CGEventFlags::CGEventFlagNull
+        };
+
+        for _ in 0..count {
+            press_key(&source, LEFT_ARROW_KEY_CODE, flags);
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        Ok(())
+    }
+
+    /// Typed through keyboard events carrying the character itself, so the
+    /// keyboard layout doesn't matter
+    fn type_text(&self, text: &str, chars_per_sec: u32) -> Result<(), String> {
+        let source = event_source()?;
+        let delay = Duration::from_secs_f64(1.0 / chars_per_sec.max(1) as f64);
+
+        for c in text.chars().filter(|c| *c != '\r') {
+            for key_down in [true, false] {
+                let key_code = if c == '\n' { RETURN_KEY_CODE } else { 0 };
+                let Ok(event) = CGEvent::new_keyboard_event(source.clone(), key_code, key_down)
+                else {
+                    continue;
+                };
+                if c != '\n' {
+                    event.set_string(c.encode_utf8(&mut [0; 4]));
+                }
+                // A modifier still held down would turn characters into shortcuts
+                event.set_flags(CGEventFlags::CGEventFlagNull);
+                event.post(CGEventTapLocation::HID);
+            }
+            thread::sleep(delay);
+        }
+
+        Ok(())
+    }
+
+    /// Press Cmd+C, for apps that don't expose the selection through
+    /// accessibility
+    fn copy_selection(&self) -> Option<String> {
+        // Clear first so stale contents aren't mistaken for the selection
+        self.clear_clipboard();
+
+        let source = event_source().ok()?;
+        for key_down in [true, false] {
+            if let Ok(event) = CGEvent::new_keyboard_event(source.clone(), C_KEY_CODE, key_down) {
+                event.set_flags(CGEventFlags::CGEventFlagCommand);
+                event.post(CGEventTapLocation::HID);
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        // Give the app time to write the clipboard
+        thread::sleep(Duration::from_millis(150));
+
+        let selection = self.read_clipboard();
+        self.clear_clipboard();
+        selection
+    }
+}
+
+/// String attribute reached from the system-wide accessibility element by
+/// following `path`, e.g. the focused app, then its focused window, then its title
+fn focused_string(path: &[&str]) -> Option<String> {
+    use core_foundation::base::{CFType, CFTypeRef, TCFType};
+    use core_foundation::string::{CFString, CFStringRef};
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateSystemWide() -> CFTypeRef;
+        fn AXUIElementCopyAttributeValue(
+            element: CFTypeRef,
+            attribute: CFStringRef,
+            value: *mut CFTypeRef,
+        ) -> i32;
+    }
+
+    /// An attribute of an accessibility element; the caller owns the value
+    unsafe fn copy_attribute(element: &CFType, attribute: &str) -> Option<CFType> {
+        let attribute = CFString::new(attribute);
+        let mut value: CFTypeRef = std::ptr::null();
+        let error = AXUIElementCopyAttributeValue(
+            element.as_CFTypeRef(),
+            attribute.as_concrete_TypeRef(),
+            &mut value,
+        );
+        // kAXErrorSuccess
+        (error == 0 && !value.is_null()).then(|| CFType::wrap_under_create_rule(value))
+    }
+
+    unsafe {
+        let system = AXUIElementCreateSystemWide();
+        if system.is_null() {
+            return None;
+        }
+        let mut element = CFType::wrap_under_create_rule(system);
+
+        for attribute in path {
+            element = copy_attribute(&element, attribute)?;
+        }
+
+        element
+            .downcast::<CFString>()
+            .map(|value| value.to_string())
+    }
+}
+
+/// The running app with a process id, `nil` once it has quit
+unsafe fn running_application(pid: i32) -> id {
+    msg_send![
+        class!(NSRunningApplication),
+        runningApplicationWithProcessIdentifier: pid
+    ]
+}
+
+impl FrontmostAppProvider for Native {
+    /// Read through System Events, whose process names are the ones its
+    /// scripts accept
+    fn focused_app(&self) -> Option<FocusedApp> {
+        let process = run_osascript(
+            "tell application \"System Events\" to set frontApp to first application process whose frontmost is true\n\
+             return (name of frontApp) & linefeed & (bundle identifier of frontApp)",
+        )?;
+        let (process_name, bundle_id) = match process.split_once('\n') {
+            Some((name, bundle_id)) => (name.trim(), Some(bundle_id.trim().to_string())),
+            None => (process.trim(), None),
+        };
+
+        Some(FocusedApp {
+            process_name: process_name.to_string(),
+            bundle_id: bundle_id.filter(|id| !id.is_empty() && id != "missing value"),
+        })
+    }
+
+    /// Read straight from NSWorkspace
+    fn running_app(&self) -> Option<RunningApp> {
+        unsafe {
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let app: id = msg_send![workspace, frontmostApplication];
+            if app == nil {
+                return None;
+            }
+            let pid: i32 = msg_send![app, processIdentifier];
+            if pid as u32 == std::process::id() {
+                return None;
+            }
+
+            let localized_name: id = msg_send![app, localizedName];
+            let name = if localized_name == nil {
+                String::new()
+            } else {
+                let utf8: *const c_char = msg_send![localized_name, UTF8String];
+                CStr::from_ptr(utf8).to_string_lossy().into_owned()
+            };
+
+            Some(RunningApp { pid, name })
+        }
+    }
+
+    fn activate(&self, pid: i32) -> bool {
+        // NSApplicationActivateIgnoringOtherApps
+        const ACTIVATE_IGNORING_OTHER_APPS: usize = 1 << 1;
+
+        /// Activation is asynchronous; give the app time to take key focus
+        const ACTIVATE_DELAY_MS: u64 = 150;
+
+        if self.running_app().is_some_and(|app| app.pid == pid) {
+            return true;
+        }
+        if !self.is_running(pid) {
+            return false;
+        }
+
+        unsafe {
+            let app = running_application(pid);
+            if app == nil {
+                return false;
+            }
+            let _: bool = msg_send![app, activateWithOptions: ACTIVATE_IGNORING_OTHER_APPS];
+        }
+
+        thread::sleep(Duration::from_millis(ACTIVATE_DELAY_MS));
+        true
+    }
+
+    fn is_running(&self, pid: i32) -> bool {
+        unsafe {
+            let app = running_application(pid);
+            if app == nil {
+                return false;
+            }
+            let terminated: bool = msg_send![app, isTerminated];
+            !terminated
+        }
+    }
+
+    fn window_title(&self) -> Option<String> {
+        focused_string(&["AXFocusedApplication", "AXFocusedWindow", "AXTitle"])
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        focused_string(&["AXFocusedUIElement", "AXSelectedText"])
+    }
+}
+
+impl OverlayWindow for Native {
+    /// A non-activating NSPanel, which floats over full-screen apps and on
+    /// every Space
+    fn build_widget(&self, app: &AppHandle) -> Result<WebviewWindow, String> {
+        use cocoa::appkit::{NSColor, NSWindow, NSWindowCollectionBehavior};
+
+        let panel = PanelBuilder::<tauri::Wry, WidgetPanel>::new(app, "widget")
+            .url(tauri::WebviewUrl::App("widget.html".into()))
+            .size(tauri::Size::Logical(tauri::LogicalSize {
+                width: WIDGET_PILL_SIZE.0,
+                height: WIDGET_PILL_SIZE.1,
+            }))
+            .level(PanelLevel::Floating)
+            .has_shadow(false)
+            .collection_behavior(
+                CollectionBehavior::new()
+                    .can_join_all_spaces()
+                    .full_screen_auxiliary()
+                    .into(),
+            )
+            .hides_on_deactivate(false)
+            .works_when_modal(true)
+            .with_window(|w| w.decorations(false).transparent(true))
+            .style_mask(StyleMask::empty().nonactivating_panel().into())
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        panel.show();
+
+        let window = panel
+            .to_window()
+            .ok_or_else(|| "Widget panel has no window".to_string())?;
+
+        unsafe {
+            if let Ok(ns_window_ptr) = window.ns_window() {
+                let ns_window = ns_window_ptr as id;
+
+                // Combine both behaviors for all spaces + fullscreen visibility
+                let behavior =
+                    NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces
+                        | NSWindowCollectionBehavior::NSWindowCollectionBehaviorFullScreenAuxiliary;
+
+                ns_window.setCollectionBehavior_(behavior);
+
+                // Set window level to ensure it stays on top
+                ns_window.setLevel_(cocoa::appkit::NSMainMenuWindowLevel as i64 + 1);
+
+                // Make window background fully transparent
+                ns_window.setOpaque_(false);
+                ns_window.setBackgroundColor_(NSColor::clearColor(nil));
+                ns_window.setHasShadow_(false);
+
+                // Disable window dragging by background
+                ns_window.setMovableByWindowBackground_(cocoa::base::NO);
+
+                ns_window.setAlphaValue_(0.9);
+            }
+        }
+
+        Ok(window)
+    }
+}
+
+impl GlobalKeyListener for Native {
+    fn has_permission(&self) -> bool {
+        event_tap::has_permission()
+    }
+
+    /// Shows the Input Monitoring prompt the first time; later calls only
+    /// report the state
+    fn request_permission(&self) -> bool {
+        event_tap::request_permission()
+    }
+
+    fn permission_hint(&self) -> &'static str {
+        "Make sure Dicto has Input Monitoring permission in System Settings > Privacy & Security > Input Monitoring"
+    }
+
+    /// A CGEventTap, which sees Globe/FN reliably and recovers when macOS
+    /// disables it
+    fn listen(&self, callback: Box<dyn Fn(KeyEvent) + Send + Sync>) -> Result<(), String> {
+        event_tap::listen(callback)
+    }
+}
@@ -0,0 +1,113 @@
+// In-memory stand-ins for the clipboard, key presses and the frontmost app,
+// used by test builds so the paste step runs without a desktop to paste into.
+
+use std::sync::Mutex;
+
+use super::{FocusedApp, FrontmostAppProvider, TextInserter};
+use crate::app_context::RunningApp;
+
+/// Plain text on the mock clipboard
+static CLIPBOARD: Mutex<Option<String>> = Mutex::new(None);
+
+/// Text pasted or typed so far, oldest first
+static PASTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Held by tests that paste, since the clipboard and pasted text are shared
+#[cfg(test)]
+pub static PASTE_TURN: Mutex<()> = Mutex::new(());
+
+/// Records what would have reached the focused app instead of sending it
+pub struct Mock;
+
+/// Text pasted or typed since the last call, oldest first
+pub fn take_pasted_text() -> Vec<String> {
+    std::mem::take(&mut *PASTED.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+fn record_pasted(text: String) {
+    PASTED.lock().unwrap_or_else(|e| e.into_inner()).push(text);
+}
+
+impl TextInserter for Mock {
+    fn copy(&self, text: &str, _html: Option<&str>) -> Result<(), String> {
+        *CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner()) = Some(text.to_string());
+        Ok(())
+    }
+
+    fn read_clipboard(&self) -> Option<String> {
+        CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn clear_clipboard(&self) {
+        *CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    fn press_paste(&self) -> Result<(), String> {
+        let text = self
+            .read_clipboard()
+            .ok_or_else(|| "Nothing on the clipboard to paste".to_string())?;
+        record_pasted(text);
+        Ok(())
+    }
+
+    fn press_left(&self, _count: usize, _select: bool) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn type_text(&self, text: &str, _chars_per_sec: u32) -> Result<(), String> {
+        record_pasted(text.to_string());
+        Ok(())
+    }
+
+    fn copy_selection(&self) -> Option<String> {
+        None
+    }
+}
+
+/// No app is ever in front, so nothing is waited on or brought back
+impl FrontmostAppProvider for Mock {
+    fn focused_app(&self) -> Option<FocusedApp> {
+        None
+    }
+
+    fn running_app(&self) -> Option<RunningApp> {
+        None
+    }
+
+    fn activate(&self, _pid: i32) -> bool {
+        true
+    }
+
+    fn is_running(&self, _pid: i32) -> bool {
+        true
+    }
+
+    fn window_title(&self) -> Option<String> {
+        None
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pasting_records_the_clipboard_and_typing_records_the_text() {
+        let _turn = PASTE_TURN.lock().unwrap_or_else(|e| e.into_inner());
+        take_pasted_text();
+        Mock.copy("pasted", None).unwrap();
+        Mock.press_paste().unwrap();
+        Mock.type_text("typed", 100).unwrap();
+
+        assert_eq!(take_pasted_text(), vec!["pasted", "typed"]);
+        assert!(take_pasted_text().is_empty());
+        assert_eq!(Mock.read_clipboard().as_deref(), Some("pasted"));
+
+        Mock.clear_clipboard();
+        assert!(Mock.press_paste().is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use rdev::Key;
+use std::path::Path;
+use tauri::{AppHandle, WebviewWindow};
+
+use crate::app_context::RunningApp;
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+mod event_tap;
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(any(test, feature = "test-harness"))]
+pub mod mock;
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+mod unsupported;
+#[cfg(target_os = "windows")]
+mod windows;
+
+#[cfg(target_os = "linux")]
+use linux::Native;
+#[cfg(target_os = "macos")]
+use macos::Native;
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+use unsupported::Native;
+#[cfg(target_os = "windows")]
+use windows::Native;
+
+/// A key going down or up, as seen by the global key listener
+#[derive(Debug, Clone, Copy)]
+pub enum KeyEvent {
+    Press(Key),
+    Release(Key),
+}
+
+/// The app owning the focused window, Dicto included
+#[derive(Debug, Clone)]
+pub struct FocusedApp {
+    /// Process name, e.g. `Electron` or `firefox`
+    pub process_name: String,
+    /// e.g. `com.tinyspeck.slackmacgap`; only macOS has bundle ids
+    pub bundle_id: Option<String>,
+}
+
+/// Puts text into other apps through the clipboard and synthetic key presses
+pub trait TextInserter: Send + Sync {
+    /// Replace the clipboard contents with plain text, plus HTML when given
+    /// and the clipboard can hold both
+    fn copy(&self, text: &str, html: Option<&str>) -> Result<(), String>;
+
+    /// Plain text currently on the clipboard, if any
+    fn read_clipboard(&self) -> Option<String>;
+
+    /// Empty the clipboard
+    fn clear_clipboard(&self);
+
+    /// Press the paste shortcut, Cmd+V or Ctrl+V
+    fn press_paste(&self) -> Result<(), String>;
+
+    /// Press the left arrow `count` times, with Shift held to select when
+    /// `select` is set
+    fn press_left(&self, count: usize, select: bool) -> Result<(), String>;
+
+    /// Type text at `chars_per_sec`, for apps that ignore a programmatic paste.
+    /// Newlines are sent as Return. Blocks until everything is typed.
+    fn type_text(&self, text: &str, chars_per_sec: u32) -> Result<(), String>;
+
+    /// Text selected in the focused app, read without accessibility. Blocks
+    /// briefly; the clipboard is left empty where it had to be used.
+    fn copy_selection(&self) -> Option<String>;
+}
+
+/// Reads and switches the app the user is working in
+pub trait FrontmostAppProvider: Send + Sync {
+    /// The app owning the focused window, Dicto included
+    fn focused_app(&self) -> Option<FocusedApp>;
+
+    /// The frontmost app by process, ignoring Dicto itself. Cheap enough to
+    /// call at the start of every recording.
+    fn running_app(&self) -> Option<RunningApp>;
+
+    /// Bring an app back to the front so keystrokes land in it. Returns false
+    /// when it is no longer running or can't be brought back.
+    fn activate(&self, pid: i32) -> bool;
+
+    /// Whether the app is still running
+    fn is_running(&self, pid: i32) -> bool;
+
+    /// Title of the focused window, e.g. "Re: Q3 budget"
+    fn window_title(&self) -> Option<String>;
+
+    /// Text selected in the focused element, through accessibility
+    fn selected_text(&self) -> Option<String>;
+}
+
+/// The floating widget window, kept above other apps without taking focus
+/// from the app being dictated into
+pub trait OverlayWindow: Send + Sync {
+    /// Build and show the widget window with the `widget` label
+    fn build_widget(&self, app: &AppHandle) -> Result<WebviewWindow, String>;
+}
+
+/// Sees key presses while other apps are focused, for the global shortcuts
+pub trait GlobalKeyListener: Send + Sync {
+    /// Whether the app may observe global key events
+    fn has_permission(&self) -> bool;
+
+    /// Ask for the permission to observe global key events where there is a
+    /// prompt for it, returning the current state
+    fn request_permission(&self) -> bool;
+
+    /// Shown to the user when the listener dies, since missing permission is
+    /// the usual cause
+    fn permission_hint(&self) -> &'static str;
+
+    /// Listen on the current thread until the listener fails. Events are only
+    /// observed, so every key still reaches the focused app.
+    fn listen(&self, callback: Box<dyn Fn(KeyEvent) + Send + Sync>) -> Result<(), String>;
+}
+
+#[cfg(not(any(test, feature = "test-harness")))]
+pub fn text_inserter() -> &'static dyn TextInserter {
+    &Native
+}
+
+/// Test builds paste into the in-memory clipboard of `mock` instead
+#[cfg(any(test, feature = "test-harness"))]
+pub fn text_inserter() -> &'static dyn TextInserter {
+    &mock::Mock
+}
+
+#[cfg(not(any(test, feature = "test-harness")))]
+pub fn frontmost_apps() -> &'static dyn FrontmostAppProvider {
+    &Native
+}
+
+/// In test builds no app is ever in front
+#[cfg(any(test, feature = "test-harness"))]
+pub fn frontmost_apps() -> &'static dyn FrontmostAppProvider {
+    &mock::Mock
+}
+
+pub fn overlay_window() -> &'static dyn OverlayWindow {
+    &Native
+}
+
+pub fn key_listener() -> &'static dyn GlobalKeyListener {
+    &Native
+}
+
+/// Bytes free for the current user on the volume holding `path`
+#[cfg(unix)]
+// The widths of the `statvfs` fields differ between platforms
+#[allow(clippy::unnecessary_cast)]
+pub fn available_disk_space(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    // Blocks available to unprivileged users, counted in fragments
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+/// Bytes free for the current user on the volume holding `path`
+#[cfg(target_os = "windows")]
+pub fn available_disk_space(path: &Path) -> Option<u64> {
+    windows::available_disk_space(path)
+}
+
+/// Free space can't be read here, so callers skip their checks
+#[cfg(not(any(unix, target_os = "windows")))]
+pub fn available_disk_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Listen through rdev, where no dedicated listener is needed
+#[cfg(not(target_os = "macos"))]
+fn listen_rdev(callback: Box<dyn Fn(KeyEvent) + Send + Sync>) -> Result<(), String> {
+    use rdev::{listen, Event, EventType};
+
+    listen(move |event: Event| match event.event_type {
+        EventType::KeyPress(key) => callback(KeyEvent::Press(key)),
+        EventType::KeyRelease(key) => callback(KeyEvent::Release(key)),
+        _ => {}
+    })
+    .map_err(|e| format!("Global key listener failed: {:?}", e))
+}
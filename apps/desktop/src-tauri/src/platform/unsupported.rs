@@ -0,0 +1,92 @@
+use tauri::{AppHandle, WebviewWindow};
+
+use super::{
+    listen_rdev, FocusedApp, FrontmostAppProvider, GlobalKeyListener, KeyEvent, OverlayWindow,
+    TextInserter,
+};
+use crate::app_context::RunningApp;
+use crate::window::build_widget_window;
+
+const UNSUPPORTED: &str = "Inserting text is only available on macOS, Linux and Windows";
+
+/// Global keys and the widget only; there is no clipboard or app tracking
+pub struct Native;
+
+impl TextInserter for Native {
+    fn copy(&self, _text: &str, _html: Option<&str>) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    fn read_clipboard(&self) -> Option<String> {
+        None
+    }
+
+    fn clear_clipboard(&self) {}
+
+    fn press_paste(&self) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    fn press_left(&self, _count: usize, _select: bool) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    fn type_text(&self, _text: &str, _chars_per_sec: u32) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    fn copy_selection(&self) -> Option<String> {
+        None
+    }
+}
+
+impl FrontmostAppProvider for Native {
+    fn focused_app(&self) -> Option<FocusedApp> {
+        None
+    }
+
+    fn running_app(&self) -> Option<RunningApp> {
+        None
+    }
+
+    /// Nothing is ever pasted into, so the app is never waited on
+    fn activate(&self, _pid: i32) -> bool {
+        true
+    }
+
+    fn is_running(&self, _pid: i32) -> bool {
+        true
+    }
+
+    fn window_title(&self) -> Option<String> {
+        None
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        None
+    }
+}
+
+impl OverlayWindow for Native {
+    fn build_widget(&self, app: &AppHandle) -> Result<WebviewWindow, String> {
+        build_widget_window(app).map_err(|e| e.to_string())
+    }
+}
+
+impl GlobalKeyListener for Native {
+    fn has_permission(&self) -> bool {
+        true
+    }
+
+    fn request_permission(&self) -> bool {
+        true
+    }
+
+    fn permission_hint(&self) -> &'static str {
+        "Make sure Dicto is allowed to read global keyboard input"
+    }
+
+    fn listen(&self, callback: Box<dyn Fn(KeyEvent) + Send + Sync>) -> Result<(), String> {
+        listen_rdev(callback)
+    }
+}
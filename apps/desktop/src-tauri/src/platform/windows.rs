@@ -0,0 +1,593 @@
+// Desktop integration on Windows through Win32: the clipboard, key presses
+// with SendInput and the foreground window. UWP apps run inside a frame window
+// owned by ApplicationFrameHost, so they are looked up through its child
+// windows.
+
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, WebviewWindow};
+
+use super::{
+    listen_rdev, FocusedApp, FrontmostAppProvider, GlobalKeyListener, KeyEvent, OverlayWindow,
+    TextInserter,
+};
+use crate::app_context::RunningApp;
+use crate::window::build_widget_window;
+use windows_sys::Win32::Foundation::{CloseHandle, BOOL, HWND, LPARAM, STILL_ACTIVE};
+use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+use windows_sys::Win32::System::DataExchange::{
+    CloseClipboard, EmptyClipboard, GetClipboardData, OpenClipboard, RegisterClipboardFormatW,
+    SetClipboardData,
+};
+use windows_sys::Win32::System::Memory::{
+    GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, GMEM_MOVEABLE,
+};
+use windows_sys::Win32::System::Ole::CF_UNICODETEXT;
+use windows_sys::Win32::System::Threading::{
+    GetExitCodeProcess, OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+    PROCESS_QUERY_LIMITED_INFORMATION,
+};
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, KEYEVENTF_UNICODE,
+    VK_CONTROL, VK_LEFT, VK_RETURN, VK_SHIFT,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    EnumChildWindows, EnumWindows, GetForegroundWindow, GetWindowLongPtrW, GetWindowTextW,
+    GetWindowThreadProcessId, IsIconic, IsWindowVisible, SetForegroundWindow, SetWindowLongPtrW,
+    ShowWindow, GWL_EXSTYLE, SW_RESTORE, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
+};
+
+/// Virtual key codes of letters are their ASCII capitals
+const VK_C: u16 = b'C' as u16;
+const VK_V: u16 = b'V' as u16;
+
+/// Process that owns the frame windows of UWP apps
+const UWP_FRAME_HOST: &str = "ApplicationFrameHost";
+
+/// Null-terminated UTF-16, for Win32 string parameters
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+// ============================================================================
+// Clipboard
+// ============================================================================
+
+/// The clipboard, open until dropped
+struct Clipboard;
+
+impl Clipboard {
+    /// Another app may hold the clipboard open for a moment, so opening is
+    /// retried briefly
+    fn open() -> Result<Self, String> {
+        for _ in 0..10 {
+            if unsafe { OpenClipboard(std::ptr::null_mut()) } != 0 {
+                return Ok(Clipboard);
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        Err("Failed to open the clipboard".to_string())
+    }
+}
+
+impl Drop for Clipboard {
+    fn drop(&mut self) {
+        unsafe {
+            CloseClipboard();
+        }
+    }
+}
+
+/// Copy `bytes` into global memory and hand it to the open clipboard, which
+/// owns it from then on
+unsafe fn set_data(format: u32, bytes: &[u8]) -> Result<(), String> {
+    let memory = GlobalAlloc(GMEM_MOVEABLE, bytes.len());
+    if memory.is_null() {
+        return Err("Failed to allocate clipboard memory".to_string());
+    }
+
+    let target = GlobalLock(memory) as *mut u8;
+    if target.is_null() {
+        GlobalFree(memory);
+        return Err("Failed to lock clipboard memory".to_string());
+    }
+    std::ptr::copy_nonoverlapping(bytes.as_ptr(), target, bytes.len());
+    GlobalUnlock(memory);
+
+    if SetClipboardData(format, memory).is_null() {
+        GlobalFree(memory);
+        return Err("Failed to set clipboard data".to_string());
+    }
+    Ok(())
+}
+
+/// The `HTML Format` payload: a header with the byte offsets of the document
+/// and of the fragment inside it, then the document
+fn cf_html(html: &str) -> String {
+    const PREFIX: &str = "<html><body><!--StartFragment-->";
+    const SUFFIX: &str = "<!--EndFragment--></body></html>";
+
+    let header = |start_html: usize,
+                  end_html: usize,
+                  start_fragment: usize,
+                  end_fragment: usize| {
+        format!(
+            "Version:0.9\r\nStartHTML:{:010}\r\nEndHTML:{:010}\r\nStartFragment:{:010}\r\nEndFragment:{:010}\r\n",
+            start_html, end_html, start_fragment, end_fragment
+        )
+    };
+
+    let start_html = header(0, 0, 0, 0).len();
+    let start_fragment = start_html + PREFIX.len();
+    let end_fragment = start_fragment + html.len();
+    let end_html = end_fragment + SUFFIX.len();
+
+    format!(
+        "{}{}{}{}",
+        header(start_html, end_html, start_fragment, end_fragment),
+        PREFIX,
+        html,
+        SUFFIX
+    )
+}
+
+/// Replace the clipboard contents with plain text, and HTML when given
+fn set_clipboard(text: &str, html: Option<&str>) -> Result<(), String> {
+    let _clipboard = Clipboard::open()?;
+
+    unsafe {
+        EmptyClipboard();
+
+        let text: Vec<u8> = wide(text)
+            .into_iter()
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect();
+        set_data(CF_UNICODETEXT as u32, &text)?;
+
+        if let Some(html) = html {
+            let format = RegisterClipboardFormatW(wide("HTML Format").as_ptr());
+            if format == 0 {
+                return Err("Failed to register the HTML clipboard format".to_string());
+            }
+            let mut payload = cf_html(html).into_bytes();
+            payload.push(0);
+            set_data(format, &payload)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Plain text on the clipboard, if any
+fn get_clipboard() -> Option<String> {
+    let _clipboard = Clipboard::open().ok()?;
+
+    unsafe {
+        let memory = GetClipboardData(CF_UNICODETEXT as u32);
+        if memory.is_null() {
+            return None;
+        }
+        let data = GlobalLock(memory) as *const u16;
+        if data.is_null() {
+            return None;
+        }
+
+        let len = (0..).take_while(|&i| *data.add(i) != 0).count();
+        let text = String::from_utf16_lossy(std::slice::from_raw_parts(data, len));
+        GlobalUnlock(memory);
+        Some(text)
+    }
+}
+
+/// Empty the clipboard
+fn clear_clipboard() {
+    match Clipboard::open() {
+        Ok(_clipboard) => unsafe {
+            EmptyClipboard();
+        },
+        Err(e) => tracing::warn!("Failed to clear the clipboard: {}", e),
+    }
+}
+
+// ============================================================================
+// Keyboard
+// ============================================================================
+
+fn key_input(virtual_key: u16, scan: u16, flags: u32) -> INPUT {
+    INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: virtual_key,
+                wScan: scan,
+                dwFlags: flags,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    }
+}
+
+/// Key events pressing `keys` down in order and releasing them in reverse
+fn chord(keys: &[u16]) -> Vec<INPUT> {
+    keys.iter()
+        .map(|key| key_input(*key, 0, 0))
+        .chain(
+            keys.iter()
+                .rev()
+                .map(|key| key_input(*key, 0, KEYEVENTF_KEYUP)),
+        )
+        .collect()
+}
+
+fn send(inputs: &[INPUT]) -> Result<(), String> {
+    let sent = unsafe {
+        SendInput(
+            inputs.len() as u32,
+            inputs.as_ptr(),
+            std::mem::size_of::<INPUT>() as i32,
+        )
+    };
+    // Windows drops input into apps running as administrator
+    if sent as usize != inputs.len() {
+        return Err(
+            "Key presses were blocked; the focused app may run as administrator".to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Press Ctrl+V
+fn press_paste() -> Result<(), String> {
+    send(&chord(&[VK_CONTROL, VK_V]))
+}
+
+/// Press Ctrl+C
+fn press_copy() -> Result<(), String> {
+    send(&chord(&[VK_CONTROL, VK_C]))
+}
+
+/// Press the left arrow `count` times, with Shift held to select when `select`
+/// is set
+fn press_left(count: usize, select: bool) -> Result<(), String> {
+    let keys: &[u16] = if select {
+        &[VK_SHIFT, VK_LEFT]
+    } else {
+        &[VK_LEFT]
+    };
+    for _ in 0..count {
+        send(&chord(keys))?;
+        thread::sleep(Duration::from_millis(5));
+    }
+    Ok(())
+}
+
+/// Type text at `chars_per_sec` as Unicode key events, so the keyboard layout
+/// doesn't matter. Newlines are sent as Return.
+fn type_text(text: &str, chars_per_sec: u32) -> Result<(), String> {
+    let delay = Duration::from_secs_f64(1.0 / chars_per_sec.max(1) as f64);
+
+    for c in text.chars().filter(|c| *c != '\r') {
+        let inputs = if c == '\n' {
+            chord(&[VK_RETURN])
+        } else {
+            // Characters outside the BMP go as their two UTF-16 halves
+            let mut units = [0; 2];
+            c.encode_utf16(&mut units)
+                .iter()
+                .flat_map(|unit| {
+                    [
+                        key_input(0, *unit, KEYEVENTF_UNICODE),
+                        key_input(0, *unit, KEYEVENTF_UNICODE | KEYEVENTF_KEYUP),
+                    ]
+                })
+                .collect()
+        };
+        send(&inputs)?;
+        thread::sleep(delay);
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Windows
+// ============================================================================
+
+fn window_pid(hwnd: HWND) -> u32 {
+    let mut pid = 0;
+    unsafe { GetWindowThreadProcessId(hwnd, &mut pid) };
+    pid
+}
+
+/// The process of a UWP app, from a child of its frame window that a process
+/// other than the frame host owns
+fn uwp_app_pid(frame: HWND, host_pid: u32) -> Option<u32> {
+    unsafe extern "system" fn find_app(child: HWND, search: LPARAM) -> BOOL {
+        let (host_pid, app_pid) = &mut *(search as *mut (u32, u32));
+        let pid = window_pid(child);
+        if pid != 0 && pid != *host_pid {
+            *app_pid = pid;
+            return 0;
+        }
+        1
+    }
+
+    let mut search = (host_pid, 0);
+    unsafe {
+        EnumChildWindows(
+            frame,
+            Some(find_app),
+            &mut search as *mut (u32, u32) as LPARAM,
+        );
+    }
+    (search.1 != 0).then_some(search.1)
+}
+
+/// Process that a top-level window belongs to, seeing through UWP frames
+fn app_pid(hwnd: HWND) -> Option<u32> {
+    let pid = window_pid(hwnd);
+    if pid == 0 {
+        return None;
+    }
+    if process_name(pid as i32).as_deref() == Some(UWP_FRAME_HOST) {
+        return uwp_app_pid(hwnd, pid).or(Some(pid));
+    }
+    Some(pid)
+}
+
+/// Process id of the foreground window's app
+fn foreground_window_pid() -> Option<i32> {
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_null() {
+        return None;
+    }
+    app_pid(hwnd).map(|pid| pid as i32)
+}
+
+/// Title of the foreground window
+fn foreground_window_title() -> Option<String> {
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_null() {
+            return None;
+        }
+        let mut buffer = [0u16; 512];
+        let len = GetWindowTextW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32);
+        (len > 0).then(|| String::from_utf16_lossy(&buffer[..len as usize]))
+    }
+}
+
+/// Executable name of a process without `.exe`, e.g. `Code`
+fn process_name(pid: i32) -> Option<String> {
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid as u32);
+        if process.is_null() {
+            return None;
+        }
+        let mut buffer = [0u16; 1024];
+        let mut len = buffer.len() as u32;
+        let ok =
+            QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, buffer.as_mut_ptr(), &mut len);
+        CloseHandle(process);
+        if ok == 0 {
+            return None;
+        }
+
+        let path = String::from_utf16_lossy(&buffer[..len as usize]);
+        Path::new(&path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+    }
+}
+
+/// Whether a process is still running
+fn is_running(pid: i32) -> bool {
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid as u32);
+        if process.is_null() {
+            return false;
+        }
+        let mut exit_code = 0;
+        let ok = GetExitCodeProcess(process, &mut exit_code);
+        CloseHandle(process);
+        ok != 0 && exit_code == STILL_ACTIVE as u32
+    }
+}
+
+/// First visible top-level window of an app, including the frame of a UWP app
+fn app_window(pid: i32) -> Option<HWND> {
+    unsafe extern "system" fn find_window(hwnd: HWND, search: LPARAM) -> BOOL {
+        let (pid, found) = &mut *(search as *mut (u32, HWND));
+        if IsWindowVisible(hwnd) != 0 && app_pid(hwnd) == Some(*pid) {
+            *found = hwnd;
+            return 0;
+        }
+        1
+    }
+
+    let mut search: (u32, HWND) = (pid as u32, std::ptr::null_mut());
+    unsafe {
+        EnumWindows(Some(find_window), &mut search as *mut (u32, HWND) as LPARAM);
+    }
+    (!search.1.is_null()).then_some(search.1)
+}
+
+/// Bring an app's window to the front, restoring it when minimized. Returns
+/// false when it has no window left.
+fn activate_window(pid: i32) -> bool {
+    let Some(hwnd) = app_window(pid) else {
+        return false;
+    };
+    unsafe {
+        if IsIconic(hwnd) != 0 {
+            ShowWindow(hwnd, SW_RESTORE);
+        }
+        SetForegroundWindow(hwnd) != 0
+    }
+}
+
+/// Keep a window from taking focus when clicked and out of the taskbar and
+/// Alt+Tab, so the widget can be used without pulling the keyboard away from
+/// the app being dictated into, UWP and full-screen apps included
+fn make_overlay(hwnd: HWND) {
+    unsafe {
+        let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        SetWindowLongPtrW(
+            hwnd,
+            GWL_EXSTYLE,
+            style | (WS_EX_NOACTIVATE | WS_EX_TOOLWINDOW) as isize,
+        );
+    }
+}
+
+/// Bytes free for the current user on the volume holding `path`
+pub fn available_disk_space(path: &Path) -> Option<u64> {
+    let path: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut available = 0;
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            path.as_ptr(),
+            &mut available,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    (ok != 0).then_some(available)
+}
+
+// ============================================================================
+// Platform
+// ============================================================================
+
+/// Win32
+pub struct Native;
+
+impl TextInserter for Native {
+    fn copy(&self, text: &str, html: Option<&str>) -> Result<(), String> {
+        set_clipboard(text, html)
+    }
+
+    fn read_clipboard(&self) -> Option<String> {
+        get_clipboard()
+    }
+
+    fn clear_clipboard(&self) {
+        clear_clipboard();
+    }
+
+    fn press_paste(&self) -> Result<(), String> {
+        press_paste()
+    }
+
+    fn press_left(&self, count: usize, select: bool) -> Result<(), String> {
+        press_left(count, select)
+    }
+
+    fn type_text(&self, text: &str, chars_per_sec: u32) -> Result<(), String> {
+        type_text(text, chars_per_sec)
+    }
+
+    /// Press Ctrl+C, the same way as on macOS
+    fn copy_selection(&self) -> Option<String> {
+        // Clear first so stale contents aren't mistaken for the selection
+        clear_clipboard();
+
+        if let Err(e) = press_copy() {
+            tracing::warn!("Failed to press copy: {}", e);
+            return None;
+        }
+
+        // Give the app time to write the clipboard
+        thread::sleep(Duration::from_millis(150));
+
+        let selection = get_clipboard();
+        clear_clipboard();
+        selection
+    }
+}
+
+impl FrontmostAppProvider for Native {
+    fn focused_app(&self) -> Option<FocusedApp> {
+        let name = foreground_window_pid().and_then(process_name)?;
+        Some(FocusedApp {
+            process_name: name,
+            bundle_id: None,
+        })
+    }
+
+    /// UWP apps are reported as themselves rather than their frame host
+    fn running_app(&self) -> Option<RunningApp> {
+        let pid = foreground_window_pid()?;
+        if pid as u32 == std::process::id() {
+            return None;
+        }
+        let name = process_name(pid).unwrap_or_default();
+
+        Some(RunningApp { pid, name })
+    }
+
+    fn activate(&self, pid: i32) -> bool {
+        /// Give the app time to take key focus
+        const ACTIVATE_DELAY_MS: u64 = 150;
+
+        if self.running_app().is_some_and(|app| app.pid == pid) {
+            return true;
+        }
+        if !is_running(pid) || !activate_window(pid) {
+            return false;
+        }
+
+        thread::sleep(Duration::from_millis(ACTIVATE_DELAY_MS));
+        true
+    }
+
+    fn is_running(&self, pid: i32) -> bool {
+        is_running(pid)
+    }
+
+    fn window_title(&self) -> Option<String> {
+        foreground_window_title()
+    }
+
+    /// Not read through UI Automation; `copy_selection` is used instead
+    fn selected_text(&self) -> Option<String> {
+        None
+    }
+}
+
+impl OverlayWindow for Native {
+    /// Made an overlay that never takes focus, so clicking it leaves the
+    /// keyboard with the app being dictated into
+    fn build_widget(&self, app: &AppHandle) -> Result<WebviewWindow, String> {
+        let window = build_widget_window(app).map_err(|e| e.to_string())?;
+        if let Ok(hwnd) = window.hwnd() {
+            make_overlay(hwnd.0 as _);
+        }
+        Ok(window)
+    }
+}
+
+impl GlobalKeyListener for Native {
+    fn has_permission(&self) -> bool {
+        true
+    }
+
+    fn request_permission(&self) -> bool {
+        true
+    }
+
+    fn permission_hint(&self) -> &'static str {
+        "Make sure Dicto is allowed to read global keyboard input"
+    }
+
+    fn listen(&self, callback: Box<dyn Fn(KeyEvent) + Send + Sync>) -> Result<(), String> {
+        listen_rdev(callback)
+    }
+}
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_specta::Event;
+use tracing::info;
+
+use crate::commands::settings::{keys, read_bool_setting, read_parsed_setting};
+use crate::jobs;
+
+/// How often the battery is checked
+const CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Battery percentage at or below which low power mode turns on by itself
+const DEFAULT_BATTERY_THRESHOLD: u8 = 20;
+
+/// Whether low power mode was active at the last check, to emit only on changes
+static LOW_POWER_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Charge and power source of the internal battery
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BatteryStatus {
+    /// Running on battery rather than a power adapter
+    pub on_battery: bool,
+    /// Charge from 0 to 100
+    pub percent: u8,
+}
+
+/// Low power status, also returned by `power_get_status`
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PowerStatus {
+    /// Transcription uses one worker, fewer threads, longer chunks and the
+    /// smallest downloaded model
+    pub low_power: bool,
+    /// Low power mode was turned on by a low battery rather than the setting
+    pub automatic: bool,
+    /// `None` on machines without a battery
+    pub battery: Option<BatteryStatus>,
+}
+
+/// Payload of `low-power-changed`, emitted when low power mode turns on or off
+#[derive(Debug, Clone, Serialize, Type, tauri_specta::Event)]
+pub struct LowPowerChanged(pub PowerStatus);
+
+/// Current power status, combining the low power setting with the battery level
+pub fn power_status() -> PowerStatus {
+    let battery = battery_status();
+
+    let manual = read_bool_setting(keys::LOW_POWER_MODE, false);
+    let battery_low = read_bool_setting(keys::BATTERY_LOW_POWER, true)
+        && battery.as_ref().is_some_and(|b| {
+            let threshold =
+                read_parsed_setting(keys::BATTERY_LOW_POWER_THRESHOLD, DEFAULT_BATTERY_THRESHOLD);
+            b.on_battery && b.percent <= threshold
+        });
+
+    PowerStatus {
+        low_power: manual || battery_low,
+        automatic: !manual && battery_low,
+        battery,
+    }
+}
+
+/// Whether transcription should run in low power mode right now
+pub fn low_power_active() -> bool {
+    power_status().low_power
+}
+
+/// Register the `battery_monitor` job, which checks the battery and emits
+/// `low-power-changed` when low power mode turns on or off
+pub fn register_battery_job(app: AppHandle) {
+    jobs::register(
+        "battery_monitor",
+        Duration::from_secs(CHECK_INTERVAL_SECS),
+        Duration::ZERO,
+        move || {
+            let app = app.clone();
+            async move {
+                let status = power_status();
+                if LOW_POWER_ACTIVE.swap(status.low_power, Ordering::SeqCst) != status.low_power {
+                    info!(
+                        "🔋 Low power mode {}",
+                        if status.low_power { "on" } else { "off" }
+                    );
+                    LowPowerChanged(status)
+                        .emit(&app)
+                        .map_err(|e| format!("Failed to emit low-power-changed: {}", e))?;
+                }
+                Ok(())
+            }
+        },
+    );
+}
+
+/// Read the internal battery through the IOKit power source API
+#[cfg(target_os = "macos")]
+pub fn battery_status() -> Option<BatteryStatus> {
+    use core_foundation::array::{CFArray, CFArrayRef};
+    use core_foundation::base::{CFType, CFTypeRef, TCFType};
+    use core_foundation::dictionary::{CFDictionary, CFDictionaryRef};
+    use core_foundation::number::CFNumber;
+    use core_foundation::string::CFString;
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOPSCopyPowerSourcesInfo() -> CFTypeRef;
+        fn IOPSCopyPowerSourcesList(blob: CFTypeRef) -> CFArrayRef;
+        fn IOPSGetPowerSourceDescription(blob: CFTypeRef, source: CFTypeRef) -> CFDictionaryRef;
+    }
+
+    unsafe {
+        let info = IOPSCopyPowerSourcesInfo();
+        if info.is_null() {
+            return None;
+        }
+        let info = CFType::wrap_under_create_rule(info);
+
+        let list = IOPSCopyPowerSourcesList(info.as_CFTypeRef());
+        if list.is_null() {
+            return None;
+        }
+        let sources: CFArray<CFType> = CFArray::wrap_under_create_rule(list);
+
+        for source in sources.iter() {
+            // The description is owned by the info blob
+            let description =
+                IOPSGetPowerSourceDescription(info.as_CFTypeRef(), source.as_CFTypeRef());
+            if description.is_null() {
+                continue;
+            }
+            let description: CFDictionary<CFString, CFType> =
+                CFDictionary::wrap_under_get_rule(description);
+
+            let number = |key: &str| {
+                description
+                    .find(CFString::new(key))
+                    .and_then(|value| value.downcast::<CFNumber>())
+                    .and_then(|value| value.to_i64())
+            };
+            let (Some(current), Some(max)) = (number("Current Capacity"), number("Max Capacity"))
+            else {
+                continue;
+            };
+
+            let on_battery = description
+                .find(CFString::new("Power Source State"))
+                .and_then(|value| value.downcast::<CFString>())
+                .is_some_and(|state| state.to_string() == "Battery Power");
+
+            return Some(BatteryStatus {
+                on_battery,
+                percent: (current * 100 / max.max(1)).clamp(0, 100) as u8,
+            });
+        }
+
+        None
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn battery_status() -> Option<BatteryStatus> {
+    None
+}
+
+/// Whether transcription is in low power mode, and the battery level
+#[tauri::command]
+#[specta::specta]
+pub fn power_get_status() -> PowerStatus {
+    power_status()
+}
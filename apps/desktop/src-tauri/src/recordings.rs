@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{error, info};
+
+use crate::db::pool::get_connection;
+use crate::jobs;
+use crate::stt::cloud::encode_wav;
+
+/// Folder under app data holding the audio of saved transcriptions, one
+/// `<transcription id>.wav` each
+const RECORDINGS_DIR: &str = "recordings";
+
+/// Audio of transcriptions that were never rated or corrected is deleted once
+/// it is this old; rated ones keep theirs for the feedback export
+const UNRATED_MAX_AGE_SECS: u64 = 24 * 60 * 60;
+
+/// How often unrated recordings are looked for
+const CLEANUP_INTERVAL_SECS: u64 = 60 * 60;
+
+/// Wait after launch before the first cleanup
+const CLEANUP_STARTUP_DELAY_SECS: u64 = 5 * 60;
+
+/// Keep the audio of a saved transcription as 16kHz mono 16-bit WAV, so it can be
+/// exported with the user's correction if the transcription gets rated
+pub fn save(app: &AppHandle, transcription_id: &str, samples: &[f32]) -> Result<(), String> {
+    let dir = recordings_dir(app)?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create recordings folder: {}", e))?;
+    std::fs::write(file_path(&dir, transcription_id), encode_wav(samples))
+        .map_err(|e| format!("Failed to save recording: {}", e))
+}
+
+/// The kept audio of a transcription, if there is any
+pub fn path(app: &AppHandle, transcription_id: &str) -> Option<PathBuf> {
+    let path = file_path(&recordings_dir(app).ok()?, transcription_id);
+    path.exists().then_some(path)
+}
+
+/// Delete the kept audio of a transcription; a missing file is not an error
+pub fn remove(app: &AppHandle, transcription_id: &str) {
+    if let Some(path) = path(app, transcription_id) {
+        if let Err(e) = std::fs::remove_file(&path) {
+            error!("Failed to remove recording {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Register the `recording_cleanup` job, which deletes the audio of transcriptions
+/// that are gone or were not rated in time
+pub fn register_cleanup_job(app: AppHandle) {
+    jobs::register(
+        "recording_cleanup",
+        Duration::from_secs(CLEANUP_INTERVAL_SECS),
+        Duration::from_secs(CLEANUP_STARTUP_DELAY_SECS),
+        move || {
+            let app = app.clone();
+            async move { remove_unrated(&app) }
+        },
+    );
+}
+
+fn recordings_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(RECORDINGS_DIR))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+fn file_path(dir: &Path, transcription_id: &str) -> PathBuf {
+    dir.join(format!("{}.wav", transcription_id))
+}
+
+/// Delete recordings whose transcription was deleted, and those older than
+/// `UNRATED_MAX_AGE_SECS` whose transcription has no feedback
+fn remove_unrated(app: &AppHandle) -> Result<(), String> {
+    let entries = match std::fs::read_dir(recordings_dir(app)?) {
+        Ok(entries) => entries,
+        // Nothing was ever recorded
+        Err(_) => return Ok(()),
+    };
+    let conn = get_connection()?;
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        let (exists, rated): (bool, bool) = conn
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM transcriptions WHERE id = ?1),
+                        EXISTS(SELECT 1 FROM transcription_feedback WHERE transcription_id = ?1)",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| format!("Failed to look up recording {}: {}", id, e))?;
+        let age = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok());
+        let expired = age.map_or(true, |age| age.as_secs() >= UNRATED_MAX_AGE_SECS);
+
+        if !exists || (!rated && expired) {
+            match std::fs::remove_file(&path) {
+                Ok(()) => removed += 1,
+                Err(e) => error!("Failed to remove recording {}: {}", path.display(), e),
+            }
+        }
+    }
+
+    info!("Removed {} unrated recordings", removed);
+
+    Ok(())
+}
@@ -0,0 +1,304 @@
+use crate::commands::settings::{keys as setting_keys, read_bool_setting, read_json_setting};
+
+/// Kind of sensitive span, used as the placeholder label
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PiiKind {
+    Email,
+    Phone,
+    Card,
+    Name,
+}
+
+impl PiiKind {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Email => "EMAIL",
+            Self::Phone => "PHONE",
+            Self::Card => "CARD",
+            Self::Name => "NAME",
+        }
+    }
+}
+
+/// Text with sensitive spans replaced by placeholders like `[EMAIL_1]`
+#[derive(Debug, Clone)]
+pub struct Redacted {
+    pub text: String,
+    /// Placeholder and the original text it stands for
+    replacements: Vec<(String, String)>,
+}
+
+impl Redacted {
+    /// Put the original spans back into text returned by the formatter
+    pub fn restore(&self, text: &str) -> String {
+        let mut restored = text.to_string();
+        for (placeholder, original) in &self.replacements {
+            restored = restored.replace(placeholder, original);
+        }
+        restored
+    }
+}
+
+/// A whitespace-separated word and its byte offset in the source text
+struct Token<'a> {
+    start: usize,
+    text: &'a str,
+}
+
+/// Redact the text if the user enabled PII redaction, otherwise pass it through
+pub fn redact_if_enabled(text: &str) -> Redacted {
+    if !read_bool_setting(setting_keys::REDACT_PII, false) {
+        return Redacted {
+            text: text.to_string(),
+            replacements: Vec::new(),
+        };
+    }
+
+    let names: Vec<String> = read_json_setting(setting_keys::REDACT_NAMES, Vec::new());
+    redact(text, &names)
+}
+
+/// Origin and path of a web URL, for sending as context: the query, fragment
+/// and credentials are dropped, and path segments are redacted like text when
+/// the user enabled PII redaction. `None` for anything but an http(s) URL.
+pub fn redact_url_if_enabled(url: &str) -> Option<String> {
+    let url = reqwest::Url::parse(url).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+
+    let path = if read_bool_setting(setting_keys::REDACT_PII, false) {
+        let names: Vec<String> = read_json_setting(setting_keys::REDACT_NAMES, Vec::new());
+        url.path()
+            .split('/')
+            .map(|segment| redact(segment, &names).text)
+            .collect::<Vec<_>>()
+            .join("/")
+    } else {
+        url.path().to_string()
+    };
+
+    Some(format!("{}{}", url.origin().ascii_serialization(), path))
+}
+
+/// Mask emails, phone numbers, card numbers and the given names
+pub fn redact(text: &str, names: &[String]) -> Redacted {
+    let tokens = tokenize(text);
+    let mut spans: Vec<(usize, usize, PiiKind)> = Vec::new();
+    let mut covered = vec![false; tokens.len()];
+
+    // Names first, so a listed name is never split by the other passes
+    for name in names {
+        let name_words: Vec<String> = name.split_whitespace().map(normalize).collect();
+        if name_words.is_empty() {
+            continue;
+        }
+
+        let mut idx = 0;
+        while idx + name_words.len() <= tokens.len() {
+            let range = idx..idx + name_words.len();
+            let matches = range.clone().all(|i| !covered[i])
+                && range
+                    .clone()
+                    .zip(&name_words)
+                    .all(|(i, word)| normalize(tokens[i].text) == *word);
+            if matches {
+                let first = &tokens[idx];
+                let last = &tokens[idx + name_words.len() - 1];
+                let (start, _) = core_bounds(first, |c| c.is_alphanumeric());
+                let (_, end) = core_bounds(last, |c| c.is_alphanumeric());
+                spans.push((start, end, PiiKind::Name));
+                range.for_each(|i| covered[i] = true);
+                idx += name_words.len();
+            } else {
+                idx += 1;
+            }
+        }
+    }
+
+    let mut idx = 0;
+    while idx < tokens.len() {
+        if covered[idx] {
+            idx += 1;
+            continue;
+        }
+
+        let (start, end) = core_bounds(&tokens[idx], |c| c.is_alphanumeric());
+        if is_email(&text[start..end]) {
+            spans.push((start, end, PiiKind::Email));
+            idx += 1;
+            continue;
+        }
+
+        // Phone and card numbers are often spoken in groups ("4111 1111 1111 1111")
+        let mut run_end = idx;
+        while run_end < tokens.len() && !covered[run_end] && is_number_part(&tokens[run_end]) {
+            run_end += 1;
+        }
+        if run_end > idx {
+            let (start, _) = core_bounds(&tokens[idx], |c| {
+                c.is_alphanumeric() || c == '+' || c == '('
+            });
+            let (_, end) = core_bounds(&tokens[run_end - 1], |c| c.is_alphanumeric());
+            let digits: String = text[start..end]
+                .chars()
+                .filter(|c| c.is_ascii_digit())
+                .collect();
+
+            if (13..=19).contains(&digits.len()) && passes_luhn(&digits) {
+                spans.push((start, end, PiiKind::Card));
+            } else if (7..=15).contains(&digits.len()) {
+                spans.push((start, end, PiiKind::Phone));
+            }
+            idx = run_end;
+            continue;
+        }
+
+        idx += 1;
+    }
+
+    spans.sort_by_key(|(start, _, _)| *start);
+    apply_spans(text, &spans)
+}
+
+/// Replace spans with numbered placeholders, reusing a placeholder for repeated values
+fn apply_spans(text: &str, spans: &[(usize, usize, PiiKind)]) -> Redacted {
+    let mut output = String::with_capacity(text.len());
+    let mut replacements: Vec<(String, String)> = Vec::new();
+    let mut counts: Vec<(PiiKind, usize)> = Vec::new();
+    let mut cursor = 0;
+
+    for &(start, end, kind) in spans {
+        if start < cursor {
+            continue;
+        }
+        output.push_str(&text[cursor..start]);
+
+        let original = &text[start..end];
+        let placeholder = match replacements
+            .iter()
+            .find(|(p, o)| o == original && p.starts_with(&format!("[{}_", kind.label())))
+        {
+            Some((placeholder, _)) => placeholder.clone(),
+            None => {
+                let count = match counts.iter_mut().find(|(k, _)| *k == kind) {
+                    Some((_, count)) => {
+                        *count += 1;
+                        *count
+                    }
+                    None => {
+                        counts.push((kind, 1));
+                        1
+                    }
+                };
+                let placeholder = format!("[{}_{}]", kind.label(), count);
+                replacements.push((placeholder.clone(), original.to_string()));
+                placeholder
+            }
+        };
+
+        output.push_str(&placeholder);
+        cursor = end;
+    }
+    output.push_str(&text[cursor..]);
+
+    Redacted {
+        text: output,
+        replacements,
+    }
+}
+
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                tokens.push(Token {
+                    start: s,
+                    text: &text[s..i],
+                });
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push(Token {
+            start: s,
+            text: &text[s..],
+        });
+    }
+
+    tokens
+}
+
+/// Byte range of the token once surrounding punctuation is trimmed.
+/// `keep_start` decides which characters may open the core.
+fn core_bounds(token: &Token, keep_start: impl Fn(char) -> bool) -> (usize, usize) {
+    let leading = token
+        .text
+        .char_indices()
+        .find(|(_, c)| keep_start(*c))
+        .map(|(i, _)| i)
+        .unwrap_or(token.text.len());
+    let trailing = token
+        .text
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_alphanumeric())
+        .map(|(i, c)| i + c.len_utf8())
+        .unwrap_or(leading);
+
+    (token.start + leading, token.start + trailing.max(leading))
+}
+
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+fn is_email(word: &str) -> bool {
+    let (local, domain) = match word.split_once('@') {
+        Some(parts) => parts,
+        None => return false,
+    };
+
+    !local.is_empty()
+        && local
+            .chars()
+            .all(|c| c.is_alphanumeric() || "._%+-".contains(c))
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && domain
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '.' || c == '-')
+}
+
+/// A token made only of digits and phone/card separators, with at least one digit
+fn is_number_part(token: &Token) -> bool {
+    let word = token.text.trim_end_matches(|c: char| ".,;:!?".contains(c));
+    word.chars().any(|c| c.is_ascii_digit())
+        && word
+            .chars()
+            .all(|c| c.is_ascii_digit() || "+()-.".contains(c))
+}
+
+/// Luhn checksum used by payment card numbers
+fn passes_luhn(digits: &str) -> bool {
+    let mut sum = 0;
+    for (i, c) in digits.chars().rev().enumerate() {
+        let mut d = c.to_digit(10).unwrap_or(0);
+        if i % 2 == 1 {
+            d *= 2;
+            if d > 9 {
+                d -= 9;
+            }
+        }
+        sum += d;
+    }
+    sum % 10 == 0
+}
@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use crate::app_context;
+use crate::llm::{self, ChatMessage, Provider};
+use crate::output;
+use crate::redaction::redact_if_enabled;
+
+/// Give up on a rewrite after this long; the selection is left untouched
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Text selected in the frontmost app. Read through accessibility, falling back
+/// to copying it for apps that don't expose their selection.
+pub fn capture_selection() -> Option<String> {
+    app_context::selected_text()
+        .or_else(output::copy_selection)
+        .filter(|text| !text.trim().is_empty())
+}
+
+/// Rewrite the selected text following a spoken instruction, e.g. "make this
+/// more formal". Returns the replacement for the selection.
+pub async fn rewrite(selection: &str, instruction: &str) -> Result<String, String> {
+    let provider = Provider::from_setting();
+    let api_key = provider.api_key()?;
+
+    // Mask sensitive spans locally; they are restored in the rewritten text
+    let redacted = redact_if_enabled(selection);
+    let user = format!(
+        "Instruction: {}\n\nText:\n{}",
+        instruction.trim(),
+        redacted.text
+    );
+    let messages = vec![
+        ChatMessage {
+            role: "system",
+            content: "You edit text for the user. Apply their spoken instruction to the \
+                      text they selected and reply with the rewritten text only, without \
+                      quotes or commentary. Keep placeholder tokens unchanged.",
+        },
+        ChatMessage {
+            role: "user",
+            content: &user,
+        },
+    ];
+
+    let rewritten = llm::complete(
+        provider,
+        &api_key,
+        messages,
+        0.3,
+        Duration::from_secs(REQUEST_TIMEOUT_SECS),
+    )
+    .await?;
+
+    Ok(redacted.restore(&rewritten))
+}
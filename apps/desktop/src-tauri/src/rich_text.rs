@@ -0,0 +1,156 @@
+/// A line of formatted text, by the block it starts
+enum Line<'a> {
+    Blank,
+    /// `* `, `- ` or `• ` item
+    Bullet(&'a str),
+    /// `1. ` or `1) ` item
+    Numbered(&'a str),
+    /// `#` to `######` heading, with its level
+    Heading(usize, &'a str),
+    Text(&'a str),
+}
+
+impl<'a> Line<'a> {
+    fn parse(line: &'a str) -> Self {
+        let line = line.trim();
+        if line.is_empty() {
+            return Line::Blank;
+        }
+
+        for marker in ["* ", "- ", "• "] {
+            if let Some(item) = line.strip_prefix(marker) {
+                return Line::Bullet(item.trim_start());
+            }
+        }
+
+        let digits = line.chars().take_while(char::is_ascii_digit).count();
+        if digits > 0 {
+            let rest = &line[digits..];
+            if let Some(item) = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+                return Line::Numbered(item.trim_start());
+            }
+        }
+
+        let level = line.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) {
+            if let Some(heading) = line[level..].strip_prefix(' ') {
+                return Line::Heading(level, heading.trim_start());
+            }
+        }
+
+        Line::Text(line)
+    }
+}
+
+/// The list being built, if any
+#[derive(PartialEq)]
+enum List {
+    Bullets,
+    Numbers,
+}
+
+/// HTML for text written with the light Markdown the formatter uses: `**bold**`,
+/// `*` and `-` bullets, numbered lists and `#` headings, with blank lines
+/// between paragraphs. `None` when the text has no formatting beyond
+/// paragraphs, so it is better pasted as plain text.
+pub fn to_html(text: &str) -> Option<String> {
+    let mut html = String::new();
+    let mut formatted = false;
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut list: Option<List> = None;
+
+    for line in text.lines() {
+        match Line::parse(line) {
+            Line::Blank => {
+                close_paragraph(&mut html, &mut paragraph);
+                close_list(&mut html, &mut list);
+            }
+            Line::Bullet(item) => {
+                close_paragraph(&mut html, &mut paragraph);
+                open_list(&mut html, &mut list, List::Bullets);
+                html.push_str(&format!("<li>{}</li>", inline(item, &mut formatted)));
+                formatted = true;
+            }
+            Line::Numbered(item) => {
+                close_paragraph(&mut html, &mut paragraph);
+                open_list(&mut html, &mut list, List::Numbers);
+                html.push_str(&format!("<li>{}</li>", inline(item, &mut formatted)));
+                formatted = true;
+            }
+            Line::Heading(level, heading) => {
+                close_paragraph(&mut html, &mut paragraph);
+                close_list(&mut html, &mut list);
+                html.push_str(&format!(
+                    "<h{level}>{}</h{level}>",
+                    inline(heading, &mut formatted)
+                ));
+                formatted = true;
+            }
+            Line::Text(line) => {
+                close_list(&mut html, &mut list);
+                paragraph.push(inline(line, &mut formatted));
+            }
+        }
+    }
+    close_paragraph(&mut html, &mut paragraph);
+    close_list(&mut html, &mut list);
+
+    formatted.then_some(html)
+}
+
+/// Lines of a paragraph are kept apart with line breaks, like greetings and
+/// sign-offs
+fn close_paragraph(html: &mut String, paragraph: &mut Vec<String>) {
+    if !paragraph.is_empty() {
+        html.push_str(&format!("<p>{}</p>", paragraph.join("<br>")));
+        paragraph.clear();
+    }
+}
+
+fn open_list(html: &mut String, list: &mut Option<List>, kind: List) {
+    if list.as_ref() == Some(&kind) {
+        return;
+    }
+    close_list(html, list);
+    html.push_str(match kind {
+        List::Bullets => "<ul>",
+        List::Numbers => "<ol>",
+    });
+    *list = Some(kind);
+}
+
+fn close_list(html: &mut String, list: &mut Option<List>) {
+    match list.take() {
+        Some(List::Bullets) => html.push_str("</ul>"),
+        Some(List::Numbers) => html.push_str("</ol>"),
+        None => {}
+    }
+}
+
+/// Escaped text with `**bold**` spans; an unmatched `**` is kept as typed
+fn inline(text: &str, formatted: &mut bool) -> String {
+    let escaped = escape(text);
+    let parts: Vec<&str> = escaped.split("**").collect();
+
+    let mut html = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            let opening = i % 2 == 1;
+            if opening && i == parts.len() - 1 {
+                html.push_str("**");
+            } else {
+                html.push_str(if opening { "<b>" } else { "</b>" });
+                *formatted = true;
+            }
+        }
+        html.push_str(part);
+    }
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
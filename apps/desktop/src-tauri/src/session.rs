@@ -0,0 +1,121 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use tracing::{error, info};
+
+use crate::commands::sessions::{append_session_entry, create_session, finish_session};
+use crate::events::{emit_entity_event, names as event_names};
+use crate::transcription::merge_with_overlap_dedup;
+
+/// Default title for sessions started without one
+const DEFAULT_SESSION_TITLE: &str = "Meeting";
+
+/// Summary event payload emitted when a session recording ends
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub title: String,
+    pub duration_ms: i64,
+    pub chunk_count: i64,
+    pub word_count: i64,
+}
+
+/// Live transcript document a session-mode recording streams its chunks into.
+/// Each completed chunk is appended to the DB as it arrives instead of being
+/// buffered for a single paste.
+pub struct LiveSession {
+    app: AppHandle,
+    session_id: String,
+    title: String,
+    /// Transcript merged so far (used to strip the overlap from each new chunk)
+    transcript: Mutex<String>,
+}
+
+impl LiveSession {
+    /// Create the session record and announce it to the frontend
+    pub fn start(app: AppHandle, title: Option<String>) -> Result<Self, String> {
+        let title = title
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_SESSION_TITLE.to_string());
+
+        let session = create_session(&title).map_err(|e| e.message)?;
+        emit_entity_event(&app, event_names::SESSIONS_CREATED, session.clone())?;
+
+        info!("🎙️ Started session {} ({})", session.id, title);
+
+        Ok(Self {
+            app,
+            session_id: session.id,
+            title,
+            transcript: Mutex::new(String::new()),
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Append a completed chunk to the live transcript
+    pub fn append_chunk(&self, chunk_index: usize, start_ms: i64, end_ms: i64, text: &str) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+
+        let (new_text, transcript) = {
+            let mut transcript = match self.transcript.lock() {
+                Ok(t) => t,
+                Err(_) => return,
+            };
+
+            // Only the words past the overlap with the previous chunk are new
+            let previous_words = transcript.split_whitespace().count();
+            let merged = merge_with_overlap_dedup(&transcript, text);
+            let new_text = merged
+                .split_whitespace()
+                .skip(previous_words)
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            *transcript = merged;
+            (new_text, transcript.clone())
+        };
+
+        if new_text.is_empty() {
+            return;
+        }
+
+        match append_session_entry(
+            &self.session_id,
+            chunk_index as i64,
+            start_ms,
+            end_ms,
+            &new_text,
+            &transcript,
+        ) {
+            Ok(session) => {
+                let _ = emit_entity_event(&self.app, event_names::SESSIONS_UPDATED, session);
+            }
+            Err(e) => error!("Failed to append session entry: {}", e.message),
+        }
+    }
+
+    /// Close the session and return the summary payload
+    pub fn finish(&self, duration_ms: i64) -> Result<SessionSummary, String> {
+        let session = finish_session(&self.session_id, duration_ms).map_err(|e| e.message)?;
+        emit_entity_event(&self.app, event_names::SESSIONS_UPDATED, session.clone())?;
+
+        info!(
+            "✅ Session {} ended ({} chunks, {} ms)",
+            session.id, session.chunk_count, session.duration_ms
+        );
+
+        Ok(SessionSummary {
+            session_id: session.id,
+            title: self.title.clone(),
+            duration_ms: session.duration_ms,
+            chunk_count: session.chunk_count,
+            word_count: session.transcript.split_whitespace().count() as i64,
+        })
+    }
+}
@@ -1,10 +1,19 @@
-use rdev::{listen, Event, EventType, Key};
+use rdev::Key;
+use serde::Serialize;
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
-use tauri::{App, AppHandle, Emitter};
-use tauri_nspanel::ManagerExt;
-use tauri_plugin_store::{JsonValue, StoreExt};
+use std::time::{Duration, Instant};
+use tauri::{App, AppHandle, Manager, Runtime};
+use tauri_plugin_store::{JsonValue, Store, StoreExt};
+use tauri_specta::Event;
+use tracing::{debug, error, info, warn};
+
+use crate::commands::settings::{keys as setting_keys, read_bool_setting};
+use crate::events;
+use crate::platform::{self, KeyEvent};
+use crate::transcription::{recording_in_progress, TranscriptionError, TranscriptionServiceHandle};
 
 /// Name of the Tauri storage
 const DICTO_TAURI_STORE: &str = "dicto_tauri_store";
@@ -15,6 +24,36 @@ const DICTO_GLOBAL_SHORTCUT: &str = "dicto_global_shortcut";
 /// Default shortcut - FN key
 const DEFAULT_SHORTCUT: &str = "fn";
 
+/// Key for storing the shortcut that pastes the last transcription again (empty = off)
+const DICTO_REPASTE_SHORTCUT: &str = "dicto_repaste_shortcut";
+
+/// Key for storing the hold-to-talk shortcut that rewrites the selected text (empty = off)
+const DICTO_REWRITE_SHORTCUT: &str = "dicto_rewrite_shortcut";
+
+/// Key for storing the hold-to-talk shortcut that captures a new note (empty = off)
+const DICTO_NOTE_SHORTCUT: &str = "dicto_note_shortcut";
+
+/// Restarts attempted in a row before the watchdog gives up
+const MAX_LISTENER_RESTARTS: u32 = 5;
+
+/// Delay before the first restart; doubles after each consecutive failure
+const LISTENER_RESTART_DELAY_MS: u64 = 1000;
+
+/// A listener that ran this long was healthy, so its death starts a fresh count
+const LISTENER_HEALTHY_SECS: u64 = 60;
+
+/// Set while the watchdog is supervising a listener
+static LISTENER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Payload emitted when the keyboard listener dies
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct ShortcutListenerFailed {
+    pub error: String,
+    pub permission_hint: String,
+    /// Whether the watchdog will try to restart the listener
+    pub will_retry: bool,
+}
+
 /// Global state for the keyboard listener
 static SHORTCUT_STATE: OnceLock<Arc<Mutex<ShortcutState>>> = OnceLock::new();
 
@@ -22,6 +61,37 @@ struct ShortcutState {
     target_keys: Vec<Key>,
     pressed_keys: HashSet<Key>,
     shortcut_active: bool,
+    /// Pastes the last transcription again
+    repaste: OptionalShortcut,
+    /// Held to dictate an instruction for rewriting the selected text
+    rewrite: OptionalShortcut,
+    /// Held to dictate a new note without leaving the current app
+    note: OptionalShortcut,
+}
+
+impl ShortcutState {
+    /// Forget every held key, e.g. after the listener missed their releases
+    fn release_all(&mut self) {
+        self.pressed_keys.clear();
+        self.shortcut_active = false;
+        self.repaste.active = false;
+        self.rewrite.active = false;
+        self.note.active = false;
+    }
+}
+
+/// A shortcut that does nothing until the user sets its keys
+struct OptionalShortcut {
+    keys: Vec<Key>,
+    /// Set from the press of the whole combination until one of its keys is released
+    active: bool,
+}
+
+impl OptionalShortcut {
+    /// Whether `pressed` just completed the combination
+    fn completed_by(&self, pressed: &HashSet<Key>) -> bool {
+        !self.active && !self.keys.is_empty() && self.keys.iter().all(|k| pressed.contains(k))
+    }
 }
 
 /// Set shortcut during application startup
@@ -30,115 +100,319 @@ pub fn enable_shortcut(app: &App) {
         .store(DICTO_TAURI_STORE)
         .expect("Creating the store should not fail");
 
-    let shortcut_str = store
-        .get(DICTO_GLOBAL_SHORTCUT)
-        .and_then(|v| match v {
-            JsonValue::String(s) => Some(s),
-            _ => None,
-        })
-        .unwrap_or_else(|| {
-            store.set(
-                DICTO_GLOBAL_SHORTCUT,
-                JsonValue::String(DEFAULT_SHORTCUT.to_string()),
-            );
-            DEFAULT_SHORTCUT.to_string()
-        });
+    let shortcut_str = read_shortcut(&store, DICTO_GLOBAL_SHORTCUT).unwrap_or_else(|| {
+        store.set(
+            DICTO_GLOBAL_SHORTCUT,
+            JsonValue::String(DEFAULT_SHORTCUT.to_string()),
+        );
+        DEFAULT_SHORTCUT.to_string()
+    });
 
     let target_keys = parse_shortcut(&shortcut_str);
-    println!(
+    info!(
         "🎹 Initializing shortcut: {} -> {:?}",
         shortcut_str, target_keys
     );
 
+    let optional = |store_key| OptionalShortcut {
+        keys: parse_shortcut(&read_shortcut(&store, store_key).unwrap_or_default()),
+        active: false,
+    };
+
     let state = Arc::new(Mutex::new(ShortcutState {
         target_keys,
         pressed_keys: HashSet::new(),
         shortcut_active: false,
+        repaste: optional(DICTO_REPASTE_SHORTCUT),
+        rewrite: optional(DICTO_REWRITE_SHORTCUT),
+        note: optional(DICTO_NOTE_SHORTCUT),
     }));
 
     SHORTCUT_STATE.set(state.clone()).ok();
 
-    let app_handle = app.handle().clone();
-    thread::spawn(move || {
-        start_listener(app_handle);
-    });
+    spawn_listener_watchdog(app.handle().clone());
 
-    println!("✅ Global keyboard listener started");
+    info!("✅ Global keyboard listener started");
 }
 
-fn start_listener(app: AppHandle) {
-    let state = SHORTCUT_STATE.get().unwrap().clone();
+/// Run the listener on its own thread and restart it if it errors or panics,
+/// since shortcuts would otherwise stop working until the app restarts
+fn spawn_listener_watchdog(app: AppHandle) {
+    if LISTENER_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
 
-    if let Err(e) = listen(move |event: Event| {
-        let mut state = state.lock().unwrap();
+    thread::spawn(move || {
+        let mut failures: u32 = 0;
+
+        loop {
+            let listener_app = app.clone();
+            let started = Instant::now();
+
+            let error = match thread::Builder::new()
+                .name("shortcut-listener".to_string())
+                .spawn(move || start_listener(listener_app))
+            {
+                Ok(handle) => match handle.join() {
+                    Ok(Ok(())) => "Keyboard listener exited".to_string(),
+                    Ok(Err(e)) => e,
+                    Err(_) => "Keyboard listener thread panicked".to_string(),
+                },
+                Err(e) => format!("Failed to spawn keyboard listener: {}", e),
+            };
+
+            if started.elapsed() >= Duration::from_secs(LISTENER_HEALTHY_SECS) {
+                failures = 0;
+            }
+            failures += 1;
+            let will_retry = failures <= MAX_LISTENER_RESTARTS;
 
-        match event.event_type {
-            EventType::KeyPress(key) => {
-                state.pressed_keys.insert(key);
-
-                // Check if all target keys are pressed
-                if !state.shortcut_active
-                    && !state.target_keys.is_empty()
-                    && state
-                        .target_keys
-                        .iter()
-                        .all(|k| state.pressed_keys.contains(k))
-                {
-                    state.shortcut_active = true;
-                    println!("🔔 Shortcut activated! Emitting start-listening");
-                    emit_event(&app, "start-listening");
-                }
+            error!("❌ Global key listener died: {}", error);
+            let permission_hint = platform::key_listener().permission_hint();
+            warn!("💡 {}", permission_hint);
+
+            let _ = ShortcutListenerFailed {
+                error,
+                permission_hint: permission_hint.to_string(),
+                will_retry,
+            }
+            .emit(&app);
+
+            if !will_retry {
+                error!(
+                    "❌ Giving up on the global key listener after {} restarts",
+                    MAX_LISTENER_RESTARTS
+                );
+                LISTENER_RUNNING.store(false, Ordering::SeqCst);
+                break;
             }
-            EventType::KeyRelease(key) => {
-                state.pressed_keys.remove(&key);
-
-                // Check if any target key was released
-                if state.shortcut_active && state.target_keys.contains(&key) {
-                    state.shortcut_active = false;
-                    println!("🔔 Shortcut released! Emitting stop-listening");
-                    emit_event(&app, "stop-listening");
-                }
+
+            thread::sleep(Duration::from_millis(
+                LISTENER_RESTART_DELAY_MS << (failures - 1),
+            ));
+
+            // Keys held when the listener died will never report a release
+            if let Some(state) = SHORTCUT_STATE.get() {
+                state
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .release_all();
             }
-            _ => {}
+
+            warn!("🔄 Restarting global key listener (attempt {})", failures);
+        }
+    });
+}
+
+fn start_listener(app: AppHandle) -> Result<(), String> {
+    let state = SHORTCUT_STATE
+        .get()
+        .ok_or("Shortcut state not initialized")?
+        .clone();
+
+    platform::key_listener().listen(Box::new(move |event| {
+        // A panic elsewhere must not take every later key event down with it
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+
+        match event {
+            KeyEvent::Press(key) => handle_key_press(&app, &mut state, key),
+            KeyEvent::Release(key) => handle_key_release(&app, &mut state, key),
         }
-    }) {
-        eprintln!("❌ Failed to start global key listener: {:?}", e);
-        eprintln!("💡 Make sure the app has accessibility permissions in System Settings");
-        eprintln!("   Go to: System Settings > Privacy & Security > Accessibility");
+    }))
+}
+
+fn handle_key_press(app: &AppHandle, state: &mut ShortcutState, key: Key) {
+    state.pressed_keys.insert(key);
+
+    // Esc cancels an active recording. Idle presses are left alone, and the
+    // listener only observes events so Esc still reaches the focused app.
+    if key == Key::Escape
+        && !state.target_keys.contains(&Key::Escape)
+        && recording_in_progress()
+        && read_bool_setting(setting_keys::ESCAPE_CANCELS_RECORDING, true)
+    {
+        // The shortcut release must not stop (and paste) the cancelled recording
+        state.shortcut_active = false;
+        cancel_recording(app);
+        return;
+    }
+
+    // Paste the last transcription again, once per press of the combination
+    if state.repaste.completed_by(&state.pressed_keys) {
+        state.repaste.active = true;
+        debug!("🔔 Re-paste shortcut pressed");
+        // Pasting reads the database and waits on the clipboard, so keep it off the listener
+        let app = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = crate::commands::transcriptions::paste_last_transcription(app) {
+                error!("Failed to paste last transcription: {}", e.message);
+            }
+        });
+        return;
+    }
+
+    // Hold to dictate how the selected text should be rewritten
+    if !state.shortcut_active && state.rewrite.completed_by(&state.pressed_keys) {
+        state.rewrite.active = true;
+        debug!("🔔 Rewrite shortcut pressed");
+        start_rewrite(app);
+        return;
+    }
+
+    // Hold to dictate a new note
+    if !state.shortcut_active
+        && !state.rewrite.active
+        && state.note.completed_by(&state.pressed_keys)
+    {
+        state.note.active = true;
+        debug!("🔔 Note shortcut pressed");
+        start_note_capture(app);
+        return;
+    }
+
+    // Check if all target keys are pressed
+    if !state.shortcut_active
+        && !state.target_keys.is_empty()
+        && state
+            .target_keys
+            .iter()
+            .all(|k| state.pressed_keys.contains(k))
+    {
+        state.shortcut_active = true;
+        debug!("🔔 Shortcut activated! Emitting start-listening");
+        let _ = events::emit_broadcast(app, "start-listening", ());
+    }
+}
+
+fn handle_key_release(app: &AppHandle, state: &mut ShortcutState, key: Key) {
+    state.pressed_keys.remove(&key);
+
+    if state.repaste.keys.contains(&key) {
+        state.repaste.active = false;
+    }
+
+    if state.rewrite.active && state.rewrite.keys.contains(&key) {
+        state.rewrite.active = false;
+        debug!("🔔 Rewrite shortcut released");
+        stop_held_recording(app);
+    }
+
+    if state.note.active && state.note.keys.contains(&key) {
+        state.note.active = false;
+        debug!("🔔 Note shortcut released");
+        stop_held_recording(app);
     }
+
+    // Check if any target key was released
+    if state.shortcut_active && state.target_keys.contains(&key) {
+        state.shortcut_active = false;
+        debug!("🔔 Shortcut released! Emitting stop-listening");
+        let _ = events::emit_broadcast(app, "stop-listening", ());
+    }
+}
+
+/// Cancel the active recording without transcribing it
+fn cancel_recording(app: &AppHandle) {
+    debug!("🔔 Esc pressed while recording - cancelling");
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let service = app.state::<TranscriptionServiceHandle>().inner().clone();
+        let result = service.lock().await.cancel_recording(app.clone());
+
+        match result {
+            Ok(()) => crate::refresh_recording_menus(&app, false),
+            Err(e) => error!("Failed to cancel recording: {}", e),
+        }
+    });
 }
 
-fn emit_event(app: &AppHandle, event_name: &str) {
-    // Try to get the widget panel and emit directly
-    let emitted = if let Ok(panel) = app.get_webview_panel("widget") {
-        if let Some(widget_window) = panel.to_window() {
-            match widget_window.emit(event_name, ()) {
-                Ok(_) => {
-                    println!("✅ Emitted {} directly to widget window", event_name);
-                    true
-                }
-                Err(e) => {
-                    eprintln!("❌ Direct emit failed: {:?}", e);
-                    false
-                }
+/// Capture the selected text and start recording the instruction for it
+fn start_rewrite(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        // Copying the selection presses Cmd+C and waits, so keep it off the listener
+        let selection = tauri::async_runtime::spawn_blocking(crate::rewriter::capture_selection)
+            .await
+            .ok()
+            .flatten();
+        let Some(selection) = selection else {
+            let _ = TranscriptionError("Select text to rewrite first".into()).emit(&app);
+            return;
+        };
+
+        // A tap shorter than the copy leaves nothing to record
+        let still_held = SHORTCUT_STATE.get().is_some_and(|state| {
+            state
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .rewrite
+                .active
+        });
+        if !still_held {
+            return;
+        }
+
+        let service = app.state::<TranscriptionServiceHandle>().inner().clone();
+        let result = service
+            .lock()
+            .await
+            .start_rewrite(app.clone(), selection)
+            .await;
+
+        match result {
+            Ok(()) => crate::refresh_recording_menus(&app, true),
+            Err(e) => {
+                error!("Failed to start rewrite: {}", e);
+                let _ = TranscriptionError(e.to_string()).emit(&app);
             }
-        } else {
-            eprintln!("⚠️ Panel exists but cannot convert to window");
-            false
         }
-    } else {
-        false
-    };
+    });
+}
 
-    // Always fallback to broadcast if direct emit failed or panel not found
-    if !emitted {
-        println!("📡 Broadcasting {} event to all windows/panels", event_name);
-        let _ = app.emit(event_name, ());
-    }
+/// Start recording a new note. Nothing is captured from the frontmost app.
+fn start_note_capture(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let service = app.state::<TranscriptionServiceHandle>().inner().clone();
+        let result = service.lock().await.start_note_capture(app.clone()).await;
+
+        match result {
+            Ok(()) => crate::refresh_recording_menus(&app, true),
+            Err(e) => {
+                error!("Failed to start note capture: {}", e);
+                let _ = TranscriptionError(e.to_string()).emit(&app);
+            }
+        }
+    });
+}
+
+/// Stop a recording started by a hold-to-talk shortcut (rewrite or note
+/// capture); what happens to the dictation was decided when it started
+fn stop_held_recording(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let service = app.state::<TranscriptionServiceHandle>().inner().clone();
+        let mut service = service.lock().await;
+        if !service.is_recording() {
+            return;
+        }
+        let result = service.stop_recording(app.clone(), String::new(), String::new());
+        drop(service);
+
+        match result {
+            Ok(()) => crate::refresh_recording_menus(&app, false),
+            Err(e) => error!("Failed to stop recording: {}", e),
+        }
+    });
 }
 
 /// Parse shortcut string like "ctrl+space" or "fn" into Vec<Key>
 fn parse_shortcut(s: &str) -> Vec<Key> {
+    if s.trim().is_empty() {
+        return Vec::new();
+    }
+
     s.split('+')
         .map(|part| string_to_key(part.trim()))
         .collect()
@@ -231,20 +505,17 @@ fn string_to_key(s: &str) -> Key {
 pub fn get_current_shortcut(app: tauri::AppHandle) -> Result<String, String> {
     let store = app.get_store(DICTO_TAURI_STORE).ok_or("Store not found")?;
 
-    Ok(store
-        .get(DICTO_GLOBAL_SHORTCUT)
-        .and_then(|v| match v {
-            JsonValue::String(s) => Some(s),
-            _ => None,
-        })
-        .unwrap_or_else(|| DEFAULT_SHORTCUT.to_string()))
+    Ok(
+        read_shortcut(&store, DICTO_GLOBAL_SHORTCUT)
+            .unwrap_or_else(|| DEFAULT_SHORTCUT.to_string()),
+    )
 }
 
 /// Change the global shortcut
 #[tauri::command]
 #[specta::specta]
 pub fn change_shortcut(app: tauri::AppHandle, key: String) -> Result<(), String> {
-    println!("Changing shortcut to: {}", key);
+    info!("Changing shortcut to: {}", key);
 
     // Store the new shortcut
     let store = app.get_store(DICTO_TAURI_STORE).ok_or("Store not found")?;
@@ -256,7 +527,92 @@ pub fn change_shortcut(app: tauri::AppHandle, key: String) -> Result<(), String>
         state.target_keys = parse_shortcut(&key);
         state.shortcut_active = false;
         state.pressed_keys.clear();
-        println!("✅ Shortcut updated to: {:?}", state.target_keys);
+        info!("✅ Shortcut updated to: {:?}", state.target_keys);
+    }
+
+    Ok(())
+}
+
+/// Get the stored re-paste shortcut ("" when none is set)
+#[tauri::command]
+#[specta::specta]
+pub fn get_repaste_shortcut(app: tauri::AppHandle) -> Result<String, String> {
+    get_optional_shortcut(&app, DICTO_REPASTE_SHORTCUT)
+}
+
+/// Change the shortcut that pastes the last transcription again ("" turns it off)
+#[tauri::command]
+#[specta::specta]
+pub fn change_repaste_shortcut(app: tauri::AppHandle, key: String) -> Result<(), String> {
+    change_optional_shortcut(&app, DICTO_REPASTE_SHORTCUT, key, |state| {
+        &mut state.repaste
+    })
+}
+
+/// Get the stored rewrite shortcut ("" when none is set)
+#[tauri::command]
+#[specta::specta]
+pub fn get_rewrite_shortcut(app: tauri::AppHandle) -> Result<String, String> {
+    get_optional_shortcut(&app, DICTO_REWRITE_SHORTCUT)
+}
+
+/// Change the hold-to-talk shortcut that rewrites the selected text ("" turns it off)
+#[tauri::command]
+#[specta::specta]
+pub fn change_rewrite_shortcut(app: tauri::AppHandle, key: String) -> Result<(), String> {
+    change_optional_shortcut(&app, DICTO_REWRITE_SHORTCUT, key, |state| {
+        &mut state.rewrite
+    })
+}
+
+/// Get the stored note capture shortcut ("" when none is set)
+#[tauri::command]
+#[specta::specta]
+pub fn get_note_shortcut(app: tauri::AppHandle) -> Result<String, String> {
+    get_optional_shortcut(&app, DICTO_NOTE_SHORTCUT)
+}
+
+/// Change the hold-to-talk shortcut that dictates a new note ("" turns it off)
+#[tauri::command]
+#[specta::specta]
+pub fn change_note_shortcut(app: tauri::AppHandle, key: String) -> Result<(), String> {
+    change_optional_shortcut(&app, DICTO_NOTE_SHORTCUT, key, |state| &mut state.note)
+}
+
+/// A shortcut as saved in the store, if one was saved
+fn read_shortcut<R: Runtime>(store: &Store<R>, store_key: &str) -> Option<String> {
+    store.get(store_key).and_then(|v| match v {
+        JsonValue::String(s) => Some(s),
+        _ => None,
+    })
+}
+
+/// The stored keys of an optional shortcut ("" when none is set)
+fn get_optional_shortcut(app: &AppHandle, store_key: &str) -> Result<String, String> {
+    let store = app.get_store(DICTO_TAURI_STORE).ok_or("Store not found")?;
+
+    Ok(read_shortcut(&store, store_key).unwrap_or_default())
+}
+
+/// Save the keys of an optional shortcut and apply them to the listener's `slot`
+fn change_optional_shortcut(
+    app: &AppHandle,
+    store_key: &str,
+    key: String,
+    slot: fn(&mut ShortcutState) -> &mut OptionalShortcut,
+) -> Result<(), String> {
+    info!("Changing {} to: {}", store_key, key);
+
+    let store = app.get_store(DICTO_TAURI_STORE).ok_or("Store not found")?;
+    store.set(store_key, JsonValue::String(key.clone()));
+
+    if let Some(state) = SHORTCUT_STATE.get() {
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        state.pressed_keys.clear();
+        let shortcut = slot(&mut state);
+        shortcut.keys = parse_shortcut(&key);
+        shortcut.active = false;
+        info!("✅ {} updated to: {:?}", store_key, shortcut.keys);
     }
 
     Ok(())
@@ -271,7 +627,26 @@ pub fn unregister_shortcut(_app: tauri::AppHandle) -> Result<(), String> {
         state.target_keys.clear();
         state.shortcut_active = false;
         state.pressed_keys.clear();
-        println!("✅ Shortcut unregistered");
+        info!("✅ Shortcut unregistered");
     }
     Ok(())
 }
+
+/// Whether the app may read global key events for the shortcut
+#[tauri::command]
+#[specta::specta]
+pub fn shortcut_permission_status() -> bool {
+    platform::key_listener().has_permission()
+}
+
+/// Ask for the permission the shortcut listener needs, starting the listener
+/// if it had given up waiting for it
+#[tauri::command]
+#[specta::specta]
+pub fn shortcut_request_permission(app: tauri::AppHandle) -> bool {
+    let granted = platform::key_listener().request_permission();
+    if granted && SHORTCUT_STATE.get().is_some() {
+        spawn_listener_watchdog(app);
+    }
+    granted
+}
@@ -0,0 +1,37 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use tracing::info;
+
+/// Already transcribed audio of a long recording, moved out of memory into a
+/// file of raw little-endian f32 samples in the recording workspace
+pub struct AudioSpill {
+    writer: BufWriter<File>,
+}
+
+impl AudioSpill {
+    /// Create an empty spill file
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        let writer = BufWriter::new(File::create(path)?);
+        info!("Spilling recorded audio to {}", path.display());
+
+        Ok(Self { writer })
+    }
+
+    /// Append samples to the end of the file
+    pub fn write(&mut self, samples: &[f32]) -> std::io::Result<()> {
+        for sample in samples {
+            self.writer.write_all(&sample.to_le_bytes())?;
+        }
+        self.writer.flush()
+    }
+}
+
+/// Read back every sample of a spill file
+pub fn read_samples(path: &Path) -> std::io::Result<Vec<f32>> {
+    let bytes = std::fs::read(path)?;
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
@@ -0,0 +1,698 @@
+use anyhow::Result;
+use deepgram::common::options::Keyword;
+use futures::future::BoxFuture;
+use reqwest::multipart::{Form, Part};
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_specta::Event;
+use tracing::{debug, info, warn};
+
+use crate::commands::keys_vault::{keys_vault_get, VaultService};
+use crate::commands::settings::{keys as setting_keys, read_setting};
+use crate::transcription::{
+    downmix_and_resample, emit_first_words, start_local_microphone, ChunkTranscript,
+    RecordingMemory, TranscriptSegment, TranscriptWord, TranscriptionOutput, TranscriptionPartial,
+    TranscriptionSettings, TRANSCRIPTION_SAMPLE_RATE,
+};
+
+/// Give up on an upload after this long and fail over to the next provider
+const REQUEST_TIMEOUT_SECS: u64 = 120;
+
+/// Longest keyterm prompt sent along; Whisper only reads the last 224 tokens
+const MAX_PROMPT_CHARS: usize = 600;
+
+/// Most keyterms sent to Deepgram; each one lengthens the websocket URL
+const MAX_DEEPGRAM_KEYWORDS: usize = 100;
+
+/// Deepgram intensifiers for the most and least relevant keyterm. Boosting
+/// everything hard makes Deepgram hear keyterms that weren't said.
+const MAX_KEYWORD_INTENSIFIER: f64 = 2.0;
+const MIN_KEYWORD_INTENSIFIER: f64 = 1.0;
+
+/// How often captured audio is forwarded to the Deepgram websocket
+const STREAM_INTERVAL_MS: u64 = 100;
+
+/// How long to wait for final results after the audio stream is closed
+const STREAM_FINALIZE_TIMEOUT_SECS: u64 = 10;
+
+/// Model reported for Deepgram transcriptions
+const DEEPGRAM_MODEL: &str = "deepgram";
+
+/// What an uploaded recording is transcribed with besides its audio
+pub struct CloudRequest<'a> {
+    /// Language code to transcribe; `None` lets the provider detect it
+    pub language: Option<&'a str>,
+    /// Keyterms to bias recognition toward, most relevant first
+    pub keyterms: &'a [String],
+}
+
+/// A hosted speech-to-text API that transcribes a finished recording at once,
+/// as opposed to Deepgram which is streamed to while recording
+pub trait CloudSttProvider: Send + Sync {
+    /// Model name saved with transcriptions
+    fn model(&self) -> &'static str;
+
+    /// Vault entry holding the API key
+    fn vault_service(&self) -> VaultService;
+
+    /// Transcribe a 16kHz mono WAV file
+    fn transcribe<'a>(
+        &'a self,
+        client: &'a Client,
+        api_key: &'a str,
+        wav: Vec<u8>,
+        request: &'a CloudRequest<'a>,
+    ) -> BoxFuture<'a, Result<ChunkTranscript, String>>;
+}
+
+/// Groq's hosted whisper-large-v3-turbo; fast, with word timestamps
+pub struct GroqWhisper;
+
+impl CloudSttProvider for GroqWhisper {
+    fn model(&self) -> &'static str {
+        "whisper-large-v3-turbo"
+    }
+
+    fn vault_service(&self) -> VaultService {
+        VaultService::Groq
+    }
+
+    fn transcribe<'a>(
+        &'a self,
+        client: &'a Client,
+        api_key: &'a str,
+        wav: Vec<u8>,
+        request: &'a CloudRequest<'a>,
+    ) -> BoxFuture<'a, Result<ChunkTranscript, String>> {
+        Box::pin(transcribe_openai_compatible(
+            client,
+            "https://api.groq.com/openai/v1/audio/transcriptions",
+            api_key,
+            self.model(),
+            true,
+            wav,
+            request,
+        ))
+    }
+}
+
+/// OpenAI's transcription API; returns text only, without timestamps
+pub struct OpenAiTranscribe;
+
+impl CloudSttProvider for OpenAiTranscribe {
+    fn model(&self) -> &'static str {
+        "gpt-4o-transcribe"
+    }
+
+    fn vault_service(&self) -> VaultService {
+        VaultService::OpenAI
+    }
+
+    fn transcribe<'a>(
+        &'a self,
+        client: &'a Client,
+        api_key: &'a str,
+        wav: Vec<u8>,
+        request: &'a CloudRequest<'a>,
+    ) -> BoxFuture<'a, Result<ChunkTranscript, String>> {
+        Box::pin(transcribe_openai_compatible(
+            client,
+            "https://api.openai.com/v1/audio/transcriptions",
+            api_key,
+            self.model(),
+            false,
+            wav,
+            request,
+        ))
+    }
+}
+
+/// A provider with its key from the vault
+pub struct ProviderKey {
+    pub provider: Box<dyn CloudSttProvider>,
+    pub api_key: String,
+}
+
+/// Upload providers with a key in the vault, in failover order: the one picked
+/// with the `cloudModel` setting first. Empty unless Groq or OpenAI is picked,
+/// so audio is never uploaded to a provider only configured for formatting.
+pub fn preferred_providers() -> Vec<ProviderKey> {
+    match read_setting(setting_keys::CLOUD_MODEL).as_deref() {
+        Some("Groq") => providers_with_keys(false),
+        Some("OpenAI") => providers_with_keys(true),
+        _ => Vec::new(),
+    }
+}
+
+/// Providers for refining a local draft (hybrid mode), which the user opted into
+/// uploading for: the picked provider first, Groq otherwise
+pub fn refinement_providers() -> Vec<ProviderKey> {
+    let prefer_openai = read_setting(setting_keys::CLOUD_MODEL).as_deref() == Some("OpenAI");
+    providers_with_keys(prefer_openai)
+}
+
+/// Both upload providers that have a key in the vault, in failover order
+fn providers_with_keys(prefer_openai: bool) -> Vec<ProviderKey> {
+    let providers: Vec<Box<dyn CloudSttProvider>> = if prefer_openai {
+        vec![Box::new(OpenAiTranscribe), Box::new(GroqWhisper)]
+    } else {
+        vec![Box::new(GroqWhisper), Box::new(OpenAiTranscribe)]
+    };
+
+    providers
+        .into_iter()
+        .filter_map(|provider| {
+            let api_key = keys_vault_get(provider.vault_service()).ok().flatten()?;
+            Some(ProviderKey { provider, api_key })
+        })
+        .collect()
+}
+
+/// Deepgram `keywords` for keyterms ranked most relevant first: the top ones get
+/// the strongest boost, tapering off down the list. Groq and OpenAI have no boost
+/// parameter and are prompted with the keyterms instead.
+pub fn deepgram_keywords(keyterms: &[String]) -> Vec<Keyword> {
+    let terms: Vec<&str> = keyterms
+        .iter()
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+        .take(MAX_DEEPGRAM_KEYWORDS)
+        .collect();
+    let steps = terms.len().saturating_sub(1).max(1) as f64;
+
+    terms
+        .iter()
+        .enumerate()
+        .map(|(rank, term)| {
+            let boost = MAX_KEYWORD_INTENSIFIER
+                - (MAX_KEYWORD_INTENSIFIER - MIN_KEYWORD_INTENSIFIER) * rank as f64 / steps;
+            Keyword {
+                keyword: term.to_string(),
+                intensifier: Some((boost * 10.0).round() / 10.0),
+            }
+        })
+        .collect()
+}
+
+/// Transcribe a recording with the first provider that succeeds, returning the
+/// transcript and the model that produced it
+pub async fn transcribe_with_failover(
+    providers: &[ProviderKey],
+    samples: &[f32],
+    request: &CloudRequest<'_>,
+) -> Result<(ChunkTranscript, &'static str), String> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let wav = encode_wav(samples);
+
+    let mut errors = Vec::new();
+    for ProviderKey { provider, api_key } in providers {
+        match provider
+            .transcribe(&client, api_key, wav.clone(), request)
+            .await
+        {
+            Ok(transcript) => return Ok((transcript, provider.model())),
+            Err(e) => {
+                warn!("{} transcription failed: {}", provider.model(), e);
+                errors.push(format!("{}: {}", provider.model(), e));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Err("No cloud transcription provider configured".to_string())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+#[derive(Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+    #[serde(default)]
+    segments: Vec<ResponseSegment>,
+    #[serde(default)]
+    words: Vec<ResponseWord>,
+}
+
+#[derive(Deserialize)]
+struct ResponseSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    #[serde(default)]
+    avg_logprob: f64,
+}
+
+#[derive(Deserialize)]
+struct ResponseWord {
+    word: String,
+    start: f64,
+    end: f64,
+}
+
+/// POST to an OpenAI-compatible `/audio/transcriptions` endpoint. With `verbose`,
+/// asks for segment and word timestamps.
+async fn transcribe_openai_compatible(
+    client: &Client,
+    url: &str,
+    api_key: &str,
+    model: &str,
+    verbose: bool,
+    wav: Vec<u8>,
+    request: &CloudRequest<'_>,
+) -> Result<ChunkTranscript, String> {
+    let file = Part::bytes(wav)
+        .file_name("recording.wav")
+        .mime_str("audio/wav")
+        .map_err(|e| e.to_string())?;
+    let mut form = Form::new()
+        .part("file", file)
+        .text("model", model.to_string())
+        .text("temperature", "0");
+
+    if verbose {
+        form = form
+            .text("response_format", "verbose_json")
+            .text("timestamp_granularities[]", "segment")
+            .text("timestamp_granularities[]", "word");
+    } else {
+        form = form.text("response_format", "json");
+    }
+    if let Some(language) = request.language {
+        // The API takes ISO-639-1 codes: `en` for `en-US`
+        let base = language.split(['-', '_']).next().unwrap_or(language);
+        form = form.text("language", base.to_ascii_lowercase());
+    }
+    if let Some(prompt) = keyterm_prompt(request.keyterms) {
+        form = form.text("prompt", prompt);
+    }
+
+    let response = client
+        .post(url)
+        .bearer_auth(api_key)
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Transcription failed ({}): {}", status, body));
+    }
+
+    let response: TranscriptionResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response: {}", e))?;
+
+    let segments = response
+        .segments
+        .iter()
+        .map(|segment| {
+            let confidence = segment.avg_logprob.exp() as f32;
+            TranscriptSegment {
+                start_ms: (segment.start * 1000.0) as i64,
+                end_ms: (segment.end * 1000.0) as i64,
+                text: segment.text.trim().to_string(),
+                confidence,
+                // Words carry no probability of their own, so they share the segment's
+                words: response
+                    .words
+                    .iter()
+                    .filter(|word| {
+                        let mid = (word.start + word.end) / 2.0;
+                        mid >= segment.start && mid < segment.end
+                    })
+                    .map(|word| TranscriptWord {
+                        text: word.word.trim().to_string(),
+                        confidence,
+                        start_ms: (word.start * 1000.0) as i64,
+                        end_ms: (word.end * 1000.0) as i64,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Ok(ChunkTranscript {
+        text: response.text.trim().to_string(),
+        segments,
+        // Reported as a name ("english"), not a code; the requested language is used
+        language: None,
+    })
+}
+
+/// "Terms: ..." prompt with as many whole keyterms as fit `MAX_PROMPT_CHARS`
+fn keyterm_prompt(keyterms: &[String]) -> Option<String> {
+    let mut prompt = String::from("Terms: ");
+    let mut included = 0;
+
+    for term in keyterms.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if prompt.len() + term.len() + 2 > MAX_PROMPT_CHARS {
+            break;
+        }
+        if included > 0 {
+            prompt.push_str(", ");
+        }
+        prompt.push_str(term);
+        included += 1;
+    }
+
+    if included == 0 {
+        return None;
+    }
+    prompt.push('.');
+    Some(prompt)
+}
+
+/// 16kHz mono 16-bit PCM WAV file of the samples
+pub fn encode_wav(samples: &[f32]) -> Vec<u8> {
+    const SAMPLE_RATE: u32 = 16000;
+    let data_len = samples.len() as u32 * 2;
+
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    // PCM, mono
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    // Byte rate, block align, bits per sample
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        wav.extend_from_slice(&value.to_le_bytes());
+    }
+
+    wav
+}
+
+/// Streaming transcriber that sends microphone audio to Deepgram over a websocket
+/// and emits live partials while recording
+pub struct CloudTranscriber {
+    /// Audio sample buffer (shared with audio capture)
+    samples: Arc<std::sync::Mutex<Vec<f32>>>,
+    /// Channel to stop audio stream
+    local_stop_tx: Option<crossbeam_channel::Sender<()>>,
+    /// Signal for the audio pump to flush the remaining audio and close the stream
+    pump_stop: Arc<AtomicBool>,
+    /// Task forwarding captured audio to the websocket
+    pump_handle: Option<tokio::task::JoinHandle<()>>,
+    /// Task collecting transcript results from the websocket
+    results_handle: Option<tokio::task::JoinHandle<TranscriptionOutput>>,
+    /// Sample rate of input audio
+    sample_rate: u32,
+    /// Number of channels in input audio
+    channels: u16,
+    /// Language requested from Deepgram; `None` when auto-detecting
+    language: Option<String>,
+}
+
+impl CloudTranscriber {
+    /// Create a new CloudTranscriber
+    pub fn new() -> Self {
+        Self {
+            samples: Arc::new(std::sync::Mutex::new(Vec::new())),
+            local_stop_tx: None,
+            pump_stop: Arc::new(AtomicBool::new(false)),
+            pump_handle: None,
+            results_handle: None,
+            sample_rate: 16000,
+            channels: 1,
+            language: None,
+        }
+    }
+
+    /// Duration of audio captured so far in milliseconds
+    pub fn recorded_duration_ms(&self) -> i64 {
+        let len = self.samples.lock().map(|s| s.len()).unwrap_or(0);
+        let frames = len / self.channels.max(1) as usize;
+        (frames as u64 * 1000 / self.sample_rate.max(1) as u64) as i64
+    }
+
+    /// Audio buffered by this recording; streaming loads no local model
+    pub fn memory_usage(&self) -> RecordingMemory {
+        let buffered = self.samples.lock().map(|s| s.len()).unwrap_or(0);
+        RecordingMemory::new(buffered, 0, 0)
+    }
+
+    /// Start streaming microphone audio to Deepgram
+    pub async fn start(
+        &mut self,
+        app: AppHandle,
+        settings: TranscriptionSettings,
+        is_active: Arc<AtomicBool>,
+        api_key: String,
+    ) -> Result<()> {
+        use deepgram::common::options::{Encoding, Language, Options};
+        use deepgram::Deepgram;
+        use futures::channel::mpsc;
+        use futures::SinkExt;
+
+        if let Ok(mut samples) = self.samples.lock() {
+            samples.clear();
+        }
+
+        let mut options = Options::builder().punctuate(true).smart_format(true);
+        self.language = None;
+        if !settings.auto_detect_language {
+            if let Some(language) = settings.languages.first() {
+                options = options.language(Language::Other(language.clone()));
+                self.language = Some(language.clone());
+            }
+        }
+        let keywords = deepgram_keywords(&settings.keyterms);
+        if !keywords.is_empty() {
+            options = options.keywords_with_intensifiers(keywords);
+        }
+
+        // Open the websocket before capturing so a bad key fails fast
+        let (mut audio_tx, audio_rx) = mpsc::channel::<Result<bytes::Bytes, std::io::Error>>(64);
+        let dg = Deepgram::new(&api_key)
+            .map_err(|e| anyhow::anyhow!("Failed to create Deepgram client: {}", e))?;
+        let results = dg
+            .transcription()
+            .stream_request_with_options(options.build())
+            .keep_alive()
+            .encoding(Encoding::Linear16)
+            .sample_rate(TRANSCRIPTION_SAMPLE_RATE)
+            .channels(1)
+            .interim_results(true)
+            .stream(audio_rx)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to open Deepgram stream: {}", e))?;
+
+        // Start microphone capture
+        let (sample_rate, channels, stop_tx) =
+            start_local_microphone(&app, is_active, self.samples.clone()).await?;
+
+        self.sample_rate = sample_rate;
+        self.channels = channels;
+        self.local_stop_tx = Some(stop_tx);
+        self.pump_stop.store(false, Ordering::SeqCst);
+
+        // Forward new audio as 16kHz mono linear16 until stopped, then close the stream
+        let samples = self.samples.clone();
+        let pump_stop = self.pump_stop.clone();
+        let pump_handle = tokio::spawn(async move {
+            let mut sent = 0usize;
+            loop {
+                let stopping = pump_stop.load(Ordering::SeqCst);
+
+                let raw = match samples.lock() {
+                    Ok(buffer) => {
+                        // Only forward whole frames so channels stay aligned
+                        let available = buffer.len() - buffer.len() % channels.max(1) as usize;
+                        let raw = buffer[sent.min(available)..available].to_vec();
+                        sent = available;
+                        raw
+                    }
+                    Err(_) => break,
+                };
+
+                if !raw.is_empty() {
+                    let audio = downmix_and_resample(&raw, sample_rate, channels);
+                    let mut pcm = Vec::with_capacity(audio.len() * 2);
+                    for sample in audio {
+                        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+                        pcm.extend_from_slice(&value.to_le_bytes());
+                    }
+                    if audio_tx.send(Ok(bytes::Bytes::from(pcm))).await.is_err() {
+                        warn!("Deepgram stream closed while sending audio");
+                        break;
+                    }
+                }
+
+                if stopping {
+                    break;
+                }
+
+                tokio::time::sleep(Duration::from_millis(STREAM_INTERVAL_MS)).await;
+            }
+            // Dropping the sender ends the audio stream and lets Deepgram finalize
+            audio_tx.close_channel();
+        });
+
+        let results_handle = tokio::spawn(collect_cloud_results(app, results));
+
+        self.pump_handle = Some(pump_handle);
+        self.results_handle = Some(results_handle);
+
+        info!("Cloud transcription started (Deepgram streaming)");
+
+        Ok(())
+    }
+
+    /// Stop streaming and return the finalized transcript
+    pub async fn stop(&mut self) -> Result<TranscriptionOutput> {
+        // Stop audio capture
+        if let Some(stop_tx) = self.local_stop_tx.take() {
+            let _ = stop_tx.send(());
+            debug!("Sent stop signal to audio stream");
+        }
+
+        // Flush the remaining audio and close the websocket input
+        self.pump_stop.store(true, Ordering::SeqCst);
+        if let Some(pump_handle) = self.pump_handle.take() {
+            pump_handle
+                .await
+                .map_err(|e| anyhow::anyhow!("Audio pump failed: {}", e))?;
+        }
+
+        let results_handle = self
+            .results_handle
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("No cloud stream"))?;
+
+        let mut output = tokio::time::timeout(
+            Duration::from_secs(STREAM_FINALIZE_TIMEOUT_SECS),
+            results_handle,
+        )
+        .await
+        .map_err(|_| anyhow::anyhow!("Timed out waiting for final cloud transcript"))?
+        .map_err(|e| anyhow::anyhow!("Cloud results task failed: {}", e))?;
+        output.model = DEEPGRAM_MODEL.to_string();
+        output.language = self.language.clone();
+        output.audio = match self.samples.lock() {
+            Ok(samples) => downmix_and_resample(&samples, self.sample_rate, self.channels),
+            Err(_) => Vec::new(),
+        };
+
+        info!("Cloud transcription complete: '{}'", output.text);
+
+        Ok(output)
+    }
+
+    /// Stop streaming and drop the connection without waiting for final results
+    pub fn cancel(&mut self) {
+        if let Some(stop_tx) = self.local_stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+
+        self.pump_stop.store(true, Ordering::SeqCst);
+        if let Some(pump_handle) = self.pump_handle.take() {
+            pump_handle.abort();
+        }
+        if let Some(results_handle) = self.results_handle.take() {
+            results_handle.abort();
+        }
+    }
+}
+
+/// Read streaming responses until the stream closes, emitting `transcription-partial`
+/// events and collecting finalized segments
+async fn collect_cloud_results(
+    app: AppHandle,
+    mut results: deepgram::listen::websocket::TranscriptionStream,
+) -> TranscriptionOutput {
+    use deepgram::common::stream_response::StreamResponse;
+    use futures::StreamExt;
+
+    let mut finals: Vec<String> = Vec::new();
+    let mut segments: Vec<TranscriptSegment> = Vec::new();
+    let mut first_words_detected = false;
+
+    while let Some(response) = results.next().await {
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("Deepgram stream error: {}", e);
+                break;
+            }
+        };
+
+        let StreamResponse::TranscriptResponse {
+            start,
+            duration,
+            is_final,
+            channel,
+            ..
+        } = response
+        else {
+            continue;
+        };
+
+        let alternative = match channel.alternatives.into_iter().next() {
+            Some(alternative) => alternative,
+            None => continue,
+        };
+        let transcript = alternative.transcript.trim().to_string();
+
+        if is_final && !transcript.is_empty() {
+            segments.push(TranscriptSegment {
+                start_ms: (start * 1000.0) as i64,
+                end_ms: ((start + duration) * 1000.0) as i64,
+                text: transcript.clone(),
+                confidence: alternative.confidence as f32,
+                words: alternative
+                    .words
+                    .into_iter()
+                    .map(|w| TranscriptWord {
+                        text: w.punctuated_word.unwrap_or(w.word),
+                        confidence: w.confidence as f32,
+                        start_ms: (w.start * 1000.0) as i64,
+                        end_ms: (w.end * 1000.0) as i64,
+                    })
+                    .collect(),
+            });
+            finals.push(transcript.clone());
+        }
+
+        let mut text = finals.join(" ");
+        if !is_final && !transcript.is_empty() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(&transcript);
+        }
+
+        if !first_words_detected && !text.trim().is_empty() {
+            first_words_detected = true;
+            emit_first_words(&app, &text);
+        }
+
+        let _ = TranscriptionPartial { text, is_final }.emit(&app);
+    }
+
+    TranscriptionOutput {
+        text: finals.join(" "),
+        segments,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,163 @@
+// Stand-in for the microphone and the speech model in test builds. Tests feed
+// audio through `inject_audio` together with the transcript it should produce;
+// the chunk workers then transcribe it with `MockTranscriber` like any other
+// recording, so chunking, overlap merging and everything after stop still run.
+
+use anyhow::{bail, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::{ChunkRequest, SttEngine};
+use crate::transcription::{ChunkTranscript, TranscriptSegment, TranscriptWord};
+
+/// Rate of injected audio; it is always mono
+pub const SAMPLE_RATE: u32 = 16000;
+
+/// Transcript of one injected clip, placed on the recording timeline
+struct Clip {
+    start_ms: i64,
+    end_ms: i64,
+    text: String,
+}
+
+struct Recording {
+    /// Sample buffer of the recording in progress, shared with the chunk processor
+    samples: Arc<Mutex<Vec<f32>>>,
+    /// Cleared when the recording stops, after which nothing more is taken in
+    is_active: Arc<AtomicBool>,
+    /// Samples injected since the recording started
+    injected: usize,
+    clips: Vec<Clip>,
+}
+
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+fn lock() -> std::sync::MutexGuard<'static, Option<Recording>> {
+    RECORDING.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn samples_to_ms(len: usize) -> i64 {
+    (len as u64 * 1000 / SAMPLE_RATE as u64) as i64
+}
+
+/// Take the place of the microphone for a new recording filling `samples`.
+/// Returns the sample rate, channel count and stop channel the microphone would.
+pub fn start_audio(
+    is_active: Arc<AtomicBool>,
+    samples: Arc<Mutex<Vec<f32>>>,
+) -> (u32, u16, crossbeam_channel::Sender<()>) {
+    *lock() = Some(Recording {
+        samples,
+        is_active,
+        injected: 0,
+        clips: Vec::new(),
+    });
+
+    let (stop_tx, _) = crossbeam_channel::bounded(1);
+    (SAMPLE_RATE, 1, stop_tx)
+}
+
+/// Whether a recording takes its audio from `inject_audio`
+pub fn active() -> bool {
+    lock().is_some()
+}
+
+/// Append 16kHz mono audio to the recording in progress; `transcript` is what
+/// the mock transcriber hears in it
+pub fn inject_audio(samples: Vec<f32>, transcript: &str) -> Result<()> {
+    let mut recording = lock();
+    let Some(recording) = recording
+        .as_mut()
+        .filter(|r| r.is_active.load(Ordering::Relaxed))
+    else {
+        bail!("Not recording");
+    };
+
+    let start_ms = samples_to_ms(recording.injected);
+    recording.injected += samples.len();
+    recording
+        .samples
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Sample buffer is poisoned"))?
+        .extend(samples);
+
+    recording.clips.push(Clip {
+        start_ms,
+        end_ms: samples_to_ms(recording.injected),
+        text: transcript.to_string(),
+    });
+    Ok(())
+}
+
+/// Speech-to-text engine for test builds. The words of each injected clip are
+/// spread evenly over its audio, and a chunk hears the words whose middle falls
+/// inside it, as a real model would.
+pub struct MockTranscriber;
+
+impl SttEngine for MockTranscriber {
+    fn transcribe(&mut self, request: &ChunkRequest) -> Result<ChunkTranscript> {
+        let chunk_start = request.offset_ms;
+        let chunk_end = chunk_start + samples_to_ms(request.samples.len());
+
+        let recording = lock();
+        let clips = recording
+            .as_ref()
+            .map(|r| r.clips.as_slice())
+            .unwrap_or(&[]);
+
+        let segments: Vec<TranscriptSegment> = clips
+            .iter()
+            .filter_map(|clip| {
+                let tokens: Vec<&str> = clip.text.split_whitespace().collect();
+                let count = tokens.len() as i64;
+                let duration = clip.end_ms - clip.start_ms;
+
+                let words: Vec<TranscriptWord> = tokens
+                    .iter()
+                    .enumerate()
+                    .map(|(i, token)| TranscriptWord {
+                        text: token.to_string(),
+                        confidence: 1.0,
+                        start_ms: clip.start_ms + duration * i as i64 / count,
+                        end_ms: clip.start_ms + duration * (i as i64 + 1) / count,
+                    })
+                    .filter(|word| {
+                        let mid = (word.start_ms + word.end_ms) / 2;
+                        mid >= chunk_start && mid < chunk_end
+                    })
+                    // Offsets are relative to the chunk, like a model reports them
+                    .map(|word| TranscriptWord {
+                        start_ms: word.start_ms - chunk_start,
+                        end_ms: word.end_ms - chunk_start,
+                        ..word
+                    })
+                    .collect();
+
+                let (first, last) = (words.first()?, words.last()?);
+                Some(TranscriptSegment {
+                    start_ms: first.start_ms,
+                    end_ms: last.end_ms,
+                    text: words
+                        .iter()
+                        .map(|w| w.text.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    confidence: 1.0,
+                    words,
+                })
+            })
+            .collect();
+
+        let text = segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Ok(ChunkTranscript {
+            text,
+            segments,
+            language: request.language.map(str::to_string),
+        })
+    }
+}
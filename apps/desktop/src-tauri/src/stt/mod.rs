@@ -0,0 +1,56 @@
+use anyhow::{bail, Result};
+use std::path::Path;
+
+use crate::model_registry::{ModelEngine, ModelEntry};
+use crate::transcription::{ChunkTranscript, DecodingOptions};
+
+pub mod cloud;
+#[cfg(any(test, feature = "test-harness"))]
+pub mod mock;
+mod moonshine;
+pub mod upload;
+mod whisper;
+
+pub use moonshine::MoonshineEngine;
+pub use whisper::WhisperEngine;
+
+/// Everything a chunk is transcribed with besides the model
+pub struct ChunkRequest<'a> {
+    /// 16kHz mono audio
+    pub samples: &'a [f32],
+    /// Where the chunk starts in the recording, in milliseconds
+    pub offset_ms: i64,
+    /// Text of the previous chunk, for engines that can be prompted with it
+    pub previous_text: Option<&'a str>,
+    /// Language code to transcribe; `None` lets the engine detect it
+    pub language: Option<&'a str>,
+    /// Keyterms to bias recognition toward, most relevant first
+    pub keyterms: &'a [String],
+    /// Decoding parameters, with the beam width adapted to the backlog
+    pub decoding: DecodingOptions,
+}
+
+/// A loaded local speech-to-text model. Each chunk worker loads its own, so an
+/// engine is only ever used from one thread at a time.
+pub trait SttEngine: Send {
+    /// Transcribe one chunk; segment offsets are relative to the chunk start
+    fn transcribe(&mut self, request: &ChunkRequest) -> Result<ChunkTranscript>;
+}
+
+/// Load the engine that runs `model` from its downloaded file or folder
+pub fn load_engine(
+    model: &ModelEntry,
+    model_path: &Path,
+    threads: i32,
+) -> Result<Box<dyn SttEngine>> {
+    #[cfg(any(test, feature = "test-harness"))]
+    if mock::active() {
+        return Ok(Box::new(mock::MockTranscriber));
+    }
+
+    match model.engine {
+        ModelEngine::Whisper => Ok(Box::new(WhisperEngine::load(model_path, threads)?)),
+        ModelEngine::Moonshine => Ok(Box::new(MoonshineEngine::load(model_path, threads)?)),
+        ModelEngine::Llama => bail!("{} is not a speech-to-text model", model.name),
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::Result;
+use ort::session::Session;
+use ort::value::Tensor;
+use std::path::Path;
+
+use super::{ChunkRequest, SttEngine};
+use crate::transcription::{ChunkTranscript, TranscriptSegment};
+
+/// Model files in the extracted sherpa-onnx archive
+const PREPROCESS_FILE: &str = "preprocess.onnx";
+const ENCODE_FILE: &str = "encode.int8.onnx";
+const DECODE_FILE: &str = "uncached_decode.int8.onnx";
+const TOKENS_FILE: &str = "tokens.txt";
+
+/// Start and end of transcript tokens
+const SOT_TOKEN: i32 = 1;
+const EOT_TOKEN: i32 = 2;
+
+/// Moonshine produces about 6 tokens per second of speech; decoding stops past
+/// this rate so a hallucinating decoder can't loop forever
+const MAX_TOKENS_PER_SECOND: f32 = 6.5;
+
+/// Samples per millisecond at 16kHz
+const SAMPLES_PER_MS: usize = 16;
+
+/// Moonshine Base (English only) through ONNX Runtime. An alternative for
+/// speakers Whisper Small struggles with, e.g. heavy accents; it can't be
+/// prompted with keyterms and reports no timestamps.
+pub struct MoonshineEngine {
+    preprocess: Session,
+    encode: Session,
+    decode: Session,
+    /// Token text by ID; `▁` stands for a space
+    tokens: Vec<String>,
+}
+
+impl MoonshineEngine {
+    /// Load the models and vocabulary from the extracted archive folder
+    pub fn load(model_dir: &Path, threads: i32) -> Result<Self> {
+        Ok(Self {
+            preprocess: load_session(&model_dir.join(PREPROCESS_FILE), threads)?,
+            encode: load_session(&model_dir.join(ENCODE_FILE), threads)?,
+            decode: load_session(&model_dir.join(DECODE_FILE), threads)?,
+            tokens: load_tokens(&model_dir.join(TOKENS_FILE))?,
+        })
+    }
+
+    /// Run the preprocessor and encoder over a chunk, returning the encoder
+    /// output's shape and data
+    fn encode_audio(&mut self, samples: &[f32]) -> Result<(Vec<i64>, Vec<f32>)> {
+        let audio = Tensor::from_array(([1usize, samples.len()], samples.to_vec()))?;
+        let outputs = self.preprocess.run(ort::inputs![audio])?;
+        let (shape, features) = outputs[0].try_extract_tensor::<f32>()?;
+        let (features_shape, features) = (shape.to_vec(), features.to_vec());
+        drop(outputs);
+
+        // Features are [1, frames, dims]
+        let frames = features_shape.get(1).copied().unwrap_or(0) as i32;
+        let features = Tensor::from_array((features_shape, features))?;
+        let features_len = Tensor::from_array(([1usize], vec![frames]))?;
+        let outputs = self.encode.run(ort::inputs![features, features_len])?;
+        let (shape, encoded) = outputs[0].try_extract_tensor::<f32>()?;
+
+        Ok((shape.to_vec(), encoded.to_vec()))
+    }
+
+    /// Most likely next token after `tokens` and its probability
+    fn next_token(&mut self, tokens: &[i32], encoded: &(Vec<i64>, Vec<f32>)) -> Result<(i32, f32)> {
+        // The uncached decoder reruns the whole prefix each step; chunks are short
+        // enough that managing the key/value cache isn't worth it
+        let ids = Tensor::from_array(([1usize, tokens.len()], tokens.to_vec()))?;
+        let encoder_out = Tensor::from_array(encoded.clone())?;
+        let seq_len = Tensor::from_array(([1usize], vec![tokens.len() as i32]))?;
+        let outputs = self.decode.run(ort::inputs![ids, encoder_out, seq_len])?;
+        let (shape, logits) = outputs[0].try_extract_tensor::<f32>()?;
+
+        // Only the logits of the last position matter
+        let vocab = shape.last().copied().unwrap_or(0) as usize;
+        let last = &logits[logits.len().saturating_sub(vocab)..];
+
+        let (best, max) = last
+            .iter()
+            .enumerate()
+            .fold((0, f32::MIN), |best, (i, &logit)| {
+                if logit > best.1 {
+                    (i, logit)
+                } else {
+                    best
+                }
+            });
+        let sum: f32 = last.iter().map(|logit| (logit - max).exp()).sum();
+
+        Ok((best as i32, 1.0 / sum))
+    }
+
+    /// Text of decoded token IDs, resolving byte fallback tokens like `<0x0A>`
+    fn detokenize(&self, ids: &[i32]) -> String {
+        let mut bytes = Vec::new();
+        for token in ids.iter().filter_map(|&id| self.tokens.get(id as usize)) {
+            match token
+                .strip_prefix("<0x")
+                .and_then(|hex| hex.strip_suffix('>'))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => bytes.push(byte),
+                None => bytes.extend_from_slice(token.replace('▁', " ").as_bytes()),
+            }
+        }
+
+        String::from_utf8_lossy(&bytes).trim().to_string()
+    }
+}
+
+impl SttEngine for MoonshineEngine {
+    fn transcribe(&mut self, request: &ChunkRequest) -> Result<ChunkTranscript> {
+        let samples = request.samples;
+        let encoded = self.encode_audio(samples)?;
+
+        let seconds = (samples.len() / SAMPLES_PER_MS) as f32 / 1000.0;
+        let max_tokens = (seconds * MAX_TOKENS_PER_SECOND).ceil() as usize;
+        let mut tokens = vec![SOT_TOKEN];
+        let mut prob_sum = 0.0f32;
+
+        while tokens.len() <= max_tokens {
+            let (token, prob) = self.next_token(&tokens, &encoded)?;
+            if token == EOT_TOKEN {
+                break;
+            }
+            tokens.push(token);
+            prob_sum += prob;
+        }
+
+        let text = self.detokenize(&tokens[1..]);
+        let segments = if text.is_empty() {
+            Vec::new()
+        } else {
+            // One untimed segment per chunk; merging falls back to word matching
+            vec![TranscriptSegment {
+                start_ms: 0,
+                end_ms: (samples.len() / SAMPLES_PER_MS) as i64,
+                text: text.clone(),
+                confidence: prob_sum / (tokens.len() - 1).max(1) as f32,
+                words: Vec::new(),
+            }]
+        };
+
+        Ok(ChunkTranscript {
+            text,
+            segments,
+            language: None,
+        })
+    }
+}
+
+fn load_session(path: &Path, threads: i32) -> Result<Session> {
+    Session::builder()
+        .and_then(|builder| builder.with_intra_threads(threads.max(1) as usize))
+        .and_then(|builder| builder.commit_from_file(path))
+        .map_err(|e| anyhow::anyhow!("Failed to load Moonshine model {}: {}", path.display(), e))
+}
+
+/// Read `tokens.txt`, one `<token> <id>` pair per line
+fn load_tokens(path: &Path) -> Result<Vec<String>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read Moonshine tokens: {}", e))?;
+
+    let mut tokens = Vec::new();
+    for line in contents.lines() {
+        let Some((token, id)) = line.rsplit_once(' ') else {
+            continue;
+        };
+        let Ok(id) = id.parse::<usize>() else {
+            continue;
+        };
+        if tokens.len() <= id {
+            tokens.resize(id + 1, String::new());
+        }
+        tokens[id] = token.to_string();
+    }
+
+    Ok(tokens)
+}
@@ -0,0 +1,165 @@
+use anyhow::Result;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tracing::{debug, info};
+
+use crate::stt::cloud::{transcribe_with_failover, CloudRequest, ProviderKey};
+use crate::transcription::{
+    downmix_and_resample, samples_to_ms, start_local_microphone, RecordingMemory,
+    TranscriptionOutput, TranscriptionSettings, TRANSCRIPTION_SAMPLE_RATE,
+};
+
+/// Longest piece of a recording uploaded at once; 10 minutes of 16kHz 16-bit
+/// WAV stays under the providers' 25MB file limit
+pub const MAX_UPLOAD_SECS: usize = 600;
+
+/// Transcriber that records locally and uploads the audio to Groq or OpenAI on
+/// stop, failing over to the other provider when a request fails
+pub struct UploadTranscriber {
+    /// Audio sample buffer (shared with audio capture)
+    samples: Arc<std::sync::Mutex<Vec<f32>>>,
+    /// Channel to stop audio stream
+    local_stop_tx: Option<crossbeam_channel::Sender<()>>,
+    /// Sample rate of input audio
+    sample_rate: u32,
+    /// Number of channels in input audio
+    channels: u16,
+    /// Providers to try, in order
+    providers: Vec<ProviderKey>,
+    /// Language requested from the provider; `None` when auto-detecting
+    language: Option<String>,
+    /// Keyterms sent as the prompt
+    keyterms: Vec<String>,
+}
+
+impl UploadTranscriber {
+    /// Create a new UploadTranscriber trying `providers` in order
+    pub fn new(providers: Vec<ProviderKey>) -> Self {
+        Self {
+            samples: Arc::new(std::sync::Mutex::new(Vec::new())),
+            local_stop_tx: None,
+            sample_rate: 16000,
+            channels: 1,
+            providers,
+            language: None,
+            keyterms: Vec::new(),
+        }
+    }
+
+    /// Duration of audio captured so far in milliseconds
+    pub fn recorded_duration_ms(&self) -> i64 {
+        let len = self.samples.lock().map(|s| s.len()).unwrap_or(0);
+        let frames = len / self.channels.max(1) as usize;
+        (frames as u64 * 1000 / self.sample_rate.max(1) as u64) as i64
+    }
+
+    /// Audio buffered by this recording; everything is kept until the upload
+    pub fn memory_usage(&self) -> RecordingMemory {
+        let buffered = self.samples.lock().map(|s| s.len()).unwrap_or(0);
+        RecordingMemory::new(buffered, 0, 0)
+    }
+
+    /// Start capturing microphone audio for upload
+    pub async fn start(
+        &mut self,
+        app: AppHandle,
+        settings: TranscriptionSettings,
+        is_active: Arc<AtomicBool>,
+    ) -> Result<()> {
+        if let Ok(mut samples) = self.samples.lock() {
+            samples.clear();
+        }
+
+        self.language = if settings.auto_detect_language {
+            None
+        } else {
+            settings.languages.first().cloned()
+        };
+        self.keyterms = settings.keyterms;
+
+        let (sample_rate, channels, stop_tx) =
+            start_local_microphone(&app, is_active, self.samples.clone()).await?;
+
+        self.sample_rate = sample_rate;
+        self.channels = channels;
+        self.local_stop_tx = Some(stop_tx);
+
+        info!(
+            "Cloud transcription started (upload to {})",
+            self.providers
+                .iter()
+                .map(|p| p.provider.model())
+                .collect::<Vec<_>>()
+                .join(", then ")
+        );
+
+        Ok(())
+    }
+
+    /// Stop capturing and transcribe the recording, one upload per piece
+    pub async fn stop(&mut self) -> Result<TranscriptionOutput> {
+        if let Some(stop_tx) = self.local_stop_tx.take() {
+            let _ = stop_tx.send(());
+            debug!("Sent stop signal to audio stream");
+        }
+
+        let raw = self
+            .samples
+            .lock()
+            .map(|samples| samples.clone())
+            .map_err(|_| anyhow::anyhow!("Sample buffer lock poisoned"))?;
+        let audio = downmix_and_resample(&raw, self.sample_rate, self.channels);
+
+        let request = CloudRequest {
+            language: self.language.as_deref(),
+            keyterms: &self.keyterms,
+        };
+        let mut output = TranscriptionOutput {
+            language: self.language.clone(),
+            ..Default::default()
+        };
+        let mut texts: Vec<String> = Vec::new();
+
+        let piece_samples = MAX_UPLOAD_SECS * TRANSCRIPTION_SAMPLE_RATE as usize;
+        for (i, piece) in audio.chunks(piece_samples).enumerate() {
+            let (transcript, model) = transcribe_with_failover(&self.providers, piece, &request)
+                .await
+                .map_err(|e| anyhow::anyhow!("Cloud transcription failed: {}", e))?;
+
+            // Shift segment offsets from piece time to recording time
+            let offset_ms = samples_to_ms(i * piece_samples);
+            output
+                .segments
+                .extend(transcript.segments.into_iter().map(|mut segment| {
+                    segment.start_ms += offset_ms;
+                    segment.end_ms += offset_ms;
+                    for word in &mut segment.words {
+                        word.start_ms += offset_ms;
+                        word.end_ms += offset_ms;
+                    }
+                    segment
+                }));
+            if !transcript.text.is_empty() {
+                texts.push(transcript.text);
+            }
+            output.model = model.to_string();
+        }
+        output.text = texts.join(" ");
+        output.audio = audio;
+
+        info!("Cloud transcription complete: '{}'", output.text);
+
+        Ok(output)
+    }
+
+    /// Stop capturing and drop the audio without uploading it
+    pub fn cancel(&mut self) {
+        if let Some(stop_tx) = self.local_stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+        if let Ok(mut samples) = self.samples.lock() {
+            samples.clear();
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use anyhow::Result;
+use std::path::Path;
+use tracing::{debug, warn};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+use super::{ChunkRequest, SttEngine};
+use crate::transcription::{ChunkTranscript, TranscriptSegment, TranscriptWord};
+
+/// Prompt tokens kept for the tail of the previous chunk's text; the keyterms get the rest
+const CONTEXT_PROMPT_TOKENS: usize = 64;
+
+/// Whisper Small through whisper.cpp
+pub struct WhisperEngine {
+    ctx: WhisperContext,
+    /// Threads each inference uses
+    threads: i32,
+    /// Initial prompt built from the keyterms on the first chunk; they don't
+    /// change during a recording
+    keyterm_prompt: Option<Option<String>>,
+}
+
+impl WhisperEngine {
+    /// Load the ggml model file
+    pub fn load(model_path: &Path, threads: i32) -> Result<Self> {
+        // whisper.cpp takes a C string, so paths that aren't valid Unicode,
+        // possible on Windows, can't be passed
+        let model_path = model_path.to_str().ok_or_else(|| {
+            anyhow::anyhow!("Model path is not valid Unicode: {}", model_path.display())
+        })?;
+        let ctx = WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
+            .map_err(|e| anyhow::anyhow!("Failed to load Whisper model: {}", e))?;
+
+        Ok(Self {
+            ctx,
+            threads,
+            keyterm_prompt: None,
+        })
+    }
+}
+
+impl SttEngine for WhisperEngine {
+    fn transcribe(&mut self, request: &ChunkRequest) -> Result<ChunkTranscript> {
+        let ctx = &self.ctx;
+        let mut state = ctx
+            .create_state()
+            .map_err(|e| anyhow::anyhow!("Failed to create Whisper state: {}", e))?;
+
+        // Configure parameters
+        let decoding = &request.decoding;
+        let strategy = if decoding.beam_size > 1 {
+            SamplingStrategy::BeamSearch {
+                beam_size: decoding.beam_size,
+                patience: -1.0,
+            }
+        } else {
+            SamplingStrategy::Greedy { best_of: 1 }
+        };
+        let mut params = FullParams::new(strategy);
+        params.set_n_threads(self.threads);
+        params.set_temperature(decoding.temperature);
+        params.set_no_speech_thold(decoding.no_speech_threshold);
+        if decoding.max_segment_length > 0 {
+            params.set_max_len(decoding.max_segment_length);
+            params.set_split_on_word(true);
+        }
+
+        // Set language
+        params.set_language(request.language.and_then(to_whisper_lang));
+
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        params.set_suppress_blank(true);
+        params.set_suppress_nst(true);
+        // Per-token timestamps let chunk merging cut at the true overlap boundary
+        params.set_token_timestamps(true);
+
+        // Prompt with the keyterms, then the end of the previous chunk. Whisper reads
+        // the prompt as preceding text, so the context goes last.
+        let keyterm_prompt = self
+            .keyterm_prompt
+            .get_or_insert_with(|| build_keyterm_prompt(ctx, request.keyterms))
+            .as_deref();
+        let context = request
+            .previous_text
+            .and_then(|text| context_tail(ctx, text));
+        let prompt = match (keyterm_prompt, context) {
+            (Some(terms), Some(context)) => Some(format!("{} {}", terms, context)),
+            (Some(terms), None) => Some(terms.to_string()),
+            (None, context) => context,
+        };
+        if let Some(prompt) = prompt {
+            params.set_initial_prompt(&prompt);
+        }
+
+        // Run transcription
+        state
+            .full(params, request.samples)
+            .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?;
+
+        let language = if request.language.is_none() {
+            state
+                .full_lang_id_from_state()
+                .ok()
+                .and_then(whisper_rs::get_lang_str)
+                .map(str::to_string)
+        } else {
+            None
+        };
+
+        // Collect results
+        let mut text = String::new();
+        let mut segments = Vec::new();
+        let num_segments = state
+            .full_n_segments()
+            .map_err(|e| anyhow::anyhow!("Failed to get segments: {}", e))?;
+
+        // Token ids at or above end-of-text are special tokens (timestamps, language, ...)
+        let token_eot = ctx.token_eot();
+
+        for i in 0..num_segments {
+            let segment_text = match state.full_get_segment_text(i) {
+                Ok(segment) => segment,
+                Err(_) => continue,
+            };
+            text.push_str(&segment_text);
+            text.push(' ');
+
+            let mut prob_sum = 0.0f32;
+            let mut prob_count = 0usize;
+            let mut words: Vec<TranscriptWord> = Vec::new();
+            for t in 0..state.full_n_tokens(i).unwrap_or(0) {
+                match state.full_get_token_id(i, t) {
+                    Ok(id) if id < token_eot => {}
+                    _ => continue,
+                }
+                let prob = match state.full_get_token_prob(i, t) {
+                    Ok(prob) => prob,
+                    Err(_) => continue,
+                };
+                prob_sum += prob;
+                prob_count += 1;
+
+                // Token timestamps are in centiseconds like segment timestamps
+                let (token_start_ms, token_end_ms) = state
+                    .full_get_token_data(i, t)
+                    .map(|data| (data.t0 * 10, data.t1 * 10))
+                    .unwrap_or((0, 0));
+
+                // A leading space starts a new word; other tokens continue the current one
+                let token_text = state.full_get_token_text_lossy(i, t).unwrap_or_default();
+                match words.last_mut() {
+                    Some(word) if !token_text.starts_with(' ') => {
+                        word.text.push_str(&token_text);
+                        word.confidence = word.confidence.min(prob);
+                        word.end_ms = word.end_ms.max(token_end_ms);
+                    }
+                    _ => words.push(TranscriptWord {
+                        text: token_text.trim().to_string(),
+                        confidence: prob,
+                        start_ms: token_start_ms,
+                        end_ms: token_end_ms,
+                    }),
+                }
+            }
+            words.retain(|w| !w.text.is_empty());
+
+            // Whisper timestamps are in centiseconds
+            segments.push(TranscriptSegment {
+                start_ms: state.full_get_segment_t0(i).unwrap_or(0) * 10,
+                end_ms: state.full_get_segment_t1(i).unwrap_or(0) * 10,
+                text: segment_text.trim().to_string(),
+                confidence: if prob_count > 0 {
+                    prob_sum / prob_count as f32
+                } else {
+                    0.0
+                },
+                words,
+            });
+        }
+
+        Ok(ChunkTranscript {
+            text: text.trim().to_string(),
+            segments,
+            language,
+        })
+    }
+}
+
+/// Convert language code to Whisper language format
+fn to_whisper_lang(lang: &str) -> Option<&'static str> {
+    match lang {
+        "en-US" | "en-GB" => Some("en"),
+        "es" => Some("es"),
+        "fr" => Some("fr"),
+        "de" => Some("de"),
+        "it" => Some("it"),
+        "pt" => Some("pt"),
+        "ja" => Some("ja"),
+        "ko" => Some("ko"),
+        "zh" => Some("zh"),
+        _ => None,
+    }
+}
+
+/// Build the "Terms: ..." initial prompt from keyterms ranked most relevant first.
+/// Whisper only keeps the last `n_text_ctx / 2` prompt tokens, so whole terms are
+/// added while they fit that budget (minus the room kept for chunk context)
+/// instead of cutting the text at a byte length.
+fn build_keyterm_prompt(ctx: &WhisperContext, keyterms: &[String]) -> Option<String> {
+    const PREFIX: &str = "Terms: ";
+    // Room for the trailing period and tokenizer rounding
+    const BUDGET_MARGIN: usize = 4;
+
+    let n_text_ctx = ctx.n_text_ctx() as usize;
+    let budget = (n_text_ctx / 2).saturating_sub(CONTEXT_PROMPT_TOKENS + BUDGET_MARGIN);
+    let count_tokens = |text: &str| ctx.tokenize(text, n_text_ctx).map(|t| t.len());
+
+    let mut included: Vec<&str> = Vec::new();
+    let mut skipped = 0;
+
+    for term in keyterms.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        let mut candidate = included.clone();
+        candidate.push(term);
+        let prompt = format!("{}{}.", PREFIX, candidate.join(", "));
+
+        match count_tokens(&prompt) {
+            Ok(tokens) if tokens <= budget => included = candidate,
+            Ok(_) => skipped += 1,
+            Err(e) => {
+                warn!("Failed to tokenize keyterm prompt: {}", e);
+                skipped += 1;
+            }
+        }
+    }
+
+    if included.is_empty() {
+        return None;
+    }
+
+    let prompt = format!("{}{}.", PREFIX, included.join(", "));
+    debug!(
+        "Keyterm prompt includes {} of {} terms ({} skipped for the {}-token budget): {}",
+        included.len(),
+        included.len() + skipped,
+        skipped,
+        budget,
+        prompt
+    );
+
+    Some(prompt)
+}
+
+/// Last words of a chunk's text that fit in `CONTEXT_PROMPT_TOKENS`
+fn context_tail(ctx: &WhisperContext, text: &str) -> Option<String> {
+    let n_text_ctx = ctx.n_text_ctx() as usize;
+    // Every word is at least one token, so older words can never fit
+    let words: Vec<&str> = text
+        .split_whitespace()
+        .rev()
+        .take(CONTEXT_PROMPT_TOKENS)
+        .collect();
+
+    let mut tail: Option<String> = None;
+    for word in words {
+        let candidate = match &tail {
+            Some(tail) => format!("{} {}", word, tail),
+            None => word.to_string(),
+        };
+        match ctx.tokenize(&candidate, n_text_ctx) {
+            Ok(tokens) if tokens.len() <= CONTEXT_PROMPT_TOKENS => tail = Some(candidate),
+            _ => break,
+        }
+    }
+
+    tail
+}
@@ -0,0 +1,861 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use reqwest::{Client, StatusCode};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+use tracing::{error, info, warn};
+
+use crate::commands::keyterms::{keyterms_delete, keyterms_get, Keyterm};
+use crate::commands::notes::{notes_delete, notes_get, Note};
+use crate::commands::settings::{keys, read_setting};
+use crate::commands::transcriptions::{transcriptions_delete, transcriptions_get, Transcription};
+use crate::db::pool::get_connection;
+use crate::events::{emit_entity_event, names as event_names};
+use crate::jobs;
+use crate::keychain::{get_secret, SYNC_KEY_ACCOUNT, SYNC_TOKEN_ACCOUNT};
+
+/// How often the background worker syncs while sync is configured
+const SYNC_INTERVAL_SECS: u64 = 300;
+
+/// Timeout for each request to the sync backend
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Object holding the encrypted manifest of every synced record
+const MANIFEST_PATH: &str = "manifest";
+
+/// XChaCha20-Poly1305 nonce length; every object is `nonce || ciphertext`, with
+/// the object's path as associated data so objects can't be swapped for each other
+const NONCE_LEN: usize = 24;
+
+/// Sync runs are retried this many times when another device updates the manifest mid-run
+const MAX_ATTEMPTS: usize = 3;
+
+/// Only one sync runs at a time (worker or `sync_now`)
+static SYNC_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Outcome of the last sync run in this session
+static LAST_RUN: Mutex<Option<SyncRun>> = Mutex::new(None);
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Kinds of records that are synced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncEntity {
+    Transcription,
+    Note,
+    Keyterm,
+}
+
+impl SyncEntity {
+    const ALL: [SyncEntity; 3] = [Self::Transcription, Self::Note, Self::Keyterm];
+
+    /// Name used in object paths and the `sync_tombstones` table
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Transcription => "transcription",
+            Self::Note => "note",
+            Self::Keyterm => "keyterm",
+        }
+    }
+
+    fn table(&self) -> &'static str {
+        match self {
+            Self::Transcription => "transcriptions",
+            Self::Note => "notes",
+            Self::Keyterm => "keyterms",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|entity| entity.as_str() == name)
+    }
+}
+
+/// Latest known version of a record on the backend
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ManifestEntry {
+    updated_at: i64,
+    #[serde(default)]
+    deleted: bool,
+    /// Object holding this version. Every upload goes to a new object, so a device
+    /// that loses the manifest race never overwrites what the winner referenced.
+    /// `None` only for deleted records.
+    object: Option<String>,
+}
+
+impl ManifestEntry {
+    /// Path of the object holding the record, if it isn't deleted
+    fn object_path(&self) -> Option<&str> {
+        if self.deleted {
+            None
+        } else {
+            self.object.as_deref()
+        }
+    }
+}
+
+/// Every synced record keyed by `entity/id`, stored encrypted next to the records
+type Manifest = BTreeMap<String, ManifestEntry>;
+
+/// A record as it exists on this device
+#[derive(Debug, Clone, Copy)]
+enum LocalRecord {
+    Live { updated_at: i64 },
+    Deleted { deleted_at: i64 },
+}
+
+impl LocalRecord {
+    fn timestamp(&self) -> i64 {
+        match self {
+            Self::Live { updated_at } => *updated_at,
+            Self::Deleted { deleted_at } => *deleted_at,
+        }
+    }
+}
+
+/// What a sync run changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+pub struct SyncSummary {
+    /// Records uploaded from this device (including deletions)
+    pub pushed: usize,
+    /// Records downloaded from the backend
+    pub pulled: usize,
+    /// Local records removed because they were deleted on another device
+    pub deleted: usize,
+}
+
+#[derive(Debug, Clone)]
+struct SyncRun {
+    finished_at: i64,
+    error: Option<String>,
+}
+
+/// Where sync state stands, for the settings screen
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct SyncStatus {
+    pub configured: bool,
+    pub endpoint: Option<String>,
+    pub syncing: bool,
+    /// When the last successful sync in this session finished (Unix seconds)
+    pub last_synced_at: Option<i64>,
+    /// Error from the last sync, if it failed
+    pub last_error: Option<String>,
+    /// Local changes and deletions not pushed yet
+    pub pending_changes: i64,
+}
+
+/// Backend location and credentials
+struct SyncConfig {
+    endpoint: String,
+    token: Option<String>,
+    cipher: XChaCha20Poly1305,
+}
+
+// ============================================================================
+// Sync
+// ============================================================================
+
+/// Push local changes and pull remote ones. Conflicts resolve to the most recent
+/// change (last writer wins), deletions included.
+pub async fn sync_now(app: &AppHandle) -> Result<SyncSummary, String> {
+    let config = load_config()?.ok_or_else(|| "Sync is not configured".to_string())?;
+
+    let _guard = SYNC_LOCK.lock().await;
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut result = Err("The sync manifest kept changing on the server".to_string());
+    for _ in 0..MAX_ATTEMPTS {
+        match run(app, &client, &config).await {
+            Ok(Some(summary)) => {
+                result = Ok(summary);
+                break;
+            }
+            // Another device wrote the manifest first; start over with its version
+            Ok(None) => continue,
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+
+    *LAST_RUN.lock().unwrap() = Some(SyncRun {
+        finished_at: now(),
+        error: result.as_ref().err().cloned(),
+    });
+
+    match &result {
+        Ok(summary) => {
+            info!(
+                "🔄 Sync finished: {} pushed, {} pulled, {} deleted",
+                summary.pushed, summary.pulled, summary.deleted
+            );
+            let _ = app.emit(event_names::SYNC_COMPLETED, summary.clone());
+        }
+        Err(e) => error!("Sync failed: {}", e),
+    }
+
+    result
+}
+
+/// One pass over the manifest. Returns `None` when the manifest changed on the
+/// server before ours could be written.
+async fn run(
+    app: &AppHandle,
+    client: &Client,
+    config: &SyncConfig,
+) -> Result<Option<SyncSummary>, String> {
+    let (remote, etag) = match get_object(client, config, MANIFEST_PATH).await? {
+        Some((data, Some(etag))) => (Some(parse_manifest(&config.cipher, &data)?), Some(etag)),
+        // Without an ETag the manifest can't be replaced without racing other devices
+        Some((_, None)) => {
+            return Err("The sync backend didn't return an ETag for the manifest".to_string())
+        }
+        None => (None, None),
+    };
+    let manifest_exists = remote.is_some();
+    let remote = remote.unwrap_or_default();
+
+    let local = local_records()?;
+    let mut next = remote.clone();
+    let mut summary = SyncSummary::default();
+
+    // Bookkeeping that only holds once the new manifest is written
+    let mut pushed: Vec<(SyncEntity, String, i64)> = Vec::new();
+    let mut cleared_tombstones: Vec<(SyncEntity, String)> = Vec::new();
+    // Objects uploaded this run, and the versions they replace
+    let mut uploaded: Vec<String> = Vec::new();
+    let mut superseded: Vec<String> = Vec::new();
+
+    let all_keys: BTreeSet<&String> = remote.keys().chain(local.keys()).collect();
+    for key in all_keys {
+        let Some((entity, id)) = parse_key(key) else {
+            warn!("Skipping unknown sync record '{}'", key);
+            continue;
+        };
+        let local_record = local.get(key).copied();
+        let remote_entry = remote.get(key);
+
+        match (local_record, remote_entry) {
+            // The backend has a newer version
+            (local_record, Some(entry))
+                if local_record.map_or(true, |record| entry.updated_at > record.timestamp()) =>
+            {
+                match (entry.object_path(), local_record) {
+                    (None, Some(LocalRecord::Live { .. })) => {
+                        apply_delete(app, entity, &id)?;
+                        summary.deleted += 1;
+                    }
+                    (None, Some(LocalRecord::Deleted { .. })) => clear_tombstone(entity, &id)?,
+                    (None, None) => {}
+                    (Some(path), _) => {
+                        // Replaced by a newer version since the manifest was read
+                        let Some((data, _)) = get_object(client, config, path).await? else {
+                            delete_objects(client, config, &uploaded).await;
+                            return Ok(None);
+                        };
+                        apply_record(
+                            app,
+                            entity,
+                            &id,
+                            entry.updated_at,
+                            &decrypt(&config.cipher, &data, path.as_bytes())?,
+                        )?;
+                        summary.pulled += 1;
+                    }
+                }
+            }
+            // This device has a newer (or the only) version
+            (Some(LocalRecord::Live { updated_at }), remote_entry)
+                if remote_entry.map_or(true, |entry| updated_at > entry.updated_at) =>
+            {
+                // Deleted locally since the changes were listed
+                let Some(data) = read_record(entity, &id)? else {
+                    continue;
+                };
+                let path = version_path(key);
+                let body = encrypt(&config.cipher, &data, path.as_bytes())?;
+                if !put_object(client, config, &path, body, Precondition::Absent).await? {
+                    return Err(format!("Failed to upload sync record '{}'", key));
+                }
+                uploaded.push(path.clone());
+                superseded.extend(
+                    remote_entry
+                        .and_then(ManifestEntry::object_path)
+                        .map(String::from),
+                );
+                next.insert(
+                    key.clone(),
+                    ManifestEntry {
+                        updated_at,
+                        deleted: false,
+                        object: Some(path),
+                    },
+                );
+                pushed.push((entity, id, updated_at));
+                summary.pushed += 1;
+            }
+            (Some(LocalRecord::Deleted { deleted_at }), remote_entry) => {
+                if let Some(path) = remote_entry.and_then(ManifestEntry::object_path) {
+                    superseded.push(path.to_string());
+                    next.insert(
+                        key.clone(),
+                        ManifestEntry {
+                            updated_at: deleted_at,
+                            deleted: true,
+                            object: None,
+                        },
+                    );
+                    summary.pushed += 1;
+                }
+                cleared_tombstones.push((entity, id));
+            }
+            // Already in sync
+            _ => {}
+        }
+    }
+
+    if next != remote || !manifest_exists {
+        let manifest = serde_json::to_vec(&next)
+            .map_err(|e| format!("Failed to serialize sync manifest: {}", e))?;
+        let precondition = match &etag {
+            Some(etag) => Precondition::Matches(etag),
+            None => Precondition::Absent,
+        };
+        if !put_object(
+            client,
+            config,
+            MANIFEST_PATH,
+            encrypt(&config.cipher, &manifest, MANIFEST_PATH.as_bytes())?,
+            precondition,
+        )
+        .await?
+        {
+            // Nothing references this run's uploads; the next attempt makes its own
+            delete_objects(client, config, &uploaded).await;
+            return Ok(None);
+        }
+    }
+
+    // Only this device's manifest referenced the old versions
+    delete_objects(client, config, &superseded).await;
+
+    for (entity, id, updated_at) in pushed {
+        mark_synced(entity, &id, updated_at)?;
+    }
+    for (entity, id) in cleared_tombstones {
+        clear_tombstone(entity, &id)?;
+    }
+
+    Ok(Some(summary))
+}
+
+/// Current sync state
+pub fn status() -> Result<SyncStatus, String> {
+    let endpoint = read_setting(keys::SYNC_ENDPOINT).filter(|endpoint| !endpoint.is_empty());
+    let last_run = LAST_RUN.lock().unwrap().clone();
+
+    Ok(SyncStatus {
+        configured: endpoint.is_some(),
+        endpoint,
+        syncing: SYNC_LOCK.try_lock().is_err(),
+        last_synced_at: last_run
+            .as_ref()
+            .filter(|run| run.error.is_none())
+            .map(|run| run.finished_at),
+        last_error: last_run.and_then(|run| run.error),
+        pending_changes: pending_changes()?,
+    })
+}
+
+/// Register the `sync` job, which syncs periodically while sync is configured
+pub fn register_sync_job(app: AppHandle) {
+    let interval = Duration::from_secs(SYNC_INTERVAL_SECS);
+    jobs::register("sync", interval, interval, move || {
+        let app = app.clone();
+        async move {
+            if load_config()
+                .map_err(|e| format!("Sync is misconfigured: {}", e))?
+                .is_none()
+            {
+                return Ok(());
+            }
+            sync_now(&app).await.map(|_| ())
+        }
+    });
+}
+
+// ============================================================================
+// Encryption
+// ============================================================================
+
+/// Generate a new random encryption key, base64-encoded
+pub fn generate_key() -> String {
+    BASE64.encode(XChaCha20Poly1305::generate_key(&mut OsRng))
+}
+
+/// Check that a base64 key is usable before it is stored
+pub fn validate_key(key: &str) -> Result<(), String> {
+    cipher_from_key(key).map(|_| ())
+}
+
+fn cipher_from_key(key: &str) -> Result<XChaCha20Poly1305, String> {
+    let bytes = BASE64
+        .decode(key.trim())
+        .map_err(|_| "Encryption key is not valid base64".to_string())?;
+    XChaCha20Poly1305::new_from_slice(&bytes)
+        .map_err(|_| "Encryption key must be 32 bytes".to_string())
+}
+
+/// Encrypt an object bound to `aad`, its path on the backend
+fn encrypt(cipher: &XChaCha20Poly1305, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| "Failed to encrypt sync data".to_string())?;
+
+    let mut data = nonce.to_vec();
+    data.extend(ciphertext);
+    Ok(data)
+}
+
+fn decrypt(cipher: &XChaCha20Poly1305, data: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+    if data.len() < NONCE_LEN {
+        return Err("Sync data is truncated".to_string());
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    cipher
+        .decrypt(
+            XNonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| {
+            "Failed to decrypt sync data; every device must use the same encryption key".to_string()
+        })
+}
+
+/// Decrypt and parse the manifest, rejecting live records that point at no object
+fn parse_manifest(cipher: &XChaCha20Poly1305, data: &[u8]) -> Result<Manifest, String> {
+    let manifest: Manifest =
+        serde_json::from_slice(&decrypt(cipher, data, MANIFEST_PATH.as_bytes())?)
+            .map_err(|e| format!("Invalid sync manifest: {}", e))?;
+
+    match manifest
+        .iter()
+        .find(|(_, entry)| !entry.deleted && entry.object.is_none())
+    {
+        Some((key, _)) => Err(format!("Invalid sync manifest: '{}' has no object", key)),
+        None => Ok(manifest),
+    }
+}
+
+// ============================================================================
+// Backend
+// ============================================================================
+
+/// Condition for overwriting an object, so concurrent writers don't lose updates
+enum Precondition<'a> {
+    /// Only if the object still has this ETag
+    Matches(&'a str),
+    /// Only if the object doesn't exist yet
+    Absent,
+}
+
+fn object_url(config: &SyncConfig, path: &str) -> String {
+    format!("{}/{}", config.endpoint.trim_end_matches('/'), path)
+}
+
+fn authorize(request: reqwest::RequestBuilder, config: &SyncConfig) -> reqwest::RequestBuilder {
+    match &config.token {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+/// Download an object with its ETag; `None` if it doesn't exist
+async fn get_object(
+    client: &Client,
+    config: &SyncConfig,
+    path: &str,
+) -> Result<Option<(Vec<u8>, Option<String>)>, String> {
+    let response = authorize(client.get(object_url(config, path)), config)
+        .send()
+        .await
+        .map_err(|e| format!("Sync request failed: {}", e))?;
+
+    match response.status() {
+        StatusCode::NOT_FOUND => Ok(None),
+        status if status.is_success() => {
+            let etag = response
+                .headers()
+                .get(reqwest::header::ETAG)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string());
+            let body = response
+                .bytes()
+                .await
+                .map_err(|e| format!("Failed to read sync response: {}", e))?;
+            Ok(Some((body.to_vec(), etag)))
+        }
+        status => Err(format!("Sync backend returned {} for '{}'", status, path)),
+    }
+}
+
+/// Upload an object. Returns false when the precondition no longer holds.
+async fn put_object(
+    client: &Client,
+    config: &SyncConfig,
+    path: &str,
+    body: Vec<u8>,
+    precondition: Precondition<'_>,
+) -> Result<bool, String> {
+    let mut request = authorize(client.put(object_url(config, path)), config)
+        .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+        .body(body);
+    request = match precondition {
+        Precondition::Matches(etag) => request.header(reqwest::header::IF_MATCH, etag),
+        Precondition::Absent => request.header(reqwest::header::IF_NONE_MATCH, "*"),
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Sync request failed: {}", e))?;
+
+    match response.status() {
+        StatusCode::PRECONDITION_FAILED => Ok(false),
+        status if status.is_success() => Ok(true),
+        status => Err(format!("Sync backend returned {} for '{}'", status, path)),
+    }
+}
+
+/// Delete objects that are no longer referenced. Failures only leave garbage
+/// behind, so they are logged rather than failing the sync.
+async fn delete_objects(client: &Client, config: &SyncConfig, paths: &[String]) {
+    for path in paths {
+        if let Err(e) = delete_object(client, config, path).await {
+            warn!("Failed to delete old sync object: {}", e);
+        }
+    }
+}
+
+/// Delete an object; deleting a missing object is not an error
+async fn delete_object(client: &Client, config: &SyncConfig, path: &str) -> Result<(), String> {
+    let response = authorize(client.delete(object_url(config, path)), config)
+        .send()
+        .await
+        .map_err(|e| format!("Sync request failed: {}", e))?;
+
+    match response.status() {
+        StatusCode::NOT_FOUND => Ok(()),
+        status if status.is_success() => Ok(()),
+        status => Err(format!("Sync backend returned {} for '{}'", status, path)),
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Read the backend settings; `None` when sync is not configured
+fn load_config() -> Result<Option<SyncConfig>, String> {
+    let endpoint = match read_setting(keys::SYNC_ENDPOINT).filter(|e| !e.is_empty()) {
+        Some(endpoint) => endpoint,
+        None => return Ok(None),
+    };
+    let key = get_secret(SYNC_KEY_ACCOUNT)?
+        .ok_or_else(|| "Sync encryption key is missing from the Keychain".to_string())?;
+
+    Ok(Some(SyncConfig {
+        endpoint,
+        token: get_secret(SYNC_TOKEN_ACCOUNT)?,
+        cipher: cipher_from_key(&key)?,
+    }))
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+fn record_key(entity: SyncEntity, id: &str) -> String {
+    format!("{}/{}", entity.as_str(), id)
+}
+
+/// A new object path for an upload of the record at `key`
+fn version_path(key: &str) -> String {
+    format!("{}/{}", key, uuid::Uuid::new_v4())
+}
+
+fn parse_key(key: &str) -> Option<(SyncEntity, String)> {
+    let (entity, id) = key.split_once('/')?;
+    Some((SyncEntity::from_name(entity)?, id.to_string()))
+}
+
+/// Every local record and pending deletion, keyed like the manifest
+fn local_records() -> Result<BTreeMap<String, LocalRecord>, String> {
+    let conn = get_connection()?;
+    let mut records = BTreeMap::new();
+
+    let mut stmt = conn
+        .prepare("SELECT entity, entity_id, deleted_at FROM sync_tombstones")
+        .map_err(|e| e.to_string())?;
+    let tombstones = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    for (entity, id, deleted_at) in tombstones {
+        records.insert(
+            format!("{}/{}", entity, id),
+            LocalRecord::Deleted { deleted_at },
+        );
+    }
+
+    // A live row wins over a stale tombstone with the same id
+    for entity in SyncEntity::ALL {
+        let mut stmt = conn
+            .prepare(&format!("SELECT id, updated_at FROM {}", entity.table()))
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        for (id, updated_at) in rows {
+            records.insert(record_key(entity, &id), LocalRecord::Live { updated_at });
+        }
+    }
+
+    Ok(records)
+}
+
+/// Count local changes that haven't been pushed yet
+fn pending_changes() -> Result<i64, String> {
+    let conn = get_connection()?;
+
+    let mut total: i64 = conn
+        .query_row("SELECT COUNT(*) FROM sync_tombstones", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    for entity in SyncEntity::ALL {
+        let count: i64 = conn
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM {} WHERE synced_at IS NULL OR updated_at > synced_at",
+                    entity.table()
+                ),
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        total += count;
+    }
+
+    Ok(total)
+}
+
+/// Serialize a local record for upload; `None` if it no longer exists
+fn read_record(entity: SyncEntity, id: &str) -> Result<Option<Vec<u8>>, String> {
+    let data = match entity {
+        SyncEntity::Transcription => {
+            transcriptions_get(id.to_string()).map(|t| serde_json::to_vec(&t))
+        }
+        SyncEntity::Note => notes_get(id.to_string()).map(|n| serde_json::to_vec(&n)),
+        SyncEntity::Keyterm => keyterms_get(id.to_string()).map(|k| serde_json::to_vec(&k)),
+    };
+
+    match data {
+        Ok(json) => json
+            .map(Some)
+            .map_err(|e| format!("Failed to serialize {}: {}", entity.as_str(), e)),
+        Err(e) if matches!(e.code, crate::commands::error::ErrorCode::NotFound) => Ok(None),
+        Err(e) => Err(e.message),
+    }
+}
+
+/// Insert or overwrite a local record with the version from the backend
+fn apply_record(
+    app: &AppHandle,
+    entity: SyncEntity,
+    id: &str,
+    updated_at: i64,
+    data: &[u8],
+) -> Result<(), String> {
+    let conn = get_connection()?;
+    let invalid = |e: serde_json::Error| format!("Invalid {} from sync: {}", entity.as_str(), e);
+
+    // synced_at = updated_at marks the row as matching the backend, and setting
+    // updated_at explicitly keeps the update trigger from bumping it
+    match entity {
+        SyncEntity::Transcription => {
+            let t: Transcription = serde_json::from_slice(data).map_err(invalid)?;
+            conn.execute(
+                "INSERT INTO transcriptions (id, text, formatted_text, created_at, is_pinned, app_name, updated_at, synced_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+                 ON CONFLICT(id) DO UPDATE SET
+                   text = excluded.text,
+                   formatted_text = excluded.formatted_text,
+                   is_pinned = excluded.is_pinned,
+                   app_name = excluded.app_name,
+                   updated_at = excluded.updated_at,
+                   synced_at = excluded.synced_at",
+                params![id, t.text, t.formatted_text, t.created_at, t.is_pinned, t.app_name, updated_at],
+            )
+            .map_err(|e| e.to_string())?;
+            emit_entity_event(app, event_names::TRANSCRIPTIONS_UPDATED, t)?;
+        }
+        SyncEntity::Note => {
+            let n: Note = serde_json::from_slice(data).map_err(invalid)?;
+            conn.execute(
+                "INSERT INTO notes (id, title, content, created_at, is_pinned, archived, updated_at, synced_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+                 ON CONFLICT(id) DO UPDATE SET
+                   title = excluded.title,
+                   content = excluded.content,
+                   is_pinned = excluded.is_pinned,
+                   archived = excluded.archived,
+                   updated_at = excluded.updated_at,
+                   synced_at = excluded.synced_at",
+                params![id, n.title, n.content, n.created_at, n.is_pinned, n.archived, updated_at],
+            )
+            .map_err(|e| e.to_string())?;
+            emit_entity_event(app, event_names::NOTES_UPDATED, n)?;
+        }
+        SyncEntity::Keyterm => {
+            let k: Keyterm = serde_json::from_slice(data).map_err(invalid)?;
+            // The same term added on two devices: keep the local copy, since
+            // keyterms are unique per category
+            let duplicate: bool = conn
+                .query_row(
+                    "SELECT EXISTS(SELECT 1 FROM keyterms
+                     WHERE text = ?1 COLLATE NOCASE AND category = ?2 AND id != ?3)",
+                    params![k.text, k.category, id],
+                    |row| row.get(0),
+                )
+                .map_err(|e| e.to_string())?;
+            if !duplicate {
+                conn.execute(
+                    "INSERT INTO keyterms (id, text, category, created_at, updated_at, synced_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+                     ON CONFLICT(id) DO UPDATE SET
+                       text = excluded.text,
+                       category = excluded.category,
+                       updated_at = excluded.updated_at,
+                       synced_at = excluded.synced_at",
+                    params![id, k.text, k.category, k.created_at, updated_at],
+                )
+                .map_err(|e| e.to_string())?;
+                emit_entity_event(app, event_names::KEYTERMS_UPDATED, k)?;
+            }
+        }
+    }
+
+    // A pulled record supersedes any local deletion of it
+    clear_tombstone(entity, id)
+}
+
+/// Delete a local record that was deleted on another device
+fn apply_delete(app: &AppHandle, entity: SyncEntity, id: &str) -> Result<(), String> {
+    let result = match entity {
+        SyncEntity::Transcription => transcriptions_delete(app.clone(), id.to_string()),
+        SyncEntity::Note => notes_delete(app.clone(), id.to_string()),
+        SyncEntity::Keyterm => keyterms_delete(app.clone(), id.to_string()),
+    };
+    match result {
+        Ok(()) => {}
+        Err(e) if matches!(e.code, crate::commands::error::ErrorCode::NotFound) => {}
+        Err(e) => return Err(e.message),
+    }
+
+    // The delete trigger recorded a tombstone, but the backend already has it
+    clear_tombstone(entity, id)
+}
+
+fn mark_synced(entity: SyncEntity, id: &str, updated_at: i64) -> Result<(), String> {
+    let conn = get_connection()?;
+    conn.execute(
+        &format!("UPDATE {} SET synced_at = ? WHERE id = ?", entity.table()),
+        params![updated_at, id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn clear_tombstone(entity: SyncEntity, id: &str) -> Result<(), String> {
+    let conn = get_connection()?;
+    conn.execute(
+        "DELETE FROM sync_tombstones WHERE entity = ? AND entity_id = ?",
+        params![entity.as_str(), id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher() -> XChaCha20Poly1305 {
+        cipher_from_key(&generate_key()).unwrap()
+    }
+
+    #[test]
+    fn objects_only_decrypt_at_their_own_path() {
+        let cipher = cipher();
+        let data = encrypt(&cipher, b"record", b"note/1/a").unwrap();
+
+        assert_eq!(decrypt(&cipher, &data, b"note/1/a").unwrap(), b"record");
+        assert!(decrypt(&cipher, &data, b"note/2/b").is_err());
+        assert!(parse_manifest(&cipher, &data).is_err());
+    }
+
+    #[test]
+    fn live_records_must_point_at_an_object() {
+        let cipher = cipher();
+        let manifest = |json: &str| {
+            let data = encrypt(&cipher, json.as_bytes(), MANIFEST_PATH.as_bytes()).unwrap();
+            parse_manifest(&cipher, &data)
+        };
+
+        let parsed = manifest(
+            r#"{"note/1":{"updated_at":2,"deleted":false,"object":"note/1/a"},
+                "note/2":{"updated_at":3,"deleted":true,"object":null}}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed["note/1"].object_path(), Some("note/1/a"));
+        assert_eq!(parsed["note/2"].object_path(), None);
+
+        assert!(manifest(r#"{"note/1":{"updated_at":2,"deleted":false,"object":null}}"#).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+/// The first `max_chars` characters of `text`. Cuts on a character boundary, so
+/// it is safe for multibyte text where slicing by byte index would panic.
+pub fn truncate(text: &str, max_chars: usize) -> &str {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+/// `text` cut to `max_chars` characters with `…` appended when anything was cut,
+/// for logs, labels and titles
+pub fn preview(text: &str, max_chars: usize) -> String {
+    let truncated = truncate(text, max_chars);
+    if truncated.len() < text.len() {
+        format!("{}…", truncated.trim_end())
+    } else {
+        text.to_string()
+    }
+}
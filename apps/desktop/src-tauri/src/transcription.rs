@@ -1,52 +1,75 @@
 use anyhow::Result;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Sample, SampleFormat};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::thread;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tauri::{AppHandle, Emitter, Manager};
-use tauri_plugin_store::StoreExt;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime, Wry};
+use tauri_specta::Event;
 use tokio::sync::Mutex;
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
-
-use crate::formatter::format_text;
-
-/// Saves a transcription to the local SQLite database.
-fn save_transcription_to_db(
-    app: &AppHandle,
-    text: &str,
-    formatted_text: Option<&str>,
-) -> Result<String, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-
-    let db_path = app_data_dir.join("dicto.db");
-
-    let conn = rusqlite::Connection::open(&db_path)
-        .map_err(|e| format!("Failed to open database: {:?}: {}", db_path, e))?;
-
-    let id = uuid::Uuid::new_v4().to_string();
-    let created_at = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| format!("Failed to get timestamp: {}", e))?
-        .as_secs() as i64;
-
-    // Use formatted_text if provided, otherwise use raw text
-    let formatted = formatted_text.unwrap_or(text);
-    conn.execute(
-        "INSERT INTO transcriptions (id, text, formatted_text, created_at) VALUES (?1, ?2, ?3, ?4)",
-        rusqlite::params![id, text, formatted, created_at],
-    )
-    .map_err(|e| format!("Failed to insert transcription: {}", e))?;
-
-    println!("✅ Saved local transcription with id: {}", id);
-
-    Ok(id)
+use tracing::{debug, info, warn};
+
+use crate::app_context::{self, RunningApp};
+use crate::audio_level;
+use crate::chat;
+use crate::commands::keys_vault::{keys_vault_get, VaultService};
+use crate::commands::keyterms::{keyterms_for_category, record_keyterm_usage};
+use crate::commands::notes::{notes_create, CreateNoteInput};
+use crate::commands::settings::{
+    keys as setting_keys, read_bool_setting, read_json_setting, read_parsed_setting, read_setting,
+};
+use crate::commands::transcriptions::transcriptions_get;
+use crate::commands::url_rules;
+use crate::commands::writing_styles::{custom_prompt_for, MARKDOWN_STYLE};
+use crate::diagnostics;
+use crate::events::{emit_entity_event, names as event_names};
+use crate::expansion;
+use crate::formatter::{format_text, read_auth_token, FormatContext};
+use crate::formatting_queue;
+use crate::interpreter;
+use crate::microphone;
+use crate::mirror;
+use crate::model_download::{
+    model_installed, model_path, selected_stt_model, smallest_installed_stt_model,
+};
+use crate::model_registry::{ModelEntry, ModelKind, ModelRegistry, DEFAULT_STT_MODEL};
+use crate::output::{emit_paste_complete, paste_into};
+use crate::performance;
+use crate::persistence::{save_transcription, update_refined_transcription, SaveOutcome};
+use crate::placeholders;
+use crate::power;
+use crate::recordings;
+use crate::redaction::redact_url_if_enabled;
+use crate::rewriter;
+use crate::session::{LiveSession, SessionSummary};
+use crate::spill::{self, AudioSpill};
+use crate::stt::cloud::{
+    preferred_providers, refinement_providers, transcribe_with_failover, CloudRequest,
+    CloudTranscriber, ProviderKey,
+};
+use crate::stt::upload::{UploadTranscriber, MAX_UPLOAD_SECS};
+use crate::stt::{load_engine, ChunkRequest, SttEngine};
+use crate::text;
+use crate::translator;
+use crate::tray;
+use crate::voice_processing;
+use crate::window;
+use crate::workspace::RecordingWorkspace;
+
+/// How a recording is turned into output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordingMode {
+    /// Push-to-talk dictation pasted into the frontmost app
+    #[default]
+    Dictation,
+    /// Long-form meeting session streamed into a live transcript document
+    Session,
+    /// Dictated message sent to a chat session instead of pasted
+    Chat,
 }
 
 #[derive(Debug, Clone, Deserialize, specta::Type)]
@@ -54,10 +77,28 @@ fn save_transcription_to_db(
 pub struct TranscriptionSettings {
     pub auto_detect_language: bool,
     pub languages: Vec<String>,
-    /// Keyterms to boost recognition (technical terms, proper nouns, acronyms)
+    /// Category of the app being dictated into (Personal, Work, Email, ...);
+    /// selects which keyterms apply
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Name of the frontmost app when recording started, saved with the transcription
+    #[serde(default)]
+    pub app_name: Option<String>,
+    /// Keyterms to boost recognition (technical terms, proper nouns, acronyms),
+    /// resolved from `category` when recording starts
+    #[serde(skip)]
     pub keyterms: Vec<String>,
-    /// Whether to use cloud transcription (AssemblyAI)
+    /// Whether to use cloud transcription (Deepgram streaming when a key is in the vault)
     pub use_cloud: bool,
+    /// Dictation (paste on stop), session (live transcript document) or chat
+    #[serde(default)]
+    pub mode: RecordingMode,
+    /// Title for the session record when recording in session mode
+    #[serde(default)]
+    pub session_title: Option<String>,
+    /// Chat session the dictation is sent to when recording in chat mode
+    #[serde(default)]
+    pub chat_session_id: Option<String>,
 }
 
 impl Default for TranscriptionSettings {
@@ -65,8 +106,30 @@ impl Default for TranscriptionSettings {
         Self {
             auto_detect_language: false,
             languages: vec!["en-US".to_string()],
+            category: None,
+            app_name: None,
             keyterms: Vec::new(),
             use_cloud: false,
+            mode: RecordingMode::Dictation,
+            session_title: None,
+            chat_session_id: None,
+        }
+    }
+}
+
+impl TranscriptionSettings {
+    /// Settings saved in the dashboard, for recordings started outside the widget
+    pub fn from_saved_settings() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            auto_detect_language: read_bool_setting(
+                setting_keys::AUTO_DETECT_LANGUAGE,
+                defaults.auto_detect_language,
+            ),
+            languages: read_json_setting(setting_keys::LANGUAGES, defaults.languages),
+            use_cloud: read_bool_setting(setting_keys::CLOUD_TRANSCRIPTION, true),
+            ..defaults
         }
     }
 }
@@ -76,7 +139,7 @@ impl Default for TranscriptionSettings {
 // ============================================================================
 
 /// Sample rate for transcription (Whisper requires 16kHz)
-const TRANSCRIPTION_SAMPLE_RATE: u32 = 16000;
+pub(crate) const TRANSCRIPTION_SAMPLE_RATE: u32 = 16000;
 
 /// Chunk duration in seconds (~5 for good balance of latency and accuracy)
 const CHUNK_DURATION_SECS: f32 = 5.0;
@@ -87,12 +150,221 @@ const OVERLAP_DURATION_SECS: f32 = 0.5;
 /// Number of samples per chunk (5s * 16000 samples/s = 480,000)
 const CHUNK_SIZE_SAMPLES: usize = (TRANSCRIPTION_SAMPLE_RATE as f32 * CHUNK_DURATION_SECS) as usize;
 
+/// Chunk duration in low power mode; fewer, longer inferences cost less overhead
+const LOW_POWER_CHUNK_DURATION_SECS: f32 = 10.0;
+
+/// Number of samples per chunk in low power mode
+const LOW_POWER_CHUNK_SIZE_SAMPLES: usize =
+    (TRANSCRIPTION_SAMPLE_RATE as f32 * LOW_POWER_CHUNK_DURATION_SECS) as usize;
+
+/// Longest chunk the processor grows to when the queue backs up; Whisper
+/// decodes 30s windows, so anything shorter costs about the same per inference
+const MAX_CHUNK_DURATION_SECS: f32 = 25.0;
+
+/// Number of samples per chunk at the longest chunk duration
+const MAX_CHUNK_SIZE_SAMPLES: usize =
+    (TRANSCRIPTION_SAMPLE_RATE as f32 * MAX_CHUNK_DURATION_SECS) as usize;
+
+/// Seconds of transcribed audio collected before it is spilled to disk, so long
+/// recordings only keep the untranscribed tail in memory
+const SPILL_BLOCK_SECS: usize = 30;
+
+/// Pending chunks at which the queue counts as backed up
+const BACKLOG_CHUNKS: usize = 3;
+
 /// Number of overlap samples (0.5s * 16000 = 8,000)
 const OVERLAP_SAMPLES: usize = (TRANSCRIPTION_SAMPLE_RATE as f32 * OVERLAP_DURATION_SECS) as usize;
 
+/// Input stream attempts (one per candidate config) before recording fails
+const MAX_STREAM_ATTEMPTS: usize = 5;
+
+/// Delay before the second stream attempt; doubled for each further attempt
+const STREAM_RETRY_BASE_DELAY_MS: u64 = 200;
+
+/// Buffer size in frames tried when the default buffer size fails
+const STREAM_RETRY_BUFFER_FRAMES: u32 = 1024;
+
+/// Sample rates tried when the device default fails
+const STREAM_RETRY_SAMPLE_RATES: [u32; 3] = [48000, 44100, 16000];
+
 /// Maximum retries for failed chunk transcription
 const MAX_CHUNK_RETRIES: usize = 2;
 
+/// Default number of chunk worker threads (each loads its own model)
+const DEFAULT_CHUNK_WORKERS: usize = 2;
+
+/// Upper bound for chunk worker threads to keep model memory in check
+const MAX_CHUNK_WORKERS: usize = 4;
+
+/// Most threads a Whisper inference uses unless set explicitly (whisper.cpp's own default)
+const DEFAULT_WHISPER_THREADS: usize = 4;
+
+/// Thread cap per Whisper inference in low power mode
+const LOW_POWER_MAX_THREADS: usize = 2;
+
+/// Default Whisper beam search width
+const DEFAULT_BEAM_SIZE: i32 = 3;
+
+/// Upper bound for the beam width; wider beams cost latency for little accuracy
+const MAX_BEAM_SIZE: i32 = 8;
+
+/// Whisper's own default probability for treating a segment as silence
+const DEFAULT_NO_SPEECH_THRESHOLD: f32 = 0.6;
+
+/// Default word probability below which a word is flagged as low confidence
+const DEFAULT_LOW_CONFIDENCE_THRESHOLD: f32 = 0.4;
+
+/// Mirrors `TranscriptionService::is_recording` for the tray and shortcut listener,
+/// which can't wait for the service lock while a recording starts or stops
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+/// Whether a recording is in progress, without taking the service lock
+pub fn recording_in_progress() -> bool {
+    RECORDING.load(Ordering::SeqCst)
+}
+
+/// Marker appended to low-confidence words in pasted text
+const LOW_CONFIDENCE_MARKER: &str = "[?]";
+
+/// Longest title given to a quick-capture note; longer first sentences are cut
+const NOTE_TITLE_MAX_CHARS: usize = 80;
+
+/// Whisper decoding parameters, tunable in the advanced settings to trade accuracy
+/// for latency
+#[derive(Debug, Clone, Copy)]
+pub struct DecodingOptions {
+    /// Beam search width; 1 decodes greedily
+    pub beam_size: i32,
+    /// Sampling temperature; 0 is deterministic
+    pub temperature: f32,
+    /// Probability above which a segment is treated as silence
+    pub no_speech_threshold: f32,
+    /// Maximum segment length in characters; 0 leaves segments unsplit
+    pub max_segment_length: i32,
+}
+
+impl Default for DecodingOptions {
+    fn default() -> Self {
+        Self {
+            beam_size: DEFAULT_BEAM_SIZE,
+            temperature: 0.0,
+            no_speech_threshold: DEFAULT_NO_SPEECH_THRESHOLD,
+            max_segment_length: 0,
+        }
+    }
+}
+
+impl DecodingOptions {
+    /// Options from the saved settings, clamped to sane ranges
+    pub fn from_saved_settings() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            beam_size: read_parsed_setting(setting_keys::WHISPER_BEAM_SIZE, defaults.beam_size)
+                .clamp(1, MAX_BEAM_SIZE),
+            temperature: read_parsed_setting(
+                setting_keys::WHISPER_TEMPERATURE,
+                defaults.temperature,
+            )
+            .clamp(0.0, 1.0),
+            no_speech_threshold: read_parsed_setting(
+                setting_keys::WHISPER_NO_SPEECH_THRESHOLD,
+                defaults.no_speech_threshold,
+            )
+            .clamp(0.0, 1.0),
+            max_segment_length: read_parsed_setting(
+                setting_keys::WHISPER_MAX_SEGMENT_LENGTH,
+                defaults.max_segment_length,
+            )
+            .max(0),
+        }
+    }
+}
+
+/// A word with the lowest probability among the tokens it was decoded from
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct TranscriptWord {
+    pub text: String,
+    pub confidence: f32,
+    /// Start offset in milliseconds (from token timestamps)
+    pub start_ms: i64,
+    /// End offset in milliseconds (from token timestamps)
+    pub end_ms: i64,
+}
+
+impl TranscriptWord {
+    /// Whether the middle of the word falls inside `[lower, upper)`
+    fn within(&self, lower: i64, upper: i64) -> bool {
+        let mid = (self.start_ms + self.end_ms) / 2;
+        mid >= lower && mid < upper
+    }
+}
+
+/// A timestamped piece of transcribed text as reported by Whisper
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct TranscriptSegment {
+    /// Start offset in milliseconds
+    pub start_ms: i64,
+    /// End offset in milliseconds
+    pub end_ms: i64,
+    pub text: String,
+    /// Mean token probability (0.0 - 1.0)
+    pub confidence: f32,
+    /// Per-word confidence, in order
+    pub words: Vec<TranscriptWord>,
+}
+
+/// Event payload describing the confidence of a finished transcription
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct TranscriptionConfidence {
+    pub segments: Vec<TranscriptSegment>,
+    /// Mean of the segment confidences
+    pub average_confidence: f32,
+    /// Number of words below the low-confidence threshold
+    pub low_confidence_words: usize,
+}
+
+/// Transcription of a single chunk (segment offsets relative to the chunk start)
+#[derive(Debug, Clone)]
+pub struct ChunkTranscript {
+    pub text: String,
+    pub segments: Vec<TranscriptSegment>,
+    /// Language the engine detected, when asked to detect it
+    pub language: Option<String>,
+}
+
+/// Merged output of a finished transcription
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptionOutput {
+    pub text: String,
+    /// Segments with offsets relative to the start of the recording
+    pub segments: Vec<TranscriptSegment>,
+    /// Model that produced the text
+    pub model: String,
+    /// Language the text was transcribed in, if known
+    pub language: Option<String>,
+    /// The recording at 16kHz mono, kept for refinement and the feedback export
+    pub audio: Vec<f32>,
+}
+
+/// Payload of `transcription-complete`, emitted once a dictation has been saved,
+/// right before it is pasted
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct TranscriptionResult {
+    /// Id of the saved transcription; `None` if saving failed
+    pub id: Option<String>,
+    pub raw: String,
+    pub formatted: String,
+    pub duration_ms: i64,
+    pub model: String,
+    /// Base language code, e.g. `en`, whether it was selected or detected
+    pub language: Option<String>,
+}
+
+impl tauri_specta::Event for TranscriptionResult {
+    const NAME: &'static str = "transcription-complete";
+}
+
 /// State of an audio chunk in the processing pipeline
 #[derive(Debug, Clone, PartialEq)]
 pub enum ChunkState {
@@ -121,12 +393,72 @@ pub struct AudioChunk {
     pub state: ChunkState,
     /// Transcription result when completed
     pub transcription: Option<String>,
+    /// Timestamped segments (relative to the recording start) when completed
+    pub segments: Vec<TranscriptSegment>,
+    /// Length of the chunk audio in milliseconds
+    pub duration_ms: i64,
     /// Error message if failed
     pub error: Option<String>,
+    /// When the chunk was added to the queue, to measure how long it waited
+    pub queued_at: std::time::Instant,
+}
+
+/// Progress payload emitted while the chunk queue drains after stop
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct TranscriptionProgress {
+    pub completed_chunks: usize,
+    pub total_chunks: usize,
+    /// Estimated milliseconds until all chunks are transcribed (once measurable)
+    pub eta: Option<u64>,
+}
+
+/// Payload of `model-load-failed`, emitted when the local model can't be loaded.
+/// The recording is abandoned rather than failing chunk after chunk.
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct ModelLoadFailed {
+    /// Manifest id of the model
+    pub model: String,
+    pub error: String,
+    /// What the user can do about it, usually downloading the model again
+    pub hint: String,
+}
+
+/// Audio and models the current recording holds in memory
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+pub struct RecordingMemory {
+    /// Samples captured from the microphone so far
+    pub buffered_samples: usize,
+    /// Resampled samples waiting in chunks for a worker
+    pub queued_chunk_samples: usize,
+    /// Bytes held by both sample buffers
+    pub sample_bytes: u64,
+    /// Models the chunk workers have loaded (local mode only)
+    pub loaded_model_contexts: usize,
+    /// Transcribed samples moved to disk (long local recordings only)
+    pub spilled_samples: usize,
+}
+
+impl RecordingMemory {
+    pub(crate) fn new(
+        buffered_samples: usize,
+        queued_chunk_samples: usize,
+        loaded_model_contexts: usize,
+    ) -> Self {
+        Self {
+            buffered_samples,
+            queued_chunk_samples,
+            sample_bytes: ((buffered_samples + queued_chunk_samples) * std::mem::size_of::<f32>())
+                as u64,
+            loaded_model_contexts,
+            spilled_samples: 0,
+        }
+    }
 }
 
 /// Processes audio chunks in background during recording
-pub struct ChunkProcessor {
+pub struct ChunkProcessor<R: Runtime = Wry> {
+    /// App handle used to report drain progress
+    app: AppHandle<R>,
     /// Reference to the raw audio sample buffer (shared with audio capture)
     samples: Arc<std::sync::Mutex<Vec<f32>>>,
     /// Queue of audio chunks being processed
@@ -135,48 +467,132 @@ pub struct ChunkProcessor {
     next_chunk_id: Arc<AtomicUsize>,
     /// How many samples have been chunked so far
     samples_chunked: Arc<AtomicUsize>,
+    /// Samples moved from the front of the sample buffer to the spill file; the
+    /// buffer starts at this position of the recording
+    samples_spilled: AtomicUsize,
+    /// Spill file for transcribed audio, created once a recording runs long
+    spill: std::sync::Mutex<Option<AudioSpill>>,
+    /// Working directory for the spill file and transcribed chunks; without one
+    /// all audio stays in memory
+    workspace: Option<Arc<RecordingWorkspace>>,
     /// Signal to stop processing
     should_stop: Arc<AtomicBool>,
-    /// Whether chunk worker is currently processing
-    is_processing: Arc<AtomicBool>,
-    /// Path to the Whisper model file
+    /// Number of chunk workers currently transcribing a chunk
+    active_workers: Arc<AtomicUsize>,
+    /// Index of the next chunk to stream into the live session (keeps session order)
+    next_session_chunk: Arc<std::sync::Mutex<usize>>,
+    /// Local model the chunk workers transcribe with
+    model: &'static ModelEntry,
+    /// Path to the model file or folder
     model_path: PathBuf,
     /// Whether to auto-detect language
     auto_detect_language: bool,
     /// Languages for transcription
     languages: Vec<String>,
-    /// Keyterms for vocabulary boosting
+    /// Keyterms for vocabulary boosting, most relevant first
     keyterms: Vec<String>,
+    /// Beam size, temperature and other Whisper parameters
+    decoding: DecodingOptions,
+    /// Threads each inference uses
+    inference_threads: i32,
+    /// Samples per chunk at 16kHz (longer in low power mode, grows when the queue backs up)
+    chunk_size_samples: AtomicUsize,
+    /// Beam width in use; narrowed from the configured one when the queue backs up
+    beam_size: AtomicI32,
+    /// Chunk ID counter at the last adaptation, so each backlog is handled once
+    adapted_at_chunk: AtomicUsize,
     /// Sample rate of input audio (before resampling)
     input_sample_rate: u32,
     /// Number of channels in input audio
     input_channels: u16,
+    /// Live transcript document chunks are streamed into (session mode only)
+    live_session: Option<Arc<LiveSession>>,
+    /// Language the engine detected in the first chunk (auto-detect only)
+    detected_language: OnceLock<String>,
+    /// Set once a chunk has produced text, so `first-words-detected` fires once
+    first_words_detected: AtomicBool,
+    /// Models currently loaded by the chunk workers
+    loaded_contexts: AtomicUsize,
+    /// Why the model failed to load; once set, no more chunks are transcribed
+    model_load_error: OnceLock<String>,
+}
+
+/// How a recording is chunked and what its chunks are transcribed with
+pub struct ChunkProcessorConfig {
+    /// Local model the chunk workers transcribe with
+    pub model: &'static ModelEntry,
+    /// Path to the model file or folder
+    pub model_path: PathBuf,
+    pub auto_detect_language: bool,
+    pub languages: Vec<String>,
+    /// Keyterms for vocabulary boosting, most relevant first
+    pub keyterms: Vec<String>,
+    pub decoding: DecodingOptions,
+    /// Threads each inference uses
+    pub inference_threads: i32,
+    /// Samples per chunk at 16kHz
+    pub chunk_size_samples: usize,
+    /// Sample rate of the captured audio
+    pub input_sample_rate: u32,
+    /// Number of channels in the captured audio
+    pub input_channels: u16,
+    /// Live transcript document to stream chunks into (session mode only)
+    pub live_session: Option<Arc<LiveSession>>,
+    /// Working directory for spilled audio and transcribed chunks
+    pub workspace: Option<Arc<RecordingWorkspace>>,
 }
 
-impl ChunkProcessor {
+impl<R: Runtime> ChunkProcessor<R> {
     /// Create a new ChunkProcessor
     pub fn new(
+        app: AppHandle<R>,
         samples: Arc<std::sync::Mutex<Vec<f32>>>,
-        model_path: PathBuf,
-        auto_detect_language: bool,
-        languages: Vec<String>,
-        keyterms: Vec<String>,
-        input_sample_rate: u32,
-        input_channels: u16,
+        config: ChunkProcessorConfig,
     ) -> Self {
+        let ChunkProcessorConfig {
+            model,
+            model_path,
+            auto_detect_language,
+            languages,
+            keyterms,
+            decoding,
+            inference_threads,
+            chunk_size_samples,
+            input_sample_rate,
+            input_channels,
+            live_session,
+            workspace,
+        } = config;
+
         Self {
+            app,
             samples,
             chunks: Arc::new(std::sync::Mutex::new(Vec::new())),
             next_chunk_id: Arc::new(AtomicUsize::new(0)),
             samples_chunked: Arc::new(AtomicUsize::new(0)),
+            samples_spilled: AtomicUsize::new(0),
+            spill: std::sync::Mutex::new(None),
+            workspace,
             should_stop: Arc::new(AtomicBool::new(false)),
-            is_processing: Arc::new(AtomicBool::new(false)),
+            active_workers: Arc::new(AtomicUsize::new(0)),
+            next_session_chunk: Arc::new(std::sync::Mutex::new(0)),
+            model,
             model_path,
             auto_detect_language,
             languages,
             keyterms,
+            decoding,
+            inference_threads,
+            chunk_size_samples: AtomicUsize::new(chunk_size_samples),
+            beam_size: AtomicI32::new(decoding.beam_size),
+            adapted_at_chunk: AtomicUsize::new(0),
             input_sample_rate,
             input_channels,
+            live_session,
+            detected_language: OnceLock::new(),
+            first_words_detected: AtomicBool::new(false),
+            loaded_contexts: AtomicUsize::new(0),
+            model_load_error: OnceLock::new(),
         }
     }
 
@@ -190,9 +606,69 @@ impl ChunkProcessor {
         self.should_stop.load(Ordering::SeqCst)
     }
 
-    /// Check if worker is currently processing a chunk
+    /// Samples waiting in chunks and models loaded by the workers
+    pub fn memory_usage(&self) -> (usize, usize) {
+        let queued = self
+            .chunks
+            .lock()
+            .map(|chunks| chunks.iter().map(|c| c.samples.len()).sum())
+            .unwrap_or(0);
+        (queued, self.loaded_contexts.load(Ordering::SeqCst))
+    }
+
+    /// Drop every chunk still waiting for a worker (used when a recording is cancelled)
+    pub fn discard_pending(&self) {
+        if let Ok(mut chunks) = self.chunks.lock() {
+            for chunk in chunks.iter_mut().filter(|c| c.state == ChunkState::Pending) {
+                chunk.state = ChunkState::Failed;
+                chunk.error = Some("Cancelled".to_string());
+                chunk.samples.clear();
+                chunk.samples.shrink_to_fit();
+            }
+        }
+    }
+
+    /// Why the model failed to load, if it did
+    pub fn model_load_error(&self) -> Option<&str> {
+        self.model_load_error.get().map(String::as_str)
+    }
+
+    /// Give up on the recording after the model failed to load: a damaged or
+    /// truncated model file fails the same way on every retry. Stops chunking,
+    /// drops queued chunks and emits `model-load-failed` once.
+    fn fail_model_load(&self, error: String) {
+        if self.model_load_error.set(error.clone()).is_err() {
+            return;
+        }
+
+        diagnostics::record_error(
+            "transcription",
+            format!("ChunkProcessor: Failed to load model: {}", error),
+        );
+        self.signal_stop();
+        if let Ok(mut chunks) = self.chunks.lock() {
+            for chunk in chunks.iter_mut().filter(|c| c.state == ChunkState::Pending) {
+                chunk.state = ChunkState::Failed;
+                chunk.error = Some("Model failed to load".to_string());
+                chunk.samples.clear();
+                chunk.samples.shrink_to_fit();
+            }
+        }
+
+        let _ = ModelLoadFailed {
+            model: self.model.id.clone(),
+            error,
+            hint: format!(
+                "The {} model file may be damaged. Download it again in Settings.",
+                self.model.name
+            ),
+        }
+        .emit(&self.app);
+    }
+
+    /// Check if any worker is currently processing a chunk
     pub fn is_processing(&self) -> bool {
-        self.is_processing.load(Ordering::SeqCst)
+        self.active_workers.load(Ordering::SeqCst) > 0
     }
 
     /// Get the number of samples that have been chunked
@@ -200,10 +676,55 @@ impl ChunkProcessor {
         self.samples_chunked.load(Ordering::SeqCst)
     }
 
+    /// Number of chunks that failed to transcribe
+    pub fn failed_chunks(&self) -> usize {
+        self.chunks
+            .lock()
+            .map(|chunks| {
+                chunks
+                    .iter()
+                    .filter(|c| c.state == ChunkState::Failed)
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Get the number of samples spilled from memory to disk
+    pub fn samples_spilled(&self) -> usize {
+        self.samples_spilled.load(Ordering::SeqCst)
+    }
+
+    /// The whole recording at 16kHz mono: the audio spilled to disk followed by
+    /// what is still in memory
+    pub fn recorded_audio(&self) -> Vec<f32> {
+        let mut raw = Vec::new();
+        match &self.workspace {
+            Some(workspace) if self.samples_spilled() > 0 => {
+                match spill::read_samples(&workspace.audio_path()) {
+                    Ok(spilled) => raw = spilled,
+                    Err(e) => warn!("ChunkProcessor: Failed to read spilled audio: {}", e),
+                }
+            }
+            _ => {}
+        }
+        if let Ok(buffer) = self.samples.lock() {
+            raw.extend_from_slice(&buffer);
+        }
+
+        downmix_and_resample(&raw, self.input_sample_rate, self.input_channels)
+    }
+
+    /// Convert a position in the raw input buffer to milliseconds of recording
+    fn input_idx_to_ms(&self, input_idx: usize) -> i64 {
+        let frames = input_idx / self.input_channels.max(1) as usize;
+        (frames as u64 * 1000 / self.input_sample_rate.max(1) as u64) as i64
+    }
+
     /// Extract the next chunk from the sample buffer if enough samples available
     fn extract_next_chunk(&self) -> Option<AudioChunk> {
         let buffer = self.samples.lock().ok()?;
         let already_chunked = self.samples_chunked.load(Ordering::SeqCst);
+        let spilled = self.samples_spilled.load(Ordering::SeqCst);
 
         // Calculate how many new samples we need (accounting for resampling)
         // Input buffer is at input_sample_rate, we need to resample to 16kHz
@@ -212,10 +733,11 @@ impl ChunkProcessor {
 
         // Calculate needed input samples for one chunk
         // After mono conversion: input_samples / channels
-        // After resampling: (input_samples / channels) / resample_ratio = CHUNK_SIZE_SAMPLES
-        // So: input_samples = CHUNK_SIZE_SAMPLES * resample_ratio * channels
+        // After resampling: (input_samples / channels) / resample_ratio = chunk_size_samples
+        // So: input_samples = chunk_size_samples * resample_ratio * channels
+        let chunk_size_samples = self.chunk_size_samples.load(Ordering::SeqCst);
         let needed_input_samples =
-            (CHUNK_SIZE_SAMPLES as f64 * resample_ratio * channels as f64) as usize;
+            (chunk_size_samples as f64 * resample_ratio * channels as f64) as usize;
 
         // Calculate start position with overlap (in input sample space)
         let overlap_input_samples =
@@ -230,12 +752,12 @@ impl ChunkProcessor {
         let end_idx = start_idx + needed_input_samples;
 
         // Check if we have enough samples
-        if end_idx > buffer.len() {
+        if end_idx > spilled + buffer.len() {
             return None;
         }
 
-        // Extract raw samples
-        let raw_samples = buffer[start_idx..end_idx].to_vec();
+        // Extract raw samples (the buffer no longer holds what was spilled)
+        let raw_samples = buffer[start_idx - spilled..end_idx - spilled].to_vec();
         drop(buffer); // Release lock
 
         // Convert to mono if needed
@@ -268,19 +790,120 @@ impl ChunkProcessor {
 
         Some(AudioChunk {
             id: chunk_id,
+            duration_ms: samples_to_ms(resampled.len()),
             samples: resampled,
             start_sample_idx: start_idx,
             end_sample_idx: end_idx,
             state: ChunkState::Pending,
             transcription: None,
+            segments: Vec::new(),
             error: None,
+            queued_at: std::time::Instant::now(),
         })
     }
 
+    /// Keep pace with the audio on slow machines: when chunks pile up, make the
+    /// next chunks longer and decode them with a narrower beam. Only grows, so a
+    /// recording doesn't flip back and forth.
+    fn adapt_to_backlog(&self) {
+        let pending = match self.chunks.lock() {
+            Ok(chunks) => chunks
+                .iter()
+                .filter(|c| c.state == ChunkState::Pending)
+                .count(),
+            Err(_) => return,
+        };
+        if pending < BACKLOG_CHUNKS && !performance::falling_behind() {
+            return;
+        }
+
+        // Give the previous adaptation a few chunks to take effect
+        let next_chunk_id = self.next_chunk_id.load(Ordering::SeqCst);
+        if next_chunk_id < self.adapted_at_chunk.load(Ordering::SeqCst) + BACKLOG_CHUNKS {
+            return;
+        }
+        self.adapted_at_chunk.store(next_chunk_id, Ordering::SeqCst);
+
+        let chunk_size = self.chunk_size_samples.load(Ordering::SeqCst);
+        let beam_size = self.beam_size.load(Ordering::SeqCst);
+        let new_chunk_size = (chunk_size * 2).min(MAX_CHUNK_SIZE_SAMPLES);
+        let new_beam_size = (beam_size / 2).max(1);
+        if new_chunk_size == chunk_size && new_beam_size == beam_size {
+            return;
+        }
+
+        self.chunk_size_samples
+            .store(new_chunk_size, Ordering::SeqCst);
+        self.beam_size.store(new_beam_size, Ordering::SeqCst);
+        debug!(
+            "ChunkProcessor: {} chunks pending, switching to {}ms chunks with beam size {}",
+            pending,
+            samples_to_ms(new_chunk_size),
+            new_beam_size
+        );
+    }
+
+    /// Move audio every chunk has already been cut from out of memory into the
+    /// spill file, keeping the overlap the next chunk starts with
+    fn spill_consumed(&self) {
+        let Some(workspace) = &self.workspace else {
+            return;
+        };
+
+        let channels = self.input_channels.max(1) as usize;
+        let block = self.input_sample_rate as usize * channels * SPILL_BLOCK_SECS;
+
+        let resample_ratio = self.input_sample_rate as f64 / TRANSCRIPTION_SAMPLE_RATE as f64;
+        let overlap_input_samples =
+            (OVERLAP_SAMPLES as f64 * resample_ratio * channels as f64) as usize;
+
+        let consumed = {
+            let mut buffer = match self.samples.lock() {
+                Ok(b) => b,
+                Err(_) => return,
+            };
+            let spilled = self.samples_spilled.load(Ordering::SeqCst);
+            let next_start = self
+                .samples_chunked
+                .load(Ordering::SeqCst)
+                .saturating_sub(overlap_input_samples);
+
+            // Whole frames only, so channels stay aligned
+            let count = next_start.saturating_sub(spilled);
+            let count = count - count % channels;
+            if count < block {
+                return;
+            }
+
+            // Copy out under the lock and write after, so capture isn't blocked on disk
+            let consumed: Vec<f32> = buffer.drain(..count).collect();
+            self.samples_spilled.fetch_add(count, Ordering::SeqCst);
+            consumed
+        };
+
+        let mut spill = match self.spill.lock() {
+            Ok(s) => s,
+            Err(_) => return,
+        };
+        if spill.is_none() {
+            match AudioSpill::create(&workspace.audio_path()) {
+                Ok(file) => *spill = Some(file),
+                Err(e) => {
+                    warn!("ChunkProcessor: Failed to create spill file: {}", e);
+                    return;
+                }
+            }
+        }
+        // The audio is already transcribed, so a failed write only loses the copy on disk
+        if let Some(Err(e)) = spill.as_mut().map(|file| file.write(&consumed)) {
+            warn!("ChunkProcessor: Failed to spill audio: {}", e);
+        }
+    }
+
     /// Add a chunk to the processing queue
     fn add_chunk(&self, chunk: AudioChunk) {
         if let Ok(mut chunks) = self.chunks.lock() {
-            println!(
+            debug!(
                 "ChunkProcessor: Added chunk {} with {} samples",
                 chunk.id,
                 chunk.samples.len()
@@ -289,10 +912,71 @@ impl ChunkProcessor {
         }
     }
 
-    /// Get the next pending chunk index
-    fn get_next_pending_chunk_idx(&self) -> Option<usize> {
+    /// Claim the next pending chunk for a worker, marking it as processing.
+    /// Finding and marking happen under one lock so two workers never take the same chunk.
+    /// Also returns how long the chunk waited in the queue.
+    fn claim_next_pending_chunk(&self) -> Option<(usize, usize, usize, Vec<f32>, Duration)> {
+        let mut chunks = self.chunks.lock().ok()?;
+        let idx = chunks.iter().position(|c| c.state == ChunkState::Pending)?;
+        chunks[idx].state = ChunkState::Processing;
+        Some((
+            idx,
+            chunks[idx].id,
+            chunks[idx].start_sample_idx,
+            chunks[idx].samples.clone(),
+            chunks[idx].queued_at.elapsed(),
+        ))
+    }
+
+    /// Text of the chunk before `idx`, if it has already been transcribed.
+    /// Workers run in parallel, so the previous chunk may still be in flight.
+    fn previous_chunk_text(&self, idx: usize) -> Option<String> {
         let chunks = self.chunks.lock().ok()?;
-        chunks.iter().position(|c| c.state == ChunkState::Pending)
+        let previous = chunks.get(idx.checked_sub(1)?)?;
+        match previous.state {
+            ChunkState::Completed => previous.transcription.clone(),
+            _ => None,
+        }
+    }
+
+    /// Stream finished chunks into the live session document in chunk order.
+    /// Workers finish out of order, so a chunk is only appended once every chunk
+    /// before it has completed or failed.
+    fn flush_live_session(&self) {
+        let session = match &self.live_session {
+            Some(session) => session,
+            None => return,
+        };
+
+        // Held for the whole flush so appends from different workers never interleave
+        let mut next = match self.next_session_chunk.lock() {
+            Ok(n) => n,
+            Err(_) => return,
+        };
+
+        loop {
+            let ready = {
+                let chunks = match self.chunks.lock() {
+                    Ok(c) => c,
+                    Err(_) => return,
+                };
+                match chunks.get(*next) {
+                    Some(chunk) if chunk.state == ChunkState::Completed => Some((
+                        chunk.id,
+                        self.input_idx_to_ms(chunk.start_sample_idx),
+                        chunk.duration_ms,
+                        chunk.transcription.clone().unwrap_or_default(),
+                    )),
+                    Some(chunk) if chunk.state == ChunkState::Failed => None,
+                    _ => return,
+                }
+            };
+
+            if let Some((chunk_id, start_ms, duration_ms, text)) = ready {
+                session.append_chunk(chunk_id, start_ms, start_ms + duration_ms, &text);
+            }
+            *next += 1;
+        }
     }
 
     /// Process any remaining audio that didn't fill a complete chunk
@@ -303,13 +987,14 @@ impl ChunkProcessor {
         };
 
         let already_chunked = self.samples_chunked.load(Ordering::SeqCst);
+        let spilled = self.samples_spilled.load(Ordering::SeqCst);
 
-        if already_chunked >= buffer.len() {
+        if already_chunked >= spilled + buffer.len() {
             // All samples already chunked
             return;
         }
 
-        let remaining = buffer[already_chunked..].to_vec();
+        let remaining = buffer[already_chunked - spilled..].to_vec();
         drop(buffer);
 
         if remaining.is_empty() {
@@ -349,15 +1034,18 @@ impl ChunkProcessor {
 
         let final_chunk = AudioChunk {
             id: chunk_id,
+            duration_ms: samples_to_ms(resampled.len()),
             samples: resampled,
             start_sample_idx: already_chunked,
             end_sample_idx: already_chunked, // Marker for final chunk
             state: ChunkState::Pending,
             transcription: None,
+            segments: Vec::new(),
             error: None,
+            queued_at: std::time::Instant::now(),
         };
 
-        println!(
+        debug!(
             "ChunkProcessor: Added final chunk {} with {} samples",
             final_chunk.id,
             final_chunk.samples.len()
@@ -366,27 +1054,57 @@ impl ChunkProcessor {
         self.add_chunk(final_chunk);
     }
 
-    /// Wait for all pending chunks to complete processing
+    /// Wait for all pending chunks to complete processing, emitting
+    /// `transcription-progress` whenever another chunk finishes
     pub fn wait_for_completion(&self, timeout: Duration) -> bool {
         let start = std::time::Instant::now();
+        let mut initial_done: Option<usize> = None;
+        let mut last_reported: Option<(usize, usize)> = None;
 
         loop {
             if start.elapsed() > timeout {
-                println!("ChunkProcessor: Timeout waiting for completion");
+                debug!("ChunkProcessor: Timeout waiting for completion");
                 return false;
             }
 
-            let all_done = {
+            // Nothing more gets transcribed once the model failed to load
+            if self.model_load_error().is_some() {
+                return true;
+            }
+
+            let (done, total) = {
                 let chunks = match self.chunks.lock() {
                     Ok(c) => c,
                     Err(_) => return false,
                 };
-                chunks
+                let done = chunks
                     .iter()
-                    .all(|c| c.state == ChunkState::Completed || c.state == ChunkState::Failed)
+                    .filter(|c| c.state == ChunkState::Completed || c.state == ChunkState::Failed)
+                    .count();
+                (done, chunks.len())
             };
 
-            if all_done {
+            if last_reported != Some((done, total)) {
+                // Estimate from the throughput observed since draining started
+                let initial = *initial_done.get_or_insert(done);
+                let drained = done.saturating_sub(initial);
+                let eta = if drained > 0 {
+                    let per_chunk_ms = start.elapsed().as_millis() as u64 / drained as u64;
+                    Some(per_chunk_ms * (total - done) as u64)
+                } else {
+                    None
+                };
+
+                let _ = TranscriptionProgress {
+                    completed_chunks: done,
+                    total_chunks: total,
+                    eta,
+                }
+                .emit(&self.app);
+                last_reported = Some((done, total));
+            }
+
+            if done == total {
                 return true;
             }
 
@@ -394,44 +1112,171 @@ impl ChunkProcessor {
         }
     }
 
-    /// Merge all completed chunk transcriptions with overlap deduplication
+    /// Time window (recording ms) each completed chunk contributes to the merged result.
+    /// Consecutive chunks overlap by `OVERLAP_DURATION_SECS`; the cut between them is placed
+    /// in the middle of that overlap so each word is taken from exactly one chunk.
+    fn chunk_windows(&self, chunks: &[&AudioChunk]) -> Vec<(i64, i64)> {
+        let spans: Vec<(i64, i64)> = chunks
+            .iter()
+            .map(|c| {
+                let start_ms = self.input_idx_to_ms(c.start_sample_idx);
+                (start_ms, start_ms + c.duration_ms)
+            })
+            .collect();
+
+        (0..spans.len())
+            .map(|i| {
+                let lower = if i > 0 {
+                    (spans[i - 1].1 + spans[i].0) / 2
+                } else {
+                    i64::MIN
+                };
+                let upper = if i + 1 < spans.len() {
+                    (spans[i].1 + spans[i + 1].0) / 2
+                } else {
+                    i64::MAX
+                };
+                (lower, upper)
+            })
+            .collect()
+    }
+
+    /// Merge all completed chunk transcriptions, cutting each chunk at the overlap
+    /// boundary using word timestamps. Chunks without word timings fall back to
+    /// word-matching deduplication.
     pub fn merge_results(&self) -> String {
         let chunks = match self.chunks.lock() {
             Ok(c) => c,
             Err(_) => return String::new(),
         };
 
-        let mut results: Vec<&str> = Vec::new();
+        for chunk in chunks.iter().filter(|c| c.state == ChunkState::Failed) {
+            debug!(
+                "ChunkProcessor: Chunk {} failed: {:?}",
+                chunk.id, chunk.error
+            );
+        }
+
+        let completed: Vec<&AudioChunk> = chunks
+            .iter()
+            .filter(|c| c.state == ChunkState::Completed)
+            .collect();
+        let windows = self.chunk_windows(&completed);
+
+        let mut merged = String::new();
 
-        for chunk in chunks.iter() {
-            if chunk.state == ChunkState::Completed {
-                if let Some(text) = &chunk.transcription {
-                    let trimmed = text.trim();
-                    if !trimmed.is_empty() {
-                        results.push(trimmed);
+        for (chunk, (lower, upper)) in completed.iter().zip(windows) {
+            let has_word_timings = chunk.segments.iter().any(|s| !s.words.is_empty());
+
+            if has_word_timings {
+                let words: Vec<&str> = chunk
+                    .segments
+                    .iter()
+                    .flat_map(|s| s.words.iter())
+                    .filter(|w| w.within(lower, upper))
+                    .map(|w| w.text.as_str())
+                    .collect();
+                if !words.is_empty() {
+                    if !merged.is_empty() {
+                        merged.push(' ');
                     }
+                    merged.push_str(&words.join(" "));
+                }
+            } else if let Some(text) = &chunk.transcription {
+                let trimmed = text.trim();
+                if !trimmed.is_empty() {
+                    merged = merge_with_overlap_dedup(&merged, trimmed);
                 }
-            } else if chunk.state == ChunkState::Failed {
-                println!(
-                    "ChunkProcessor: Chunk {} failed: {:?}",
-                    chunk.id, chunk.error
-                );
             }
         }
 
-        if results.is_empty() {
-            return String::new();
+        merged
+    }
+
+    /// Show the text recognized so far in the widget's transcript preview
+    fn report_progress_text(&self) {
+        let text = self.merge_results();
+        if !self.first_words_detected.swap(true, Ordering::SeqCst) {
+            emit_first_words(&self.app, &text);
+        }
+        let _ = TranscriptionPartial {
+            text,
+            is_final: false,
         }
+        .emit(&self.app);
+    }
 
-        if results.len() == 1 {
-            return results[0].to_string();
+    /// Language of the transcription: the detected one when auto-detecting,
+    /// otherwise the first selected language
+    pub fn language(&self) -> Option<String> {
+        if self.auto_detect_language || self.languages.is_empty() {
+            self.detected_language.get().cloned()
+        } else {
+            Some(self.languages[0].clone())
         }
+    }
+
+    /// Name of the model, from its file or folder name
+    pub fn model_name(&self) -> String {
+        self.model_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
 
-        // Merge with overlap deduplication
-        let mut merged = results[0].to_string();
+    /// Collect timestamped segments of all completed chunks, trimmed to the same
+    /// overlap boundaries as `merge_results`
+    pub fn merge_segments(&self) -> Vec<TranscriptSegment> {
+        let chunks = match self.chunks.lock() {
+            Ok(c) => c,
+            Err(_) => return Vec::new(),
+        };
+
+        let completed: Vec<&AudioChunk> = chunks
+            .iter()
+            .filter(|c| c.state == ChunkState::Completed)
+            .collect();
+        let windows = self.chunk_windows(&completed);
+
+        let mut merged: Vec<TranscriptSegment> = Vec::new();
+
+        for (chunk, (lower, upper)) in completed.iter().zip(windows) {
+            for segment in &chunk.segments {
+                if segment.words.is_empty() {
+                    // No word timings: keep the segment if it ends past what is already kept
+                    let last_end = merged.last().map(|s| s.end_ms).unwrap_or(0);
+                    if segment.end_ms > last_end {
+                        merged.push(segment.clone());
+                    }
+                    continue;
+                }
+
+                let words: Vec<TranscriptWord> = segment
+                    .words
+                    .iter()
+                    .filter(|w| w.within(lower, upper))
+                    .cloned()
+                    .collect();
+                if words.is_empty() {
+                    continue;
+                }
 
-        for text in results.iter().skip(1) {
-            merged = merge_with_overlap_dedup(&merged, text);
+                let mut trimmed = segment.clone();
+                if words.len() < segment.words.len() {
+                    trimmed.start_ms = words
+                        .first()
+                        .map(|w| w.start_ms)
+                        .unwrap_or(segment.start_ms);
+                    trimmed.end_ms = words.last().map(|w| w.end_ms).unwrap_or(segment.end_ms);
+                    trimmed.text = words
+                        .iter()
+                        .map(|w| w.text.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                }
+                trimmed.words = words;
+                merged.push(trimmed);
+            }
         }
 
         merged
@@ -442,92 +1287,142 @@ impl ChunkProcessor {
         let processor = Arc::clone(self);
 
         thread::spawn(move || {
-            println!("ChunkProcessor: Monitor thread started");
+            debug!("ChunkProcessor: Monitor thread started");
 
             loop {
                 if processor.should_stop() {
-                    println!("ChunkProcessor: Monitor thread stopping");
+                    debug!("ChunkProcessor: Monitor thread stopping");
                     break;
                 }
 
                 // Try to extract next chunk
+                processor.adapt_to_backlog();
                 if let Some(chunk) = processor.extract_next_chunk() {
                     processor.add_chunk(chunk);
+                    processor.spill_consumed();
                 }
 
                 // Sleep briefly before checking again
                 thread::sleep(Duration::from_millis(100));
             }
 
-            println!("ChunkProcessor: Monitor thread ended");
+            debug!("ChunkProcessor: Monitor thread ended");
         })
     }
 
-    /// Spawn the chunk worker thread that transcribes pending chunks
-    pub fn spawn_chunk_worker(self: &Arc<Self>) -> thread::JoinHandle<()> {
+    /// Spawn a chunk worker thread that transcribes pending chunks.
+    /// Several workers can run at once; each keeps its own loaded model.
+    pub fn spawn_chunk_worker(self: &Arc<Self>, worker_id: usize) -> thread::JoinHandle<()> {
         let processor = Arc::clone(self);
 
         thread::spawn(move || {
-            println!("ChunkProcessor: Worker thread started");
+            debug!("ChunkProcessor: Worker {} started", worker_id);
 
             // Load model once for reuse
-            let mut whisper_ctx: Option<WhisperContext> = None;
+            let mut engine: Option<Box<dyn SttEngine>> = None;
 
             loop {
-                // Find next pending chunk
-                let chunk_idx = processor.get_next_pending_chunk_idx();
-
-                match chunk_idx {
-                    Some(idx) => {
-                        processor.is_processing.store(true, Ordering::SeqCst);
-
-                        // Get chunk samples and mark as processing
-                        let (chunk_id, samples) = {
-                            let mut chunks = match processor.chunks.lock() {
-                                Ok(c) => c,
-                                Err(_) => {
-                                    processor.is_processing.store(false, Ordering::SeqCst);
-                                    continue;
+                if processor.model_load_error().is_some() {
+                    debug!("ChunkProcessor: Worker {} stopping (no model)", worker_id);
+                    break;
+                }
+
+                // Claim next pending chunk
+                match processor.claim_next_pending_chunk() {
+                    Some((idx, chunk_id, start_sample_idx, samples, queue_wait)) => {
+                        processor.active_workers.fetch_add(1, Ordering::SeqCst);
+
+                        // Load the model on the first chunk. A load failure isn't
+                        // retried: it ends the whole recording.
+                        if engine.is_none() {
+                            match load_engine(
+                                processor.model,
+                                &processor.model_path,
+                                processor.inference_threads,
+                            ) {
+                                Ok(loaded) => {
+                                    engine = Some(loaded);
+                                    processor.loaded_contexts.fetch_add(1, Ordering::SeqCst);
+                                }
+                                Err(e) => {
+                                    if let Ok(mut chunks) = processor.chunks.lock() {
+                                        chunks[idx].state = ChunkState::Failed;
+                                        chunks[idx].error = Some(e.to_string());
+                                    }
+                                    processor.fail_model_load(e.to_string());
+                                    processor.active_workers.fetch_sub(1, Ordering::SeqCst);
+                                    break;
                                 }
-                            };
-                            chunks[idx].state = ChunkState::Processing;
-                            (chunks[idx].id, chunks[idx].samples.clone())
-                        };
+                            }
+                        }
 
-                        println!(
-                            "ChunkProcessor: Processing chunk {} ({} samples)",
+                        debug!(
+                            "ChunkProcessor: Worker {} processing chunk {} ({} samples)",
+                            worker_id,
                             chunk_id,
                             samples.len()
                         );
 
+                        // Carry names and terminology across the chunk boundary
+                        let previous_text = processor.previous_chunk_text(idx);
+
                         // Transcribe chunk with retry
+                        let chunk_start_ms = processor.input_idx_to_ms(start_sample_idx);
+                        let inference_started = std::time::Instant::now();
                         let result = processor.transcribe_chunk_with_retry(
                             &samples,
-                            &mut whisper_ctx,
+                            chunk_start_ms,
+                            previous_text.as_deref(),
+                            engine.as_deref_mut().unwrap(),
                             MAX_CHUNK_RETRIES,
                         );
+                        performance::record_chunk(
+                            chunk_id,
+                            samples_to_ms(samples.len()),
+                            queue_wait.as_millis() as i64,
+                            inference_started.elapsed().as_millis() as i64,
+                        );
+
+                        let produced_text = matches!(&result, Ok(t) if !t.text.trim().is_empty());
 
                         // Update chunk with result
                         if let Ok(mut chunks) = processor.chunks.lock() {
                             match result {
-                                Ok(text) => {
-                                    println!(
+                                Ok(transcript) => {
+                                    let text = transcript.text;
+                                    debug!(
                                         "ChunkProcessor: Chunk {} completed: '{}'",
                                         chunk_id,
-                                        if text.len() > 50 {
-                                            format!("{}...", &text[..50])
-                                        } else {
-                                            text.clone()
-                                        }
+                                        text::preview(&text, 50)
                                     );
+                                    if let Some(workspace) = &processor.workspace {
+                                        workspace.save_chunk(chunk_id, &text);
+                                    }
                                     chunks[idx].state = ChunkState::Completed;
                                     chunks[idx].transcription = Some(text);
+                                    // Shift segment offsets from chunk time to recording time
+                                    chunks[idx].segments = transcript
+                                        .segments
+                                        .into_iter()
+                                        .map(|mut segment| {
+                                            segment.start_ms += chunk_start_ms;
+                                            segment.end_ms += chunk_start_ms;
+                                            for word in &mut segment.words {
+                                                word.start_ms += chunk_start_ms;
+                                                word.end_ms += chunk_start_ms;
+                                            }
+                                            segment
+                                        })
+                                        .collect();
                                     // Clear samples to free memory
                                     chunks[idx].samples.clear();
                                     chunks[idx].samples.shrink_to_fit();
                                 }
                                 Err(e) => {
-                                    println!("ChunkProcessor: Chunk {} failed: {}", chunk_id, e);
+                                    diagnostics::record_error(
+                                        "transcription",
+                                        format!("ChunkProcessor: Chunk {} failed: {}", chunk_id, e),
+                                    );
                                     chunks[idx].state = ChunkState::Failed;
                                     chunks[idx].error = Some(e);
                                     chunks[idx].samples.clear();
@@ -536,7 +1431,14 @@ impl ChunkProcessor {
                             }
                         }
 
-                        processor.is_processing.store(false, Ordering::SeqCst);
+                        // Stream finished chunks into the live session document (session mode)
+                        processor.flush_live_session();
+
+                        if produced_text {
+                            processor.report_progress_text();
+                        }
+
+                        processor.active_workers.fetch_sub(1, Ordering::SeqCst);
                     }
                     None => {
                         // No pending chunks
@@ -549,7 +1451,7 @@ impl ChunkProcessor {
                                 .unwrap_or(false);
 
                             if !has_pending {
-                                println!("ChunkProcessor: Worker thread stopping (all done)");
+                                debug!("ChunkProcessor: Worker {} stopping (all done)", worker_id);
                                 break;
                             }
                         }
@@ -559,7 +1461,11 @@ impl ChunkProcessor {
                 }
             }
 
-            println!("ChunkProcessor: Worker thread ended");
+            if engine.is_some() {
+                processor.loaded_contexts.fetch_sub(1, Ordering::SeqCst);
+            }
+
+            debug!("ChunkProcessor: Worker {} ended", worker_id);
         })
     }
 
@@ -567,18 +1473,20 @@ impl ChunkProcessor {
     fn transcribe_chunk_with_retry(
         &self,
         samples: &[f32],
-        whisper_ctx: &mut Option<WhisperContext>,
+        offset_ms: i64,
+        previous_text: Option<&str>,
+        engine: &mut dyn SttEngine,
         max_retries: usize,
-    ) -> Result<String, String> {
+    ) -> Result<ChunkTranscript, String> {
         let mut attempts = 0;
 
         loop {
-            let result = self.transcribe_chunk(samples, whisper_ctx);
+            let result = self.transcribe_chunk(samples, offset_ms, previous_text, engine);
 
             match result {
-                Ok(text) => return Ok(text),
+                Ok(transcript) => return Ok(transcript),
                 Err(e) if attempts < max_retries => {
-                    println!(
+                    debug!(
                         "ChunkProcessor: Transcription attempt {} failed: {}",
                         attempts + 1,
                         e
@@ -591,83 +1499,108 @@ impl ChunkProcessor {
         }
     }
 
-    /// Transcribe a single chunk using Whisper
+    /// Transcribe a single chunk with the loaded engine
     fn transcribe_chunk(
         &self,
         samples: &[f32],
-        whisper_ctx: &mut Option<WhisperContext>,
-    ) -> Result<String> {
-        // Initialize context if not already loaded
-        if whisper_ctx.is_none() {
-            let ctx = WhisperContext::new_with_params(
-                self.model_path.to_str().unwrap(),
-                WhisperContextParameters::default(),
-            )
-            .map_err(|e| anyhow::anyhow!("Failed to load Whisper model: {}", e))?;
-            *whisper_ctx = Some(ctx);
-        }
-
-        let ctx = whisper_ctx.as_ref().unwrap();
-        let mut state = ctx
-            .create_state()
-            .map_err(|e| anyhow::anyhow!("Failed to create Whisper state: {}", e))?;
-
-        // Configure parameters
-        let mut params = FullParams::new(SamplingStrategy::BeamSearch {
-            beam_size: 3,
-            patience: -1.0,
-        });
-
-        // Set language
-        if self.auto_detect_language || self.languages.is_empty() {
-            params.set_language(None);
+        offset_ms: i64,
+        previous_text: Option<&str>,
+        engine: &mut dyn SttEngine,
+    ) -> Result<ChunkTranscript> {
+        let language = if self.auto_detect_language || self.languages.is_empty() {
+            None
         } else {
-            let whisper_lang = to_whisper_lang(&self.languages[0]);
-            params.set_language(whisper_lang);
-        }
-
-        params.set_print_special(false);
-        params.set_print_progress(false);
-        params.set_print_realtime(false);
-        params.set_print_timestamps(false);
-        params.set_suppress_blank(true);
-        params.set_suppress_nst(true);
-
-        // Set keyterms as initial prompt
-        if !self.keyterms.is_empty() {
-            let prompt = format!("Terms: {}.", self.keyterms.join(", "));
-            let truncated = if prompt.len() > 800 {
-                format!("{}...", &prompt[..800])
-            } else {
-                prompt
-            };
-            params.set_initial_prompt(&truncated);
+            Some(self.languages[0].as_str())
+        };
+        let request = ChunkRequest {
+            samples,
+            offset_ms,
+            previous_text,
+            language,
+            keyterms: &self.keyterms,
+            decoding: DecodingOptions {
+                beam_size: self.beam_size.load(Ordering::SeqCst),
+                ..self.decoding
+            },
+        };
+        let transcript = engine.transcribe(&request)?;
+
+        if let Some(lang) = &transcript.language {
+            let _ = self.detected_language.set(lang.clone());
         }
 
-        // Run transcription
-        state
-            .full(params, samples)
-            .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?;
+        Ok(transcript)
+    }
+}
+
+/// Title for a quick-capture note: its first sentence, without the closing
+/// punctuation and cut to `NOTE_TITLE_MAX_CHARS`
+fn note_title(text: &str) -> String {
+    let text = text.trim();
+    let end = text.find(['.', '!', '?', '\n']).unwrap_or(text.len());
+    let sentence = text[..end].trim();
 
-        // Collect results
-        let mut text = String::new();
-        let num_segments = state
-            .full_n_segments()
-            .map_err(|e| anyhow::anyhow!("Failed to get segments: {}", e))?;
+    text::preview(sentence, NOTE_TITLE_MAX_CHARS)
+}
+
+/// Normalize a word for loose comparison (case and punctuation insensitive)
+fn comparable_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
 
-        for i in 0..num_segments {
-            if let Ok(segment) = state.full_get_segment_text(i) {
-                text.push_str(&segment);
-                text.push(' ');
+/// Append a `[?]` marker to words of `text` that Whisper decoded with low confidence.
+/// Flagged words are matched in order with a small lookahead, so light edits by the
+/// command interpreter or formatter don't derail the alignment.
+fn mark_low_confidence_words(text: &str, segments: &[TranscriptSegment], threshold: f32) -> String {
+    const LOOKAHEAD: usize = 3;
+
+    let flagged: Vec<String> = segments
+        .iter()
+        .flat_map(|s| s.words.iter())
+        .filter(|w| w.confidence < threshold)
+        .map(|w| comparable_word(&w.text))
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    if flagged.is_empty() {
+        return text.to_string();
+    }
+
+    let mut next = 0;
+    let mut marked: Vec<String> = Vec::new();
+
+    for word in text.split_whitespace() {
+        let candidate = comparable_word(word);
+        let hit = flagged[next..]
+            .iter()
+            .take(LOOKAHEAD)
+            .position(|f| *f == candidate);
+
+        match hit {
+            Some(offset) if !candidate.is_empty() => {
+                next += offset + 1;
+                marked.push(format!("{}{}", word, LOW_CONFIDENCE_MARKER));
             }
+            _ => marked.push(word.to_string()),
         }
 
-        Ok(text.trim().to_string())
+        if next >= flagged.len() {
+            // Nothing left to mark; keep the remaining words untouched
+            marked.extend(
+                text.split_whitespace()
+                    .skip(marked.len())
+                    .map(|w| w.to_string()),
+            );
+            break;
+        }
     }
+
+    marked.join(" ")
 }
 
 /// Merge two text segments with overlap deduplication
-fn merge_with_overlap_dedup(text_a: &str, text_b: &str) -> String {
+pub(crate) fn merge_with_overlap_dedup(text_a: &str, text_b: &str) -> String {
     let words_a: Vec<&str> = text_a.split_whitespace().collect();
     let words_b: Vec<&str> = text_b.split_whitespace().collect();
 
@@ -706,27 +1639,9 @@ fn merge_with_overlap_dedup(text_a: &str, text_b: &str) -> String {
     format!("{} {}", text_a, text_b)
 }
 
-/// Convert language code to Whisper language format
-fn to_whisper_lang(lang: &str) -> Option<&'static str> {
-    match lang {
-        "en-US" | "en-GB" => Some("en"),
-        "es" => Some("es"),
-        "fr" => Some("fr"),
-        "de" => Some("de"),
-        "it" => Some("it"),
-        "pt" => Some("pt"),
-        "ja" => Some("ja"),
-        "ko" => Some("ko"),
-        "zh" => Some("zh"),
-        _ => None,
-    }
-}
-
 /// Macro for local audio capture - accumulates f32 samples for Whisper
 macro_rules! create_local_stream {
-    ($device:ident, $config:expr, $samples:ident, $sample_type:ty, $app:ident, $last_emit:ident, $is_active:ident, $stop_tx:ident) => {{
-        let app_clone = $app.clone();
-        let last_emit_clone = $last_emit.clone();
+    ($device:ident, $config:expr, $samples:ident, $sample_type:ty, $is_active:ident) => {{
         let is_active_clone = $is_active.clone();
         let samples_clone = $samples.clone();
         $device
@@ -738,143 +1653,267 @@ macro_rules! create_local_stream {
                         return;
                     }
 
+                    // Convert to f32 normalized [-1.0, 1.0]; f32 input passes through
+                    // unchanged, integer formats are scaled from their full range
+                    let f32_samples: Vec<f32> = data
+                        .iter()
+                        .map(|sample| sample.to_sample::<f32>())
+                        .collect();
+
                     // Calculate audio level for visualization
-                    let mut sum: f64 = 0.0;
-                    let mut f32_samples: Vec<f32> = Vec::with_capacity(data.len());
-
-                    for sample in data {
-                        let sample_value: i16 = sample.to_sample();
-                        // Convert to f32 normalized [-1.0, 1.0]
-                        let f32_sample = sample_value as f32 / 32768.0;
-                        f32_samples.push(f32_sample);
-                        sum += (sample_value as f64).abs();
-                    }
+                    let sum: f64 = f32_samples.iter().map(|s| s.abs() as f64).sum();
 
                     // Accumulate samples for Whisper
                     if let Ok(mut samples_guard) = samples_clone.lock() {
                         samples_guard.extend(f32_samples);
                     }
 
-                    // Calculate average amplitude (0-100 scale)
-                    let avg_amplitude = (sum / data.len() as f64) / 327.68;
-
-                    // Throttle event emission to ~30fps (every ~33ms)
-                    let now = SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap()
-                        .as_millis() as u64;
-                    let last = last_emit_clone.load(Ordering::Relaxed);
-
-                    if now - last >= 33 {
-                        last_emit_clone.store(now, Ordering::Relaxed);
-                        println!("Emitting audio level: {:.2}", avg_amplitude);
-                        let _ = app_clone.emit("audio-level", avg_amplitude);
-                    }
+                    // Calculate average amplitude (0-100 scale) for the visualizers,
+                    // which receive it at their own frame rate
+                    let avg_amplitude = (sum / data.len().max(1) as f64) * 100.0;
+                    audio_level::publish(avg_amplitude as f32);
                 },
-                |err| eprintln!("Audio stream error: {:?}", err),
+                |err| warn!("Audio stream error: {:?}", err),
                 None,
             )
             .map_err(|e| anyhow::anyhow!("Failed to build audio stream: {}", e))
     }};
 }
 
-/// Local microphone capture - accumulates samples for Whisper transcription
-fn start_local_microphone(
-    app: AppHandle,
-    is_active: Arc<AtomicBool>,
-    samples: Arc<std::sync::Mutex<Vec<f32>>>,
-) -> Result<(u32, u16, crossbeam_channel::Sender<()>)> {
-    let host = cpal::default_host();
-    let device = host
-        .default_input_device()
-        .ok_or_else(|| anyhow::anyhow!("No input device available"))?;
-    let config = device
+/// Names of the available input devices, in host order
+pub fn input_device_names() -> Vec<String> {
+    cpal::default_host()
+        .input_devices()
+        .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// The microphone chosen in settings, falling back to the system default
+/// when none is chosen or it is no longer connected
+fn select_input_device(host: &cpal::Host) -> Result<cpal::Device> {
+    let preferred = read_setting(setting_keys::INPUT_DEVICE).filter(|name| !name.is_empty());
+
+    if let Some(name) = preferred {
+        let device = host.input_devices().ok().and_then(|mut devices| {
+            devices.find(|d| d.name().ok().as_deref() == Some(name.as_str()))
+        });
+        match device {
+            Some(device) => return Ok(device),
+            None => info!("Input device '{}' not found, using system default", name),
+        }
+    }
+
+    host.default_input_device()
+        .ok_or_else(|| anyhow::anyhow!("No input device available"))
+}
+
+/// Payload of `recording-retry`, emitted when the input stream failed to start and
+/// another attempt follows
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct RecordingRetry {
+    /// The attempt that failed, starting at 1
+    pub attempt: usize,
+    pub max_attempts: usize,
+    pub error: String,
+}
+
+/// Input configs to try in order: 16kHz mono when the device supports it, the
+/// device default, the default with a fixed buffer size, then other common sample
+/// rates the device supports
+fn candidate_input_configs(
+    device: &cpal::Device,
+) -> Result<Vec<(cpal::StreamConfig, SampleFormat)>> {
+    let default = device
         .default_input_config()
         .map_err(|e| anyhow::anyhow!("Failed to get input config: {}", e))?;
+    let default_rate = default.sample_rate().0;
+    let sample_format = default.sample_format();
+    let supported: Vec<cpal::SupportedStreamConfigRange> = device
+        .supported_input_configs()
+        .map(|configs| configs.collect())
+        .unwrap_or_default();
+
+    let mut candidates = Vec::new();
+
+    // Captured audio is already in Whisper's format, so nothing is downmixed or resampled
+    let transcription_format = supported
+        .iter()
+        .filter(|range| range.channels() == 1 && supports_rate(range, TRANSCRIPTION_SAMPLE_RATE))
+        .max_by_key(|range| range.sample_format() == SampleFormat::F32);
+    if let Some(range) = transcription_format {
+        let config = range
+            .clone()
+            .with_sample_rate(cpal::SampleRate(TRANSCRIPTION_SAMPLE_RATE));
+        candidates.push((config.config(), config.sample_format()));
+    }
 
-    let sample_rate = config.sample_rate().0;
-    let channels = config.channels();
+    candidates.push((default.config(), sample_format));
 
-    println!(
-        "Local audio config - sample_rate: {}, channels: {}",
-        sample_rate, channels
-    );
+    let mut fixed_buffer = default.config();
+    fixed_buffer.buffer_size = cpal::BufferSize::Fixed(STREAM_RETRY_BUFFER_FRAMES);
+    candidates.push((fixed_buffer, sample_format));
 
-    let (stop_tx, stop_rx) = crossbeam_channel::bounded::<()>(1);
-    let last_emit = Arc::new(AtomicU64::new(0));
+    for rate in STREAM_RETRY_SAMPLE_RATES
+        .iter()
+        .filter(|rate| **rate != default_rate)
+    {
+        if let Some(range) = supported.iter().find(|range| supports_rate(range, *rate)) {
+            let config = range.clone().with_sample_rate(cpal::SampleRate(*rate));
+            candidates.push((config.config(), config.sample_format()));
+        }
+    }
 
-    let samples_for_thread = samples.clone();
-    let is_active_for_thread = is_active.clone();
-    let app_for_thread = app.clone();
-    let last_emit_for_thread = last_emit.clone();
-    let config_clone = config.clone();
+    candidates.truncate(MAX_STREAM_ATTEMPTS);
+    Ok(candidates)
+}
 
-    thread::spawn(move || {
-        let stream_result = match config_clone.sample_format() {
-            SampleFormat::F32 => create_local_stream!(
-                device,
-                config_clone,
-                samples_for_thread,
-                f32,
-                app_for_thread,
-                last_emit_for_thread,
-                is_active_for_thread,
-                stop_tx
-            ),
-            SampleFormat::I16 => create_local_stream!(
-                device,
-                config_clone,
-                samples_for_thread,
-                i16,
-                app_for_thread,
-                last_emit_for_thread,
-                is_active_for_thread,
-                stop_tx
-            ),
-            SampleFormat::U16 => create_local_stream!(
-                device,
-                config_clone,
-                samples_for_thread,
-                u16,
-                app_for_thread,
-                last_emit_for_thread,
-                is_active_for_thread,
-                stop_tx
+/// Whether a supported config range can capture at `rate` in a format we handle
+fn supports_rate(range: &cpal::SupportedStreamConfigRange, rate: u32) -> bool {
+    range.min_sample_rate().0 <= rate
+        && range.max_sample_rate().0 >= rate
+        && sample_format_supported(range.sample_format())
+}
+
+/// Whether `build_local_stream` can capture in this sample format
+fn sample_format_supported(sample_format: SampleFormat) -> bool {
+    matches!(
+        sample_format,
+        SampleFormat::F32
+            | SampleFormat::F64
+            | SampleFormat::I8
+            | SampleFormat::I16
+            | SampleFormat::I32
+            | SampleFormat::I64
+            | SampleFormat::U8
+            | SampleFormat::U16
+            | SampleFormat::U32
+            | SampleFormat::U64
+    )
+}
+
+/// Build and start an input stream for one config. 24-bit interfaces are
+/// exposed by the host as I32 or F32.
+fn build_local_stream(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sample_format: SampleFormat,
+    samples: &Arc<std::sync::Mutex<Vec<f32>>>,
+    is_active: &Arc<AtomicBool>,
+) -> Result<cpal::Stream> {
+    let stream = match sample_format {
+        SampleFormat::F32 => create_local_stream!(device, config.clone(), samples, f32, is_active),
+        SampleFormat::F64 => create_local_stream!(device, config.clone(), samples, f64, is_active),
+        SampleFormat::I8 => create_local_stream!(device, config.clone(), samples, i8, is_active),
+        SampleFormat::I16 => create_local_stream!(device, config.clone(), samples, i16, is_active),
+        SampleFormat::I32 => create_local_stream!(device, config.clone(), samples, i32, is_active),
+        SampleFormat::I64 => create_local_stream!(device, config.clone(), samples, i64, is_active),
+        SampleFormat::U8 => create_local_stream!(device, config.clone(), samples, u8, is_active),
+        SampleFormat::U16 => create_local_stream!(device, config.clone(), samples, u16, is_active),
+        SampleFormat::U32 => create_local_stream!(device, config.clone(), samples, u32, is_active),
+        SampleFormat::U64 => create_local_stream!(device, config.clone(), samples, u64, is_active),
+        sample_format => {
+            return Err(anyhow::anyhow!(
+                "Unsupported sample format: {:?}",
+                sample_format
+            ))
+        }
+    }?;
+
+    stream
+        .play()
+        .map_err(|e| anyhow::anyhow!("Failed to start audio stream: {}", e))?;
+    Ok(stream)
+}
+
+/// Local microphone capture - accumulates samples for Whisper transcription.
+/// A stream that fails to start (e.g. another app holds the device exclusively)
+/// is retried with backoff and alternate configs, emitting `recording-retry`.
+/// The retries sleep on the stream's thread; callers await the outcome without
+/// blocking the async runtime.
+pub(crate) async fn start_local_microphone(
+    app: &AppHandle,
+    is_active: Arc<AtomicBool>,
+    samples: Arc<std::sync::Mutex<Vec<f32>>>,
+) -> Result<(u32, u16, crossbeam_channel::Sender<()>)> {
+    // Echo cancellation and noise suppression, at the cost of the device choice
+    if read_bool_setting(setting_keys::VOICE_PROCESSING, false) {
+        match voice_processing::start(is_active.clone(), samples.clone()) {
+            Ok(stop_tx) => return Ok((voice_processing::SAMPLE_RATE, 1, stop_tx)),
+            Err(e) => warn!(
+                "Voice processing unavailable, using the regular input: {}",
+                e
             ),
-            sample_format => {
-                eprintln!("Unsupported sample format: {:?}", sample_format);
-                return;
-            }
-        };
+        }
+    }
 
-        let stream = match stream_result {
-            Ok(s) => s,
-            Err(e) => {
-                eprintln!("Failed to create local stream: {}", e);
-                return;
-            }
-        };
+    // The host isn't held across the await below
+    let device = select_input_device(&cpal::default_host())?;
+    let candidates = candidate_input_configs(&device)?;
 
-        stream.play().unwrap();
-        println!("Local audio stream started");
+    let (stop_tx, stop_rx) = crossbeam_channel::bounded::<()>(1);
+    let (started_tx, started_rx) = tokio::sync::oneshot::channel::<Result<(u32, u16)>>();
+    let app = app.clone();
+
+    // The stream lives on its own thread until the stop signal
+    thread::spawn(move || {
+        let max_attempts = candidates.len();
+        let mut last_error = anyhow::anyhow!("No input config available");
+
+        for (idx, (config, sample_format)) in candidates.into_iter().enumerate() {
+            match build_local_stream(&device, &config, sample_format, &samples, &is_active) {
+                Ok(stream) => {
+                    debug!(
+                        "Local audio config - sample_rate: {}, channels: {}",
+                        config.sample_rate.0, config.channels
+                    );
+                    let _ = started_tx.send(Ok((config.sample_rate.0, config.channels)));
 
-        // Wait for stop signal
-        let _ = stop_rx.recv();
+                    // Wait for stop signal
+                    let _ = stop_rx.recv();
 
-        drop(stream);
-        println!("Local audio stream stopped");
+                    drop(stream);
+                    debug!("Local audio stream stopped");
+                    return;
+                }
+                Err(e) => {
+                    warn!(
+                        "Audio stream attempt {} of {} failed: {}",
+                        idx + 1,
+                        max_attempts,
+                        e
+                    );
+                    if idx + 1 < max_attempts {
+                        let _ = RecordingRetry {
+                            attempt: idx + 1,
+                            max_attempts,
+                            error: e.to_string(),
+                        }
+                        .emit(&app);
+                        thread::sleep(Duration::from_millis(STREAM_RETRY_BASE_DELAY_MS << idx));
+                    }
+                    last_error = e;
+                }
+            }
+        }
+
+        let _ = started_tx.send(Err(last_error));
     });
 
+    let (sample_rate, channels) = started_rx
+        .await
+        .map_err(|_| anyhow::anyhow!("Audio stream thread exited"))??;
+
+    debug!("Local audio stream started");
     Ok((sample_rate, channels, stop_tx))
 }
 
 
 
 // ============================================================================
-// Local Transcriber (Whisper-based)
+// Local Transcriber (Whisper or Moonshine)
 // ============================================================================
 
-/// Local transcriber using Whisper for offline transcription
+/// Local transcriber using an on-device model for offline transcription
 pub struct LocalTranscriber {
     /// Audio sample buffer (shared with audio capture)
     samples: Arc<std::sync::Mutex<Vec<f32>>>,
@@ -882,14 +1921,18 @@ pub struct LocalTranscriber {
     chunk_processor: Option<Arc<ChunkProcessor>>,
     /// Thread handle for chunk monitor
     chunk_monitor_handle: Option<thread::JoinHandle<()>>,
-    /// Thread handle for chunk worker
-    chunk_worker_handle: Option<thread::JoinHandle<()>>,
+    /// Thread handles for chunk workers
+    chunk_worker_handles: Vec<thread::JoinHandle<()>>,
     /// Channel to stop audio stream
     local_stop_tx: Option<crossbeam_channel::Sender<()>>,
     /// Sample rate of input audio
     sample_rate: u32,
     /// Number of channels in input audio
     channels: u16,
+    /// Live transcript document (session mode only)
+    live_session: Option<Arc<LiveSession>>,
+    /// Working directory of the current recording
+    workspace: Option<Arc<RecordingWorkspace>>,
 }
 
 impl LocalTranscriber {
@@ -899,10 +1942,42 @@ impl LocalTranscriber {
             samples: Arc::new(std::sync::Mutex::new(Vec::new())),
             chunk_processor: None,
             chunk_monitor_handle: None,
-            chunk_worker_handle: None,
+            chunk_worker_handles: Vec::new(),
             local_stop_tx: None,
             sample_rate: 16000,
             channels: 1,
+            live_session: None,
+            workspace: None,
+        }
+    }
+
+    /// Live session this recording streams into, if recording in session mode
+    pub fn live_session(&self) -> Option<Arc<LiveSession>> {
+        self.live_session.clone()
+    }
+
+    /// Duration of audio captured so far in milliseconds
+    pub fn recorded_duration_ms(&self) -> i64 {
+        let spilled = self
+            .chunk_processor
+            .as_ref()
+            .map(|processor| processor.samples_spilled())
+            .unwrap_or(0);
+        let len = spilled + self.samples.lock().map(|s| s.len()).unwrap_or(0);
+        let frames = len / self.channels.max(1) as usize;
+        (frames as u64 * 1000 / self.sample_rate.max(1) as u64) as i64
+    }
+
+    /// Audio buffered by this recording and the models loaded for it
+    pub fn memory_usage(&self) -> RecordingMemory {
+        let buffered = self.samples.lock().map(|s| s.len()).unwrap_or(0);
+        let Some(processor) = self.chunk_processor.as_ref() else {
+            return RecordingMemory::new(buffered, 0, 0);
+        };
+        let (queued, contexts) = processor.memory_usage();
+        RecordingMemory {
+            spilled_samples: processor.samples_spilled(),
+            ..RecordingMemory::new(buffered, queued, contexts)
         }
     }
 
@@ -918,55 +1993,126 @@ impl LocalTranscriber {
             samples.clear();
         }
 
-        // Start microphone capture
+        // Start microphone capture; test builds take injected audio instead
+        #[cfg(not(any(test, feature = "test-harness")))]
         let (sample_rate, channels, stop_tx) =
-            start_local_microphone(app.clone(), is_active.clone(), self.samples.clone())?;
+            start_local_microphone(&app, is_active.clone(), self.samples.clone()).await?;
+        #[cfg(any(test, feature = "test-harness"))]
+        let (sample_rate, channels, stop_tx) =
+            crate::stt::mock::start_audio(is_active.clone(), self.samples.clone());
 
         self.sample_rate = sample_rate;
         self.channels = channels;
         self.local_stop_tx = Some(stop_tx);
 
-        // Get Whisper model path
-        let app_data_dir = app
-            .path()
-            .app_data_dir()
-            .map_err(|e| anyhow::anyhow!("Failed to get app data directory: {}", e))?;
+        // Spilled audio and transcribed chunks go to disk; the recording works without it
+        self.workspace = match RecordingWorkspace::create(&app, sample_rate, channels) {
+            Ok(workspace) => Some(Arc::new(workspace)),
+            Err(e) => {
+                warn!("{}", e);
+                None
+            }
+        };
+
+        // Transcribe with the local model selected in settings, falling back to
+        // the default model until that one is downloaded
+        let mut model = selected_stt_model().map_err(|e| anyhow::anyhow!(e.message))?;
+        if model.id != DEFAULT_STT_MODEL && !model_installed(&app, model) {
+            let default = ModelRegistry::global()
+                .ok()
+                .and_then(|registry| registry.get_of_kind(DEFAULT_STT_MODEL, ModelKind::Stt));
+            if let Some(default) = default {
+                info!(
+                    "{} model is not downloaded, transcribing with {}",
+                    model.name, default.name
+                );
+                model = default;
+            }
+        }
+
+        // Low power mode (set by hand or by a low battery) transcribes one longer chunk
+        // at a time with fewer threads, on the smallest downloaded model
+        let low_power = power::low_power_active();
+        if low_power {
+            if let Some(smaller) =
+                smallest_installed_stt_model(&app).filter(|m| m.size_bytes < model.size_bytes)
+            {
+                info!(
+                    "🔋 Low power mode, transcribing with {} instead of {}",
+                    smaller.name, model.name
+                );
+                model = smaller;
+            }
+        }
+        let model_path = model_path(&app, model).map_err(|e| anyhow::anyhow!(e))?;
 
-        let model_path = app_data_dir.join("stt").join("ggml-small-q8_0.bin");
+        // Session mode streams chunks into a live transcript record
+        self.live_session = if settings.mode == RecordingMode::Session {
+            let session = LiveSession::start(app.clone(), settings.session_title.clone())
+                .map_err(|e| anyhow::anyhow!("Failed to start session: {}", e))?;
+            Some(Arc::new(session))
+        } else {
+            None
+        };
+
+        let worker_count = if low_power {
+            1
+        } else {
+            read_parsed_setting(setting_keys::CHUNK_WORKERS, DEFAULT_CHUNK_WORKERS)
+                .clamp(1, MAX_CHUNK_WORKERS)
+        };
+        let inference_threads = inference_thread_count(worker_count, low_power);
+        let (chunk_size_samples, chunk_duration_secs) = if low_power {
+            (LOW_POWER_CHUNK_SIZE_SAMPLES, LOW_POWER_CHUNK_DURATION_SECS)
+        } else {
+            (CHUNK_SIZE_SAMPLES, CHUNK_DURATION_SECS)
+        };
 
         // Create chunk processor
         let chunk_processor = Arc::new(ChunkProcessor::new(
+            app.clone(),
             self.samples.clone(),
-            model_path,
-            settings.auto_detect_language,
-            settings.languages.clone(),
-            settings.keyterms.clone(),
-            sample_rate,
-            channels,
+            ChunkProcessorConfig {
+                model,
+                model_path,
+                auto_detect_language: settings.auto_detect_language,
+                languages: settings.languages.clone(),
+                keyterms: settings.keyterms.clone(),
+                decoding: DecodingOptions::from_saved_settings(),
+                inference_threads,
+                chunk_size_samples,
+                input_sample_rate: sample_rate,
+                input_channels: channels,
+                live_session: self.live_session.clone(),
+                workspace: self.workspace.clone(),
+            },
         ));
 
         // Spawn background processing threads
+        performance::start_recording(worker_count);
         let monitor_handle = chunk_processor.spawn_chunk_monitor();
-        let worker_handle = chunk_processor.spawn_chunk_worker();
+        let worker_handles = (0..worker_count)
+            .map(|worker_id| chunk_processor.spawn_chunk_worker(worker_id))
+            .collect();
 
         self.chunk_processor = Some(chunk_processor);
         self.chunk_monitor_handle = Some(monitor_handle);
-        self.chunk_worker_handle = Some(worker_handle);
+        self.chunk_worker_handles = worker_handles;
 
-        println!(
-            "Local transcription started with chunked processing (chunk: {}s)",
-            CHUNK_DURATION_SECS
+        info!(
+            "Local transcription started with chunked processing (chunk: {}s, workers: {}, threads: {})",
+            chunk_duration_secs, worker_count, inference_threads
         );
 
         Ok(())
     }
 
     /// Stop local transcription and return merged results
-    pub async fn stop(&mut self) -> Result<String> {
+    pub async fn stop(&mut self) -> Result<TranscriptionOutput> {
         // Stop audio capture
         if let Some(stop_tx) = self.local_stop_tx.take() {
             let _ = stop_tx.send(());
-            println!("Sent stop signal to audio stream");
+            debug!("Sent stop signal to audio stream");
         }
 
         // Get the chunk processor
@@ -984,7 +2130,7 @@ impl LocalTranscriber {
 
         // Take thread handles
         let monitor_handle = self.chunk_monitor_handle.take();
-        let worker_handle = self.chunk_worker_handle.take();
+        let worker_handles = std::mem::take(&mut self.chunk_worker_handles);
 
         // Wait for all chunks to be processed (run in blocking task)
         let timeout = Duration::from_secs(300); // 5 minute timeout
@@ -995,6 +2141,10 @@ impl LocalTranscriber {
                 .map_err(|e| anyhow::anyhow!("Failed to wait for completion: {}", e))?;
 
         if !completed {
+            // Keep the audio and the chunks transcribed so far for recovery
+            if let Some(workspace) = self.workspace.take() {
+                workspace.retain("Chunk processing timed out");
+            }
             return Err(anyhow::anyhow!("Chunk processing timed out"));
         }
 
@@ -1003,53 +2153,371 @@ impl LocalTranscriber {
             if let Some(handle) = monitor_handle {
                 let _ = handle.join();
             }
-            if let Some(handle) = worker_handle {
+            for handle in worker_handles {
                 let _ = handle.join();
             }
         })
         .await
         .map_err(|e| anyhow::anyhow!("Failed to join threads: {}", e))?;
 
+        // Keep the audio so the recording can be transcribed once the model is fixed
+        if let Some(error) = chunk_processor.model_load_error() {
+            if let Some(workspace) = self.workspace.take() {
+                workspace.retain("Model failed to load");
+            }
+            return Err(anyhow::anyhow!(
+                "Failed to load the {} model: {}. The model file may be damaged; download it again in Settings.",
+                chunk_processor.model.name,
+                error
+            ));
+        }
+
         // Merge all chunk transcriptions
         let transcription = chunk_processor.merge_results();
+        let segments = chunk_processor.merge_segments();
 
-        println!(
-            "Whisper chunked transcription complete: '{}'",
-            if transcription.len() > 100 {
-                format!("{}...", &transcription[..100])
-            } else {
-                transcription.clone()
+        // Read before the workspace and its spill file go away
+        let audio = chunk_processor.recorded_audio();
+
+        // The workspace is deleted once the processor lets go of it, unless chunks failed
+        if let Some(workspace) = self.workspace.take() {
+            let failed = chunk_processor.failed_chunks();
+            if failed > 0 {
+                workspace.retain(&format!("{} chunks failed", failed));
             }
+        }
+
+        info!(
+            "Local chunked transcription complete: '{}'",
+            text::preview(&transcription, 100)
         );
 
-        Ok(transcription)
+        Ok(TranscriptionOutput {
+            text: transcription,
+            segments,
+            model: chunk_processor.model_name(),
+            language: chunk_processor.language(),
+            audio,
+        })
+    }
+
+    /// Stop capturing and drop queued chunks without transcribing them.
+    /// Workers finish the chunk they are on and exit; nothing waits for them.
+    pub fn cancel(&mut self) {
+        if let Some(stop_tx) = self.local_stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+
+        if let Some(chunk_processor) = self.chunk_processor.take() {
+            chunk_processor.signal_stop();
+            // The monitor may be adding a chunk; let it finish so that chunk is dropped
+            // too. The caller holds the service lock, so wait on a blocking thread.
+            let monitor_handle = self.chunk_monitor_handle.take();
+            tauri::async_runtime::spawn_blocking(move || {
+                if let Some(handle) = monitor_handle {
+                    let _ = handle.join();
+                }
+                chunk_processor.discard_pending();
+            });
+        }
+
+        self.chunk_worker_handles.clear();
+        self.workspace = None;
     }
 }
 
 
+// ============================================================================
+// Active Transcriber
+// ============================================================================
+
+/// Interim transcript emitted while recording
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct TranscriptionPartial {
+    /// Finalized text so far followed by the current interim hypothesis
+    pub text: String,
+    /// Whether the latest piece is final (will not be revised)
+    pub is_final: bool,
+}
+
+/// Mix interleaved input down to mono and resample it to the transcription rate
+pub(crate) fn downmix_and_resample(raw: &[f32], sample_rate: u32, channels: u16) -> Vec<f32> {
+    let channels = channels.max(1) as usize;
+    let mono: Vec<f32> = if channels > 1 {
+        raw.chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+            .collect()
+    } else {
+        raw.to_vec()
+    };
+
+    if sample_rate != TRANSCRIPTION_SAMPLE_RATE {
+        resample_audio(&mono, sample_rate, TRANSCRIPTION_SAMPLE_RATE)
+    } else {
+        mono
+    }
+}
+
+/// Transcriber backing the current recording
+enum ActiveTranscriber {
+    Local(LocalTranscriber),
+    Cloud(CloudTranscriber),
+    Upload(UploadTranscriber),
+}
+
+impl ActiveTranscriber {
+    fn live_session(&self) -> Option<Arc<LiveSession>> {
+        match self {
+            Self::Local(t) => t.live_session(),
+            Self::Cloud(_) | Self::Upload(_) => None,
+        }
+    }
+
+    fn recorded_duration_ms(&self) -> i64 {
+        match self {
+            Self::Local(t) => t.recorded_duration_ms(),
+            Self::Cloud(t) => t.recorded_duration_ms(),
+            Self::Upload(t) => t.recorded_duration_ms(),
+        }
+    }
+
+    fn memory_usage(&self) -> RecordingMemory {
+        match self {
+            Self::Local(t) => t.memory_usage(),
+            Self::Cloud(t) => t.memory_usage(),
+            Self::Upload(t) => t.memory_usage(),
+        }
+    }
+
+    async fn stop(&mut self) -> Result<TranscriptionOutput> {
+        match self {
+            Self::Local(t) => t.stop().await,
+            Self::Cloud(t) => t.stop().await,
+            Self::Upload(t) => t.stop().await,
+        }
+    }
+
+    fn cancel(&mut self) {
+        match self {
+            Self::Local(t) => t.cancel(),
+            Self::Cloud(t) => t.cancel(),
+            Self::Upload(t) => t.cancel(),
+        }
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
-/// Emit paste-complete event to the widget window
-fn emit_paste_complete(app: &AppHandle) {
-    if let Some(widget_window) = app.get_webview_window("widget") {
-        let _ = widget_window.emit("paste-complete", ());
+/// Payload of `transcription-processing`, emitted when a recording stops and its
+/// transcript is being finished
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct TranscriptionProcessing;
+
+/// Payload of `transcription-error`: why a dictation produced nothing to paste
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct TranscriptionError(pub String);
+
+/// Payload of `recording-cancelled`, emitted when a recording is dropped
+/// without being transcribed
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct RecordingCancelled;
+
+/// Payload of `session-finalizing`, emitted when a session recording stops and
+/// its last chunks are being transcribed
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct SessionFinalizing;
+
+/// Payload of `session-ended`, emitted once a session's transcript is complete
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct SessionEnded(pub SessionSummary);
+
+/// Payload of `session-started`, emitted once audio capture is running
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct SessionStarted {
+    pub mode: RecordingMode,
+}
+
+/// Payload of `first-words-detected`, emitted the first time a recording produces text
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct FirstWordsDetected {
+    pub text: String,
+}
+
+/// Grow the widget into the transcript preview and tell it the first words arrived
+pub(crate) fn emit_first_words<R: Runtime>(app: &AppHandle<R>, text: &str) {
+    window::set_widget_expanded(app, true);
+    let _ = FirstWordsDetected {
+        text: text.to_string(),
     }
+    .emit(app);
+}
+
+/// Elapsed time payload emitted every second while recording
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct RecordingElapsed {
+    pub elapsed_secs: u64,
+}
+
+/// Emit `recording-elapsed` and update the tray title once a second until `running` clears
+fn spawn_elapsed_timer(app: AppHandle, running: Arc<AtomicBool>) {
+    tokio::spawn(async move {
+        let started = std::time::Instant::now();
+        let mut ticker = tokio::time::interval(Duration::from_secs(1));
+
+        loop {
+            ticker.tick().await;
+            if !running.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let elapsed_secs = started.elapsed().as_secs();
+            let _ = RecordingElapsed { elapsed_secs }.emit(&app);
+            tray::set_recording_timer(&app, Some(elapsed_secs));
+        }
+    });
 }
 
 // ============================================================================
 // Transcription Service (Orchestration Layer)
 // ============================================================================
 
+/// Cloud pass that refines the local draft of a hybrid-mode dictation
+struct Refinement {
+    /// Providers to try, in order
+    providers: Vec<ProviderKey>,
+    /// Language requested from the provider; `None` when auto-detecting
+    language: Option<String>,
+    /// Keyterms sent as the prompt
+    keyterms: Vec<String>,
+}
+
+/// Payload of `transcription-refined`, emitted when the cloud pass of a hybrid-mode
+/// dictation has replaced the saved draft
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+pub struct TranscriptionRefined {
+    pub id: String,
+    /// Text that was pasted
+    pub draft: String,
+    /// Refined raw text now saved
+    pub refined: String,
+    /// Whether the saved formatted text was replaced too, so the pasted draft can
+    /// be swapped for it with `transcriptions_replace_pasted`
+    pub replaceable: bool,
+    pub model: String,
+}
+
+/// Transcribe the audio of a saved draft again in the cloud and replace the
+/// saved text when it came out different. Formatting isn't run again, so a
+/// formatted draft keeps its formatted text.
+async fn refine_transcription(
+    app: AppHandle,
+    id: String,
+    draft_raw: String,
+    draft_saved: String,
+    audio: Vec<f32>,
+    refinement: Refinement,
+) {
+    if audio.is_empty() || audio.len() > MAX_UPLOAD_SECS * TRANSCRIPTION_SAMPLE_RATE as usize {
+        info!("Skipping refinement: recording is empty or too long to upload at once");
+        return;
+    }
+
+    let request = CloudRequest {
+        language: refinement.language.as_deref(),
+        keyterms: &refinement.keyterms,
+    };
+    let (transcript, model) =
+        match transcribe_with_failover(&refinement.providers, &audio, &request).await {
+            Ok(result) => result,
+            Err(e) => {
+                diagnostics::record_error("refinement", format!("Failed to refine: {}", e));
+                return;
+            }
+        };
+
+    let refined = interpreter::interpret(&transcript.text);
+    if refined.trim().is_empty() || refined == draft_raw {
+        info!("Refinement by {} left the draft unchanged", model);
+        return;
+    }
+
+    // The saved text is only the draft as spoken when nothing was formatted or expanded
+    let replaceable = draft_saved == draft_raw;
+    let formatted = replaceable.then_some(refined.as_str());
+    if let Err(e) = update_refined_transcription(&id, &refined, formatted, &transcript.segments) {
+        diagnostics::record_error("database", e);
+        return;
+    }
+    info!("Refined transcription {} with {}: '{}'", id, model, refined);
+
+    mirror::mirror_transcription_by_id(&id);
+    if let Ok(updated) = transcriptions_get(id.clone()) {
+        let _ = emit_entity_event(&app, event_names::TRANSCRIPTIONS_UPDATED, updated);
+    }
+    let _ = TranscriptionRefined {
+        id,
+        draft: draft_saved,
+        refined,
+        replaceable,
+        model: model.to_string(),
+    }
+    .emit(&app);
+}
+
+/// Sets the active flag of a recording that is starting and clears it when
+/// dropped, so a start that fails partway doesn't leave it set
+struct ActiveFlagGuard(Option<Arc<AtomicBool>>);
+
+impl ActiveFlagGuard {
+    fn set(flag: Arc<AtomicBool>) -> Self {
+        flag.store(true, Ordering::Relaxed);
+        Self(Some(flag))
+    }
+
+    /// Leave the flag set; the recording is running
+    fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for ActiveFlagGuard {
+    fn drop(&mut self) {
+        if let Some(flag) = self.0.take() {
+            flag.store(false, Ordering::Relaxed);
+        }
+    }
+}
+
 pub struct TranscriptionService {
     is_recording: bool,
     is_active: Arc<AtomicBool>,
     auto_detect_language: bool,
     languages: Vec<String>,
     use_cloud: bool,
+    /// App the current recording is dictated into
+    target_app: Option<String>,
+    /// Process that was frontmost when recording started, brought back to the
+    /// front before pasting in case the user switched apps meanwhile
+    paste_target: Option<RunningApp>,
+    /// What the formatter is told about the app dictated into; captured when the
+    /// recording starts, except for the URL
+    format_context: FormatContext,
+    /// Text selected when a rewrite recording started; the dictation is an
+    /// instruction for rewriting it instead of text to paste
+    rewrite_selection: Option<String>,
+    /// Chat session a chat-mode recording is sent to
+    chat_session_id: Option<String>,
+    /// Set for a quick-capture recording, which becomes a new note instead of
+    /// being pasted
+    capture_note: bool,
+    /// Cloud pass run after the local draft is pasted (hybrid mode only)
+    refinement: Option<Refinement>,
     // Active transcriber
-    transcriber: Option<LocalTranscriber>,
+    transcriber: Option<ActiveTranscriber>,
+    /// Cleared on stop to end the elapsed timer of the current recording
+    timer_running: Arc<AtomicBool>,
 }
 
 impl TranscriptionService {
@@ -1060,7 +2528,15 @@ impl TranscriptionService {
             auto_detect_language: false,
             languages: vec!["en-US".to_string()],
             use_cloud: false,
+            target_app: None,
+            paste_target: None,
+            format_context: FormatContext::default(),
+            rewrite_selection: None,
+            chat_session_id: None,
+            capture_note: false,
+            refinement: None,
             transcriber: None,
+            timer_running: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -1068,32 +2544,205 @@ impl TranscriptionService {
         self.is_recording
     }
 
+    fn set_recording(&mut self, recording: bool) {
+        self.is_recording = recording;
+        RECORDING.store(recording, Ordering::SeqCst);
+    }
+
+    /// Memory held by the current recording (empty when idle)
+    pub fn memory_usage(&self) -> RecordingMemory {
+        self.transcriber
+            .as_ref()
+            .map(ActiveTranscriber::memory_usage)
+            .unwrap_or_default()
+    }
+
     pub async fn start_recording(
         &mut self,
         app: AppHandle,
-        settings: TranscriptionSettings,
+        mut settings: TranscriptionSettings,
     ) -> Result<()> {
         if self.is_recording {
             return Err(anyhow::anyhow!("Already recording"));
         }
 
-        // Set active flag to true
-        self.is_active.store(true, Ordering::Relaxed);
+        // Test builds inject their audio, so they need no microphone
+        if cfg!(not(any(test, feature = "test-harness"))) {
+            microphone::ensure_permission(&app)?;
+        }
+
+        settings.keyterms =
+            keyterms_for_category(settings.category.as_deref()).unwrap_or_else(|e| {
+                warn!("Failed to load keyterms: {}", e.message);
+                Vec::new()
+            });
+        debug!(
+            "Keyterms for category {:?}: {:?}",
+            settings.category, settings.keyterms
+        );
+
+        // Set before the microphone starts, since its callback checks the flag, and
+        // cleared again if anything below fails
+        let active = ActiveFlagGuard::set(self.is_active.clone());
         self.auto_detect_language = settings.auto_detect_language;
         self.languages = settings.languages.clone();
         self.use_cloud = settings.use_cloud;
+        self.target_app = settings.app_name.clone();
+        self.paste_target = app_context::frontmost_running_app();
+        self.chat_session_id = if settings.mode == RecordingMode::Chat {
+            Some(
+                settings
+                    .chat_session_id
+                    .clone()
+                    .ok_or_else(|| anyhow::anyhow!("Chat mode needs a chat session"))?,
+            )
+        } else {
+            None
+        };
+        // The widget doesn't take focus, so the dictated-into window is still focused
+        self.format_context = FormatContext {
+            app: settings.app_name.clone(),
+            url: None,
+            window_title: app_context::window_title_if_enabled(),
+            selected_text_length: app_context::selected_text_length(),
+        };
 
-        // Create local transcriber
-        let mut transcriber = LocalTranscriber::new();
+        // Hybrid mode transcribes locally for an instant paste, then refines the saved
+        // text with Groq or OpenAI. It needs one of their keys to be worth it.
+        let hybrid_providers = if settings.mode == RecordingMode::Dictation
+            && read_bool_setting(setting_keys::HYBRID_TRANSCRIPTION, false)
+        {
+            refinement_providers()
+        } else {
+            Vec::new()
+        };
+        self.refinement = if hybrid_providers.is_empty() {
+            None
+        } else {
+            Some(Refinement {
+                providers: hybrid_providers,
+                language: if settings.auto_detect_language {
+                    None
+                } else {
+                    settings.languages.first().cloned()
+                },
+                keyterms: settings.keyterms.clone(),
+            })
+        };
+
+        // Use the cloud when enabled and a transcription key is in the vault: upload
+        // to Groq or OpenAI when picked, otherwise stream to Deepgram. Session mode
+        // stays local so chunks keep streaming into the live document.
+        let use_cloud = settings.use_cloud
+            && settings.mode != RecordingMode::Session
+            && self.refinement.is_none();
+        let upload_providers = if use_cloud {
+            preferred_providers()
+        } else {
+            Vec::new()
+        };
+        let cloud_key = if use_cloud && upload_providers.is_empty() {
+            keys_vault_get(VaultService::Deepgram).ok().flatten()
+        } else {
+            None
+        };
+
+        let mode = settings.mode;
 
         // Start transcription
-        transcriber
-            .start(app.clone(), settings, self.is_active.clone())
-            .await?;
+        let transcriber = if !upload_providers.is_empty() {
+            let mut transcriber = UploadTranscriber::new(upload_providers);
+            transcriber
+                .start(app.clone(), settings, self.is_active.clone())
+                .await?;
+            ActiveTranscriber::Upload(transcriber)
+        } else if let Some(api_key) = cloud_key {
+            let mut transcriber = CloudTranscriber::new();
+            transcriber
+                .start(app.clone(), settings, self.is_active.clone(), api_key)
+                .await?;
+            ActiveTranscriber::Cloud(transcriber)
+        } else {
+            let mut transcriber = LocalTranscriber::new();
+            transcriber
+                .start(app.clone(), settings, self.is_active.clone())
+                .await?;
+            ActiveTranscriber::Local(transcriber)
+        };
 
         // Store transcriber and mark as recording
         self.transcriber = Some(transcriber);
-        self.is_recording = true;
+        self.set_recording(true);
+        active.disarm();
+
+        // A fresh flag per recording so a lingering timer from the last one can't resume
+        self.timer_running = Arc::new(AtomicBool::new(true));
+        spawn_elapsed_timer(app.clone(), self.timer_running.clone());
+
+        let _ = SessionStarted { mode }.emit(&app);
+
+        Ok(())
+    }
+
+    /// Start a recording whose dictation rewrites `selection`, e.g. "make this more
+    /// formal". The rewritten text is pasted over the selection on stop.
+    pub async fn start_rewrite(&mut self, app: AppHandle, selection: String) -> Result<()> {
+        self.start_recording(app, TranscriptionSettings::from_saved_settings())
+            .await?;
+        self.rewrite_selection = Some(selection);
+        Ok(())
+    }
+
+    /// Start a quick-capture recording. On stop the dictation is saved as a new
+    /// note; the clipboard and the frontmost app are left alone.
+    pub async fn start_note_capture(&mut self, app: AppHandle) -> Result<()> {
+        self.start_recording(app, TranscriptionSettings::from_saved_settings())
+            .await?;
+        self.capture_note = true;
+        Ok(())
+    }
+
+    /// Stop recording and discard the audio without transcribing or pasting.
+    /// A session-mode recording keeps what was already streamed into its document.
+    pub fn cancel_recording(&mut self, app: AppHandle) -> Result<()> {
+        if !self.is_recording {
+            return Err(anyhow::anyhow!("Not recording"));
+        }
+
+        self.is_active.store(false, Ordering::Relaxed);
+        self.set_recording(false);
+        self.target_app = None;
+        self.paste_target = None;
+        self.format_context = FormatContext::default();
+        self.rewrite_selection = None;
+        self.chat_session_id = None;
+        self.capture_note = false;
+        self.refinement = None;
+
+        self.timer_running.store(false, Ordering::Relaxed);
+        tray::set_recording_timer(&app, None);
+
+        let mut transcriber = self
+            .transcriber
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("No active transcriber"))?;
+
+        let live_session = transcriber.live_session();
+        let duration_ms = transcriber.recorded_duration_ms();
+        transcriber.cancel();
+
+        if let Some(session) = live_session {
+            match session.finish(duration_ms) {
+                Ok(summary) => {
+                    let _ = SessionEnded(summary).emit(&app);
+                }
+                Err(e) => warn!("Failed to finish session {}: {}", session.id(), e),
+            }
+        }
+
+        info!("🚫 Recording cancelled");
+        let _ = RecordingCancelled.emit(&app);
+        emit_paste_complete(&app);
 
         Ok(())
     }
@@ -1110,10 +2759,14 @@ impl TranscriptionService {
 
         // Set active flag to false to stop audio processing
         self.is_active.store(false, Ordering::Relaxed);
-        self.is_recording = false;
+        self.set_recording(false);
+
+        self.timer_running.store(false, Ordering::Relaxed);
+        tray::set_recording_timer(&app, None);
 
         // Emit processing event
-        let _ = app.emit("transcription-processing", ());
+        let _ = TranscriptionProcessing.emit(&app);
+        let _ = SessionFinalizing.emit(&app);
 
         // Take transcriber
         let mut transcriber = self
@@ -1124,167 +2777,365 @@ impl TranscriptionService {
         // Get app for async task
         let app_clone = app.clone();
         let use_cloud = self.use_cloud;
+        let target_app = self.target_app.take();
+        let paste_target = self.paste_target.take();
+        let mut format_context = std::mem::take(&mut self.format_context);
+        let rewrite_selection = self.rewrite_selection.take();
+        let chat_session_id = self.chat_session_id.take();
+        let capture_note = std::mem::take(&mut self.capture_note);
+        let refinement = self.refinement.take();
 
         // Spawn async task to stop transcription and process results
         tokio::spawn(async move {
+            let live_session = transcriber.live_session();
+            let duration_ms = transcriber.recorded_duration_ms();
+
             // Stop transcription and get raw text
-            let transcription = match transcriber.stop().await {
-                Ok(text) => text,
+            let stop_result = transcriber.stop().await;
+
+            // Session mode: chunks were already streamed into the live transcript,
+            // so close the document instead of pasting
+            if let Some(session) = live_session {
+                if let Err(e) = &stop_result {
+                    warn!("Session transcription failed: {}", e);
+                    let _ = TranscriptionError(e.to_string()).emit(&app_clone);
+                }
+                match session.finish(duration_ms) {
+                    Ok(summary) => {
+                        let _ = SessionEnded(summary).emit(&app_clone);
+                    }
+                    Err(e) => warn!("Failed to finish session {}: {}", session.id(), e),
+                }
+                emit_paste_complete(&app_clone);
+                return;
+            }
+
+            let (transcription, segments, model, language, audio) = match stop_result {
+                Ok(output) => (
+                    output.text,
+                    output.segments,
+                    output.model,
+                    // Settings hold `en-US` where Whisper detects `en`
+                    output.language.as_deref().map(translator::base_language),
+                    output.audio,
+                ),
                 Err(e) => {
-                    eprintln!("Transcription failed: {}", e);
-                    let _ = app_clone.emit("transcription-error", format!("{}", e));
+                    diagnostics::record_error(
+                        "transcription",
+                        format!("Transcription failed: {}", e),
+                    );
+                    let _ = TranscriptionError(e.to_string()).emit(&app_clone);
                     emit_paste_complete(&app_clone);
                     return;
                 }
             };
 
+            let threshold = read_parsed_setting(
+                setting_keys::LOW_CONFIDENCE_THRESHOLD,
+                DEFAULT_LOW_CONFIDENCE_THRESHOLD,
+            );
+
+            // Surface per-segment confidence to the frontend
+            if !segments.is_empty() {
+                let average_confidence =
+                    segments.iter().map(|s| s.confidence).sum::<f32>() / segments.len() as f32;
+                let low_confidence_words = segments
+                    .iter()
+                    .flat_map(|s| s.words.iter())
+                    .filter(|w| w.confidence < threshold)
+                    .count();
+                let _ = TranscriptionConfidence {
+                    segments: segments.clone(),
+                    average_confidence,
+                    low_confidence_words,
+                }
+                .emit(&app_clone);
+            }
+
+            // Rewrite spoken commands (spell mode, ...) into their literal output
+            let transcription = interpreter::interpret(&transcription);
+
             if transcription.trim().is_empty() {
-                println!("No transcription produced");
+                info!("No transcription produced");
                 emit_paste_complete(&app_clone);
                 return;
             }
 
-            // Apply formatting if cloud is enabled and auth token is available
-            let (raw_text, final_text) =
-                if use_cloud && !app_name.is_empty() && !style.is_empty() {
-                    // Read auth token from Tauri store
-                    let auth_token: Option<String> = app_clone
-                        .store("auth.json")
-                        .ok()
-                        .and_then(|s| s.get("token"))
-                        .and_then(|v| v.as_str().map(|s| s.to_string()));
-
-                    println!("{:?}", auth_token);
-
-                    if let Some(ref token) = auth_token {
-                        println!("Formatting for category: {}, style: {}", app_name, style);
-                        // app_name here is actually the category (Personal, Work, Email, General)
-                        // passed from the frontend after detecting the active app
-                        match format_text(token, &app_name, &style, &app_name, &transcription).await
-                        {
-                            Ok(formatted) => {
-                                println!("Formatted: {}", formatted);
-                                (transcription.clone(), formatted)
-                            }
-                            Err(e) => {
-                                eprintln!("Failed to format: {}", e);
-                                (transcription.clone(), transcription.clone())
-                            }
-                        }
-                    } else {
-                        println!("No auth token, skipping formatting");
-                        (transcription.clone(), transcription.clone())
-                    }
-                } else {
-                    (transcription.clone(), transcription.clone())
-                };
-
-            // Save transcription to database
-            if let Err(e) = save_transcription_to_db(&app_clone, &raw_text, Some(&final_text)) {
-                eprintln!("Failed to save transcription: {}", e);
+            // Chat mode: the dictation is the next message of the chat. The reply
+            // streams back as chat events; nothing is pasted or saved.
+            if let Some(session_id) = chat_session_id {
+                if let Err(e) = chat::send(app_clone.clone(), session_id, transcription) {
+                    diagnostics::record_error("chat", format!("Failed to send to chat: {}", e));
+                    let _ = TranscriptionError(e).emit(&app_clone);
+                }
+                emit_paste_complete(&app_clone);
+                return;
             }
 
-            // Paste the result
-            paste_text(app_clone, final_text);
-        });
-
-        Ok(())
-    }
-}
-
-fn paste_text(app: AppHandle, text: String) {
-    #[cfg(target_os = "macos")]
-    {
-        use cocoa::base::{id, nil};
-        use cocoa::foundation::NSString;
-        use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation};
-        use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
-        use objc::{class, msg_send, sel, sel_impl};
-
-        // Clone app handle and text for the thread
-        let app_clone = app.clone();
-        let text_clone = text.clone();
-
-        thread::spawn(move || {
-            unsafe {
-                let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
-                if pasteboard == nil {
-                    eprintln!("Failed to get pasteboard");
-                    if let Some(widget_window) = app_clone.get_webview_window("widget") {
-                        let _ = widget_window.emit("paste-complete", ());
+            // Quick capture: the dictation becomes a new note, titled with its first
+            // sentence. Nothing is pasted or saved to the history.
+            if capture_note {
+                let input = CreateNoteInput {
+                    title: note_title(&transcription),
+                    content: transcription,
+                };
+                match notes_create(app_clone.clone(), input) {
+                    Ok(note) => info!("Captured note: {}", note.title),
+                    Err(e) => {
+                        diagnostics::record_error(
+                            "notes",
+                            format!("Failed to save captured note: {}", e.message),
+                        );
+                        let _ = TranscriptionError(e.message).emit(&app_clone);
                     }
-                    return;
                 }
+                emit_paste_complete(&app_clone);
+                return;
+            }
 
-                // Clear contents
-                let _: () = msg_send![pasteboard, clearContents];
-
-                // Create NSString for the text
-                let ns_string = NSString::alloc(nil).init_str(&text_clone);
-                if ns_string == nil {
-                    eprintln!("Failed to create NSString");
-                    if let Some(widget_window) = app_clone.get_webview_window("widget") {
-                        let _ = widget_window.emit("paste-complete", ());
+            // Rewrite mode: the selection is still selected, so pasting replaces it.
+            // Nothing is saved since the dictation was an instruction.
+            if let Some(selection) = rewrite_selection {
+                match rewriter::rewrite(&selection, &transcription).await {
+                    Ok(rewritten) => {
+                        info!("Rewrote selection: {}", rewritten);
+                        if let Err(e) = paste_into(app_clone, paste_target, rewritten) {
+                            diagnostics::record_error(
+                                "paste",
+                                format!("Failed to paste rewritten text: {}", e),
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        diagnostics::record_error(
+                            "rewrite",
+                            format!("Failed to rewrite selection: {}", e),
+                        );
+                        let _ = TranscriptionError(e).emit(&app_clone);
+                        emit_paste_complete(&app_clone);
                     }
-                    return;
                 }
+                return;
+            }
 
-                let ns_string_type = NSString::alloc(nil).init_str("public.utf8-plain-text");
-                let success: bool =
-                    msg_send![pasteboard, setString:ns_string forType:ns_string_type];
+            // The app dictated into, while it is still in front
+            let target = app_context::target_context(target_app.as_deref());
 
-                if !success {
-                    eprintln!("Failed to set string to pasteboard");
-                    if let Some(widget_window) = app_clone.get_webview_window("widget") {
-                        let _ = widget_window.emit("paste-complete", ());
-                    }
-                    return;
+            // Notes and Markdown editors get headings and lists from spoken commands
+            let markdown = target.as_ref().is_some_and(|app| app.markdown);
+            let transcription = if markdown {
+                interpreter::apply_markdown_commands(&transcription)
+            } else {
+                transcription
+            };
+
+            // Translate into the configured output language; the spoken text stays the raw text
+            let output_text =
+                translator::translate_if_enabled(&transcription, language.as_deref()).await;
+
+            // Nothing is formatted when no style was picked at all
+            let url = target
+                .and_then(|app| app.url)
+                .filter(|_| use_cloud && !style.is_empty());
+            // Only the origin and path leave the device, and they're kept in the
+            // offline queue the same way
+            format_context.url = url.as_deref().and_then(redact_url_if_enabled);
+
+            // A URL rule for the browser tab overrides the category and style picked
+            // from the app
+            let rule = url
+                .as_deref()
+                .filter(|_| url_rules::has_enabled_rules())
+                .and_then(url_rules::style_for_url);
+            let (app_name, style) = match rule {
+                Some((category, rule_style)) => {
+                    info!("URL rule applies: {} / {}", category.as_str(), rule_style);
+                    (category.as_str().to_string(), rule_style)
                 }
+                None => (app_name, style),
+            };
 
-                // Small delay before sending keyboard events
-                thread::sleep(Duration::from_millis(50));
+            // The Markdown style keeps that structure through formatting
+            let style = if markdown && !style.is_empty() {
+                MARKDOWN_STYLE.to_string()
+            } else {
+                style
+            };
+
+            // Set when the formatting server is unreachable; the raw text is pasted now
+            // and formatted later by the offline queue
+            let mut queue_formatting = false;
+            let mut custom_prompt = None;
 
-                // Send Cmd+V keyboard events
-                if let Ok(event_source) = CGEventSource::new(CGEventSourceStateID::HIDSystemState) {
-                    if let Ok(key_down) = CGEvent::new_keyboard_event(event_source.clone(), 9, true)
+            // Apply formatting if cloud is enabled and auth token is available
+            let (raw_text, final_text) = if use_cloud && !app_name.is_empty() && !style.is_empty() {
+                // Read auth token from the Keychain
+                let auth_token = read_auth_token();
+
+                if let Some(ref token) = auth_token {
+                    info!("Formatting for category: {}, style: {}", app_name, style);
+                    // app_name here is actually the category (Personal, Work, Email, General)
+                    // passed from the frontend after detecting the active app
+                    custom_prompt = custom_prompt_for(&app_name).map(|template| {
+                        app_context::resolve_prompt(&template, target_app.as_deref())
+                    });
+                    match format_text(
+                        token,
+                        &app_name,
+                        &style,
+                        &output_text,
+                        &format_context,
+                        custom_prompt.as_deref(),
+                    )
+                    .await
                     {
-                        key_down.set_flags(CGEventFlags::CGEventFlagCommand);
-                        let _ = key_down.post(CGEventTapLocation::HID);
+                        Ok(formatted) => {
+                            info!("Formatted: {}", formatted);
+                            (transcription.clone(), formatted)
+                        }
+                        Err(e) => {
+                            diagnostics::record_error(
+                                "formatting",
+                                format!("Failed to format: {}", e),
+                            );
+                            queue_formatting = e.is_network();
+                            (transcription.clone(), output_text.clone())
+                        }
                     }
+                } else {
+                    info!("No auth token, skipping formatting");
+                    (transcription.clone(), output_text.clone())
+                }
+            } else {
+                (transcription.clone(), output_text.clone())
+            };
 
-                    thread::sleep(Duration::from_millis(50));
+            // Expand text shortcuts after formatting so the snippets are pasted as written.
+            // The pasted text keeps the {cursor} marker for the paste step; the saved one doesn't.
+            let final_text = expansion::expand_shortcuts(&final_text, &app_name);
+            let (saved_text, _) = placeholders::take_cursor(&final_text);
 
-                    if let Ok(key_up) = CGEvent::new_keyboard_event(event_source, 9, false) {
-                        key_up.set_flags(CGEventFlags::CGEventFlagCommand);
-                        let _ = key_up.post(CGEventTapLocation::HID);
+            // Save transcription to database
+            let saved_id = match save_transcription(
+                &raw_text,
+                Some(&saved_text),
+                &segments,
+                target_app.as_deref(),
+            ) {
+                // An accidental repeat is still pasted, but the earlier transcription
+                // was already mirrored, queued and announced
+                Ok(SaveOutcome::Duplicate(_)) => {
+                    paste_result(app_clone, paste_target, final_text, &segments, threshold);
+                    return;
+                }
+                Ok(SaveOutcome::Saved(id)) => {
+                    mirror::mirror_transcription_by_id(&id);
+                    // Kept for a day, or for good once the transcription is rated
+                    if !audio.is_empty() {
+                        if let Err(e) = recordings::save(&app_clone, &id, &audio) {
+                            warn!("{}", e);
+                        }
+                    }
+                    if queue_formatting {
+                        if let Err(e) = formatting_queue::enqueue(
+                            &id,
+                            &app_name,
+                            &style,
+                            &format_context,
+                            custom_prompt.as_deref(),
+                        ) {
+                            warn!("{}", e);
+                        }
                     }
+                    Some(id)
                 }
+                Err(e) => {
+                    diagnostics::record_error(
+                        "database",
+                        format!("Failed to save transcription: {}", e),
+                    );
+                    None
+                }
+            };
 
-                // Wait a bit for paste to complete
-                thread::sleep(Duration::from_millis(100));
-
-                // Clear clipboard after pasting (don't restore old contents to avoid exceptions)
-                let _: () = msg_send![pasteboard, clearContents];
-
-                println!("✅ Pasted successfully.");
+            if let Err(e) = record_keyterm_usage(&raw_text) {
+                warn!("Failed to record keyterm usage: {}", e.message);
             }
 
-            // Always emit paste-complete event
-            if let Some(widget_window) = app_clone.get_webview_window("widget") {
-                let _ = widget_window.emit("paste-complete", ());
+            let _ = TranscriptionResult {
+                id: saved_id.clone(),
+                raw: raw_text.clone(),
+                formatted: saved_text.clone(),
+                duration_ms,
+                model,
+                language,
+            }
+            .emit(&app_clone);
+
+            // Hybrid mode: the draft is pasted below while the cloud pass runs
+            if let (Some(refinement), Some(id)) = (refinement, saved_id) {
+                tokio::spawn(refine_transcription(
+                    app_clone.clone(),
+                    id,
+                    raw_text,
+                    saved_text,
+                    audio,
+                    refinement,
+                ));
             }
+
+            paste_result(app_clone, paste_target, final_text, &segments, threshold);
         });
+
+        Ok(())
     }
+}
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        eprintln!("Paste functionality is only available on macOS");
-        if let Some(widget_window) = app.get_webview_window("widget") {
-            let _ = widget_window.emit("paste-complete", ());
-        }
+/// Paste a dictation's text, flagging low-confidence words first when enabled
+fn paste_result(
+    app: AppHandle,
+    target: Option<RunningApp>,
+    text: String,
+    segments: &[TranscriptSegment],
+    threshold: f32,
+) {
+    // Optionally flag words worth double-checking (pasted text only)
+    let text = if read_bool_setting(setting_keys::MARK_LOW_CONFIDENCE, false) {
+        mark_low_confidence_words(&text, segments, threshold)
+    } else {
+        text
+    };
+
+    if let Err(e) = paste_into(app, target, text) {
+        diagnostics::record_error("paste", format!("Failed to paste transcription: {}", e));
     }
 }
 
+/// Threads each inference uses. Unless set explicitly, the cores are split
+/// between the chunk workers so they don't compete; low power mode caps the result.
+pub(crate) fn inference_thread_count(worker_count: usize, low_power: bool) -> i32 {
+    let cores = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(DEFAULT_WHISPER_THREADS);
+
+    let threads = match read_parsed_setting(setting_keys::WHISPER_THREADS, 0usize) {
+        0 => (cores / worker_count).clamp(1, DEFAULT_WHISPER_THREADS),
+        threads => threads.min(cores),
+    };
+    let threads = if low_power {
+        threads.min(LOW_POWER_MAX_THREADS)
+    } else {
+        threads
+    };
+
+    threads as i32
+}
+
+/// Length in milliseconds of a mono 16kHz sample buffer
+pub(crate) fn samples_to_ms(len: usize) -> i64 {
+    (len as u64 * 1000 / TRANSCRIPTION_SAMPLE_RATE as u64) as i64
+}
+
 /// Simple linear resampling
 fn resample_audio(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
     if from_rate == to_rate {
@@ -1319,3 +3170,145 @@ pub type TranscriptionServiceHandle = Arc<Mutex<TranscriptionService>>;
 pub fn create_transcription_service() -> TranscriptionServiceHandle {
     Arc::new(Mutex::new(TranscriptionService::new()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::mock;
+
+    /// Injected audio is shared by the whole process, so recordings take turns
+    static RECORDING: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    fn speech(secs: f32) -> Vec<f32> {
+        vec![0.0; (secs * mock::SAMPLE_RATE as f32) as usize]
+    }
+
+    /// Record `clips` (duration in seconds, what is said in it) and transcribe
+    /// them through the chunk processor the way `LocalTranscriber` does
+    fn transcribe(clips: &[(f32, &str)], workers: usize) -> (String, Vec<TranscriptSegment>) {
+        let _turn = RECORDING.lock().unwrap_or_else(|e| e.into_inner());
+        let app = tauri::test::mock_app();
+        let samples = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let is_active = Arc::new(AtomicBool::new(true));
+        let (sample_rate, channels, _stop_tx) =
+            mock::start_audio(is_active.clone(), samples.clone());
+
+        for (secs, text) in clips {
+            mock::inject_audio(speech(*secs), text).unwrap();
+        }
+        is_active.store(false, Ordering::Relaxed);
+
+        let processor = Arc::new(ChunkProcessor::new(
+            app.handle().clone(),
+            samples,
+            ChunkProcessorConfig {
+                model: ModelRegistry::global()
+                    .unwrap()
+                    .get(DEFAULT_STT_MODEL)
+                    .unwrap(),
+                model_path: PathBuf::from("mock.bin"),
+                auto_detect_language: false,
+                languages: vec!["en".to_string()],
+                keyterms: Vec::new(),
+                decoding: DecodingOptions::default(),
+                inference_threads: 1,
+                chunk_size_samples: CHUNK_SIZE_SAMPLES,
+                input_sample_rate: sample_rate,
+                input_channels: channels,
+                live_session: None,
+                workspace: None,
+            },
+        ));
+
+        // Cut the chunks the monitor would have while recording
+        while let Some(chunk) = processor.extract_next_chunk() {
+            processor.add_chunk(chunk);
+        }
+        let handles: Vec<_> = (0..workers)
+            .map(|worker_id| processor.spawn_chunk_worker(worker_id))
+            .collect();
+        processor.process_final_chunk();
+        processor.signal_stop();
+
+        assert!(processor.wait_for_completion(Duration::from_secs(10)));
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(processor.model_load_error(), None);
+
+        (processor.merge_results(), processor.merge_segments())
+    }
+
+    #[test]
+    fn short_recording_is_one_final_chunk() {
+        let (text, segments) = transcribe(&[(2.0, "hello world")], 1);
+
+        assert_eq!(text, "hello world");
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].start_ms, 0);
+        assert_eq!(segments[0].end_ms, 2000);
+    }
+
+    #[test]
+    fn words_in_chunk_overlaps_are_kept_once() {
+        let clips = [
+            (4.0, "the quick brown fox"),
+            (3.5, "jumps over the lazy dog"),
+            (5.0, "and then it runs far away"),
+        ];
+        let (text, segments) = transcribe(&clips, 2);
+
+        let spoken = clips.map(|(_, text)| text).join(" ");
+        assert_eq!(text, spoken);
+
+        let from_segments: Vec<&str> = segments
+            .iter()
+            .flat_map(|segment| segment.words.iter().map(|word| word.text.as_str()))
+            .collect();
+        assert_eq!(from_segments.join(" "), spoken);
+        assert!(segments
+            .windows(2)
+            .all(|pair| pair[0].end_ms <= pair[1].start_ms));
+    }
+
+    #[test]
+    fn word_offsets_are_on_the_recording_timeline() {
+        let (_, segments) = transcribe(&[(6.0, "one"), (6.0, "two")], 1);
+
+        let starts: Vec<i64> = segments.iter().map(|segment| segment.start_ms).collect();
+        assert_eq!(starts, vec![0, 6000]);
+    }
+
+    #[test]
+    fn silence_transcribes_to_nothing() {
+        let (text, segments) = transcribe(&[(7.0, "")], 1);
+
+        assert_eq!(text, "");
+        assert!(segments.is_empty());
+    }
+
+    #[test]
+    fn audio_is_refused_once_the_recording_stops() {
+        let _turn = RECORDING.lock().unwrap_or_else(|e| e.into_inner());
+        let is_active = Arc::new(AtomicBool::new(true));
+        let samples = Arc::new(std::sync::Mutex::new(Vec::new()));
+        mock::start_audio(is_active.clone(), samples.clone());
+
+        mock::inject_audio(speech(1.0), "kept").unwrap();
+        is_active.store(false, Ordering::Relaxed);
+
+        assert!(mock::inject_audio(speech(1.0), "dropped").is_err());
+        assert_eq!(samples.lock().unwrap().len(), speech(1.0).len());
+    }
+
+    #[test]
+    fn failed_start_clears_the_active_flag() {
+        let is_active = Arc::new(AtomicBool::new(false));
+
+        drop(ActiveFlagGuard::set(is_active.clone()));
+        assert!(!is_active.load(Ordering::Relaxed));
+
+        ActiveFlagGuard::set(is_active.clone()).disarm();
+        assert!(is_active.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use crate::commands::keys_vault::keys_vault_get;
+use crate::commands::settings::{keys as setting_keys, read_setting};
+use crate::llm::{self, ChatMessage, Provider};
+use crate::redaction::redact_if_enabled;
+
+/// Give up on a translation after this long and paste the spoken text instead
+const REQUEST_TIMEOUT_SECS: u64 = 20;
+
+/// Translate a dictation into the output language, if one is set.
+/// Falls back to the spoken text when translation is off, not needed, or fails.
+pub async fn translate_if_enabled(text: &str, spoken_language: Option<&str>) -> String {
+    let Some(target) = read_setting(setting_keys::OUTPUT_LANGUAGE).filter(|l| !l.is_empty()) else {
+        return text.to_string();
+    };
+
+    // Whisper reports "es" where settings hold "es" or "en-US"; compare the base language
+    if let Some(spoken) = spoken_language {
+        if base_language(spoken) == base_language(&target) {
+            return text.to_string();
+        }
+    }
+
+    let provider = Provider::from_setting();
+    let api_key = match keys_vault_get(provider.vault_service()) {
+        Ok(Some(key)) => key,
+        Ok(None) => {
+            warn!(
+                "No {} key in the vault, skipping translation",
+                provider.vault_service().as_str()
+            );
+            return text.to_string();
+        }
+        Err(e) => {
+            error!("Failed to read translation key: {}", e.message);
+            return text.to_string();
+        }
+    };
+
+    // Mask sensitive spans locally; they are restored in the translation
+    let redacted = redact_if_enabled(text);
+    match translate(provider, &api_key, &redacted.text, &target).await {
+        Ok(translated) => {
+            info!("Translated into {}: {}", target, translated);
+            redacted.restore(&translated)
+        }
+        Err(e) => {
+            error!("Failed to translate: {}", e);
+            text.to_string()
+        }
+    }
+}
+
+async fn translate(
+    provider: Provider,
+    api_key: &str,
+    text: &str,
+    target: &str,
+) -> Result<String, String> {
+    let instructions = format!(
+        "Translate the user's dictated text into {}. Keep the meaning, tone, names and \
+         any placeholder tokens unchanged. If it is already in {}, return it as is. \
+         Reply with the translation only.",
+        language_name(target),
+        language_name(target)
+    );
+    let messages = vec![
+        ChatMessage {
+            role: "system",
+            content: &instructions,
+        },
+        ChatMessage {
+            role: "user",
+            content: text,
+        },
+    ];
+
+    llm::complete(
+        provider,
+        api_key,
+        messages,
+        0.2,
+        Duration::from_secs(REQUEST_TIMEOUT_SECS),
+    )
+    .await
+}
+
+/// `en` for `en-US`
+pub fn base_language(code: &str) -> String {
+    code.split(['-', '_'])
+        .next()
+        .unwrap_or(code)
+        .to_ascii_lowercase()
+}
+
+/// English name of a language code, for the prompt
+fn language_name(code: &str) -> &str {
+    match code {
+        "en-US" => "American English",
+        "en-GB" => "British English",
+        _ => match base_language(code).as_str() {
+            "en" => "English",
+            "es" => "Spanish",
+            "fr" => "French",
+            "de" => "German",
+            "it" => "Italian",
+            "pt" => "Portuguese",
+            "ja" => "Japanese",
+            "ko" => "Korean",
+            "zh" => "Chinese",
+            _ => code,
+        },
+    }
+}
@@ -1,53 +1,209 @@
+use std::sync::Mutex;
 use tauri::{
+    image::Image,
     menu::{CheckMenuItemBuilder, Menu, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     AppHandle, Emitter, Listener, Manager, Runtime,
 };
+use tracing::{debug, error, warn};
 
-fn get_current_languages<R: Runtime>(app: &AppHandle<R>) -> (Vec<String>, bool) {
-    use rusqlite::Connection;
-    use std::path::PathBuf;
-
-    let app_data_dir = app.path().app_data_dir().ok();
-    if let Some(dir) = app_data_dir {
-        let db_path: PathBuf = dir.join("dicto.db");
-        if let Ok(conn) = Connection::open(&db_path) {
-            // Get languages setting
-            let languages = conn
-                .query_row(
-                    "SELECT value FROM settings WHERE key = 'languages'",
-                    [],
-                    |row| row.get::<_, String>(0),
-                )
-                .ok()
-                .and_then(|v| serde_json::from_str::<Vec<String>>(&v).ok())
-                .unwrap_or_else(|| vec!["en-US".to_string()]);
-
-            // Get auto-detect setting
-            let auto_detect = conn
-                .query_row(
-                    "SELECT value FROM settings WHERE key = 'autoDetectLanguage'",
-                    [],
-                    |row| row.get::<_, String>(0),
-                )
-                .ok()
-                .map(|v| v == "true")
-                .unwrap_or(false);
-
-            return (languages, auto_detect);
+use crate::app_context::{self, RunningApp};
+use crate::commands::settings::{
+    keys as setting_keys, read_bool_setting, read_json_setting, read_setting, Setting,
+};
+use crate::commands::transcriptions::{
+    transcriptions_copy_to_clipboard, transcriptions_list, ListTranscriptionsParams,
+    TranscriptionVersion,
+};
+use crate::commands::writing_styles::{writing_styles_get, WritingStyleCategory};
+use crate::events::{self, names as event_names};
+use crate::model_download::{installed_llm_model, installed_stt_model, DownloadProgress};
+use crate::text;
+use crate::transcription::{input_device_names, recording_in_progress};
+use crate::window::sync_dock_icon;
+
+/// Menu id prefix for input device items (followed by the device name)
+const INPUT_DEVICE_ID_PREFIX: &str = "input_device:";
+
+/// Menu id prefix for writing style items (followed by `<category>:<style>`)
+const STYLE_ID_PREFIX: &str = "style:";
+
+/// Menu id prefix for recent transcription items (followed by the transcription id)
+const RECENT_ID_PREFIX: &str = "recent:";
+
+/// How many transcriptions the Recent submenu lists
+const RECENT_TRANSCRIPTIONS: i64 = 5;
+
+/// Longest recent transcription label, in characters
+const RECENT_LABEL_CHARS: usize = 40;
+
+/// App that was frontmost when the tray icon was last hovered or clicked
+static LAST_FOCUSED_APP: Mutex<Option<RunningApp>> = Mutex::new(None);
+
+/// App that a dictation started from the tray should paste into
+static PASTE_TARGET_APP: Mutex<Option<i32>> = Mutex::new(None);
+
+/// Model being downloaded and its last whole percentage
+static DOWNLOAD_PROGRESS: Mutex<Option<(String, u32)>> = Mutex::new(None);
+
+/// Settings shown in the menu, loaded once and then kept current from `settings:updated`
+static TRAY_SETTINGS: Mutex<Option<TraySettings>> = Mutex::new(None);
+
+/// The settings the tray menu shows checkmarks for
+#[derive(Debug, Clone, PartialEq)]
+struct TraySettings {
+    languages: Vec<String>,
+    auto_detect_language: bool,
+    input_device: Option<String>,
+}
+
+impl TraySettings {
+    fn load() -> Self {
+        Self {
+            languages: read_json_setting(setting_keys::LANGUAGES, default_languages()),
+            auto_detect_language: read_bool_setting(setting_keys::AUTO_DETECT_LANGUAGE, false),
+            input_device: read_setting(setting_keys::INPUT_DEVICE).filter(|d| !d.is_empty()),
+        }
+    }
+
+    /// Apply an updated setting; returns whether anything in the menu changed
+    fn apply(&mut self, setting: &Setting) -> bool {
+        let previous = self.clone();
+        match setting.key.as_str() {
+            setting_keys::LANGUAGES => {
+                self.languages =
+                    serde_json::from_str(&setting.value).unwrap_or_else(|_| default_languages());
+            }
+            setting_keys::AUTO_DETECT_LANGUAGE => {
+                self.auto_detect_language = setting.value == "true";
+            }
+            setting_keys::INPUT_DEVICE => {
+                self.input_device = Some(setting.value.clone()).filter(|d| !d.is_empty());
+            }
+            _ => {}
         }
+        *self != previous
     }
+}
 
-    (vec!["en-US".to_string()], false)
+fn default_languages() -> Vec<String> {
+    vec!["en-US".to_string()]
+}
+
+/// Current tray settings, read through the settings layer the first time
+fn tray_settings() -> TraySettings {
+    match TRAY_SETTINGS.lock() {
+        Ok(mut cached) => cached.get_or_insert_with(TraySettings::load).clone(),
+        Err(_) => TraySettings::load(),
+    }
+}
+
+/// Remember the frontmost app before the tray menu takes focus
+pub fn remember_frontmost_app() {
+    if let Some(app) = app_context::frontmost_running_app() {
+        if let Ok(mut last) = LAST_FOCUSED_APP.lock() {
+            *last = Some(app);
+        }
+    }
+}
+
+/// Start or stop dictation from the tray or the File menu, mirroring the global shortcut
+pub fn toggle_dictation<R: Runtime>(app: &AppHandle<R>) {
+    if recording_in_progress() {
+        // Return focus to the app dictation started in before the result is pasted
+        if let Some(pid) = PASTE_TARGET_APP.lock().ok().and_then(|mut t| t.take()) {
+            app_context::activate_app(pid);
+        }
+        debug!("🔔 Stop Dictation clicked from tray");
+        let _ = events::emit_broadcast(app, "stop-listening", ());
+    } else {
+        let target = LAST_FOCUSED_APP
+            .lock()
+            .ok()
+            .and_then(|t| t.as_ref().map(|app| app.pid));
+        if let Some(pid) = target {
+            app_context::activate_app(pid);
+        }
+        if let Ok(mut paste_target) = PASTE_TARGET_APP.lock() {
+            *paste_target = target;
+        }
+        debug!("🔔 Start Dictation clicked from tray");
+        let _ = events::emit_broadcast(app, "start-listening", ());
+    }
+}
+
+/// App icon with a red recording dot in the bottom-right corner
+fn recording_icon(base: &Image<'_>) -> Image<'static> {
+    let (width, height) = (base.width(), base.height());
+    let mut rgba = base.rgba().to_vec();
+
+    let radius = width.min(height) as f32 * 0.22;
+    let center_x = width as f32 - radius - 1.0;
+    let center_y = height as f32 - radius - 1.0;
+
+    for y in 0..height {
+        for x in 0..width {
+            let dx = x as f32 + 0.5 - center_x;
+            let dy = y as f32 + 0.5 - center_y;
+            if dx * dx + dy * dy <= radius * radius {
+                let offset = ((y * width + x) * 4) as usize;
+                rgba[offset..offset + 4].copy_from_slice(&[0xFF, 0x3B, 0x30, 0xFF]);
+            }
+        }
+    }
+
+    Image::new(&rgba, width, height).to_owned()
 }
 
 fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
-    let (current_languages, auto_detect) = get_current_languages(app);
+    let TraySettings {
+        languages: current_languages,
+        auto_detect_language: auto_detect,
+        input_device: current_device,
+    } = tray_settings();
 
     // Create menu items
+    let recording = recording_in_progress();
+    let dictation_label = if recording {
+        "Stop Dictation"
+    } else {
+        "Start Dictation"
+    };
+    let toggle_dictation =
+        MenuItemBuilder::with_id("toggle_dictation", dictation_label).build(app)?;
+    let separator_dictation = PredefinedMenuItem::separator(app)?;
     let open_dashboard = MenuItemBuilder::with_id("open_dashboard", "Open Dashboard").build(app)?;
     let separator1 = PredefinedMenuItem::separator(app)?;
 
+    // Model status - informational, so the items are disabled
+    let stt_label = match installed_stt_model(app) {
+        Some(name) => format!("Speech Model: {}", name),
+        None => "Speech Model: Not installed".to_string(),
+    };
+    let llm_label = match installed_llm_model(app) {
+        Some(name) => format!("Formatting Model: {}", name),
+        None => "Formatting Model: Not installed".to_string(),
+    };
+    let stt_status = MenuItemBuilder::with_id("stt_model_status", stt_label)
+        .enabled(false)
+        .build(app)?;
+    let llm_status = MenuItemBuilder::with_id("llm_model_status", llm_label)
+        .enabled(false)
+        .build(app)?;
+    let download_label = DOWNLOAD_PROGRESS
+        .lock()
+        .ok()
+        .and_then(|progress| progress.clone())
+        .map(|(model, percentage)| format!("Downloading {}… {}%", model, percentage));
+    let download_status = download_label
+        .map(|label| {
+            MenuItemBuilder::with_id("model_download_progress", label)
+                .enabled(false)
+                .build(app)
+        })
+        .transpose()?;
+    let separator_models = PredefinedMenuItem::separator(app)?;
+
     // Quick Actions submenu
     let add_to_dictionary = MenuItemBuilder::with_id("add_dictionary", "Add to Dictionary").build(app)?;
     let quick_actions = SubmenuBuilder::new(app, "Quick Actions")
@@ -105,6 +261,73 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
         .item(&lang_auto)
         .build()?;
 
+    // Input Device submenu - system default plus every cpal input device
+    let device_default = CheckMenuItemBuilder::with_id("input_device_default", "System Default")
+        .checked(current_device.is_none())
+        .build(app)?;
+    let separator_device = PredefinedMenuItem::separator(app)?;
+    let mut input_devices = SubmenuBuilder::new(app, "Input Device")
+        .item(&device_default)
+        .item(&separator_device);
+    for name in input_device_names() {
+        let item =
+            CheckMenuItemBuilder::with_id(format!("{}{}", INPUT_DEVICE_ID_PREFIX, name), &name)
+                .checked(current_device.as_deref() == Some(name.as_str()))
+                .build(app)?;
+        input_devices = input_devices.item(&item);
+    }
+    let input_devices = input_devices.build()?;
+
+    // Style submenu - writing styles for the category of the last focused app
+    let category = LAST_FOCUSED_APP
+        .lock()
+        .ok()
+        .and_then(|t| {
+            t.as_ref()
+                .map(|app| WritingStyleCategory::for_app(&app.name))
+        })
+        .unwrap_or(WritingStyleCategory::General);
+    let current_style = writing_styles_get(category.clone())
+        .map(|style| style.selected_style)
+        .unwrap_or_else(|_| category.default_style().to_string());
+    let mut styles = SubmenuBuilder::new(app, format!("Style ({})", category.as_str()));
+    for (key, label) in category.styles() {
+        let item = CheckMenuItemBuilder::with_id(
+            format!("{}{}:{}", STYLE_ID_PREFIX, category.as_str(), key),
+            *label,
+        )
+        .checked(current_style == *key)
+        .build(app)?;
+        styles = styles.item(&item);
+    }
+    let styles = styles.build()?;
+
+    // Recent submenu - clicking a transcription copies it to the clipboard
+    let recent_transcriptions = transcriptions_list(Some(ListTranscriptionsParams {
+        limit: Some(RECENT_TRANSCRIPTIONS),
+        ..Default::default()
+    }))
+    .map(|page| page.items)
+    .unwrap_or_default();
+    let mut recent = SubmenuBuilder::new(app, "Copy Recent");
+    if recent_transcriptions.is_empty() {
+        let empty = MenuItemBuilder::with_id("recent_empty", "No transcriptions yet")
+            .enabled(false)
+            .build(app)?;
+        recent = recent.item(&empty);
+    }
+    for transcription in &recent_transcriptions {
+        let label = text::preview(
+            transcription.text_for(&TranscriptionVersion::Formatted),
+            RECENT_LABEL_CHARS,
+        );
+        let item =
+            MenuItemBuilder::with_id(format!("{}{}", RECENT_ID_PREFIX, transcription.id), label)
+                .build(app)?;
+        recent = recent.item(&item);
+    }
+    let recent = recent.build()?;
+
     let separator2 = PredefinedMenuItem::separator(app)?;
     let settings = MenuItemBuilder::with_id("settings", "Settings").build(app)?;
     let separator3 = PredefinedMenuItem::separator(app)?;
@@ -114,16 +337,29 @@ fn build_tray_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
     let menu = Menu::with_items(
         app,
         &[
+            &toggle_dictation,
+            &separator_dictation,
             &open_dashboard,
             &separator1,
-            &quick_actions,
-            &languages,
-            &separator2,
-            &settings,
-            &separator3,
-            &quit,
+            &stt_status,
+            &llm_status,
         ],
     )?;
+    if let Some(download_status) = &download_status {
+        menu.append(download_status)?;
+    }
+    menu.append_items(&[
+        &separator_models,
+        &quick_actions,
+        &languages,
+        &input_devices,
+        &styles,
+        &recent,
+        &separator2,
+        &settings,
+        &separator3,
+        &quit,
+    ])?;
 
     Ok(menu)
 }
@@ -135,21 +371,27 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
     let _tray = TrayIconBuilder::with_id("main")
         .icon(app.default_window_icon().unwrap().clone())
         .menu(&menu)
+        // Right-click always opens the menu; left-click is handled below
         .show_menu_on_left_click(false)
         .on_menu_event(move |app, event| {
             match event.id.as_ref() {
+                "toggle_dictation" => {
+                    toggle_dictation(app);
+                }
                 "open_dashboard" => {
-                    println!("Open Dashboard clicked");
+                    debug!("Open Dashboard clicked");
                     if let Some(window) = app.get_webview_window("main") {
-                        println!("Main window found, attempting to show");
+                        debug!("Main window found, attempting to show");
                         if let Err(e) = window.unminimize() {
-                            println!("Failed to unminimize: {}", e);
+                            warn!("Failed to unminimize: {}", e);
                         }
                         if let Err(e) = window.show() {
-                            println!("Failed to show: {}", e);
+                            warn!("Failed to show: {}", e);
                         }
+                        // Bring the Dock icon back while the window is open
+                        sync_dock_icon(app);
                         if let Err(e) = window.set_focus() {
-                            println!("Failed to set focus: {}", e);
+                            warn!("Failed to set focus: {}", e);
                         }
                         #[cfg(target_os = "macos")]
                         {
@@ -162,11 +404,11 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
                             }
                         }
                     } else {
-                        println!("Main window not found!");
+                        warn!("Main window not found!");
                     }
                 }
                 "add_dictionary" => {
-                    println!("Add to Dictionary clicked");
+                    debug!("Add to Dictionary clicked");
                     let _ = app.emit("open-add-keyterm", ());
                 }
                 "lang_en-US" => {
@@ -202,42 +444,155 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
                 "lang_auto" => {
                     let _ = app.emit("toggle-auto-detect-language", ());
                 }
+                "input_device_default" => {
+                    let _ = app.emit("select-input-device", "");
+                }
+                id if id.starts_with(INPUT_DEVICE_ID_PREFIX) => {
+                    let name = &id[INPUT_DEVICE_ID_PREFIX.len()..];
+                    let _ = app.emit("select-input-device", name);
+                }
+                id if id.starts_with(STYLE_ID_PREFIX) => {
+                    if let Some((category, style)) = id[STYLE_ID_PREFIX.len()..].split_once(':') {
+                        let _ = app.emit(
+                            "select-writing-style",
+                            serde_json::json!({ "category": category, "style": style }),
+                        );
+                    }
+                }
+                id if id.starts_with(RECENT_ID_PREFIX) => {
+                    let transcription_id = id[RECENT_ID_PREFIX.len()..].to_string();
+                    if let Err(e) = transcriptions_copy_to_clipboard(
+                        transcription_id,
+                        TranscriptionVersion::Formatted,
+                    ) {
+                        error!("Failed to copy transcription: {}", e.message);
+                    }
+                }
                 "settings" => {
-                    println!("Settings clicked from tray");
+                    debug!("Settings clicked from tray");
                     let _ = app.emit("open-settings", ());
                 }
                 "quit" => {
-                    println!("Quit clicked from tray");
+                    debug!("Quit clicked from tray");
                     app.exit(0);
                 }
                 _ => {
-                    println!("Unhandled menu item: {:?}", event.id);
+                    debug!("Unhandled menu item: {:?}", event.id);
                 }
             }
         })
-        .on_tray_icon_event(|_tray, event| {
-            // Explicitly handle tray icon events to prevent default left-click menu
+        .on_tray_icon_event(|tray, event| {
+            // Hovering the icon refreshes the Style submenu for the app the user is in
+            if let TrayIconEvent::Enter { .. } = event {
+                remember_frontmost_app();
+                if let Err(e) = update_tray_menu(tray.app_handle()) {
+                    error!("Failed to update tray menu: {}", e);
+                }
+            }
+
+            // Remember where focus was before the tray took it, for tray-started dictation
+            if let TrayIconEvent::Click {
+                button_state: MouseButtonState::Down,
+                ..
+            } = event
+            {
+                remember_frontmost_app();
+            }
+
+            // Left-click toggles dictation when enabled, otherwise it does nothing
             if let TrayIconEvent::Click {
                 button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
                 ..
-            } = event {
-                println!("Left click on tray icon - doing nothing");
+            } = event
+            {
+                if read_bool_setting(setting_keys::TRAY_LEFT_CLICK_RECORDS, false) {
+                    toggle_dictation(tray.app_handle());
+                } else {
+                    debug!("Left click on tray icon - doing nothing");
+                }
             }
         })
         .build(app)?;
 
-    // Listen for settings changes to update the tray
+    // Keep the checkmarks current from the updated settings themselves, rebuilding
+    // only when a setting the menu shows has changed
+    let app_handle = app.clone();
+    app.listen(event_names::SETTINGS_UPDATED, move |event| {
+        let Ok(setting) = serde_json::from_str::<Setting>(event.payload()) else {
+            return;
+        };
+        let changed = match TRAY_SETTINGS.lock() {
+            Ok(mut cached) => cached
+                .get_or_insert_with(TraySettings::load)
+                .apply(&setting),
+            Err(_) => false,
+        };
+        if changed {
+            if let Err(e) = update_tray_menu(&app_handle) {
+                error!("Failed to update tray menu: {}", e);
+            }
+        }
+    });
+
+    // The Style submenu shows the selected writing style
     let app_handle = app.clone();
-    app.listen("settings-changed", move |_| {
-        println!("Settings changed, updating tray menu...");
+    app.listen(event_names::WRITING_STYLES_UPDATED, move |_| {
         if let Err(e) = update_tray_menu(&app_handle) {
-            eprintln!("Failed to update tray menu: {}", e);
+            error!("Failed to update tray menu: {}", e);
         }
     });
 
+    // Show model download progress, rebuilding only when the whole percentage moves
+    let app_handle = app.clone();
+    app.listen("model-download-progress", move |event| {
+        let Ok(progress) = serde_json::from_str::<DownloadProgress>(event.payload()) else {
+            return;
+        };
+        let percentage = progress.percentage.clamp(0.0, 100.0) as u32;
+        let changed = match DOWNLOAD_PROGRESS.lock() {
+            Ok(mut current) => {
+                let next = Some((progress.model, percentage));
+                let changed = *current != next;
+                *current = next;
+                changed
+            }
+            Err(_) => false,
+        };
+        if changed {
+            if let Err(e) = update_tray_menu(&app_handle) {
+                error!("Failed to update tray menu: {}", e);
+            }
+        }
+    });
+
+    for event_name in ["model-download-complete", "model-download-error"] {
+        let app_handle = app.clone();
+        app.listen(event_name, move |_| {
+            if let Ok(mut current) = DOWNLOAD_PROGRESS.lock() {
+                *current = None;
+            }
+            if let Err(e) = update_tray_menu(&app_handle) {
+                error!("Failed to update tray menu: {}", e);
+            }
+        });
+    }
+
     Ok(())
 }
 
+/// Show `● 0:42` next to the tray icon while recording, or clear it
+pub fn set_recording_timer<R: Runtime>(app: &AppHandle<R>, elapsed_secs: Option<u64>) {
+    let Some(tray) = app.tray_by_id("main") else {
+        return;
+    };
+
+    let title = elapsed_secs.map(|secs| format!("● {}:{:02}", secs / 60, secs % 60));
+    if let Err(e) = tray.set_title(title) {
+        error!("Failed to set tray title: {}", e);
+    }
+}
+
 pub fn update_tray_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
     // Rebuild the entire menu with updated settings
     let menu = build_tray_menu(app)?;
@@ -245,6 +600,16 @@ pub fn update_tray_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
     // Get the tray icon and set the new menu
     if let Some(tray) = app.tray_by_id("main") {
         tray.set_menu(Some(menu))?;
+
+        // Mark the icon while recording
+        if let Some(icon) = app.default_window_icon() {
+            let icon = if recording_in_progress() {
+                recording_icon(icon)
+            } else {
+                icon.clone()
+            };
+            tray.set_icon(Some(icon))?;
+        }
     }
 
     Ok(())
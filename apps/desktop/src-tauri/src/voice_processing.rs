@@ -0,0 +1,360 @@
+use anyhow::Result;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+/// Sample rate the voice processing unit delivers; it resamples internally
+pub const SAMPLE_RATE: u32 = 16000;
+
+/// Capture the system default microphone through the macOS Voice Processing IO
+/// unit, which cancels echo from the speakers and suppresses background noise.
+/// Audio arrives as 16kHz mono f32. Returns a sender that stops the capture.
+#[cfg(target_os = "macos")]
+pub fn start(
+    is_active: Arc<AtomicBool>,
+    samples: Arc<Mutex<Vec<f32>>>,
+) -> Result<crossbeam_channel::Sender<()>> {
+    let (stop_tx, stop_rx) = crossbeam_channel::bounded::<()>(1);
+    let (started_tx, started_rx) = crossbeam_channel::bounded::<Result<()>>(1);
+
+    // The audio unit lives on its own thread until the stop signal
+    std::thread::spawn(move || {
+        let capture = match macos::VoiceCapture::start(is_active, samples) {
+            Ok(capture) => capture,
+            Err(e) => {
+                let _ = started_tx.send(Err(e));
+                return;
+            }
+        };
+        let _ = started_tx.send(Ok(()));
+
+        let _ = stop_rx.recv();
+        drop(capture);
+        info!("Voice processing capture stopped");
+    });
+
+    started_rx
+        .recv()
+        .map_err(|_| anyhow::anyhow!("Voice processing thread exited"))??;
+
+    info!("Voice processing capture started");
+    Ok(stop_tx)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn start(
+    _is_active: Arc<AtomicBool>,
+    _samples: Arc<Mutex<Vec<f32>>>,
+) -> Result<crossbeam_channel::Sender<()>> {
+    Err(anyhow::anyhow!(
+        "Voice processing is only available on macOS"
+    ))
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use anyhow::Result;
+    use std::ffi::c_void;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use crate::audio_level;
+
+    type OSStatus = i32;
+    type AudioUnit = *mut c_void;
+    type AudioComponent = *mut c_void;
+    type AURenderCallback = extern "C" fn(
+        ref_con: *mut c_void,
+        action_flags: *mut u32,
+        time_stamp: *const c_void,
+        bus_number: u32,
+        number_frames: u32,
+        data: *mut AudioBufferList,
+    ) -> OSStatus;
+
+    const K_AUDIO_UNIT_TYPE_OUTPUT: u32 = u32::from_be_bytes(*b"auou");
+    const K_AUDIO_UNIT_SUB_TYPE_VOICE_PROCESSING_IO: u32 = u32::from_be_bytes(*b"vpio");
+    const K_AUDIO_UNIT_MANUFACTURER_APPLE: u32 = u32::from_be_bytes(*b"appl");
+    const K_AUDIO_FORMAT_LINEAR_PCM: u32 = u32::from_be_bytes(*b"lpcm");
+    const K_AUDIO_FORMAT_FLAG_IS_FLOAT: u32 = 1 << 0;
+    const K_AUDIO_FORMAT_FLAG_IS_PACKED: u32 = 1 << 3;
+
+    const K_AUDIO_UNIT_PROPERTY_STREAM_FORMAT: u32 = 8;
+    const K_AUDIO_OUTPUT_UNIT_PROPERTY_ENABLE_IO: u32 = 2003;
+    const K_AUDIO_OUTPUT_UNIT_PROPERTY_SET_INPUT_CALLBACK: u32 = 2005;
+
+    const K_AUDIO_UNIT_SCOPE_GLOBAL: u32 = 0;
+    const K_AUDIO_UNIT_SCOPE_INPUT: u32 = 1;
+    const K_AUDIO_UNIT_SCOPE_OUTPUT: u32 = 2;
+
+    /// Element 1 carries the microphone, element 0 the speakers
+    const INPUT_BUS: u32 = 1;
+    const OUTPUT_BUS: u32 = 0;
+
+    #[repr(C)]
+    struct AudioComponentDescription {
+        component_type: u32,
+        component_sub_type: u32,
+        component_manufacturer: u32,
+        component_flags: u32,
+        component_flags_mask: u32,
+    }
+
+    #[repr(C)]
+    struct AudioStreamBasicDescription {
+        sample_rate: f64,
+        format_id: u32,
+        format_flags: u32,
+        bytes_per_packet: u32,
+        frames_per_packet: u32,
+        bytes_per_frame: u32,
+        channels_per_frame: u32,
+        bits_per_channel: u32,
+        reserved: u32,
+    }
+
+    #[repr(C)]
+    struct AudioBuffer {
+        number_channels: u32,
+        data_byte_size: u32,
+        data: *mut c_void,
+    }
+
+    #[repr(C)]
+    struct AudioBufferList {
+        number_buffers: u32,
+        buffers: [AudioBuffer; 1],
+    }
+
+    #[repr(C)]
+    struct AURenderCallbackStruct {
+        input_proc: AURenderCallback,
+        input_proc_ref_con: *mut c_void,
+    }
+
+    #[link(name = "AudioToolbox", kind = "framework")]
+    extern "C" {
+        fn AudioComponentFindNext(
+            component: AudioComponent,
+            description: *const AudioComponentDescription,
+        ) -> AudioComponent;
+        fn AudioComponentInstanceNew(component: AudioComponent, unit: *mut AudioUnit) -> OSStatus;
+        fn AudioComponentInstanceDispose(unit: AudioUnit) -> OSStatus;
+        fn AudioUnitSetProperty(
+            unit: AudioUnit,
+            property: u32,
+            scope: u32,
+            element: u32,
+            data: *const c_void,
+            size: u32,
+        ) -> OSStatus;
+        fn AudioUnitInitialize(unit: AudioUnit) -> OSStatus;
+        fn AudioUnitUninitialize(unit: AudioUnit) -> OSStatus;
+        fn AudioOutputUnitStart(unit: AudioUnit) -> OSStatus;
+        fn AudioOutputUnitStop(unit: AudioUnit) -> OSStatus;
+        fn AudioUnitRender(
+            unit: AudioUnit,
+            action_flags: *mut u32,
+            time_stamp: *const c_void,
+            bus_number: u32,
+            number_frames: u32,
+            data: *mut AudioBufferList,
+        ) -> OSStatus;
+    }
+
+    /// State the input callback needs; owned by `VoiceCapture`
+    struct CallbackContext {
+        unit: AudioUnit,
+        is_active: Arc<AtomicBool>,
+        samples: Arc<Mutex<Vec<f32>>>,
+    }
+
+    /// A running voice processing unit; stops and disposes it on drop
+    pub struct VoiceCapture {
+        unit: AudioUnit,
+        context: *mut CallbackContext,
+    }
+
+    fn check(status: OSStatus, what: &str) -> Result<()> {
+        if status == 0 {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Failed to {} (OSStatus {})", what, status))
+        }
+    }
+
+    fn set_property<T>(
+        unit: AudioUnit,
+        property: u32,
+        scope: u32,
+        element: u32,
+        value: &T,
+    ) -> OSStatus {
+        unsafe {
+            AudioUnitSetProperty(
+                unit,
+                property,
+                scope,
+                element,
+                value as *const T as *const c_void,
+                std::mem::size_of::<T>() as u32,
+            )
+        }
+    }
+
+    impl VoiceCapture {
+        pub fn start(is_active: Arc<AtomicBool>, samples: Arc<Mutex<Vec<f32>>>) -> Result<Self> {
+            let description = AudioComponentDescription {
+                component_type: K_AUDIO_UNIT_TYPE_OUTPUT,
+                component_sub_type: K_AUDIO_UNIT_SUB_TYPE_VOICE_PROCESSING_IO,
+                component_manufacturer: K_AUDIO_UNIT_MANUFACTURER_APPLE,
+                component_flags: 0,
+                component_flags_mask: 0,
+            };
+
+            let mut unit: AudioUnit = std::ptr::null_mut();
+            unsafe {
+                let component = AudioComponentFindNext(std::ptr::null_mut(), &description);
+                if component.is_null() {
+                    return Err(anyhow::anyhow!("Voice processing audio unit not available"));
+                }
+                check(
+                    AudioComponentInstanceNew(component, &mut unit),
+                    "create the voice processing unit",
+                )?;
+            }
+
+            let context = Box::into_raw(Box::new(CallbackContext {
+                unit,
+                is_active,
+                samples,
+            }));
+            let capture = Self { unit, context };
+
+            // Record from the microphone only; nothing is played back
+            check(
+                set_property(
+                    unit,
+                    K_AUDIO_OUTPUT_UNIT_PROPERTY_ENABLE_IO,
+                    K_AUDIO_UNIT_SCOPE_INPUT,
+                    INPUT_BUS,
+                    &1u32,
+                ),
+                "enable voice processing input",
+            )?;
+            check(
+                set_property(
+                    unit,
+                    K_AUDIO_OUTPUT_UNIT_PROPERTY_ENABLE_IO,
+                    K_AUDIO_UNIT_SCOPE_OUTPUT,
+                    OUTPUT_BUS,
+                    &0u32,
+                ),
+                "disable voice processing output",
+            )?;
+
+            // Ask for Whisper's format so nothing is downmixed or resampled later
+            let format = AudioStreamBasicDescription {
+                sample_rate: super::SAMPLE_RATE as f64,
+                format_id: K_AUDIO_FORMAT_LINEAR_PCM,
+                format_flags: K_AUDIO_FORMAT_FLAG_IS_FLOAT | K_AUDIO_FORMAT_FLAG_IS_PACKED,
+                bytes_per_packet: 4,
+                frames_per_packet: 1,
+                bytes_per_frame: 4,
+                channels_per_frame: 1,
+                bits_per_channel: 32,
+                reserved: 0,
+            };
+            check(
+                set_property(
+                    unit,
+                    K_AUDIO_UNIT_PROPERTY_STREAM_FORMAT,
+                    K_AUDIO_UNIT_SCOPE_OUTPUT,
+                    INPUT_BUS,
+                    &format,
+                ),
+                "set the voice processing format",
+            )?;
+
+            let callback = AURenderCallbackStruct {
+                input_proc: input_callback,
+                input_proc_ref_con: capture.context as *mut c_void,
+            };
+            check(
+                set_property(
+                    unit,
+                    K_AUDIO_OUTPUT_UNIT_PROPERTY_SET_INPUT_CALLBACK,
+                    K_AUDIO_UNIT_SCOPE_GLOBAL,
+                    INPUT_BUS,
+                    &callback,
+                ),
+                "set the voice processing callback",
+            )?;
+
+            unsafe {
+                check(AudioUnitInitialize(unit), "initialize voice processing")?;
+                check(AudioOutputUnitStart(unit), "start voice processing")?;
+            }
+
+            Ok(capture)
+        }
+    }
+
+    impl Drop for VoiceCapture {
+        fn drop(&mut self) {
+            unsafe {
+                AudioOutputUnitStop(self.unit);
+                AudioUnitUninitialize(self.unit);
+                AudioComponentInstanceDispose(self.unit);
+                // The unit is gone, so the callback can no longer use the context
+                drop(Box::from_raw(self.context));
+            }
+        }
+    }
+
+    /// Pull the processed microphone audio out of the unit and buffer it
+    extern "C" fn input_callback(
+        ref_con: *mut c_void,
+        action_flags: *mut u32,
+        time_stamp: *const c_void,
+        bus_number: u32,
+        number_frames: u32,
+        _data: *mut AudioBufferList,
+    ) -> OSStatus {
+        let context = unsafe { &*(ref_con as *const CallbackContext) };
+
+        let mut frames = vec![0.0f32; number_frames as usize];
+        let mut buffers = AudioBufferList {
+            number_buffers: 1,
+            buffers: [AudioBuffer {
+                number_channels: 1,
+                data_byte_size: number_frames * 4,
+                data: frames.as_mut_ptr() as *mut c_void,
+            }],
+        };
+
+        let status = unsafe {
+            AudioUnitRender(
+                context.unit,
+                action_flags,
+                time_stamp,
+                bus_number,
+                number_frames,
+                &mut buffers,
+            )
+        };
+        if status != 0 || !context.is_active.load(Ordering::Relaxed) {
+            return status;
+        }
+
+        // Average amplitude (0-100 scale) for the visualizers
+        let sum: f64 = frames.iter().map(|s| s.abs() as f64).sum();
+        audio_level::publish((sum / frames.len().max(1) as f64 * 100.0) as f32);
+
+        if let Ok(mut samples) = context.samples.lock() {
+            samples.extend(frames);
+        }
+
+        0
+    }
+}
@@ -1,4 +1,13 @@
-use tauri::{WebviewUrl, WebviewWindow};
+use tauri::{AppHandle, Manager, Runtime, WebviewUrl, WebviewWindow};
+use tracing::error;
+
+use crate::commands::settings::{keys as setting_keys, read_bool_setting};
+
+/// Size of the widget pill while idle or listening
+pub const WIDGET_PILL_SIZE: (f64, f64) = (80.0, 32.0);
+
+/// Size of the widget once it shows the transcript preview above the pill
+pub const WIDGET_PREVIEW_SIZE: (f64, f64) = (320.0, 120.0);
 
 pub fn build_main_window<'a>(
     app: &'a tauri::AppHandle,
@@ -24,3 +33,82 @@ pub fn build_main_window<'a>(
 
     builder
 }
+
+/// Build the floating widget where there is no NSPanel: an undecorated,
+/// transparent window kept above other apps
+#[cfg(not(target_os = "macos"))]
+pub fn build_widget_window(app: &tauri::AppHandle) -> tauri::Result<WebviewWindow> {
+    WebviewWindow::builder(app, "widget", WebviewUrl::App("widget.html".into()))
+        .inner_size(WIDGET_PILL_SIZE.0, WIDGET_PILL_SIZE.1)
+        .decorations(false)
+        .transparent(true)
+        .shadow(false)
+        .resizable(false)
+        .always_on_top(true)
+        .visible_on_all_workspaces(true)
+        .skip_taskbar(true)
+        .focused(false)
+        .build()
+}
+
+/// Hide the Dock icon in menubar-only mode, keeping it while the main window is visible
+/// so the window can take focus and appear in Cmd+Tab
+pub fn sync_dock_icon<R: Runtime>(app: &AppHandle<R>) {
+    let menubar_only = read_bool_setting(setting_keys::MENUBAR_ONLY, false);
+    let main_visible = app
+        .get_webview_window("main")
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false);
+
+    set_dock_icon_visible(app, !menubar_only || main_visible);
+}
+
+#[cfg(target_os = "macos")]
+fn set_dock_icon_visible<R: Runtime>(app: &AppHandle<R>, visible: bool) {
+    let policy = if visible {
+        tauri::ActivationPolicy::Regular
+    } else {
+        tauri::ActivationPolicy::Accessory
+    };
+
+    if let Err(e) = app.set_activation_policy(policy) {
+        error!("Failed to set activation policy: {}", e);
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn set_dock_icon_visible<R: Runtime>(_app: &AppHandle<R>, _visible: bool) {}
+
+/// Grow the widget into the transcript preview or shrink it back to the pill,
+/// keeping its bottom edge and horizontal center where they are
+pub fn set_widget_expanded<R: Runtime>(app: &AppHandle<R>, expanded: bool) {
+    let Some(window) = app.get_webview_window("widget") else {
+        return;
+    };
+    let (width, height) = if expanded {
+        WIDGET_PREVIEW_SIZE
+    } else {
+        WIDGET_PILL_SIZE
+    };
+
+    let (Ok(position), Ok(size), Ok(scale_factor)) = (
+        window.outer_position(),
+        window.outer_size(),
+        window.scale_factor(),
+    ) else {
+        return;
+    };
+    let position = position.to_logical::<f64>(scale_factor);
+    let size = size.to_logical::<f64>(scale_factor);
+    if (size.width - width).abs() < 1.0 && (size.height - height).abs() < 1.0 {
+        return;
+    }
+
+    if let Err(e) = window.set_size(tauri::LogicalSize::new(width, height)) {
+        error!("Failed to resize widget: {}", e);
+        return;
+    }
+    let x = position.x + (size.width - width) / 2.0;
+    let y = position.y + size.height - height;
+    let _ = window.set_position(tauri::LogicalPosition::new(x, y));
+}
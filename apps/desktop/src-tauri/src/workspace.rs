@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tracing::{error, info};
+
+use crate::commands::error::CommandError;
+use crate::jobs;
+
+/// Folder under app data holding one workspace per recording
+const WORKSPACES_DIR: &str = "workspaces";
+
+const METADATA_FILE: &str = "recording.json";
+const AUDIO_FILE: &str = "audio.f32";
+const CHUNKS_DIR: &str = "chunks";
+
+/// Kept workspaces are deleted automatically once they are this old
+const MAX_WORKSPACE_AGE_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// How often old workspaces are looked for
+const CLEANUP_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+/// Wait after launch before the first cleanup, leaving time to recover a crash
+const CLEANUP_STARTUP_DELAY_SECS: u64 = 10 * 60;
+
+/// ID of the workspace of the recording in progress, which cleanup leaves alone
+static ACTIVE_WORKSPACE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Recovery metadata written to `recording.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordingMetadata {
+    id: String,
+    started_at: i64,
+    /// Format of `audio.f32`: raw little-endian f32 samples at this rate and channel count
+    sample_rate: u32,
+    channels: u16,
+    /// `recording` while in progress (or after a crash), `failed` when kept after an error
+    status: String,
+    error: Option<String>,
+}
+
+/// Result of `cleanup_workspaces`
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WorkspaceCleanupReport {
+    /// Workspaces deleted
+    pub removed: usize,
+    /// Bytes freed on disk
+    pub reclaimed_bytes: u64,
+}
+
+/// Working directory of one recording: the audio spill, transcribed chunks and
+/// recovery metadata. Deleted on drop unless the recording failed.
+pub struct RecordingWorkspace {
+    dir: PathBuf,
+    metadata: Mutex<RecordingMetadata>,
+    retained: AtomicBool,
+}
+
+impl RecordingWorkspace {
+    /// Create the workspace of a new recording under app data
+    pub fn create(app: &AppHandle, sample_rate: u32, channels: u16) -> Result<Self, String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let dir = workspaces_dir(app)?.join(&id);
+        std::fs::create_dir_all(dir.join(CHUNKS_DIR))
+            .map_err(|e| format!("Failed to create recording workspace: {}", e))?;
+
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let workspace = Self {
+            dir,
+            metadata: Mutex::new(RecordingMetadata {
+                id: id.clone(),
+                started_at,
+                sample_rate,
+                channels,
+                status: "recording".to_string(),
+                error: None,
+            }),
+            retained: AtomicBool::new(false),
+        };
+        workspace.write_metadata()?;
+
+        if let Ok(mut active) = ACTIVE_WORKSPACE.lock() {
+            *active = Some(id);
+        }
+
+        Ok(workspace)
+    }
+
+    /// Raw audio spilled from memory during long recordings
+    pub fn audio_path(&self) -> PathBuf {
+        self.dir.join(AUDIO_FILE)
+    }
+
+    /// Keep a transcribed chunk so its text survives a failed recording
+    pub fn save_chunk(&self, chunk_id: usize, text: &str) {
+        let path = self
+            .dir
+            .join(CHUNKS_DIR)
+            .join(format!("{:05}.txt", chunk_id));
+        if let Err(e) = std::fs::write(path, text) {
+            error!("Failed to cache chunk {}: {}", chunk_id, e);
+        }
+    }
+
+    /// Keep the workspace on disk after the recording failed, for recovery
+    pub fn retain(&self, error: &str) {
+        self.retained.store(true, Ordering::SeqCst);
+
+        if let Ok(mut metadata) = self.metadata.lock() {
+            metadata.status = "failed".to_string();
+            metadata.error = Some(error.to_string());
+        }
+        if let Err(e) = self.write_metadata() {
+            error!("{}", e);
+        }
+        info!("Kept recording workspace {}", self.dir.display());
+    }
+
+    fn write_metadata(&self) -> Result<(), String> {
+        let metadata = self
+            .metadata
+            .lock()
+            .map_err(|_| "Recording metadata lock poisoned".to_string())?;
+        let json = serde_json::to_string_pretty(&*metadata)
+            .map_err(|e| format!("Failed to serialize recording metadata: {}", e))?;
+        std::fs::write(self.dir.join(METADATA_FILE), json)
+            .map_err(|e| format!("Failed to write recording metadata: {}", e))
+    }
+}
+
+impl Drop for RecordingWorkspace {
+    fn drop(&mut self) {
+        if let (Ok(mut active), Ok(metadata)) = (ACTIVE_WORKSPACE.lock(), self.metadata.lock()) {
+            if active.as_deref() == Some(metadata.id.as_str()) {
+                *active = None;
+            }
+        }
+
+        if !self.retained.load(Ordering::SeqCst) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+fn workspaces_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(WORKSPACES_DIR))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// Bytes on disk for a directory and everything in it
+fn dir_size(path: &Path) -> u64 {
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| match entry.metadata() {
+                    Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+                    Ok(metadata) => metadata.len(),
+                    Err(_) => 0,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Delete the workspaces kept from failed or interrupted recordings
+#[tauri::command]
+#[specta::specta]
+pub fn cleanup_workspaces(app: AppHandle) -> Result<WorkspaceCleanupReport, CommandError> {
+    Ok(remove_workspaces(&app, None)?)
+}
+
+/// Register the `workspace_cleanup` job, which deletes kept workspaces once
+/// they are too old to be worth recovering
+pub fn register_cleanup_job(app: AppHandle) {
+    jobs::register(
+        "workspace_cleanup",
+        Duration::from_secs(CLEANUP_INTERVAL_SECS),
+        Duration::from_secs(CLEANUP_STARTUP_DELAY_SECS),
+        move || {
+            let app = app.clone();
+            async move {
+                remove_workspaces(&app, Some(Duration::from_secs(MAX_WORKSPACE_AGE_SECS)))
+                    .map(|_| ())
+            }
+        },
+    );
+}
+
+/// Delete kept workspaces other than the active one, only those last modified
+/// longer than `max_age` ago when set
+fn remove_workspaces(
+    app: &AppHandle,
+    max_age: Option<Duration>,
+) -> Result<WorkspaceCleanupReport, String> {
+    let dir = workspaces_dir(app)?;
+    let active = ACTIVE_WORKSPACE
+        .lock()
+        .ok()
+        .and_then(|active| active.clone());
+
+    let mut report = WorkspaceCleanupReport {
+        removed: 0,
+        reclaimed_bytes: 0,
+    };
+
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        // Nothing was ever kept
+        Err(_) => return Ok(report),
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() || active.as_deref() == path.file_name().and_then(|n| n.to_str()) {
+            continue;
+        }
+        if let Some(max_age) = max_age {
+            let age = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok());
+            if age.map_or(true, |age| age < max_age) {
+                continue;
+            }
+        }
+
+        let size = dir_size(&path);
+        match std::fs::remove_dir_all(&path) {
+            Ok(()) => {
+                report.removed += 1;
+                report.reclaimed_bytes += size;
+            }
+            Err(e) => error!("Failed to remove workspace {}: {}", path.display(), e),
+        }
+    }
+
+    info!(
+        "Removed {} recording workspaces ({} bytes)",
+        report.removed, report.reclaimed_bytes
+    );
+
+    Ok(report)
+}
@@ -5,7 +5,7 @@
 export const commands = {
   async startRecording(
     settings: TranscriptionSettings,
-  ): Promise<Result<null, string>> {
+  ): Promise<Result<null, CommandError>> {
     try {
       return {
         status: "ok",
@@ -46,7 +46,7 @@ export const commands = {
       else return { status: "error", error: e as any };
     }
   },
-  async getFrontmostApp(): Promise<Result<AppInfo, string>> {
+  async getFrontmostApp(): Promise<Result<AppContext, string>> {
     try {
       return { status: "ok", data: await TAURI_INVOKE("get_frontmost_app") };
     } catch (e) {
@@ -55,45 +55,53 @@ export const commands = {
     }
   },
   /**
-   * Check if an STT model is downloaded and get its status
+   * Stream the microphone level (0-100) to `on_level` at up to `fps` frames per
+   * second while recording. Returns an ID for `audio_level_unsubscribe`.
+   */
+  async audioLevelSubscribe(
+    onLevel: TAURI_CHANNEL<number>,
+    fps: number | null,
+  ): Promise<number> {
+    return await TAURI_INVOKE("audio_level_subscribe", { onLevel, fps });
+  },
+  /**
+   * Stop streaming the microphone level to a visualizer
+   */
+  async audioLevelUnsubscribe(id: number): Promise<null> {
+    return await TAURI_INVOKE("audio_level_unsubscribe", { id });
+  },
+  /**
+   * Every model that can be downloaded
    */
-  async checkSttModelStatus(
-    model: SttModel,
-  ): Promise<Result<SttModelStatus, string>> {
+  async modelsCatalog(): Promise<Result<ModelEntry[], CommandError>> {
     try {
-      return {
-        status: "ok",
-        data: await TAURI_INVOKE("check_stt_model_status", { model }),
-      };
+      return { status: "ok", data: await TAURI_INVOKE("models_catalog") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Download an STT model in the background
+   * Models of the catalog that are downloaded
    */
-  async downloadSttModel(model: SttModel): Promise<Result<null, string>> {
+  async modelsInstalled(): Promise<Result<InstalledModel[], CommandError>> {
     try {
-      return {
-        status: "ok",
-        data: await TAURI_INVOKE("download_stt_model", { model }),
-      };
+      return { status: "ok", data: await TAURI_INVOKE("models_installed") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Check if an LLM model is downloaded and get its status
+   * Check if a model of the catalog is downloaded and get its status
    */
-  async checkLlmModelStatus(
-    model: LlmModel,
-  ): Promise<Result<LlmModelStatus, string>> {
+  async checkModelStatus(
+    id: string,
+  ): Promise<Result<ModelStatus, CommandError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("check_llm_model_status", { model }),
+        data: await TAURI_INVOKE("check_model_status", { id }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -101,19 +109,35 @@ export const commands = {
     }
   },
   /**
-   * Download an LLM model in the background
+   * Download any model of the catalog in the background, by its manifest id
    */
-  async downloadLlmModel(model: LlmModel): Promise<Result<null, string>> {
+  async downloadModel(id: string): Promise<Result<null, CommandError>> {
     try {
-      return {
-        status: "ok",
-        data: await TAURI_INVOKE("download_llm_model", { model }),
-      };
+      return { status: "ok", data: await TAURI_INVOKE("download_model", { id }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Run the reference clip through every installed model and recommend the one
+   * that suits this machine best
+   */
+  async benchmarkModels(): Promise<Result<BenchmarkReport, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("benchmark_models") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
+  /**
+   * Processor, GPU and memory of this machine with the model size it suits,
+   * for pre-selecting defaults and warning before large downloads
+   */
+  async hardwareCapabilities(): Promise<HardwareCapabilities> {
+    return await TAURI_INVOKE("hardware_capabilities");
+  },
   /**
    * Get the current stored shortcut as a string
    */
@@ -150,6 +174,100 @@ export const commands = {
       else return { status: "error", error: e as any };
     }
   },
+  /**
+   * Get the stored re-paste shortcut ("" when none is set)
+   */
+  async getRepasteShortcut(): Promise<Result<string, string>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("get_repaste_shortcut") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Change the shortcut that pastes the last transcription again ("" turns it off)
+   */
+  async changeRepasteShortcut(key: string): Promise<Result<null, string>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("change_repaste_shortcut", { key }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get the stored rewrite shortcut ("" when none is set)
+   */
+  async getRewriteShortcut(): Promise<Result<string, string>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("get_rewrite_shortcut") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Change the hold-to-talk shortcut that rewrites the selected text ("" turns it off)
+   */
+  async changeRewriteShortcut(key: string): Promise<Result<null, string>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("change_rewrite_shortcut", { key }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get the stored note capture shortcut ("" when none is set)
+   */
+  async getNoteShortcut(): Promise<Result<string, string>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("get_note_shortcut") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Change the hold-to-talk shortcut that dictates a new note ("" turns it off)
+   */
+  async changeNoteShortcut(key: string): Promise<Result<null, string>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("change_note_shortcut", { key }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Whether the app may read global key events for the shortcut
+   */
+  async shortcutPermissionStatus(): Promise<boolean> {
+    return await TAURI_INVOKE("shortcut_permission_status");
+  },
+  /**
+   * Ask for the permission the shortcut listener needs, starting the listener
+   * if it had given up waiting for it
+   */
+  async shortcutRequestPermission(): Promise<boolean> {
+    return await TAURI_INVOKE("shortcut_request_permission");
+  },
+  /**
+   * Whether the app may record from the microphone
+   */
+  async microphonePermissionStatus(): Promise<MicrophonePermission> {
+    return await TAURI_INVOKE("microphone_permission_status");
+  },
   /**
    * List all keyterms, optionally filtered by category
    */
@@ -224,6 +342,23 @@ export const commands = {
       else return { status: "error", error: e as any };
     }
   },
+  /**
+   * Suggest keyterms from correction history: words the user repeatedly fixes
+   * transcripts to that aren't keyterms yet, most corrected first
+   */
+  async keytermsSuggestions(): Promise<
+    Result<KeytermSuggestion[], CommandError>
+  > {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("keyterms_suggestions"),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
   /**
    * List all settings
    */
@@ -281,6 +416,22 @@ export const commands = {
       else return { status: "error", error: e as any };
     }
   },
+  /**
+   * Add the default writing styles, example shortcuts and starter keyterms on
+   * first run, all in one transaction. Styles are only added for categories
+   * without one, and examples only when the user has no shortcuts or keyterms
+   * yet. Does nothing once the defaults have been seeded, so deleting an
+   * example doesn't bring it back; databases in use before seeding existed are
+   * marked seeded by a migration.
+   */
+  async seedDefaults(): Promise<Result<SeedReport, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("seed_defaults") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
   /**
    * List all keys (masked - doesn't expose full API keys)
    */
@@ -338,6 +489,58 @@ export const commands = {
       else return { status: "error", error: e as any };
     }
   },
+  /**
+   * List recorded entity changes, newest first
+   */
+  async auditLogList(
+    params: AuditLogParams | null,
+  ): Promise<Result<AuditLogEntry[], CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("audit_log_list", { params }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get the signed-in user's auth token from the Keychain
+   */
+  async authGetToken(): Promise<Result<string | null, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("auth_get_token") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Save the auth token in the Keychain
+   */
+  async authSetToken(token: string): Promise<Result<null, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("auth_set_token", { token }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Remove the auth token from the Keychain (sign out)
+   */
+  async authClearToken(): Promise<Result<null, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("auth_clear_token") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
   /**
    * List transcriptions with pagination
    */
@@ -354,6 +557,22 @@ export const commands = {
       else return { status: "error", error: e as any };
     }
   },
+  /**
+   * List transcriptions in a date range grouped by local day, newest day first
+   */
+  async transcriptionsListGrouped(
+    range: DateRange | null,
+  ): Promise<Result<TranscriptionDay[], CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("transcriptions_list_grouped", { range }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
   /**
    * Get a single transcription by ID
    */
@@ -404,13 +623,16 @@ export const commands = {
     }
   },
   /**
-   * Delete a transcription
+   * Pin or unpin a transcription so it stays on top of the pinned-first list
    */
-  async transcriptionsDelete(id: string): Promise<Result<null, CommandError>> {
+  async transcriptionsSetPinned(
+    id: string,
+    pinned: boolean,
+  ): Promise<Result<Transcription, CommandError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("transcriptions_delete", { id }),
+        data: await TAURI_INVOKE("transcriptions_set_pinned", { id, pinned }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -418,15 +640,19 @@ export const commands = {
     }
   },
   /**
-   * Get analytics for transcriptions
+   * Copy the raw or formatted text of a transcription to the clipboard
    */
-  async transcriptionsAnalytics(): Promise<
-    Result<TranscriptionAnalytics, CommandError>
-  > {
+  async transcriptionsCopyToClipboard(
+    id: string,
+    which: TranscriptionVersion,
+  ): Promise<Result<null, CommandError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("transcriptions_analytics"),
+        data: await TAURI_INVOKE("transcriptions_copy_to_clipboard", {
+          id,
+          which,
+        }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -434,37 +660,32 @@ export const commands = {
     }
   },
   /**
-   * List all notes
+   * Paste the most recent transcription into the focused app again, for when
+   * the original paste was missed
    */
-  async notesList(): Promise<Result<Note[], CommandError>> {
+  async pasteLastTranscription(): Promise<Result<null, CommandError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("notes_list") };
-    } catch (e) {
-      if (e instanceof Error) throw e;
-      else return { status: "error", error: e as any };
-    }
-  },
-  /**
-   * Get a single note by ID
-   */
-  async notesGet(id: string): Promise<Result<Note, CommandError>> {
-    try {
-      return { status: "ok", data: await TAURI_INVOKE("notes_get", { id }) };
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("paste_last_transcription"),
+      };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Create a new note
+   * Swap the draft pasted by a hybrid-mode dictation for its refined text. The
+   * draft is selected back from the caret, so this only works until the caret moves.
    */
-  async notesCreate(
-    input: CreateNoteInput,
-  ): Promise<Result<Note, CommandError>> {
+  async transcriptionsReplacePasted(
+    id: string,
+    draft: string,
+  ): Promise<Result<null, CommandError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("notes_create", { input }),
+        data: await TAURI_INVOKE("transcriptions_replace_pasted", { id, draft }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -472,16 +693,13 @@ export const commands = {
     }
   },
   /**
-   * Update an existing note
+   * Delete a transcription
    */
-  async notesUpdate(
-    id: string,
-    input: UpdateNoteInput,
-  ): Promise<Result<Note, CommandError>> {
+  async transcriptionsDelete(id: string): Promise<Result<null, CommandError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("notes_update", { id, input }),
+        data: await TAURI_INVOKE("transcriptions_delete", { id }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -489,26 +707,38 @@ export const commands = {
     }
   },
   /**
-   * Delete a note
+   * Get analytics for transcriptions
    */
-  async notesDelete(id: string): Promise<Result<null, CommandError>> {
+  async transcriptionsAnalytics(): Promise<
+    Result<TranscriptionAnalytics, CommandError>
+  > {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("notes_delete", { id }) };
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("transcriptions_analytics"),
+      };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * List all shortcuts, optionally filtered by category
+   * Rate a transcription and optionally store the corrected text.
+   * Phrases that differ from the raw transcript are counted towards suggestions.
    */
-  async shortcutsList(
-    category: ShortcutCategory | null,
-  ): Promise<Result<Shortcut[], CommandError>> {
+  async transcriptionsRate(
+    id: string,
+    rating: TranscriptionRating,
+    correctedText: string | null,
+  ): Promise<Result<TranscriptionFeedback, CommandError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("shortcuts_list", { category }),
+        data: await TAURI_INVOKE("transcriptions_rate", {
+          id,
+          rating,
+          correctedText,
+        }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -516,13 +746,16 @@ export const commands = {
     }
   },
   /**
-   * Get a single shortcut by ID
+   * Export rated transcriptions as JSON lines (one audio/correction pair per line)
+   * for evaluating or fine-tuning models. Returns the number of records.
    */
-  async shortcutsGet(id: string): Promise<Result<Shortcut, CommandError>> {
+  async transcriptionsFeedbackExport(
+    path: string,
+  ): Promise<Result<number, CommandError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("shortcuts_get", { id }),
+        data: await TAURI_INVOKE("transcriptions_feedback_export", { path }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -530,15 +763,15 @@ export const commands = {
     }
   },
   /**
-   * Create a new shortcut
+   * Corrections the user has made repeatedly, most frequent first
    */
-  async shortcutsCreate(
-    input: CreateShortcutInput,
-  ): Promise<Result<Shortcut, CommandError>> {
+  async transcriptionsCorrectionSuggestions(): Promise<
+    Result<CorrectionSuggestion[], CommandError>
+  > {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("shortcuts_create", { input }),
+        data: await TAURI_INVOKE("transcriptions_correction_suggestions"),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -546,16 +779,16 @@ export const commands = {
     }
   },
   /**
-   * Update an existing shortcut
+   * Diff the raw transcript against the formatted text word by word, so the
+   * changes made by the formatter can be reviewed. Punctuation counts as part of a word.
    */
-  async shortcutsUpdate(
+  async transcriptionsDiff(
     id: string,
-    input: UpdateShortcutInput,
-  ): Promise<Result<Shortcut, CommandError>> {
+  ): Promise<Result<TranscriptionDiff, CommandError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("shortcuts_update", { id, input }),
+        data: await TAURI_INVOKE("transcriptions_diff", { id }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -563,13 +796,15 @@ export const commands = {
     }
   },
   /**
-   * Delete a shortcut
+   * List notes, pinned ones first
    */
-  async shortcutsDelete(id: string): Promise<Result<null, CommandError>> {
+  async notesList(
+    params: ListNotesParams | null,
+  ): Promise<Result<Note[], CommandError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("shortcuts_delete", { id }),
+        data: await TAURI_INVOKE("notes_list", { params }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -577,26 +812,524 @@ export const commands = {
     }
   },
   /**
-   * List all writing styles
+   * Get a single note by ID
    */
-  async writingStylesList(): Promise<Result<WritingStyle[], CommandError>> {
+  async notesGet(id: string): Promise<Result<Note, CommandError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("writing_styles_list") };
+      return { status: "ok", data: await TAURI_INVOKE("notes_get", { id }) };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Get a single writing style by category
+   * Create a new note
    */
-  async writingStylesGet(
-    category: WritingStyleCategory,
-  ): Promise<Result<WritingStyle, CommandError>> {
+  async notesCreate(
+    input: CreateNoteInput,
+  ): Promise<Result<Note, CommandError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("writing_styles_get", { category }),
+        data: await TAURI_INVOKE("notes_create", { input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Update an existing note
+   */
+  async notesUpdate(
+    id: string,
+    input: UpdateNoteInput,
+  ): Promise<Result<Note, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("notes_update", { id, input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Delete a note
+   */
+  async notesDelete(id: string): Promise<Result<null, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("notes_delete", { id }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Pin or unpin a note so it stays on top of the list
+   */
+  async notesSetPinned(
+    id: string,
+    pinned: boolean,
+  ): Promise<Result<Note, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("notes_set_pinned", { id, pinned }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Archive a note to hide it from the main list, or bring it back
+   */
+  async notesSetArchived(
+    id: string,
+    archived: boolean,
+  ): Promise<Result<Note, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("notes_set_archived", { id, archived }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Attach a file to a note. The file is copied into app data, so the original
+   * can be moved or deleted.
+   */
+  async notesAddAttachment(
+    id: string,
+    sourcePath: string,
+  ): Promise<Result<NoteAttachment, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("notes_add_attachment", { id, sourcePath }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Remove a file from its note and delete the copy in app data
+   */
+  async notesRemoveAttachment(
+    attachmentId: string,
+  ): Promise<Result<null, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("notes_remove_attachment", { attachmentId }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * List the previous versions of a note, newest first
+   */
+  async notesRevisionsList(
+    id: string,
+  ): Promise<Result<NoteRevision[], CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("notes_revisions_list", { id }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Restore a previous version of a note. The current version is kept as a new
+   * revision, so a restore can be undone.
+   */
+  async notesRestoreRevision(
+    id: string,
+    revision: number,
+  ): Promise<Result<Note, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("notes_restore_revision", { id, revision }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Start a chat session and return its ID
+   */
+  async chatStart(): Promise<string> {
+    return await TAURI_INVOKE("chat_start");
+  },
+  /**
+   * Messages of a chat session, oldest first
+   */
+  async chatHistory(sessionId: string): Promise<Result<ChatTurn[], string>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("chat_history", { sessionId }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Send a message to a chat session. The reply streams back as `chat-delta`
+   * events followed by `chat-complete`, or `chat-error` if it fails.
+   */
+  async chatSend(
+    sessionId: string,
+    text: string,
+  ): Promise<Result<null, string>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("chat_send", { sessionId, text }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Paste the last reply of a chat session into the frontmost app
+   */
+  async chatInsertAnswer(sessionId: string): Promise<Result<null, string>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("chat_insert_answer", { sessionId }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * End a chat session and forget its history
+   */
+  async chatEnd(sessionId: string): Promise<null> {
+    return await TAURI_INVOKE("chat_end", { sessionId });
+  },
+  /**
+   * List all shortcuts, optionally filtered by category
+   */
+  async shortcutsList(
+    category: ShortcutCategory | null,
+  ): Promise<Result<Shortcut[], CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("shortcuts_list", { category }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get a single shortcut by ID
+   */
+  async shortcutsGet(id: string): Promise<Result<Shortcut, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("shortcuts_get", { id }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Create a new shortcut
+   */
+  async shortcutsCreate(
+    input: CreateShortcutInput,
+  ): Promise<Result<Shortcut, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("shortcuts_create", { input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Update an existing shortcut
+   */
+  async shortcutsUpdate(
+    id: string,
+    input: UpdateShortcutInput,
+  ): Promise<Result<Shortcut, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("shortcuts_update", { id, input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Delete a shortcut
+   */
+  async shortcutsDelete(id: string): Promise<Result<null, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("shortcuts_delete", { id }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * List all macros
+   */
+  async macrosList(): Promise<Result<Macro[], CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("macros_list") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get a single macro by ID
+   */
+  async macrosGet(id: string): Promise<Result<Macro, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("macros_get", { id }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Create a new macro
+   */
+  async macrosCreate(
+    input: CreateMacroInput,
+  ): Promise<Result<Macro, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("macros_create", { input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Update an existing macro
+   */
+  async macrosUpdate(
+    id: string,
+    input: UpdateMacroInput,
+  ): Promise<Result<Macro, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("macros_update", { id, input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Delete a macro
+   */
+  async macrosDelete(id: string): Promise<Result<null, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("macros_delete", { id }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Whether transcription is in low power mode, and the battery level
+   */
+  async powerGetStatus(): Promise<PowerStatus> {
+    return await TAURI_INVOKE("power_get_status");
+  },
+  /**
+   * Memory, database and recent error state for bug reports
+   */
+  async diagnosticsReport(): Promise<Result<DiagnosticsReport, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("diagnostics_report") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Chunk timings of the current or most recent local recording
+   */
+  async performanceStats(): Promise<PerformanceStats> {
+    return await TAURI_INVOKE("performance_stats");
+  },
+  /**
+   * Delete the workspaces kept from failed or interrupted recordings
+   */
+  async cleanupWorkspaces(): Promise<
+    Result<WorkspaceCleanupReport, CommandError>
+  > {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("cleanup_workspaces") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * List the background jobs with the result of their last run
+   */
+  async jobsStatus(): Promise<JobStatus[]> {
+    return await TAURI_INVOKE("jobs_status");
+  },
+  /**
+   * Check the database for corruption and compact it
+   */
+  async dbMaintenance(): Promise<Result<DbMaintenanceReport, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("db_maintenance") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Connection pool state and SQLITE_BUSY counts since launch
+   */
+  async dbDiagnostics(): Promise<DbDiagnostics> {
+    return await TAURI_INVOKE("db_diagnostics");
+  },
+  /**
+   * Whether iCloud Drive is available to mirror into
+   */
+  async mirrorIcloudAvailable(): Promise<boolean> {
+    return await TAURI_INVOKE("mirror_icloud_available");
+  },
+  /**
+   * Write every existing transcription and note to the iCloud Drive folder
+   */
+  async mirrorExportAll(): Promise<Result<number, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("mirror_export_all") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Turn on sync with the given backend. Returns the encryption key, which must be
+   * entered on every other device that syncs with this backend.
+   */
+  async syncConfigure(
+    input: SyncConfigInput,
+  ): Promise<Result<string, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("sync_configure", { input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Turn off sync. The encryption key is kept so sync can be turned back on.
+   */
+  async syncDisable(): Promise<Result<null, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("sync_disable") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Sync with the backend right away
+   */
+  async syncNow(): Promise<Result<SyncSummary, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("sync_now") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Whether sync is configured, running, or has changes waiting
+   */
+  async syncStatus(): Promise<Result<SyncStatus, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("sync_status") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * The encryption key, to set up sync on another device
+   */
+  async syncGetKey(): Promise<Result<string | null, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("sync_get_key") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * List all writing styles
+   */
+  async writingStylesList(): Promise<Result<WritingStyle[], CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("writing_styles_list") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get a single writing style by category
+   */
+  async writingStylesGet(
+    category: WritingStyleCategory,
+  ): Promise<Result<WritingStyle, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("writing_styles_get", { category }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -606,14 +1339,152 @@ export const commands = {
   /**
    * Update a writing style (upsert)
    */
-  async writingStylesUpdate(
-    category: WritingStyleCategory,
-    input: UpdateWritingStyleInput,
-  ): Promise<Result<WritingStyle, CommandError>> {
+  async writingStylesUpdate(
+    category: WritingStyleCategory,
+    input: UpdateWritingStyleInput,
+  ): Promise<Result<WritingStyle, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("writing_styles_update", { category, input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * List all URL rules, most specific pattern first
+   */
+  async urlRulesList(): Promise<Result<UrlRule[], CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("url_rules_list") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get a single URL rule by ID
+   */
+  async urlRulesGet(id: string): Promise<Result<UrlRule, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("url_rules_get", { id }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Create a new URL rule
+   */
+  async urlRulesCreate(
+    input: CreateUrlRuleInput,
+  ): Promise<Result<UrlRule, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("url_rules_create", { input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Update an existing URL rule
+   */
+  async urlRulesUpdate(
+    id: string,
+    input: UpdateUrlRuleInput,
+  ): Promise<Result<UrlRule, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("url_rules_update", { id, input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Delete a URL rule
+   */
+  async urlRulesDelete(id: string): Promise<Result<null, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("url_rules_delete", { id }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * List all app rules, by app name
+   */
+  async appRulesList(): Promise<Result<AppRule[], CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("app_rules_list") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get a single app rule by ID
+   */
+  async appRulesGet(id: string): Promise<Result<AppRule, CommandError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("app_rules_get", { id }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Create a new app rule
+   */
+  async appRulesCreate(
+    input: CreateAppRuleInput,
+  ): Promise<Result<AppRule, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("app_rules_create", { input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Update an existing app rule
+   */
+  async appRulesUpdate(
+    id: string,
+    input: UpdateAppRuleInput,
+  ): Promise<Result<AppRule, CommandError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("writing_styles_update", { category, input }),
+        data: await TAURI_INVOKE("app_rules_update", { id, input }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Delete a app rule
+   */
+  async appRulesDelete(id: string): Promise<Result<null, CommandError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("app_rules_delete", { id }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -624,29 +1495,435 @@ export const commands = {
 
 /** user-defined events **/
 
+export const events = __makeEvents__<{
+  chatDelta: ChatDelta;
+  chatFailed: ChatFailed;
+  chatReply: ChatReply;
+  downloadComplete: DownloadComplete;
+  downloadError: DownloadError;
+  downloadProgress: DownloadProgress;
+  firstWordsDetected: FirstWordsDetected;
+  formattingCompletedLate: FormattingCompletedLate;
+  lowPowerChanged: LowPowerChanged;
+  microphonePermissionDenied: MicrophonePermissionDenied;
+  modelLoadFailed: ModelLoadFailed;
+  pasteCountdown: PasteCountdown;
+  pasteTargetGone: PasteTargetGone;
+  pipelineLog: PipelineLog;
+  recordingCancelled: RecordingCancelled;
+  recordingElapsed: RecordingElapsed;
+  recordingRetry: RecordingRetry;
+  sessionEnded: SessionEnded;
+  sessionFinalizing: SessionFinalizing;
+  sessionStarted: SessionStarted;
+  shortcutListenerFailed: ShortcutListenerFailed;
+  transcriptionConfidence: TranscriptionConfidence;
+  transcriptionError: TranscriptionError;
+  transcriptionPartial: TranscriptionPartial;
+  transcriptionProcessing: TranscriptionProcessing;
+  transcriptionProgress: TranscriptionProgress;
+  transcriptionRefined: TranscriptionRefined;
+  transcriptionResult: TranscriptionResult;
+}>({
+  chatDelta: "chat-delta",
+  chatFailed: "chat-error",
+  chatReply: "chat-complete",
+  downloadComplete: "model-download-complete",
+  downloadError: "model-download-error",
+  downloadProgress: "model-download-progress",
+  firstWordsDetected: "first-words-detected",
+  formattingCompletedLate: "formatting-completed-late",
+  lowPowerChanged: "low-power-changed",
+  microphonePermissionDenied: "microphone-permission-denied",
+  modelLoadFailed: "model-load-failed",
+  pasteCountdown: "paste-countdown",
+  pasteTargetGone: "paste-target-gone",
+  pipelineLog: "pipeline-log",
+  recordingCancelled: "recording-cancelled",
+  recordingElapsed: "recording-elapsed",
+  recordingRetry: "recording-retry",
+  sessionEnded: "session-ended",
+  sessionFinalizing: "session-finalizing",
+  sessionStarted: "session-started",
+  shortcutListenerFailed: "shortcut-listener-failed",
+  transcriptionConfidence: "transcription-confidence",
+  transcriptionError: "transcription-error",
+  transcriptionPartial: "transcription-partial",
+  transcriptionProcessing: "transcription-processing",
+  transcriptionProgress: "transcription-progress",
+  transcriptionRefined: "transcription-refined",
+  transcriptionResult: "transcription-complete",
+});
+
 /** user-defined constants **/
 
 /** user-defined types **/
 
-export type AppInfo = { app_name: string; url: string | null };
+/**
+ * The app being dictated into, the same shape for native apps, browsers and
+ * Electron apps
+ */
+export type AppContext = {
+  /**
+   * e.g. `com.tinyspeck.slackmacgap`; `None` when it can't be read
+   */
+  bundle_id: string | null;
+  /**
+   * Display name, e.g. `Slack` even though its process is called `Electron`
+   */
+  name: string;
+  /**
+   * Active tab of a supported browser
+   */
+  url: string | null;
+  /**
+   * Writing style category from the URL's domain, falling back to the app
+   */
+  category: WritingStyleCategory;
+  /**
+   * Whether the app is a Markdown editor or Dicto's notes
+   */
+  markdown: boolean;
+};
+/**
+ * How dictated text is inserted into one app
+ */
+export type AppRule = {
+  id: string;
+  /**
+   * App name as shown in the menu bar, e.g. `Terminal`; matched ignoring case
+   */
+  app_name: string;
+  output_method: OutputMethod;
+  /**
+   * Characters per second when typing; `None` uses the default rate
+   */
+  typing_rate: number | null;
+  enabled: boolean;
+  created_at: number;
+  updated_at: number;
+};
+export type AuditAction = "create" | "update" | "delete";
+export type AuditEntity =
+  | "keyterm"
+  | "shortcut"
+  | "macro"
+  | "note"
+  | "setting"
+  | "writing_style"
+  | "url_rule"
+  | "app_rule";
+/**
+ * One recorded change to an entity
+ */
+export type AuditLogEntry = {
+  id: number;
+  entity: string;
+  /**
+   * ID of the changed row (the key for settings, the category for writing styles)
+   */
+  entity_id: string;
+  action: string;
+  created_at: number;
+};
+export type AuditLogParams = {
+  entity?: AuditEntity | null;
+  entity_id?: string | null;
+  limit?: number | null;
+};
+/**
+ * Charge and power source of the internal battery
+ */
+export type BatteryStatus = {
+  /**
+   * Running on battery rather than a power adapter
+   */
+  on_battery: boolean;
+  /**
+   * Charge from 0 to 100
+   */
+  percent: number;
+};
+/**
+ * Result of `benchmark_models`
+ */
+export type BenchmarkReport = {
+  clip_duration_ms: number;
+  /**
+   * One entry per installed model
+   */
+  results: ModelBenchmark[];
+  /**
+   * Most accurate model fast enough for this machine; the fastest one when
+   * none is, and `None` when no model is installed
+   */
+  recommended: string | null;
+};
+/**
+ * Payload of `chat-delta`, emitted for each piece of a streaming reply
+ */
+export type ChatDelta = { session_id: string; delta: string };
+/**
+ * Payload of `chat-error`, emitted when a reply fails
+ */
+export type ChatFailed = { session_id: string; error: string };
+/**
+ * Payload of `chat-complete`, emitted with the whole reply once it has streamed
+ */
+export type ChatReply = { session_id: string; reply: string };
+export type ChatRole = "user" | "assistant";
+/**
+ * A message of a chat session
+ */
+export type ChatTurn = { role: ChatRole; content: string };
+/**
+ * Timing of a single transcribed chunk
+ */
+export type ChunkTiming = {
+  chunk_id: number;
+  /**
+   * Length of the chunk audio
+   */
+  audio_ms: number;
+  /**
+   * Time the chunk waited for a free worker
+   */
+  queue_wait_ms: number;
+  /**
+   * Time Whisper took to transcribe the chunk, retries included
+   */
+  inference_ms: number;
+  /**
+   * Inference time divided by audio length; above 1 is slower than real time
+   */
+  realtime_factor: number;
+};
 export type CommandError = { code: ErrorCode; message: string };
+/**
+ * A phrase the user keeps correcting, offered as a replacement or keyterm
+ */
+export type CorrectionSuggestion = {
+  original: string;
+  corrected: string;
+  count: number;
+  last_seen_at: number;
+};
+export type CreateAppRuleInput = {
+  app_name: string;
+  output_method: OutputMethod;
+  typing_rate: number | null;
+};
 export type CreateKeytermInput = { text: string; category: KeytermCategory };
+export type CreateMacroInput = { trigger: string; template: string };
 export type CreateNoteInput = { title: string; content: string };
 export type CreateShortcutInput = {
   trigger: string;
   replacement: string;
   category: ShortcutCategory;
+  /**
+   * Defaults to false
+   */
+  case_sensitive?: boolean | null;
+  /**
+   * Defaults to true
+   */
+  whole_word?: boolean | null;
 };
 export type CreateTranscriptionInput = {
   text: string;
   formatted_text: string | null;
 };
+export type CreateUrlRuleInput = {
+  pattern: string;
+  category: WritingStyleCategory;
+  style: string | null;
+};
+/**
+ * Inclusive range of creation times in Unix seconds; open ends are unbounded
+ */
+export type DateRange = { from: number | null; to: number | null };
+/**
+ * Database contention since launch
+ */
+export type DbDiagnostics = {
+  busy_timeout_ms: number;
+  /**
+   * Write transactions run through `write_transaction`
+   */
+  write_transactions: number;
+  /**
+   * Times a write transaction hit SQLITE_BUSY and was retried
+   */
+  busy_retries: number;
+  /**
+   * Write transactions that were still busy after every retry
+   */
+  busy_failures: number;
+  pool_connections: number;
+  pool_idle_connections: number;
+};
+/**
+ * Result of a maintenance run
+ */
+export type DbMaintenanceReport = {
+  /**
+   * Whether `PRAGMA integrity_check` found no problems
+   */
+  integrity_ok: boolean;
+  /**
+   * Problems reported by the integrity check (empty when it passed)
+   */
+  integrity_errors: string[];
+  /**
+   * Size of the database and its WAL file in bytes, before and after
+   */
+  size_before: number;
+  size_after: number;
+  ran_at: number;
+};
+/**
+ * State of the app worth attaching to a bug report
+ */
+export type DiagnosticsReport = {
+  app_version: string;
+  os: string;
+  arch: string;
+  generated_at: number;
+  /**
+   * Resident memory of the whole process; `None` where it can't be read
+   */
+  resident_memory_bytes: number | null;
+  model: ModelFootprint;
+  recording: boolean;
+  /**
+   * Audio buffered by the current recording
+   */
+  recording_memory: RecordingMemory;
+  /**
+   * Database file plus its WAL
+   */
+  database_bytes: number;
+  database: DbDiagnostics;
+  /**
+   * Chunk timings of the current or most recent local recording
+   */
+  performance: PerformanceStats;
+  /**
+   * Most recent first
+   */
+  recent_errors: RecentError[];
+};
+export type DiffKind = "equal" | "removed" | "added";
+/**
+ * A run of consecutive words that changed the same way
+ */
+export type DiffSpan = { kind: DiffKind; text: string };
+/**
+ * Payload of `model-download-complete`
+ */
+export type DownloadComplete = { model: string };
+/**
+ * Payload of `model-download-error`
+ */
+export type DownloadError = { model: string; error: string };
+/**
+ * Payload of `model-download-progress`
+ */
+export type DownloadProgress = {
+  model: string;
+  downloaded: number;
+  total: number;
+  percentage: number;
+};
 export type ErrorCode =
   | "DatabaseError"
   | "NotFound"
   | "ValidationError"
   | "DuplicateEntry"
-  | "InvalidInput";
+  | "InvalidInput"
+  | "InsufficientDiskSpace"
+  | "PermissionDenied"
+  | "RecordingFailed";
+/**
+ * Payload of `first-words-detected`, emitted the first time a recording produces text
+ */
+export type FirstWordsDetected = { text: string };
+/**
+ * Payload emitted when a queued transcription is formatted after the fact
+ */
+export type FormattingCompletedLate = {
+  transcription_id: string;
+  formatted_text: string;
+};
+/**
+ * What this machine offers for on-device inference
+ */
+export type HardwareCapabilities = {
+  /**
+   * Apple Silicon rather than Intel, even when the app runs under Rosetta
+   */
+  apple_silicon: boolean;
+  /**
+   * The app runs translated by Rosetta, which makes inference much slower
+   */
+  rosetta: boolean;
+  /**
+   * A Metal GPU is available for acceleration
+   */
+  metal: boolean;
+  /**
+   * Physical memory, shared with the GPU on Apple Silicon; `None` when it
+   * can't be read
+   */
+  memory_bytes: number | null;
+  cpu_brand: string | null;
+  cpu_cores: number;
+  suggested_model_size: ModelSize;
+  /**
+   * Manifest id of the local model to pre-select in settings
+   */
+  suggested_model: string;
+};
+/**
+ * A downloaded model
+ */
+export type InstalledModel = {
+  id: string;
+  name: string;
+  kind: ModelKind;
+  /**
+   * File, or folder for archive-based models
+   */
+  path: string;
+  size_bytes: number;
+};
+/**
+ * A periodic background job and the result of its last run
+ */
+export type JobStatus = {
+  /**
+   * Name the job was registered under, e.g. `sync`
+   */
+  name: string;
+  interval_secs: number;
+  /**
+   * Whether a run is in progress
+   */
+  running: boolean;
+  /**
+   * Runs finished since launch
+   */
+  runs: number;
+  /**
+   * When the last run finished, unset until the first run
+   */
+  last_run_at: number | null;
+  last_duration_ms: number | null;
+  /**
+   * Error of the last run, unset when it succeeded
+   */
+  last_error: string | null;
+};
 /**
  * Masked version for listing (hides full API key)
  */
@@ -663,21 +1940,193 @@ export type Keyterm = {
   category: string;
   created_at: number;
   updated_at: number;
+  /**
+   * Clashes with an older entry of the same category. Set when uniqueness
+   * started being enforced; cleared once the entry is edited.
+   */
+  is_duplicate?: boolean;
 };
 export type KeytermCategory = "all" | "Personal" | "Work" | "Email" | "Notes";
+/**
+ * A word the user keeps correcting transcripts to, not yet in the dictionary
+ */
+export type KeytermSuggestion = {
+  text: string;
+  /**
+   * How many times the user has corrected a transcript to this text
+   */
+  count: number;
+};
+export type ListNotesParams = {
+  /**
+   * Order within the pinned and unpinned groups; newest first when unset
+   */
+  sort?: NoteSort | null;
+  /**
+   * Only archived notes when true, only the others when false; both when unset
+   */
+  archived?: boolean | null;
+};
 export type ListTranscriptionsParams = {
   limit: number | null;
   offset: number | null;
+  sort?: TranscriptionSort | null;
+  /**
+   * Only transcriptions created at or after this Unix time
+   */
+  from?: number | null;
+  /**
+   * Only transcriptions created at or before this Unix time
+   */
+  to?: number | null;
+  /**
+   * Only transcriptions dictated into this app
+   */
+  app_name?: string | null;
+  /**
+   * Only transcriptions whose formatted text differs from the raw text (or, when false, doesn't)
+   */
+  has_formatted?: boolean | null;
+};
+/**
+ * Payload of `low-power-changed`, emitted when low power mode turns on or off
+ */
+export type LowPowerChanged = PowerStatus;
+/**
+ * A spoken trigger phrase expanded into a template when dictated
+ */
+export type Macro = {
+  id: string;
+  /**
+   * Phrase that triggers the macro, e.g. "insert signature"
+   */
+  trigger: string;
+  /**
+   * Text pasted in place of the trigger; may span several lines and contain
+   * `{date}`, `{time}` and `{clipboard}` placeholders
+   */
+  template: string;
+  created_at: number;
+  updated_at: number;
+};
+/**
+ * Whether the app may record from the microphone
+ */
+export type MicrophonePermission =
+  | "not_determined"
+  | "restricted"
+  | "denied"
+  | "granted";
+/**
+ * Payload of `microphone-permission-denied`, emitted when a recording can't
+ * start because microphone access was refused. Also the error `start_recording`
+ * fails with in that case.
+ */
+export type MicrophonePermissionDenied = {
+  permission: MicrophonePermission;
+  permission_hint: string;
+};
+/**
+ * How one model did on the reference clip
+ */
+export type ModelBenchmark = {
+  /**
+   * Manifest id of the model
+   */
+  model: string;
+  /**
+   * Word similarity of the transcript to the reference text, 0.0 to 1.0
+   */
+  accuracy: number;
+  /**
+   * Processing time divided by the clip's length; below 1.0 is faster than real time
+   */
+  realtime_factor: number;
+  load_ms: number;
+  transcribe_ms: number;
+  transcript: string;
+};
+/**
+ * Runtime that loads a model's files
+ */
+export type ModelEngine = "whisper" | "moonshine" | "llama";
+/**
+ * A model in the manifest
+ */
+export type ModelEntry = {
+  /**
+   * Stable identifier used by `download_model`
+   */
+  id: string;
+  /**
+   * Display name, also the `model` of download events
+   */
+  name: string;
+  kind: ModelKind;
+  engine: ModelEngine;
+  url: string;
+  /**
+   * Name the download is saved under
+   */
+  filename: string;
+  /**
+   * Hex SHA-256 of the download; checked when present
+   */
+  sha256: string | null;
+  /**
+   * Download size, for the disk space check when the server sends no Content-Length
+   */
+  size_bytes: number;
+  /**
+   * Folder an archive is extracted to; `None` for single-file models
+   */
+  extracted_folder: string | null;
+};
+/**
+ * Local speech model on disk and in memory
+ */
+export type ModelFootprint = {
+  file_name: string;
+  /**
+   * `None` when the model hasn't been downloaded
+   */
+  file_bytes: number | null;
+  /**
+   * Rough memory held by loaded models: every chunk worker loads its own copy
+   */
+  loaded_bytes: number;
+};
+/**
+ * What a model is used for
+ */
+export type ModelKind = "stt" | "llm";
+/**
+ * Payload of `model-load-failed`, emitted when the local model can't be loaded.
+ * The recording is abandoned rather than failing chunk after chunk.
+ */
+export type ModelLoadFailed = {
+  /**
+   * Manifest id of the model
+   */
+  model: string;
+  error: string;
+  /**
+   * What the user can do about it, usually downloading the model again
+   */
+  hint: string;
 };
 /**
- * Large Language Models (Text-to-Text)
+ * Largest model class this machine transcribes comfortably while dictating
  */
-export type LlmModel = "Qwen";
+export type ModelSize = "base" | "small" | "medium" | "large";
 /**
- * Status of an LLM model
+ * Download status of a model
  */
-export type LlmModelStatus = {
-  model: LlmModel;
+export type ModelStatus = {
+  /**
+   * Manifest id of the model
+   */
+  id: string;
   downloaded: boolean;
   file_size: number | null;
   path: string | null;
@@ -688,12 +2137,233 @@ export type Note = {
   content: string;
   created_at: number;
   updated_at: number;
+  /**
+   * Pinned notes are listed first
+   */
+  is_pinned?: boolean;
+  /**
+   * Archived notes are kept but hidden from the main list
+   */
+  archived?: boolean;
+  /**
+   * Files attached to the note, oldest first. They stay on this device.
+   */
+  attachments?: NoteAttachment[];
+};
+/**
+ * A file attached to a note, such as the recording of a meeting
+ */
+export type NoteAttachment = {
+  id: string;
+  note_id: string;
+  /**
+   * Name of the file as attached
+   */
+  file_name: string;
+  /**
+   * Where the copy in app data lives
+   */
+  path: string;
+  size_bytes: number;
+  created_at: number;
+};
+/**
+ * A previous version of a note
+ */
+export type NoteRevision = {
+  note_id: string;
+  /**
+   * Counts up from 1 per note
+   */
+  revision: number;
+  title: string;
+  content: string;
+  /**
+   * When this version was written, i.e. the note's `updated_at` at the time
+   */
+  saved_at: number;
 };
+export type NoteSort = "updated" | "created" | "title";
+export type OutputMethod = "paste" | "type";
 export type PaginatedTranscriptions = {
   items: Transcription[];
   total: number;
   has_more: boolean;
 };
+/**
+ * Payload of `paste-countdown`, emitted every second while waiting for the app
+ * dictated into to come back to the front
+ */
+export type PasteCountdown = {
+  app_name: string;
+  /**
+   * Seconds left before giving up and leaving the text on the clipboard
+   */
+  remaining_secs: number;
+};
+/**
+ * Payload of `paste-target-gone`, emitted when the app dictated into quit before
+ * the paste, or never came back to the front; the text was left on the
+ * clipboard instead
+ */
+export type PasteTargetGone = {
+  app_name: string;
+  /**
+   * The app is still running but the wait for it to come back timed out
+   */
+  timed_out: boolean;
+};
+/**
+ * Transcription speed of the current or most recent local recording
+ */
+export type PerformanceStats = {
+  worker_count: number;
+  chunks: number;
+  total_audio_ms: number;
+  total_inference_ms: number;
+  average_queue_wait_ms: number;
+  max_queue_wait_ms: number;
+  /**
+   * Total inference time divided by total audio length
+   */
+  realtime_factor: number;
+  /**
+   * The last chunks waited longer than their own audio, so the backlog grows
+   */
+  falling_behind: boolean;
+  /**
+   * Most recent first
+   */
+  recent_chunks: ChunkTiming[];
+};
+/**
+ * Payload of `pipeline-log`, emitted for each log line of the app while the
+ * pipeline log setting is on
+ */
+export type PipelineLog = {
+  /**
+   * `error`, `warn`, `info` or `debug`
+   */
+  level: string;
+  /**
+   * Module that logged, e.g. `transcription` or `stt::whisper`
+   */
+  module: string;
+  message: string;
+};
+/**
+ * Low power status, also returned by `power_get_status`
+ */
+export type PowerStatus = {
+  /**
+   * Transcription uses one worker, fewer threads, longer chunks and the
+   * smallest downloaded model
+   */
+  low_power: boolean;
+  /**
+   * Low power mode was turned on by a low battery rather than the setting
+   */
+  automatic: boolean;
+  /**
+   * `None` on machines without a battery
+   */
+  battery: BatteryStatus | null;
+};
+/**
+ * An error logged while the app was running
+ */
+export type RecentError = {
+  /**
+   * Unix time the error happened
+   */
+  at: number;
+  /**
+   * Part of the app that failed, e.g. `transcription` or `paste`
+   */
+  source: string;
+  message: string;
+};
+/**
+ * Payload of `recording-cancelled`, emitted when a recording is dropped
+ * without being transcribed
+ */
+export type RecordingCancelled = null;
+/**
+ * Elapsed time payload emitted every second while recording
+ */
+export type RecordingElapsed = { elapsed_secs: number };
+/**
+ * Audio and models the current recording holds in memory
+ */
+export type RecordingMemory = {
+  /**
+   * Samples captured from the microphone so far
+   */
+  buffered_samples: number;
+  /**
+   * Resampled samples waiting in chunks for a worker
+   */
+  queued_chunk_samples: number;
+  /**
+   * Bytes held by both sample buffers
+   */
+  sample_bytes: number;
+  /**
+   * Whisper contexts the chunk workers have loaded (local mode only)
+   */
+  loaded_model_contexts: number;
+  /**
+   * Transcribed samples moved to disk (long local recordings only)
+   */
+  spilled_samples: number;
+};
+/**
+ * How a recording is turned into output
+ */
+export type RecordingMode = "dictation" | "session" | "chat";
+/**
+ * Payload of `recording-retry`, emitted when the input stream failed to start and
+ * another attempt follows
+ */
+export type RecordingRetry = {
+  /**
+   * The attempt that failed, starting at 1
+   */
+  attempt: number;
+  max_attempts: number;
+  error: string;
+};
+/**
+ * How many defaults `seed_defaults` added; all zero when they were seeded before
+ */
+export type SeedReport = {
+  writing_styles: number;
+  shortcuts: number;
+  keyterms: number;
+};
+/**
+ * Payload of `session-ended`, emitted once a session's transcript is complete
+ */
+export type SessionEnded = SessionSummary;
+/**
+ * Payload of `session-finalizing`, emitted when a session recording stops and
+ * its last chunks are being transcribed
+ */
+export type SessionFinalizing = null;
+/**
+ * Payload of `session-started`, emitted once audio capture is running
+ */
+export type SessionStarted = { mode: RecordingMode };
+/**
+ * Summary event payload emitted when a session recording ends
+ */
+export type SessionSummary = {
+  session_id: string;
+  title: string;
+  duration_ms: number;
+  chunk_count: number;
+  word_count: number;
+};
 export type SetKeyInput = { service: VaultService; api_key: string };
 export type SetSettingInput = { key: string; value: string };
 export type Setting = {
@@ -707,65 +2377,363 @@ export type Shortcut = {
   trigger: string;
   replacement: string;
   category: string;
+  /**
+   * Only match the trigger with exactly this capitalization. Otherwise any
+   * capitalization matches and the replacement follows it ("BRB" -> "BE RIGHT BACK").
+   */
+  case_sensitive: boolean;
+  /**
+   * Only match the trigger as whole words, not inside other words
+   */
+  whole_word: boolean;
   created_at: number;
   updated_at: number;
+  /**
+   * Clashes with an older entry of the same category. Set when uniqueness
+   * started being enforced; cleared once the entry is edited.
+   */
+  is_duplicate?: boolean;
 };
 export type ShortcutCategory = "all" | "Personal" | "Work" | "Email" | "Notes";
 /**
- * Speech-to-Text models
+ * Payload emitted when the keyboard listener dies
  */
-export type SttModel = "Whisper";
+export type ShortcutListenerFailed = {
+  error: string;
+  permission_hint: string;
+  /**
+   * Whether the watchdog will try to restart the listener
+   */
+  will_retry: boolean;
+};
+export type SyncConfigInput = {
+  /**
+   * Base URL of an object store that takes plain GET, PUT and DELETE requests with
+   * bearer auth and ETags, e.g. a self-hosted server
+   */
+  endpoint: string;
+  /**
+   * Bearer token sent with every request, if the backend needs one
+   */
+  token: string | null;
+  /**
+   * Base64 key from another device; a new key is generated when omitted
+   */
+  encryption_key: string | null;
+};
 /**
- * Status of an STT model
+ * Where sync state stands, for the settings screen
  */
-export type SttModelStatus = {
-  model: SttModel;
-  downloaded: boolean;
-  file_size: number | null;
-  path: string | null;
+export type SyncStatus = {
+  configured: boolean;
+  endpoint: string | null;
+  syncing: boolean;
+  /**
+   * When the last successful sync in this session finished (Unix seconds)
+   */
+  last_synced_at: number | null;
+  /**
+   * Error from the last sync, if it failed
+   */
+  last_error: string | null;
+  /**
+   * Local changes and deletions not pushed yet
+   */
+  pending_changes: number;
+};
+/**
+ * What a sync run changed
+ */
+export type SyncSummary = {
+  /**
+   * Records uploaded from this device (including deletions)
+   */
+  pushed: number;
+  /**
+   * Records downloaded from the backend
+   */
+  pulled: number;
+  /**
+   * Local records removed because they were deleted on another device
+   */
+  deleted: number;
+};
+/**
+ * A timestamped piece of transcribed text as reported by Whisper
+ */
+export type TranscriptSegment = {
+  /**
+   * Start offset in milliseconds
+   */
+  start_ms: number;
+  /**
+   * End offset in milliseconds
+   */
+  end_ms: number;
+  text: string;
+  /**
+   * Mean token probability (0.0 - 1.0)
+   */
+  confidence: number;
+  /**
+   * Per-word confidence, in order
+   */
+  words: TranscriptWord[];
+};
+export type TranscriptWord = {
+  text: string;
+  confidence: number;
+  /**
+   * Start offset in milliseconds (from token timestamps)
+   */
+  start_ms: number;
+  /**
+   * End offset in milliseconds (from token timestamps)
+   */
+  end_ms: number;
 };
 export type Transcription = {
   id: string;
   text: string;
   formatted_text: string | null;
   created_at: number;
+  is_pinned: boolean;
+  /**
+   * App the text was dictated into, when known
+   */
+  app_name: string | null;
 };
 export type TranscriptionAnalytics = {
   total_count: number;
   total_words: number;
 };
+/**
+ * Event payload describing the confidence of a finished transcription
+ */
+export type TranscriptionConfidence = {
+  segments: TranscriptSegment[];
+  /**
+   * Mean of the segment confidences
+   */
+  average_confidence: number;
+  /**
+   * Number of words below the low-confidence threshold
+   */
+  low_confidence_words: number;
+};
+/**
+ * Word-level diff of the raw transcript against the formatted text
+ */
+export type TranscriptionDiff = {
+  transcription_id: string;
+  spans: DiffSpan[];
+  words_removed: number;
+  words_added: number;
+};
+/**
+ * Transcriptions created on one local calendar day
+ */
+export type TranscriptionDay = {
+  /**
+   * Local date as `YYYY-MM-DD`
+   */
+  date: string;
+  word_count: number;
+  items: Transcription[];
+};
+/**
+ * Payload of `transcription-error`: why a dictation produced nothing to paste
+ */
+export type TranscriptionError = string;
+export type TranscriptionFeedback = {
+  transcription_id: string;
+  rating: TranscriptionRating;
+  /**
+   * What the user actually said, when they corrected the transcript
+   */
+  corrected_text: string | null;
+  created_at: number;
+  updated_at: number;
+};
+/**
+ * Interim transcript emitted while recording
+ */
+export type TranscriptionPartial = {
+  /**
+   * Finalized text so far followed by the current interim hypothesis
+   */
+  text: string;
+  /**
+   * Whether the latest piece is final (will not be revised)
+   */
+  is_final: boolean;
+};
+/**
+ * Payload of `transcription-processing`, emitted when a recording stops and its
+ * transcript is being finished
+ */
+export type TranscriptionProcessing = null;
+/**
+ * Progress payload emitted while the chunk queue drains after stop
+ */
+export type TranscriptionProgress = {
+  completed_chunks: number;
+  total_chunks: number;
+  /**
+   * Estimated milliseconds until all chunks are transcribed (once measurable)
+   */
+  eta: number | null;
+};
+export type TranscriptionRating = "good" | "bad";
+/**
+ * Payload of `transcription-refined`, emitted when the cloud pass of a hybrid-mode
+ * dictation has replaced the saved draft
+ */
+export type TranscriptionRefined = {
+  id: string;
+  /**
+   * Text that was pasted
+   */
+  draft: string;
+  /**
+   * Refined raw text now saved
+   */
+  refined: string;
+  /**
+   * Whether the saved formatted text was replaced too, so the pasted draft can
+   * be swapped for it with `transcriptions_replace_pasted`
+   */
+  replaceable: boolean;
+  model: string;
+};
+/**
+ * Payload of `transcription-complete`, emitted once a dictation has been saved,
+ * right before it is pasted
+ */
+export type TranscriptionResult = {
+  /**
+   * Id of the saved transcription; `None` if saving failed
+   */
+  id: string | null;
+  raw: string;
+  formatted: string;
+  duration_ms: number;
+  model: string;
+  /**
+   * Base language code, e.g. `en`, whether it was selected or detected
+   */
+  language: string | null;
+};
+export type TranscriptionSort = "newest" | "pinned_first";
+/**
+ * Which version of a transcription to use
+ */
+export type TranscriptionVersion = "raw" | "formatted";
 export type TranscriptionSettings = {
   autoDetectLanguage: boolean;
   languages: string[];
   /**
-   * Keyterms to boost recognition (technical terms, proper nouns, acronyms)
+   * App category used to pick keyterms that boost recognition
+   */
+  category?: string | null;
+  /**
+   * Name of the frontmost app when recording started, saved with the transcription
    */
-  keyterms: string[];
+  appName?: string | null;
   /**
    * Whether to use cloud transcription (AssemblyAI)
    */
   useCloud: boolean;
+  /**
+   * Dictation (paste on stop), session (live transcript document) or chat
+   */
+  mode?: RecordingMode;
+  /**
+   * Title for the session record when recording in session mode
+   */
+  sessionTitle?: string | null;
+  /**
+   * Chat session the dictation is sent to when recording in chat mode
+   */
+  chatSessionId?: string | null;
+};
+export type UpdateAppRuleInput = {
+  app_name: string | null;
+  output_method: OutputMethod | null;
+  /**
+   * Zero clears the rate, going back to the default
+   */
+  typing_rate: number | null;
+  enabled: boolean | null;
 };
 export type UpdateKeytermInput = {
   text: string | null;
   category: KeytermCategory | null;
 };
+export type UpdateMacroInput = {
+  trigger: string | null;
+  template: string | null;
+};
 export type UpdateNoteInput = { title: string | null; content: string | null };
 export type UpdateShortcutInput = {
   trigger: string | null;
   replacement: string | null;
   category: ShortcutCategory | null;
+  case_sensitive?: boolean | null;
+  whole_word?: boolean | null;
 };
 export type UpdateTranscriptionInput = {
   text: string | null;
   formatted_text: string | null;
 };
+export type UpdateUrlRuleInput = {
+  pattern: string | null;
+  category: WritingStyleCategory | null;
+  /**
+   * An empty string clears the style
+   */
+  style: string | null;
+  enabled: boolean | null;
+};
 export type UpdateWritingStyleInput = {
   selected_style: string | null;
   default_prompt: string | null;
   custom_prompt: string | null;
 };
+/**
+ * Writing style applied when dictating into a browser tab whose URL matches
+ */
+export type UrlRule = {
+  id: string;
+  /**
+   * Host with an optional path, without the scheme. `*` matches any run of
+   * characters, e.g. `mail.google.com` or `*.atlassian.net/browse`. A host also
+   * matches its subdomains and a path matches everything below it.
+   */
+  pattern: string;
+  category: WritingStyleCategory;
+  /**
+   * Style to format with; `None` uses the category's selected style
+   */
+  style: string | null;
+  enabled: boolean;
+  created_at: number;
+  updated_at: number;
+};
 export type VaultService = "deepgram" | "groq" | "openai" | "gemini";
+/**
+ * Result of `cleanup_workspaces`
+ */
+export type WorkspaceCleanupReport = {
+  /**
+   * Workspaces deleted
+   */
+  removed: number;
+  /**
+   * Bytes freed on disk
+   */
+  reclaimed_bytes: number;
+};
 export type WritingStyle = {
   category: string;
   selected_style: string;
@@ -777,9 +2745,60 @@ export type WritingStyleCategory = "Personal" | "Work" | "Email" | "General";
 
 /** tauri-specta globals **/
 
-import { invoke as TAURI_INVOKE } from "@tauri-apps/api/core";
+import {
+  invoke as TAURI_INVOKE,
+  Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
 import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
 
+type __EventObj__<T> = {
+  listen: (
+    cb: TAURI_API_EVENT.EventCallback<T>,
+  ) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
+  once: (
+    cb: TAURI_API_EVENT.EventCallback<T>,
+  ) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
+  emit: null extends T
+    ? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
+    : (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
+};
+
 export type Result<T, E> =
   | { status: "ok"; data: T }
   | { status: "error"; error: E };
+
+function __makeEvents__<T extends Record<string, any>>(
+  mappings: Record<keyof T, string>,
+) {
+  return new Proxy(
+    {} as unknown as {
+      [K in keyof T]: __EventObj__<T[K]> & {
+        (handle: __WebviewWindow__): __EventObj__<T[K]>;
+      };
+    },
+    {
+      get: (_, event) => {
+        const name = mappings[event as keyof T];
+
+        return new Proxy((() => {}) as any, {
+          apply: (_, __, [window]: [__WebviewWindow__]) => ({
+            listen: (arg: any) => window.listen(name, arg),
+            once: (arg: any) => window.once(name, arg),
+            emit: (arg: any) => window.emit(name, arg),
+          }),
+          get: (_, command: keyof __EventObj__<any>) => {
+            switch (command) {
+              case "listen":
+                return (arg: any) => TAURI_API_EVENT.listen(name, arg);
+              case "once":
+                return (arg: any) => TAURI_API_EVENT.once(name, arg);
+              case "emit":
+                return (arg: any) => TAURI_API_EVENT.emit(name, arg);
+            }
+          },
+        });
+      },
+    },
+  );
+}
@@ -9,6 +9,8 @@ import {
 } from "lucide-react";
 import { listen } from "@tauri-apps/api/event";
 import { useEffect, useState } from "react";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
 import { SettingsDialog } from "@/components/settings-dialog";
 import { cn } from "@/lib/utils";
 import { formatKey, sortKeys } from "@/lib/keyboard-utils";
@@ -80,6 +82,17 @@ export function AppSidebar() {
     fetchVersion();
   }, []);
 
+  // Copy a diagnostics report to attach to the issue
+  const handleReportIssue = async () => {
+    const result = await commands.diagnosticsReport();
+    if (result.status === "error") {
+      toast.error(result.error.message);
+      return;
+    }
+    await navigator.clipboard.writeText(JSON.stringify(result.data, null, 2));
+    toast.success("Diagnostics copied to clipboard");
+  };
+
   return (
     <Sidebar className="mt-10 border-r-0! bg-background" collapsible="icon">
       <SidebarContent className="gap-1.5 px-3 pt-1">
@@ -216,6 +229,13 @@ export function AppSidebar() {
                 )}>Release</span>
               </div>
             </div>
+            <button
+              type="button"
+              className="mt-8 text-xs text-muted-foreground underline-offset-2 hover:underline"
+              onClick={handleReportIssue}
+            >
+              Report an issue
+            </button>
           </div>
         </DialogContent>
       </Dialog>
@@ -0,0 +1,40 @@
+import { Plus, Sparkles } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import type { KeytermSuggestion } from "@/hooks/use-keyterms";
+
+interface KeytermSuggestionsProps {
+	suggestions: KeytermSuggestion[];
+	onAdd: (text: string) => void;
+}
+
+/** Words the user keeps correcting transcripts to, addable with one click */
+export function KeytermSuggestions({
+	suggestions,
+	onAdd,
+}: KeytermSuggestionsProps) {
+	if (suggestions.length === 0) return null;
+
+	return (
+		<div className="mb-6 flex flex-col gap-3 rounded-md border p-4">
+			<div className="flex items-center gap-2 text-sm">
+				<Sparkles className="size-4 text-muted-foreground" />
+				<span className="font-medium">Suggested from your corrections</span>
+			</div>
+			<div className="flex flex-wrap gap-2">
+				{suggestions.map((suggestion) => (
+					<Button
+						key={suggestion.text}
+						variant="outline"
+						size="sm"
+						onClick={() => onAdd(suggestion.text)}
+						title={`Corrected ${suggestion.count} times`}
+					>
+						<Plus className="size-3.5" />
+						{suggestion.text}
+						<span className="text-muted-foreground/70">{suggestion.count}</span>
+					</Button>
+				))}
+			</div>
+		</div>
+	);
+}
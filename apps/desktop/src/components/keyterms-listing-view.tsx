@@ -221,6 +221,7 @@ export function KeytermsListingView({
 									</span>
 									<span className="text-muted-foreground text-xs">
 										{getCategoryLabel(term.category)}
+										{term.is_duplicate && " · Duplicate, edit or delete it"}
 									</span>
 								</button>
 								<Button
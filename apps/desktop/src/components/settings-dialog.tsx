@@ -1,17 +1,30 @@
-import { listen } from "@tauri-apps/api/event";
 import {
 	Check,
 	ChevronDown,
 	Loader2,
 } from "lucide-react";
 import { useEffect, useState } from "react";
-import { commands } from "@/bindings";
+import {
+	commands,
+	type DbMaintenanceReport,
+	events,
+	type HardwareCapabilities,
+	type ModelEntry,
+	type PowerStatus,
+} from "@/bindings";
 import { Button } from "@/components/ui/button";
 import {
 	Dialog,
 	DialogContent,
 } from "@/components/ui/dialog";
 import { Label } from "@/components/ui/label";
+import {
+	Select,
+	SelectContent,
+	SelectItem,
+	SelectTrigger,
+	SelectValue,
+} from "@/components/ui/select";
 import {
 	Combobox,
 	ComboboxChips,
@@ -23,6 +36,7 @@ import {
 	ComboboxList,
 	ComboboxItem,
 } from "@/components/ui/combobox";
+import type { CloudModel } from "@/hooks/use-settings";
 import { useSettings } from "@/hooks/use-settings";
 import { useShortcutEditor } from "@/hooks/use-shortcut-editor";
 import { shortcutStringToKeys, keysToShortcutString } from "@/lib/keyboard-utils";
@@ -47,6 +61,9 @@ const LANGUAGE_ITEMS = [
 	{ value: "zh", label: "🇨🇳 Chinese" },
 ];
 
+/** Select items can't have an empty value, so this stands in for "no translation" */
+const SAME_AS_SPOKEN = "same";
+
 export function SettingsDialog({ open, onOpenChange }: SettingsDialogProps) {
 	return (
 		<Dialog open={open} onOpenChange={onOpenChange} modal={false}>
@@ -72,7 +89,11 @@ function GeneralSettings() {
 
 	// Model states
 	const [whisperState, setWhisperState] = useState<ModelDownloadState>(INITIAL_MODEL_STATE);
+	const [moonshineState, setMoonshineState] = useState<ModelDownloadState>(INITIAL_MODEL_STATE);
 	const [qwenState, setQwenState] = useState<ModelDownloadState>(INITIAL_MODEL_STATE);
+	const [powerStatus, setPowerStatus] = useState<PowerStatus | null>(null);
+	const [hardware, setHardware] = useState<HardwareCapabilities | null>(null);
+	const [speechModels, setSpeechModels] = useState<ModelEntry[]>([]);
 
 	// Fetch current shortcut on mount
 	useEffect(() => {
@@ -89,17 +110,49 @@ function GeneralSettings() {
 		getCurrentShortcut();
 	}, []);
 
+	// Hardware decides which model to suggest and whether to warn before downloading
+	useEffect(() => {
+		commands.hardwareCapabilities().then(setHardware);
+	}, []);
+
+	// Speech models to choose from, as listed in the model manifest
+	useEffect(() => {
+		commands.modelsCatalog().then((result) => {
+			if (result.status === "ok") {
+				setSpeechModels(result.data.filter((model) => model.kind === "stt"));
+			}
+		});
+	}, []);
+
+	// Track the battery, which can turn low power mode on by itself
+	useEffect(() => {
+		commands.powerGetStatus().then(setPowerStatus);
+		const unlisten = events.lowPowerChanged.listen((event) => {
+			setPowerStatus(event.payload);
+		});
+		return () => {
+			unlisten.then((fn) => fn());
+		};
+	}, []);
+
 	// Check model status on mount
 	useEffect(() => {
 		const checkModelStatus = async () => {
-			const whisperResult = await commands.checkSttModelStatus("Whisper");
+			const whisperResult = await commands.checkModelStatus("whisper-small");
 			if (whisperResult.status === "ok") {
 				setWhisperState((prev) => ({
 					...prev,
 					isDownloaded: whisperResult.data.downloaded,
 				}));
 			}
-			const qwenResult = await commands.checkLlmModelStatus("Qwen");
+			const moonshineResult = await commands.checkModelStatus("moonshine-base");
+			if (moonshineResult.status === "ok") {
+				setMoonshineState((prev) => ({
+					...prev,
+					isDownloaded: moonshineResult.data.downloaded,
+				}));
+			}
+			const qwenResult = await commands.checkModelStatus("qwen-0.5b");
 			if (qwenResult.status === "ok") {
 				setQwenState((prev) => ({
 					...prev,
@@ -113,64 +166,61 @@ function GeneralSettings() {
 
 	// Listen for download events
 	useEffect(() => {
-		const unlistenProgress = listen<DownloadProgress>(
-			"model-download-progress",
-			(event) => {
-				const { model, downloaded, total, percentage } = event.payload;
-				const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
-					...prev,
-					isDownloading: true,
-					progress: percentage,
-					downloaded,
-					total,
-					error: null,
-				});
-
-				if (model === "Whisper Small") {
-					setWhisperState(updateFn);
-				} else if (model === "Qwen 0.5B") {
-					setQwenState(updateFn);
-				}
-			},
-		);
+		const unlistenProgress = events.downloadProgress.listen((event) => {
+			const { model, downloaded, total, percentage } = event.payload;
+			const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
+				...prev,
+				isDownloading: true,
+				progress: percentage,
+				downloaded,
+				total,
+				error: null,
+			});
+
+			if (model === "Whisper Small") {
+				setWhisperState(updateFn);
+			} else if (model === "Moonshine Base") {
+				setMoonshineState(updateFn);
+			} else if (model === "Qwen 0.5B") {
+				setQwenState(updateFn);
+			}
+		});
 
-		const unlistenComplete = listen<DownloadComplete>(
-			"model-download-complete",
-			(event) => {
-				const { model } = event.payload;
-				const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
-					...prev,
-					isDownloading: false,
-					progress: 100,
-					isDownloaded: true,
-					error: null,
-				});
-
-				if (model === "Whisper Small") {
-					setWhisperState(updateFn);
-				} else if (model === "Qwen 0.5B") {
-					setQwenState(updateFn);
-				}
-			},
-		);
+		const unlistenComplete = events.downloadComplete.listen((event) => {
+			const { model } = event.payload;
+			const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
+				...prev,
+				isDownloading: false,
+				progress: 100,
+				isDownloaded: true,
+				error: null,
+			});
+
+			if (model === "Whisper Small") {
+				setWhisperState(updateFn);
+			} else if (model === "Moonshine Base") {
+				setMoonshineState(updateFn);
+			} else if (model === "Qwen 0.5B") {
+				setQwenState(updateFn);
+			}
+		});
 
-		const unlistenError = listen<DownloadError>(
-			"model-download-error",
-			(event) => {
-				const { model, error } = event.payload;
-				const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
-					...prev,
-					isDownloading: false,
-					error,
-				});
-
-				if (model === "Whisper Small") {
-					setWhisperState(updateFn);
-				} else if (model === "Qwen 0.5B") {
-					setQwenState(updateFn);
-				}
-			},
-		);
+		const unlistenError = events.downloadError.listen((event) => {
+			const { model, error } = event.payload;
+			const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
+				...prev,
+				isDownloading: false,
+				error,
+			});
+
+			if (model === "Whisper Small") {
+				setWhisperState(updateFn);
+			} else if (model === "Moonshine Base") {
+				setMoonshineState(updateFn);
+			} else if (model === "Qwen 0.5B") {
+				setQwenState(updateFn);
+			}
+		});
 
 		return () => {
 			unlistenProgress.then((fn) => fn());
@@ -186,12 +236,29 @@ function GeneralSettings() {
 			progress: 0,
 			error: null,
 		}));
-		const result = await commands.downloadSttModel("Whisper");
+		const result = await commands.downloadModel("whisper-small");
 		if (result.status === "error") {
 			setWhisperState((prev) => ({
 				...prev,
 				isDownloading: false,
-				error: result.error,
+				error: result.error.message,
+			}));
+		}
+	};
+
+	const handleDownloadMoonshine = async () => {
+		setMoonshineState((prev) => ({
+			...prev,
+			isDownloading: true,
+			progress: 0,
+			error: null,
+		}));
+		const result = await commands.downloadModel("moonshine-base");
+		if (result.status === "error") {
+			setMoonshineState((prev) => ({
+				...prev,
+				isDownloading: false,
+				error: result.error.message,
 			}));
 		}
 	};
@@ -240,9 +307,10 @@ function GeneralSettings() {
 
 	// Calculate storage used
 	const whisperSize = whisperState.isDownloaded ? 0.5 : 0;
+	const moonshineSize = moonshineState.isDownloaded ? 0.3 : 0;
 	const qwenSize = qwenState.isDownloaded ? 0.4 : 0;
-	const storageUsed = whisperSize + qwenSize;
-	const totalStorage = 0.9;
+	const storageUsed = whisperSize + moonshineSize + qwenSize;
+	const totalStorage = 1.2;
 
 	return (
 		<div className="space-y-6">
@@ -270,6 +338,98 @@ function GeneralSettings() {
 					onCancel={handleCancelShortcut}
 					onSetSpecialKey={setSpecialKey}
 				/>
+				<RepasteShortcutSetting />
+				<RewriteShortcutSetting />
+				<NoteShortcutSetting />
+				<SettingToggle
+					label="Left-click menu bar icon to record"
+					description="Right-click the icon to open the menu"
+					checked={settings.trayLeftClickRecords}
+					onChange={(checked) => setSetting("trayLeftClickRecords", checked)}
+				/>
+				<SettingToggle
+					label="Esc cancels recording"
+					description="Discard the recording without transcribing it"
+					checked={settings.escapeCancelsRecording}
+					onChange={(checked) => setSetting("escapeCancelsRecording", checked)}
+				/>
+				<SettingToggle
+					label="Menu bar only"
+					description="Hide the Dock icon while the dashboard is closed"
+					checked={settings.menubarOnly}
+					onChange={(checked) => setSetting("menubarOnly", checked)}
+				/>
+				<SettingToggle
+					label="Skip duplicate transcriptions"
+					description="Don't save a dictation that repeats one from a few seconds ago"
+					checked={settings.suppressDuplicateTranscriptions}
+					onChange={(checked) =>
+						setSetting("suppressDuplicateTranscriptions", checked)
+					}
+				/>
+				<SettingToggle
+					label="Wait for the app before pasting"
+					description="If you switched apps while transcribing, paste once you switch back instead of switching for you"
+					checked={settings.waitForTargetApp}
+					onChange={(checked) => setSetting("waitForTargetApp", checked)}
+				/>
+				<SettingToggle
+					label="Paste as rich text"
+					description="Keep bold text and lists when pasting into apps like Mail and Notes"
+					checked={settings.richTextPaste}
+					onChange={(checked) => setSetting("richTextPaste", checked)}
+				/>
+				<SettingToggle
+					label="Reduce echo and background noise"
+					description="Filter out speaker audio and noise; records from the default microphone"
+					checked={settings.voiceProcessing}
+					onChange={(checked) => setSetting("voiceProcessing", checked)}
+				/>
+				<SettingToggle
+					label="Low power mode"
+					description="Transcribe with fewer CPU cores and the smallest downloaded model to save battery"
+					checked={settings.lowPowerMode}
+					onChange={(checked) => setSetting("lowPowerMode", checked)}
+				/>
+				{powerStatus?.battery && (
+					<SettingToggle
+						label="Save power on low battery"
+						description={
+							powerStatus.automatic
+								? `Low power mode is on (battery at ${powerStatus.battery.percent}%)`
+								: "Turn on low power mode below 20% battery"
+						}
+						checked={settings.batteryLowPower}
+						onChange={(checked) => setSetting("batteryLowPower", checked)}
+					/>
+				)}
+				<SettingToggle
+					label="Mirror to iCloud Drive"
+					description="Save transcriptions and notes as Markdown in iCloud Drive/Dicto"
+					checked={settings.mirrorToICloud}
+					onChange={async (checked) => {
+						await setSetting("mirrorToICloud", checked);
+						// Copy what's already there; new entries are mirrored as they're saved
+						if (checked) {
+							const result = await commands.mirrorExportAll();
+							if (result.status === "error") {
+								console.error("Failed to mirror to iCloud Drive:", result.error);
+							}
+						}
+					}}
+				/>
+				<SettingToggle
+					label="Use window titles as context"
+					description="Send the title of the window you dictate into, like an email subject, when formatting"
+					checked={settings.captureWindowTitle}
+					onChange={(checked) => setSetting("captureWindowTitle", checked)}
+				/>
+				<SettingToggle
+					label="Pipeline log"
+					description="Stream detailed logs to the app while troubleshooting with support"
+					checked={settings.pipelineLog}
+					onChange={(checked) => setSetting("pipelineLog", checked)}
+				/>
 			</div>
 
 			<div className="h-px bg-border" />
@@ -338,6 +498,63 @@ function GeneralSettings() {
 						</ComboboxPopup>
 					</Combobox>
 				</div>
+
+				{/* Output language */}
+				<div className="flex items-center justify-between gap-4">
+					<div>
+						<p className="text-sm">Output language</p>
+						<p className="text-muted-foreground text-xs">
+							Translate dictations before pasting (uses your intelligence key)
+						</p>
+					</div>
+					<Select
+						value={settings.outputLanguage || SAME_AS_SPOKEN}
+						onValueChange={(value) =>
+							setSetting("outputLanguage", value === SAME_AS_SPOKEN ? "" : value)
+						}
+					>
+						<SelectTrigger className="w-[180px] h-8 text-xs">
+							<SelectValue />
+						</SelectTrigger>
+						<SelectContent>
+							<SelectItem value={SAME_AS_SPOKEN}>Same as spoken</SelectItem>
+							{LANGUAGE_ITEMS.map((language) => (
+								<SelectItem key={language.value} value={language.value}>
+									{language.label}
+								</SelectItem>
+							))}
+						</SelectContent>
+					</Select>
+				</div>
+
+				{/* Cloud transcription provider */}
+				<div className="flex items-center justify-between gap-4">
+					<div>
+						<p className="text-sm">Cloud transcription</p>
+						<p className="text-muted-foreground text-xs">
+							Groq and OpenAI transcribe after you stop, using your keys
+						</p>
+					</div>
+					<Select
+						value={settings.cloudModel}
+						onValueChange={(value) => setSetting("cloudModel", value as CloudModel)}
+					>
+						<SelectTrigger className="w-[180px] h-8 text-xs">
+							<SelectValue />
+						</SelectTrigger>
+						<SelectContent>
+							<SelectItem value="Deepgram">Deepgram (live)</SelectItem>
+							<SelectItem value="Groq">Groq Whisper</SelectItem>
+							<SelectItem value="OpenAI">OpenAI</SelectItem>
+						</SelectContent>
+					</Select>
+				</div>
+				<SettingToggle
+					label="Refine dictations in the cloud"
+					description="Paste the on-device result at once, then fix the saved text with Groq or OpenAI"
+					checked={settings.hybridTranscription}
+					onChange={(checked) => setSetting("hybridTranscription", checked)}
+				/>
 			</div>
 
 			<div className="h-px bg-border" />
@@ -353,6 +570,47 @@ function GeneralSettings() {
 					state={whisperState}
 					onSetup={handleDownloadWhisper}
 				/>
+				{hardware?.suggested_model_size === "base" && !whisperState.isDownloaded && (
+					<p className="text-amber-600 text-xs">
+						{hardware.rosetta
+							? "Dicto is running under Rosetta, so Whisper may transcribe slowly."
+							: "This Mac has little memory for Whisper; Moonshine is lighter."}
+					</p>
+				)}
+
+				{/* Speech model */}
+				<div className="flex items-center justify-between gap-4">
+					<div>
+						<p className="text-sm">Speech model</p>
+						<p className="text-muted-foreground text-xs">
+							Moonshine is English only but can do better with strong accents
+						</p>
+					</div>
+					<Select
+						value={settings.localModel}
+						onValueChange={(value) => setSetting("localModel", value)}
+					>
+						<SelectTrigger className="w-[180px] h-8 text-xs">
+							<SelectValue />
+						</SelectTrigger>
+						<SelectContent>
+							{speechModels.map((model) => (
+								<SelectItem key={model.id} value={model.id}>
+									{model.name}
+									{hardware?.suggested_model === model.id && " (suggested)"}
+								</SelectItem>
+							))}
+						</SelectContent>
+					</Select>
+				</div>
+				{settings.localModel === "moonshine-base" && (
+					<FeatureCard
+						title="Moonshine"
+						description="Used instead of Whisper once set up"
+						state={moonshineState}
+						onSetup={handleDownloadMoonshine}
+					/>
+				)}
 			</div>
 			{/* Privacy notice + technical details */}
 			<div className="space-y-2 -mt-3">
@@ -387,6 +645,12 @@ function GeneralSettings() {
 									Whisper Small · 0.5 GB · {whisperState.isDownloaded ? "Ready" : "Not downloaded"}
 								</span>
 							</div>
+							<div className="flex items-center justify-between text-sm">
+								<span>Accent-robust Recognition</span>
+								<span className="text-muted-foreground text-xs">
+									Moonshine Base · 0.3 GB · {moonshineState.isDownloaded ? "Ready" : "Not downloaded"}
+								</span>
+							</div>
 						</div>
 
 						<div className="h-px bg-border" />
@@ -398,6 +662,67 @@ function GeneralSettings() {
 					</div>
 				)}
 			</div>
+
+			<div className="h-px bg-border" />
+
+			{/* Advanced Section */}
+			<div className="space-y-3">
+				<Label className="font-medium text-sm">Advanced</Label>
+				<DatabaseMaintenanceSetting />
+			</div>
+		</div>
+	);
+}
+
+function formatBytes(bytes: number) {
+	if (bytes < 1024 * 1024) {
+		return `${(bytes / 1024).toFixed(0)} KB`;
+	}
+	return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+/** Manual trigger for the integrity check and VACUUM that otherwise run monthly */
+function DatabaseMaintenanceSetting() {
+	const [running, setRunning] = useState(false);
+	const [report, setReport] = useState<DbMaintenanceReport | null>(null);
+	const [error, setError] = useState<string | null>(null);
+
+	const runMaintenance = async () => {
+		setRunning(true);
+		setError(null);
+		const result = await commands.dbMaintenance();
+		if (result.status === "ok") {
+			setReport(result.data);
+		} else {
+			setError(result.error.message);
+		}
+		setRunning(false);
+	};
+
+	return (
+		<div className="flex items-center justify-between">
+			<div>
+				<p className="text-sm">Database maintenance</p>
+				<p className="text-muted-foreground text-xs">
+					{error
+						? `Failed: ${error}`
+						: report
+							? report.integrity_ok
+								? `Compacted from ${formatBytes(report.size_before)} to ${formatBytes(report.size_after)}`
+								: "Integrity check found problems; the database was not compacted"
+							: "Check for corruption and reclaim space. Runs monthly on its own."}
+				</p>
+			</div>
+			<Button
+				size="sm"
+				variant="outline"
+				onClick={runMaintenance}
+				disabled={running}
+				className="h-7 shrink-0 gap-1.5 text-xs"
+			>
+				{running && <Loader2 className="h-3.5 w-3.5 animate-spin" />}
+				Run now
+			</Button>
 		</div>
 	);
 }
@@ -458,6 +783,183 @@ function FeatureCard({
 	);
 }
 
+/** Optional shortcut that pastes the last transcription again */
+function RepasteShortcutSetting() {
+	const [shortcut, setShortcut] = useState<string[]>([]);
+
+	useEffect(() => {
+		commands.getRepasteShortcut().then((res) => {
+			if (res.status === "ok" && res.data) {
+				setShortcut(shortcutStringToKeys(res.data));
+			}
+		});
+	}, []);
+
+	const saveRepasteShortcut = async (keys: string[]) => {
+		setShortcut(keys);
+		const result = await commands.changeRepasteShortcut(
+			keysToShortcutString(keys),
+		);
+		if (result.status === "error") {
+			console.error("Failed to save re-paste shortcut:", result.error);
+		}
+	};
+
+	const { isEditing, currentKeys, startEditing, saveShortcut, cancelEditing, setSpecialKey } =
+		useShortcutEditor({
+			onSave: saveRepasteShortcut,
+		});
+
+	const handleEdit = async () => {
+		// Keep the old combination from firing while the new one is typed
+		await commands.changeRepasteShortcut("");
+		startEditing();
+	};
+
+	const handleCancel = async () => {
+		cancelEditing();
+		await commands.changeRepasteShortcut(keysToShortcutString(shortcut));
+	};
+
+	return (
+		<div className="space-y-2">
+			<div>
+				<Label className="font-medium text-sm">Paste last transcription</Label>
+				<p className="text-muted-foreground text-xs">
+					Paste the most recent transcription again if an app missed it
+				</p>
+			</div>
+			<ShortcutItem
+				shortcut={shortcut}
+				isEditing={isEditing}
+				currentKeys={currentKeys}
+				onEdit={handleEdit}
+				onSave={saveShortcut}
+				onCancel={handleCancel}
+				onSetSpecialKey={setSpecialKey}
+			/>
+		</div>
+	);
+}
+
+/** Optional hold-to-talk shortcut that rewrites the selected text */
+function RewriteShortcutSetting() {
+	const [shortcut, setShortcut] = useState<string[]>([]);
+
+	useEffect(() => {
+		commands.getRewriteShortcut().then((res) => {
+			if (res.status === "ok" && res.data) {
+				setShortcut(shortcutStringToKeys(res.data));
+			}
+		});
+	}, []);
+
+	const saveRewriteShortcut = async (keys: string[]) => {
+		setShortcut(keys);
+		const result = await commands.changeRewriteShortcut(
+			keysToShortcutString(keys),
+		);
+		if (result.status === "error") {
+			console.error("Failed to save rewrite shortcut:", result.error);
+		}
+	};
+
+	const { isEditing, currentKeys, startEditing, saveShortcut, cancelEditing, setSpecialKey } =
+		useShortcutEditor({
+			onSave: saveRewriteShortcut,
+		});
+
+	const handleEdit = async () => {
+		// Keep the old combination from firing while the new one is typed
+		await commands.changeRewriteShortcut("");
+		startEditing();
+	};
+
+	const handleCancel = async () => {
+		cancelEditing();
+		await commands.changeRewriteShortcut(keysToShortcutString(shortcut));
+	};
+
+	return (
+		<div className="space-y-2">
+			<div>
+				<Label className="font-medium text-sm">Rewrite selection</Label>
+				<p className="text-muted-foreground text-xs">
+					Select text, hold the shortcut and say how to change it, e.g. "make this more formal"
+				</p>
+			</div>
+			<ShortcutItem
+				shortcut={shortcut}
+				isEditing={isEditing}
+				currentKeys={currentKeys}
+				onEdit={handleEdit}
+				onSave={saveShortcut}
+				onCancel={handleCancel}
+				onSetSpecialKey={setSpecialKey}
+			/>
+		</div>
+	);
+}
+
+/** Optional hold-to-talk shortcut that dictates a new note */
+function NoteShortcutSetting() {
+	const [shortcut, setShortcut] = useState<string[]>([]);
+
+	useEffect(() => {
+		commands.getNoteShortcut().then((res) => {
+			if (res.status === "ok" && res.data) {
+				setShortcut(shortcutStringToKeys(res.data));
+			}
+		});
+	}, []);
+
+	const saveNoteShortcut = async (keys: string[]) => {
+		setShortcut(keys);
+		const result = await commands.changeNoteShortcut(
+			keysToShortcutString(keys),
+		);
+		if (result.status === "error") {
+			console.error("Failed to save note shortcut:", result.error);
+		}
+	};
+
+	const { isEditing, currentKeys, startEditing, saveShortcut, cancelEditing, setSpecialKey } =
+		useShortcutEditor({
+			onSave: saveNoteShortcut,
+		});
+
+	const handleEdit = async () => {
+		// Keep the old combination from firing while the new one is typed
+		await commands.changeNoteShortcut("");
+		startEditing();
+	};
+
+	const handleCancel = async () => {
+		cancelEditing();
+		await commands.changeNoteShortcut(keysToShortcutString(shortcut));
+	};
+
+	return (
+		<div className="space-y-2">
+			<div>
+				<Label className="font-medium text-sm">Quick note</Label>
+				<p className="text-muted-foreground text-xs">
+					Hold the shortcut to dictate a new note without leaving the app you are in
+				</p>
+			</div>
+			<ShortcutItem
+				shortcut={shortcut}
+				isEditing={isEditing}
+				currentKeys={currentKeys}
+				onEdit={handleEdit}
+				onSave={saveShortcut}
+				onCancel={handleCancel}
+				onSetSpecialKey={setSpecialKey}
+			/>
+		</div>
+	);
+}
+
 function SettingToggle({
 	label,
 	description,
@@ -496,22 +998,6 @@ function SettingToggle({
 	);
 }
 
-interface DownloadProgress {
-	model: string;
-	downloaded: number;
-	total: number;
-	percentage: number;
-}
-
-interface DownloadComplete {
-	model: string;
-}
-
-interface DownloadError {
-	model: string;
-	error: string;
-}
-
 interface ModelDownloadState {
 	isDownloading: boolean;
 	progress: number;
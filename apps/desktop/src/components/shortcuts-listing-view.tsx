@@ -256,6 +256,7 @@ export function ShortcutsListingView({
 										</span>
 										<span className="text-muted-foreground/70 text-xs">
 											{getCategoryLabel(shortcut.category)}
+											{shortcut.is_duplicate && " · Duplicate, edit or delete it"}
 										</span>
 									</div>
 								</button>
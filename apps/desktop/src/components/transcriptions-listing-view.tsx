@@ -1,6 +1,7 @@
-import { Copy, RefreshCw, Trash2 } from "lucide-react";
+import { Copy, Pin, PinOff, RefreshCw, Trash2 } from "lucide-react";
 import { useMemo } from "react";
 import { toast } from "sonner";
+import { commands } from "@/bindings";
 import { Button } from "@/components/ui/button";
 import { ButtonGroup } from "@/components/ui/button-group";
 import { Skeleton } from "@/components/ui/skeleton";
@@ -13,6 +14,7 @@ interface Transcription {
 	text: string;
 	formattedText: string;
 	createdAt: Date;
+	isPinned: boolean;
 }
 
 function formatDateGroup(date: Date): string {
@@ -51,6 +53,7 @@ interface GroupedTranscription {
 	formattedText: string;
 	date: Date;
 	timestamp: string;
+	isPinned: boolean;
 }
 
 function groupTranscriptionsByDate(
@@ -60,7 +63,8 @@ function groupTranscriptionsByDate(
 
 	for (const transcription of transcriptions) {
 		const date = transcription.createdAt;
-		const dateGroup = formatDateGroup(date);
+		// Pinned items come first from the backend, so their group is listed first
+		const dateGroup = transcription.isPinned ? "pinned" : formatDateGroup(date);
 		const timestamp = formatTimestamp(date);
 
 		if (!grouped[dateGroup]) {
@@ -73,6 +77,7 @@ function groupTranscriptionsByDate(
 			formattedText: transcription.formattedText,
 			date,
 			timestamp,
+			isPinned: transcription.isPinned,
 		});
 	}
 
@@ -146,14 +151,23 @@ function EmptyState() {
 interface TranscriptionItemProps {
 	transcription: GroupedTranscription;
 	onDelete: (id: string) => void;
+	onTogglePin: (id: string, pinned: boolean) => void;
 }
 
 function TranscriptionItem({
 	transcription,
 	onDelete,
+	onTogglePin,
 }: TranscriptionItemProps) {
-	const handleCopy = () => {
-		navigator.clipboard.writeText(transcription.formattedText);
+	const handleCopy = async () => {
+		const result = await commands.transcriptionsCopyToClipboard(
+			transcription.id,
+			"formatted",
+		);
+		if (result.status === "error") {
+			toast.error(result.error.message);
+			return;
+		}
 		toast.success("Copied to clipboard");
 	};
 
@@ -181,6 +195,20 @@ function TranscriptionItem({
 				orientation="horizontal"
 				className="opacity-0 transition-opacity group-hover/item:opacity-100"
 			>
+				<Button
+					variant="ghost"
+					size="icon-xs"
+					onClick={() =>
+						onTogglePin(transcription.id, !transcription.isPinned)
+					}
+					className="h-8 w-8 p-0"
+				>
+					{transcription.isPinned ? (
+						<PinOff className="h-4 w-4" />
+					) : (
+						<Pin className="h-4 w-4" />
+					)}
+				</Button>
 				<Button
 					variant="ghost"
 					size="icon-xs"
@@ -203,8 +231,16 @@ function TranscriptionItem({
 }
 
 export function TranscriptionsListingView() {
-	const { transcriptions, analytics, loading, remove, refetch, loadMore, hasMore } =
-		useTranscriptions();
+	const {
+		transcriptions,
+		analytics,
+		loading,
+		remove,
+		setPinned,
+		refetch,
+		loadMore,
+		hasMore,
+	} = useTranscriptions();
 
 	const groupedTranscriptions = useMemo(
 		() => groupTranscriptionsByDate(transcriptions),
@@ -319,6 +355,7 @@ export function TranscriptionsListingView() {
 													<TranscriptionItem
 														transcription={transcription}
 														onDelete={remove}
+														onTogglePin={setPinned}
 													/>
 												</div>
 											</div>
@@ -1,6 +1,7 @@
+import { Channel } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { useEffect, useRef, useState } from "react";
-import { commands, type KeytermCategory } from "@/bindings";
+import { type ReactNode, useEffect, useRef, useState } from "react";
+import { commands, events } from "@/bindings";
 import { useSettings } from "@/hooks/use-settings";
 import {
 	type Category,
@@ -10,28 +11,14 @@ import {
 import { X } from "lucide-react";
 import { Button } from "./ui/button";
 
-// Helper function to fetch keyterms for a category
-async function fetchKeytermsForCategory(
-	category: string | null,
-): Promise<string[]> {
-	try {
-		const result = await commands.keytermsList(
-			(category as KeytermCategory) ?? null,
-		);
-		if (result.status === "error") return [];
-		// Filter to include "all" category items as well
-		return result.data
-			.filter((kt) => kt.category === category || kt.category === "all")
-			.map((kt) => kt.text);
-	} catch (error) {
-		console.error("Failed to fetch keyterms:", error);
-		return [];
-	}
-}
-
-
 type WidgetState = "dictate" | "recording" | "processing";
 
+function formatElapsed(secs: number): string {
+	const minutes = Math.floor(secs / 60);
+	const seconds = secs % 60;
+	return `${minutes}:${seconds.toString().padStart(2, "0")}`;
+}
+
 interface TranscriptionResult {
 	channel: {
 		alternatives: Array<{
@@ -44,11 +31,45 @@ interface TranscriptionResult {
 	end: number;
 }
 
+/** Characters of the live transcript shown in the expanded widget */
+const PREVIEW_CHARS = 160;
+
+function previewTail(text: string): string {
+	return text.length > PREVIEW_CHARS
+		? `…${text.slice(-PREVIEW_CHARS).trimStart()}`
+		: text;
+}
+
+/** Lays out the pill, with the transcript preview above it once words arrive */
+function WidgetFrame({
+	preview,
+	children,
+}: {
+	preview: string | null;
+	children: ReactNode;
+}) {
+	return (
+		<div className="flex h-full w-full flex-col items-center justify-end gap-1.5">
+			{preview !== null && (
+				<div className="flex w-full flex-1 flex-col justify-end overflow-hidden rounded-xl border border-white/10 bg-black px-3 py-2 shadow-lg">
+					<p className="text-[11px] leading-snug text-white/90">
+						{previewTail(preview)}
+					</p>
+				</div>
+			)}
+			{children}
+		</div>
+	);
+}
+
 const WAVEFORM_BARS = Array.from({ length: 7 }, (_, i) => ({
 	id: `bar-${i}`,
 	offset: Math.sin(i * 0.5) * 0.5 + 0.5, // Creates variation between 0-1
 }));
 
+// Frame rate of the audio level driving the waveform
+const AUDIO_LEVEL_FPS = 30;
+
 const PROCESSING_DOTS = Array.from({ length: 5 }, (_, i) => ({
 	id: `dot-${i}`,
 	delay: i * 0.1,
@@ -65,7 +86,11 @@ export function Widget() {
 	const selectedStyleRef = useRef<string | null>(null);
 	const { settings } = useSettings();
 	const settingsRef = useRef(settings);
-	const keytermsRef = useRef<string[]>([]);
+	const [elapsedSecs, setElapsedSecs] = useState(0);
+	// Live transcript; null keeps the widget collapsed to the pill
+	const [preview, setPreview] = useState<string | null>(null);
+	// Seconds left to switch back to the app dictated into, while the paste waits for it
+	const [pasteCountdown, setPasteCountdown] = useState<number | null>(null);
 
 	console.log("settings", settings);
 
@@ -77,21 +102,24 @@ export function Widget() {
 	// Listen for audio level events and transcription events
 	useEffect(() => {
 		const setupListeners = async () => {
-			// Listen for audio level events
-			const audioLevelUnlisten = await listen<number>(
-				"audio-level",
-				(event) => {
-					const level = event.payload;
-
-					// Update bar heights with variation
-					const newHeights = WAVEFORM_BARS.map((bar) => {
-						const variation = bar.offset * 0.6 + 0.4; // 0.4 to 1.0 range
-						const baseHeight = level * 10 * variation; // Scale for widget size (max ~14px)
-						return Math.max(4, Math.min(14, baseHeight));
-					});
-					setBarHeights(newHeights);
-				},
+			// Receive the audio level over a dedicated channel
+			const audioLevel = new Channel<number>();
+			audioLevel.onmessage = (level) => {
+				// Update bar heights with variation
+				const newHeights = WAVEFORM_BARS.map((bar) => {
+					const variation = bar.offset * 0.6 + 0.4; // 0.4 to 1.0 range
+					const baseHeight = level * 10 * variation; // Scale for widget size (max ~14px)
+					return Math.max(4, Math.min(14, baseHeight));
+				});
+				setBarHeights(newHeights);
+			};
+			const audioLevelId = await commands.audioLevelSubscribe(
+				audioLevel,
+				AUDIO_LEVEL_FPS,
 			);
+			const audioLevelUnlisten = () => {
+				commands.audioLevelUnsubscribe(audioLevelId);
+			};
 
 			// Listen for transcription results (accumulate text)
 			const transcriptionUnlisten = await listen<TranscriptionResult>(
@@ -125,26 +153,60 @@ export function Widget() {
 				},
 			);
 
+			// Recording lifecycle, driven by the pipeline: the widget expands once
+			// words are recognized and collapses again on paste-complete
+			const sessionStartedUnlisten = await events.sessionStarted.listen(() => {
+				setPreview(null);
+			});
+			const firstWordsUnlisten = await events.firstWordsDetected.listen((event) => {
+				setPreview(event.payload.text);
+			});
+			const partialUnlisten = await events.transcriptionPartial.listen((event) => {
+				setPreview((current) =>
+					current === null ? null : event.payload.text,
+				);
+			});
+			const sessionFinalizingUnlisten = await events.sessionFinalizing.listen(() => {
+				setState("processing");
+			});
+
+			// Listen for the recording timer
+			const elapsedUnlisten = await events.recordingElapsed.listen((event) => {
+				setElapsedSecs(event.payload.elapsed_secs);
+			});
+
 			// Listen for paste-complete event to stop spinner
 			const pasteCompleteUnlisten = await listen("paste-complete", () => {
 				console.log("Paste complete - stopping spinner");
 				setState("dictate");
 				setTranscription(""); // Clear transcription after pasting
+				setElapsedSecs(0);
+				setPreview(null);
+				setPasteCountdown(null);
 				transcriptionRef.current = ""; // Clear ref
 				seenStartTimestamps.current.clear(); // Clear seen timestamps
 			});
 
+			// The paste is waiting for the user to switch back to the app dictated into
+			const pasteCountdownUnlisten = await events.pasteCountdown.listen((event) => {
+				setPasteCountdown(event.payload.remaining_secs);
+			});
+
 			// Listen for transcription errors
-			const errorUnlisten = await listen<string>(
-				"transcription-error",
+			const errorUnlisten = await events.transcriptionError.listen((event) => {
+				console.error("Transcription error:", event.payload);
+			});
+
+			// Recording never started because microphone access was refused
+			const micDeniedUnlisten = await events.microphonePermissionDenied.listen(
 				(event) => {
-					console.error("Transcription error:", event.payload);
+					console.error("Microphone access denied:", event.payload.permission_hint);
+					setState("dictate");
 				},
 			);
 
 			// Listen for transcription-processing event (local transcription started)
-			const transcriptionProcessingUnlisten = await listen(
-				"transcription-processing",
+			const transcriptionProcessingUnlisten = await events.transcriptionProcessing.listen(
 				() => {
 					console.log(
 						"Transcription processing started - switching to processing state",
@@ -173,7 +235,7 @@ export function Widget() {
 								if (appResult.status === "ok") {
 									const appInfo = appResult.data;
 									category = getCategoryForApp(
-										appInfo.app_name,
+										appInfo.name,
 										appInfo.url ?? undefined,
 									);
 									detectedCategoryRef.current = category;
@@ -188,7 +250,7 @@ export function Widget() {
 
 									console.log(
 										"Detected app:",
-										appInfo.app_name,
+										appInfo.name,
 										"URL:",
 										appInfo.url,
 										"Category:",
@@ -205,16 +267,13 @@ export function Widget() {
 									selectedStyleRef.current = null;
 								}
 
-								// Fetch keyterms for the detected category
-								const keyterms = await fetchKeytermsForCategory(category);
-								keytermsRef.current = keyterms;
-								console.log("Keyterms for category:", category, keyterms);
-
-								// Start recording with keyterms
+								// Keyterms for the category are resolved on the Rust side
 								await commands.startRecording({
 									autoDetectLanguage: currentSettings.autoDetectLanguage,
 									languages: currentSettings.languages,
-									keyterms: keyterms,
+									category,
+									appName:
+										appResult.status === "ok" ? appResult.data.name : null,
 									useCloud: currentSettings.cloudTranscription,
 								});
 							})
@@ -277,10 +336,17 @@ export function Widget() {
 
 			unlistenRefs.current = [
 				audioLevelUnlisten,
+				elapsedUnlisten,
 				transcriptionUnlisten,
 				pasteCompleteUnlisten,
+				pasteCountdownUnlisten,
 				errorUnlisten,
+				micDeniedUnlisten,
 				transcriptionProcessingUnlisten,
+				sessionStartedUnlisten,
+				firstWordsUnlisten,
+				partialUnlisten,
+				sessionFinalizingUnlisten,
 				unlistenStart,
 				unlistenStop,
 			];
@@ -312,7 +378,7 @@ export function Widget() {
 			if (appResult.status === "ok") {
 				const appInfo = appResult.data;
 				category = getCategoryForApp(
-					appInfo.app_name,
+					appInfo.name,
 					appInfo.url ?? undefined,
 				);
 				detectedCategoryRef.current = category;
@@ -327,7 +393,7 @@ export function Widget() {
 
 				console.log(
 					"Detected app:",
-					appInfo.app_name,
+					appInfo.name,
 					"URL:",
 					appInfo.url,
 					"Category:",
@@ -341,18 +407,18 @@ export function Widget() {
 				selectedStyleRef.current = null;
 			}
 
-			// Fetch keyterms for the detected category
-			const keyterms = await fetchKeytermsForCategory(category);
-			keytermsRef.current = keyterms;
-			console.log("Keyterms for category:", category, keyterms);
-
-			// Start recording with keyterms
-			await commands.startRecording({
+			// Keyterms for the category are resolved on the Rust side
+			const result = await commands.startRecording({
 				autoDetectLanguage: settings.autoDetectLanguage,
 				languages: settings.languages,
-				keyterms: keyterms,
+				category,
+				appName: appResult.status === "ok" ? appResult.data.name : null,
         useCloud: settings.cloudTranscription
 			});
+			if (result.status === "error") {
+				console.error("Failed to start recording:", result.error.message);
+				return;
+			}
 
 			setState("recording");
 		} catch (error) {
@@ -391,7 +457,7 @@ export function Widget() {
 
 	if (state === "processing") {
 		return (
-			<div className="flex h-full w-full items-end justify-center">
+			<WidgetFrame preview={preview}>
 				<div className="flex h-7.5 items-center justify-center gap-1.5 rounded-full border border-white/10 bg-black px-2.5 shadow-lg">
 					{/* Cancel button */}
 					<Button
@@ -420,15 +486,21 @@ export function Widget() {
 							/>
 						))}
 					</div>
+					{/* Time left to switch back before the text is only copied */}
+					{pasteCountdown !== null && (
+						<span className="font-mono text-[10px] text-white/80 tabular-nums">
+							{pasteCountdown}s
+						</span>
+					)}
 				</div>
-			</div>
+			</WidgetFrame>
 		);
 	}
 
 	if (state === "recording") {
 		return (
-			<div className="flex h-full w-full items-end justify-center">
-				<div className="group flex h-7.5 w-12.5 items-center justify-center gap-2 rounded-full border border-white/10 bg-black px-3 shadow-lg transition-all hover:border-white/20">
+			<WidgetFrame preview={preview}>
+				<div className="group flex h-7.5 min-w-12.5 items-center justify-center gap-2 rounded-full border border-white/10 bg-black px-3 shadow-lg transition-all hover:border-white/20">
 					{/* Waveform Section */}
 					<button
 						type="button"
@@ -448,8 +520,12 @@ export function Widget() {
 							))}
 						</div>
 					</button>
+					{/* Elapsed time */}
+					<span className="font-mono text-[10px] text-white/80 tabular-nums">
+						{formatElapsed(elapsedSecs)}
+					</span>
 				</div>
-			</div>
+			</WidgetFrame>
 		);
 	}
 	return (
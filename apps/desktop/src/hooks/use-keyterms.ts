@@ -3,10 +3,11 @@ import {
   type Keyterm,
   type UpdateKeytermInput,
   type KeytermCategory,
+  type KeytermSuggestion,
 } from "@/bindings";
 import { useTauriQuery, useTauriMutation, queryKeys } from "@/lib/tauri-query";
 
-export type { Keyterm, KeytermCategory };
+export type { Keyterm, KeytermCategory, KeytermSuggestion };
 
 export function useKeyterms(category?: KeytermCategory) {
   const query = useTauriQuery(
@@ -30,8 +31,14 @@ export function useKeyterms(category?: KeytermCategory) {
     invalidateKeys: [queryKeys.keyterms.all],
   });
 
+  const suggestionsQuery = useTauriQuery(
+    queryKeys.keyterms.suggestions(),
+    commands.keytermsSuggestions
+  );
+
   return {
     keyterms: query.data ?? [],
+    suggestions: suggestionsQuery.data ?? [],
     loading: query.isLoading,
     error: query.error?.message ?? null,
     refetch: query.refetch,
@@ -0,0 +1,43 @@
+import { commands, type Macro, type UpdateMacroInput } from "@/bindings";
+import { useTauriQuery, useTauriMutation, queryKeys } from "@/lib/tauri-query";
+
+export type { Macro };
+
+export function useMacros() {
+  const query = useTauriQuery(queryKeys.macros.list(), () =>
+    commands.macrosList()
+  );
+
+  const createMutation = useTauriMutation(commands.macrosCreate, {
+    invalidateKeys: [queryKeys.macros.all],
+  });
+
+  const updateMutation = useTauriMutation(
+    ({ id, input }: { id: string; input: UpdateMacroInput }) =>
+      commands.macrosUpdate(id, input),
+    {
+      invalidateKeys: [queryKeys.macros.all],
+    }
+  );
+
+  const deleteMutation = useTauriMutation(commands.macrosDelete, {
+    invalidateKeys: [queryKeys.macros.all],
+  });
+
+  return {
+    macros: query.data ?? [],
+    loading: query.isLoading,
+    error: query.error?.message ?? null,
+    refetch: query.refetch,
+    create: async (trigger: string, template: string) => {
+      const result = await createMutation.mutateAsync({ trigger, template });
+      return result.id;
+    },
+    update: async (id: string, trigger: string, template: string) => {
+      await updateMutation.mutateAsync({ id, input: { trigger, template } });
+    },
+    remove: async (id: string) => {
+      await deleteMutation.mutateAsync(id);
+    },
+  };
+}
@@ -1,6 +1,8 @@
 import {
   commands,
   type Note as DbNote,
+  type NoteAttachment,
+  type NoteSort,
   type UpdateNoteInput,
 } from "@/bindings";
 import { useTauriQuery, useTauriMutation, queryKeys } from "@/lib/tauri-query";
@@ -13,6 +15,9 @@ export interface Note {
   content: string;
   createdAt: Date;
   updatedAt: Date;
+  isPinned: boolean;
+  archived: boolean;
+  attachments: NoteAttachment[];
 }
 
 // Transform DB note to frontend Note
@@ -23,11 +28,21 @@ function mapNote(n: DbNote): Note {
     content: n.content,
     createdAt: new Date(n.created_at * 1000),
     updatedAt: new Date(n.updated_at * 1000),
+    isPinned: n.is_pinned ?? false,
+    archived: n.archived ?? false,
+    attachments: n.attachments ?? [],
   };
 }
 
-export function useNotes() {
-  const query = useTauriQuery(queryKeys.notes.list(), commands.notesList);
+export function useNotes(
+  options: { sort?: NoteSort; archived?: boolean } = {}
+) {
+  const sort = options.sort ?? "created";
+  const archived = options.archived ?? false;
+  const query = useTauriQuery(
+    [...queryKeys.notes.list(), sort, String(archived)],
+    () => commands.notesList({ sort, archived })
+  );
 
   const notes = useMemo(
     () => (query.data ?? []).map(mapNote),
@@ -50,6 +65,22 @@ export function useNotes() {
     invalidateKeys: [queryKeys.notes.all],
   });
 
+  const pinMutation = useTauriMutation(
+    ({ id, pinned }: { id: string; pinned: boolean }) =>
+      commands.notesSetPinned(id, pinned),
+    {
+      invalidateKeys: [queryKeys.notes.all],
+    }
+  );
+
+  const archiveMutation = useTauriMutation(
+    ({ id, archived }: { id: string; archived: boolean }) =>
+      commands.notesSetArchived(id, archived),
+    {
+      invalidateKeys: [queryKeys.notes.all],
+    }
+  );
+
   const getById = useCallback(
     async (id: string): Promise<Note | null> => {
       const result = await commands.notesGet(id);
@@ -83,6 +114,12 @@ export function useNotes() {
     remove: async (id: string) => {
       await deleteMutation.mutateAsync(id);
     },
+    setPinned: async (id: string, pinned: boolean) => {
+      await pinMutation.mutateAsync({ id, pinned });
+    },
+    setArchived: async (id: string, archived: boolean) => {
+      await archiveMutation.mutateAsync({ id, archived });
+    },
     getById,
   };
 }
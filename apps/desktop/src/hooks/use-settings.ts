@@ -7,6 +7,7 @@ export type SettingKey =
   | "language"
   | "languages"
   | "autoDetectLanguage"
+  | "outputLanguage"
   | "smartFormat"
   | "punctuate"
   | "paragraphs"
@@ -15,40 +16,102 @@ export type SettingKey =
   | "cloudIntelligence"
   | "localModel"
   | "cloudModel"
-  | "intelligenceModel";
-
-export type LocalModel = "Whisper";
-export type CloudModel = "Deepgram";
+  | "hybridTranscription"
+  | "intelligenceModel"
+  | "inputDevice"
+  | "trayLeftClickRecords"
+  | "menubarOnly"
+  | "escapeCancelsRecording"
+  | "suppressDuplicateTranscriptions"
+  | "waitForTargetApp"
+  | "richTextPaste"
+  | "voiceProcessing"
+  | "lowPowerMode"
+  | "batteryLowPower"
+  | "mirrorToICloud"
+  | "captureWindowTitle"
+  | "pipelineLog";
+
+/** Manifest id of an on-device speech model, e.g. `whisper-small` */
+export type LocalModel = string;
+export type CloudModel = "Deepgram" | "Groq" | "OpenAI";
 export type IntelligenceModel = "Groq" | "OpenAI" | "Gemini";
 
 export type SettingsMap = {
   language: string;
   languages: string[];
   autoDetectLanguage: boolean;
+  /** Language dictations are translated into before formatting; empty keeps the spoken language */
+  outputLanguage: string;
   smartFormat: boolean;
   punctuate: boolean;
   paragraphs: boolean;
   postProcess: boolean;
   cloudTranscription: boolean;
   cloudIntelligence: boolean;
+  /** On-device speech model; falls back to Whisper Small until the chosen one is downloaded */
   localModel: LocalModel;
+  /** Deepgram streams while recording; Groq and OpenAI upload on stop and fail over to each other */
   cloudModel: CloudModel;
+  /** Paste the local draft right away, then re-transcribe with Groq or OpenAI and update the saved text */
+  hybridTranscription: boolean;
   intelligenceModel: IntelligenceModel;
+  /** Microphone name; empty uses the system default */
+  inputDevice: string;
+  /** Left-clicking the tray icon toggles dictation; right-click opens the menu */
+  trayLeftClickRecords: boolean;
+  /** Hide the Dock icon while the main window is closed (macOS) */
+  menubarOnly: boolean;
+  /** Esc cancels an active recording without transcribing */
+  escapeCancelsRecording: boolean;
+  /** Skip saving a transcription that nearly repeats one saved moments before */
+  suppressDuplicateTranscriptions: boolean;
+  /** Wait for the user to switch back to the app dictated into before pasting, instead of switching to it */
+  waitForTargetApp: boolean;
+  /** Paste bold text, lists and headings as rich text too, for apps like Mail and Notes */
+  richTextPaste: boolean;
+  /** Record through macOS voice processing (echo cancellation, noise suppression); uses the default microphone */
+  voiceProcessing: boolean;
+  /** Transcribe with one worker, at most two threads and longer chunks to spare the battery */
+  lowPowerMode: boolean;
+  /** Turn low power mode on by itself while the battery runs low */
+  batteryLowPower: boolean;
+  /** Mirror transcriptions and notes as Markdown files into iCloud Drive */
+  mirrorToICloud: boolean;
+  /** Send the focused window's title (e.g. an email subject) to the formatter as context */
+  captureWindowTitle: boolean;
+  /** Stream the app's debug logs as `pipeline-log` events, for support sessions */
+  pipelineLog: boolean;
 };
 
 const DEFAULT_SETTINGS: SettingsMap = {
   language: "en-US",
   languages: ["en-US"],
   autoDetectLanguage: false,
+  outputLanguage: "",
   smartFormat: true,
   punctuate: true,
   paragraphs: true,
   postProcess: false,
   cloudTranscription: true,
   cloudIntelligence: true,
-  localModel: "Whisper",
+  localModel: "whisper-small",
   cloudModel: "Deepgram",
+  hybridTranscription: false,
   intelligenceModel: "Groq",
+  inputDevice: "",
+  trayLeftClickRecords: false,
+  menubarOnly: false,
+  escapeCancelsRecording: true,
+  suppressDuplicateTranscriptions: true,
+  waitForTargetApp: false,
+  richTextPaste: false,
+  voiceProcessing: false,
+  lowPowerMode: false,
+  batteryLowPower: true,
+  mirrorToICloud: false,
+  captureWindowTitle: true,
+  pipelineLog: false,
 };
 
 const BOOLEAN_KEYS: SettingKey[] = [
@@ -58,14 +121,33 @@ const BOOLEAN_KEYS: SettingKey[] = [
   "postProcess",
   "cloudTranscription",
   "cloudIntelligence",
+  "hybridTranscription",
   "autoDetectLanguage",
+  "trayLeftClickRecords",
+  "menubarOnly",
+  "escapeCancelsRecording",
+  "suppressDuplicateTranscriptions",
+  "waitForTargetApp",
+  "richTextPaste",
+  "voiceProcessing",
+  "lowPowerMode",
+  "batteryLowPower",
+  "mirrorToICloud",
+  "captureWindowTitle",
+  "pipelineLog",
 ];
 
 const JSON_KEYS: SettingKey[] = ["languages"];
 
 export type SettingValue<K extends SettingKey> = K extends "languages"
   ? string[]
-  : K extends "language" | "localModel" | "cloudModel" | "intelligenceModel"
+  : K extends
+        | "language"
+        | "outputLanguage"
+        | "localModel"
+        | "cloudModel"
+        | "intelligenceModel"
+        | "inputDevice"
     ? string
     : boolean;
 
@@ -8,6 +8,12 @@ import { useTauriQuery, useTauriMutation, queryKeys } from "@/lib/tauri-query";
 
 export type { Shortcut, ShortcutCategory };
 
+/** How a shortcut's trigger is matched in dictated text */
+export type ShortcutMatchOptions = {
+  case_sensitive?: boolean;
+  whole_word?: boolean;
+};
+
 export function useShortcuts(category?: ShortcutCategory) {
   const query = useTauriQuery(
     category ? queryKeys.shortcuts.list(category) : queryKeys.shortcuts.list(),
@@ -35,8 +41,18 @@ export function useShortcuts(category?: ShortcutCategory) {
     loading: query.isLoading,
     error: query.error?.message ?? null,
     refetch: query.refetch,
-    create: async (trigger: string, replacement: string, category: ShortcutCategory) => {
-      const result = await createMutation.mutateAsync({ trigger, replacement, category });
+    create: async (
+      trigger: string,
+      replacement: string,
+      category: ShortcutCategory,
+      options?: ShortcutMatchOptions
+    ) => {
+      const result = await createMutation.mutateAsync({
+        trigger,
+        replacement,
+        category,
+        ...options,
+      });
       return result.id;
     },
     update: async (id: string, trigger: string, replacement: string, category: ShortcutCategory) => {
@@ -14,6 +14,7 @@ export interface Transcription {
   text: string;
   formattedText: string;
   createdAt: Date;
+  isPinned: boolean;
 }
 
 export interface Analytics {
@@ -30,6 +31,7 @@ function mapTranscription(t: DbTranscription): Transcription {
     text: t.text,
     formattedText: t.formatted_text ?? t.text,
     createdAt: new Date(t.created_at * 1000),
+    isPinned: t.is_pinned,
   };
 }
 
@@ -43,6 +45,7 @@ export function useTranscriptions() {
       const result = await commands.transcriptionsList({
         limit: PAGE_SIZE,
         offset: pageParam,
+        sort: "pinned_first",
       });
       return unwrapResult(result);
     },
@@ -67,6 +70,14 @@ export function useTranscriptions() {
     ],
   });
 
+  const pinMutation = useTauriMutation(
+    ({ id, pinned }: { id: string; pinned: boolean }) =>
+      commands.transcriptionsSetPinned(id, pinned),
+    {
+      invalidateKeys: [queryKeys.transcriptions.list()],
+    }
+  );
+
   // Flatten paginated results
   const transcriptions = useMemo(
     () =>
@@ -100,6 +111,9 @@ export function useTranscriptions() {
     remove: async (id: string) => {
       await deleteMutation.mutateAsync(id);
     },
+    setPinned: async (id: string, pinned: boolean) => {
+      await pinMutation.mutateAsync({ id, pinned });
+    },
     loadMore: () => infiniteQuery.fetchNextPage(),
     hasMore: infiniteQuery.hasNextPage ?? false,
   };
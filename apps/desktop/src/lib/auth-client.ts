@@ -1,36 +1,26 @@
 import { emailOTPClient } from "better-auth/client/plugins";
 import { createAuthClient } from "better-auth/react";
-import { Store } from "@tauri-apps/plugin-store";
-
-let store: Store | null = null;
-
-async function getStore(): Promise<Store> {
-	if (!store) {
-		store = await Store.load("auth.json");
-	}
-	return store;
-}
+import { commands } from "@/bindings";
 
 export const getToken = async (): Promise<string | undefined> => {
 	try {
-		const s = await getStore();
-		return await s.get<string>("token");
+		const result = await commands.authGetToken();
+		return result.status === "ok" ? (result.data ?? undefined) : undefined;
 	} catch {
 		return;
 	}
 };
 
 export const setToken = async (token: string): Promise<void> => {
-	const s = await getStore();
-	await s.set("token", token);
-	await s.save();
+	const result = await commands.authSetToken(token);
+	if (result.status === "error") {
+		throw new Error(result.error.message);
+	}
 };
 
 export const deleteToken = async (): Promise<void> => {
 	try {
-		const s = await getStore();
-		await s.delete("token");
-		await s.save();
+		await commands.authClearToken();
 	} catch {
 		// Ignore error if token doesn't exist
 	}
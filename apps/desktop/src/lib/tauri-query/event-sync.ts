@@ -22,16 +22,28 @@ const EVENT_INVALIDATION_MAP: Record<string, readonly (readonly string[])[]> = {
   "shortcuts:updated": [queryKeys.shortcuts.all],
   "shortcuts:deleted": [queryKeys.shortcuts.all],
 
+  // Macros
+  "macros:created": [queryKeys.macros.all],
+  "macros:updated": [queryKeys.macros.all],
+  "macros:deleted": [queryKeys.macros.all],
+
   // Transcriptions
   "transcriptions:created": [
     queryKeys.transcriptions.list(),
     queryKeys.transcriptions.analytics(),
   ],
-  "transcriptions:updated": [queryKeys.transcriptions.all],
+  "transcriptions:updated": [
+    queryKeys.transcriptions.all,
+    queryKeys.keyterms.suggestions(),
+  ],
   "transcriptions:deleted": [
     queryKeys.transcriptions.all,
     queryKeys.transcriptions.analytics(),
   ],
+  "transcriptions:rated": [
+    queryKeys.transcriptions.all,
+    queryKeys.keyterms.suggestions(),
+  ],
 
   // Settings
   "settings:updated": [queryKeys.settings.all],
@@ -42,6 +54,13 @@ const EVENT_INVALIDATION_MAP: Record<string, readonly (readonly string[])[]> = {
   // Keys Vault
   "keys_vault:updated": [queryKeys.keysVault.all],
   "keys_vault:deleted": [queryKeys.keysVault.all],
+
+  // Sync pulls records from other devices
+  "sync:completed": [
+    queryKeys.transcriptions.all,
+    queryKeys.notes.all,
+    queryKeys.keyterms.all,
+  ],
 };
 
 /**
@@ -18,6 +18,7 @@ export const queryKeys = {
         ? ([...queryKeys.keyterms.all, "list", category] as const)
         : ([...queryKeys.keyterms.all, "list"] as const),
     detail: (id: string) => [...queryKeys.keyterms.all, "detail", id] as const,
+    suggestions: () => [...queryKeys.keyterms.all, "suggestions"] as const,
   },
 
   // Shortcuts
@@ -30,6 +31,13 @@ export const queryKeys = {
     detail: (id: string) => [...queryKeys.shortcuts.all, "detail", id] as const,
   },
 
+  // Macros
+  macros: {
+    all: ["macros"] as const,
+    list: () => [...queryKeys.macros.all, "list"] as const,
+    detail: (id: string) => [...queryKeys.macros.all, "detail", id] as const,
+  },
+
   // Transcriptions
   transcriptions: {
     all: ["transcriptions"] as const,
@@ -37,6 +45,7 @@ export const queryKeys = {
     detail: (id: string) =>
       [...queryKeys.transcriptions.all, "detail", id] as const,
     analytics: () => [...queryKeys.transcriptions.all, "analytics"] as const,
+    grouped: () => [...queryKeys.transcriptions.all, "grouped"] as const,
   },
 
   // Settings
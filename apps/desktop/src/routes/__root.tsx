@@ -1,7 +1,8 @@
 import { createRootRoute, Outlet, useNavigate } from "@tanstack/react-router";
 import { useEffect, useState, useRef } from "react";
-import { listen, emit } from "@tauri-apps/api/event";
+import { listen } from "@tauri-apps/api/event";
 import { openUrl } from "@tauri-apps/plugin-opener";
+import { commands, events, type WritingStyleCategory } from "@/bindings";
 import { SettingsDialog } from "@/components/settings-dialog";
 import { useSettings } from "@/hooks/use-settings";
 import { toast } from "sonner";
@@ -26,23 +27,14 @@ function RootComponent() {
 		setSettingRef.current = setSetting;
 	}, [settings, setSetting]);
 
-	// Emit settings-changed event when languages or autoDetect change
-	const prevSettingsRef = useRef({ languages: settings.languages, autoDetect: settings.autoDetectLanguage });
+	// Add the default styles, shortcuts and keyterms on first run
 	useEffect(() => {
-		const hasLanguagesChanged =
-			JSON.stringify(prevSettingsRef.current.languages) !== JSON.stringify(settings.languages);
-		const hasAutoDetectChanged =
-			prevSettingsRef.current.autoDetect !== settings.autoDetectLanguage;
-
-		if (hasLanguagesChanged || hasAutoDetectChanged) {
-			console.log("Settings changed, emitting event to update tray");
-			emit("settings-changed");
-			prevSettingsRef.current = {
-				languages: settings.languages,
-				autoDetect: settings.autoDetectLanguage
-			};
-		}
-	}, [settings.languages, settings.autoDetectLanguage]);
+		commands.seedDefaults().then((result) => {
+			if (result.status === "error") {
+				console.error("Failed to seed defaults:", result.error);
+			}
+		});
+	}, []);
 
 	useEffect(() => {
 		const unlistenPromises = [
@@ -94,17 +86,55 @@ function RootComponent() {
 				if (newLanguages.length > 0) {
 					await setSettingRef.current("languages", newLanguages);
 					await setSettingRef.current("autoDetectLanguage", false);
-					await emit("settings-changed");
 					toast.success(`Language ${isRemoving ? 'removed' : 'added'}: ${lang}`);
 				} else {
 					toast.error("At least one language must be selected");
 				}
 			}),
+			listen<string>("select-input-device", async (event) => {
+				console.log("Selecting input device:", event.payload);
+				await setSettingRef.current("inputDevice", event.payload);
+				toast.success(
+					event.payload ? `Input device: ${event.payload}` : "Using system default input device",
+				);
+			}),
+			listen<{ category: WritingStyleCategory; style: string }>(
+				"select-writing-style",
+				async (event) => {
+					const { category, style } = event.payload;
+					console.log("Selecting writing style:", category, style);
+					const result = await commands.writingStylesUpdate(category, {
+						selected_style: style,
+						default_prompt: null,
+						custom_prompt: null,
+					});
+					if (result.status === "error") {
+						toast.error("Failed to update writing style");
+						return;
+					}
+					toast.success(`${category} style updated`);
+				},
+			),
+			events.shortcutListenerFailed.listen((event) => {
+				console.error("Shortcut listener failed:", event.payload.error);
+				toast.error(
+					event.payload.will_retry
+						? "Keyboard shortcut stopped working, restarting..."
+						: "Keyboard shortcut stopped working",
+					{ description: event.payload.permission_hint },
+				);
+			}),
+			events.pasteTargetGone.listen((event) => {
+				const { app_name, timed_out } = event.payload;
+				toast.info(
+					timed_out ? `${app_name} didn't come back in time` : `${app_name} was closed`,
+					{ description: "Your transcription was copied to the clipboard instead" },
+				);
+			}),
 			listen("toggle-auto-detect-language", async () => {
 				console.log("Toggling auto-detect language");
 				const newValue = !settingsRef.current.autoDetectLanguage;
 				await setSettingRef.current("autoDetectLanguage", newValue);
-				await emit("settings-changed");
 				toast.success(newValue ? "Auto-detect enabled" : "Auto-detect disabled");
 			}),
 		];
@@ -1,5 +1,7 @@
 import { createFileRoute, Link } from "@tanstack/react-router";
 import { Check, CircleQuestionMark, HelpCircle, Lock, Mic } from "lucide-react";
+import { useEffect, useState } from "react";
+import { type BenchmarkReport, commands } from "@/bindings";
 import { AuthLeftPanel } from "@/components/auth-left-panel";
 import { DictoLogo } from "@/components/dicto-logo";
 import { Button } from "@/components/ui/button";
@@ -10,6 +12,7 @@ import {
 } from "@/components/ui/tooltip";
 import { useAccessibilityPermission } from "@/hooks/use-accessibility-permission";
 import { useMicrophonePermission } from "@/hooks/use-microphone-permission";
+import { useSettings } from "@/hooks/use-settings";
 
 export const Route = createFileRoute("/_auth/onboarding")({
 	component: RouteComponent,
@@ -137,6 +140,7 @@ function RouteComponent() {
 									)}
 								</Button>
 							</div>
+							<ModelRecommendation />
 						</div>
 
 						<Link
@@ -159,6 +163,92 @@ function RouteComponent() {
 	);
 }
 
+/** Times the downloaded speech models on this machine and offers the best fit */
+function ModelRecommendation() {
+	const { settings, setSetting } = useSettings();
+	const [report, setReport] = useState<BenchmarkReport | null>(null);
+	const [modelNames, setModelNames] = useState<Record<string, string>>({});
+	const [isBenchmarking, setIsBenchmarking] = useState(true);
+
+	useEffect(() => {
+		commands
+			.benchmarkModels()
+			.then((result) => {
+				if (result.status === "ok") {
+					setReport(result.data);
+				} else {
+					console.error("Failed to benchmark models:", result.error);
+				}
+			})
+			.finally(() => setIsBenchmarking(false));
+
+		commands.modelsCatalog().then((result) => {
+			if (result.status === "ok") {
+				setModelNames(
+					Object.fromEntries(
+						result.data.map((model) => [model.id, model.name]),
+					),
+				);
+			}
+		});
+	}, []);
+
+	const recommended = report?.recommended ?? null;
+	const recommendedName = recommended
+		? (modelNames[recommended] ?? recommended)
+		: null;
+	const isSelected = recommended !== null && settings.localModel === recommended;
+
+	const handleUseRecommended = () => {
+		if (recommended) {
+			setSetting("localModel", recommended);
+		}
+	};
+
+	return (
+		<div className="flex items-center justify-between rounded-lg border border-b-3 bg-white p-2">
+			<div className="flex items-center justify-center gap-2 text-left">
+				<p className="font-normal text-sm">Speech model</p>
+				<Tooltip>
+					<TooltipTrigger>
+						<CircleQuestionMark className="h-4 w-4 text-gray-600" />
+					</TooltipTrigger>
+					<TooltipContent>
+						<p className="text-sm">
+							Picked by transcribing a short clip with each downloaded model.
+						</p>
+					</TooltipContent>
+				</Tooltip>
+			</div>
+			{recommended === null && !isBenchmarking ? (
+				<p className="px-3 py-1.5 text-muted-foreground text-sm">
+					Download one in Settings
+				</p>
+			) : (
+				<Button
+					disabled={isBenchmarking || isSelected}
+					variant="outline"
+					size="xs"
+					className="rounded-md border-b-3 px-3 py-1.5 font-medium text-sm transition"
+					onClick={handleUseRecommended}
+					isLoading={isBenchmarking}
+				>
+					{isBenchmarking ? (
+						"Testing models"
+					) : isSelected ? (
+						<>
+							<Check className="mr-1.5 h-4 w-4" />
+							{recommendedName}
+						</>
+					) : (
+						`Use ${recommendedName}`
+					)}
+				</Button>
+			)}
+		</div>
+	);
+}
+
 function PermissionVisual({
 	type,
 }: {
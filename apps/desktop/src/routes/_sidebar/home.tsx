@@ -1,9 +1,8 @@
-import { listen } from "@tauri-apps/api/event";
 import { createFileRoute } from "@tanstack/react-router";
 import { Cloud, CloudOffIcon, Download, ExternalLink, Check, Sparkles } from "lucide-react";
 import type { SVGProps } from "react";
 import { useState, useEffect } from "react";
-import { commands } from "@/bindings";
+import { commands, events } from "@/bindings";
 import { Shell } from "@/components/shell";
 import { TranscriptionsListingView } from "@/components/transcriptions-listing-view";
 import { Button } from "@/components/ui/button";
@@ -74,22 +73,6 @@ export const Route = createFileRoute("/_sidebar/home")({
 	component: RouteComponent,
 });
 
-interface DownloadProgress {
-	model: string;
-	downloaded: number;
-	total: number;
-	percentage: number;
-}
-
-interface DownloadComplete {
-	model: string;
-}
-
-interface DownloadError {
-	model: string;
-	error: string;
-}
-
 function RouteComponent() {
   const session = authClient.useSession()
   console.log(session)
@@ -118,7 +101,7 @@ function RouteComponent() {
 	// Check model status on mount
 	useEffect(() => {
 		const checkModelStatus = async () => {
-			const whisperResult = await commands.checkSttModelStatus("Whisper");
+			const whisperResult = await commands.checkModelStatus("whisper-small");
 
 			const whisperDownloaded = whisperResult.status === "ok" && whisperResult.data.downloaded;
 
@@ -136,59 +119,50 @@ function RouteComponent() {
 
 	// Listen for download events
 	useEffect(() => {
-		const unlistenProgress = listen<DownloadProgress>(
-			"model-download-progress",
-			(event) => {
-				const { model, downloaded, total, percentage } = event.payload;
-				const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
-					...prev,
-					isDownloading: true,
-					progress: percentage,
-					downloaded,
-					total,
-					error: null,
-				});
+		const unlistenProgress = events.downloadProgress.listen((event) => {
+			const { model, downloaded, total, percentage } = event.payload;
+			const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
+				...prev,
+				isDownloading: true,
+				progress: percentage,
+				downloaded,
+				total,
+				error: null,
+			});
 
-				if (model === "Whisper Small") {
-					setWhisperState(updateFn);
-				}
-			},
-		);
+			if (model === "Whisper Small") {
+				setWhisperState(updateFn);
+			}
+		});
 
-		const unlistenComplete = listen<DownloadComplete>(
-			"model-download-complete",
-			(event) => {
-				const { model } = event.payload;
-				const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
-					...prev,
-					isDownloading: false,
-					progress: 100,
-					isDownloaded: true,
-					error: null,
-				});
+		const unlistenComplete = events.downloadComplete.listen((event) => {
+			const { model } = event.payload;
+			const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
+				...prev,
+				isDownloading: false,
+				progress: 100,
+				isDownloaded: true,
+				error: null,
+			});
 
-				if (model === "Whisper Small") {
-					setWhisperState(updateFn);
-					setShowModelDialog(false);
-				}
-			},
-		);
+			if (model === "Whisper Small") {
+				setWhisperState(updateFn);
+				setShowModelDialog(false);
+			}
+		});
 
-		const unlistenError = listen<DownloadError>(
-			"model-download-error",
-			(event) => {
-				const { model, error } = event.payload;
-				const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
-					...prev,
-					isDownloading: false,
-					error,
-				});
+		const unlistenError = events.downloadError.listen((event) => {
+			const { model, error } = event.payload;
+			const updateFn = (prev: ModelDownloadState): ModelDownloadState => ({
+				...prev,
+				isDownloading: false,
+				error,
+			});
 
-				if (model === "Whisper Small") {
-					setWhisperState(updateFn);
-				}
-			},
-		);
+			if (model === "Whisper Small") {
+				setWhisperState(updateFn);
+			}
+		});
 
 		return () => {
 			unlistenProgress.then((fn) => fn());
@@ -204,12 +178,12 @@ function RouteComponent() {
 			progress: 0,
 			error: null,
 		}));
-		const result = await commands.downloadSttModel("Whisper");
+		const result = await commands.downloadModel("whisper-small");
 		if (result.status === "error") {
 			setWhisperState((prev) => ({
 				...prev,
 				isDownloading: false,
-				error: result.error,
+				error: result.error.message,
 			}));
 		}
 	};
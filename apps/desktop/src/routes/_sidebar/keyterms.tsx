@@ -5,6 +5,7 @@ import {
   CATEGORIES,
   KeytermsListingView,
 } from "@/components/keyterms-listing-view";
+import { KeytermSuggestions } from "@/components/keyterm-suggestions";
 import { Shell } from "@/components/shell";
 import { Button } from "@/components/ui/button";
 import {
@@ -31,7 +32,8 @@ export const Route = createFileRoute("/_sidebar/keyterms")({
 });
 
 function RouteComponent() {
-  const { keyterms, loading, error, create, update, remove } = useKeyterms();
+  const { keyterms, suggestions, loading, error, create, update, remove } =
+    useKeyterms();
   const [dialogOpen, setDialogOpen] = useState(false);
   const [newTerm, setNewTerm] = useState("");
   const [newCategory, setNewCategory] = useState("all");
@@ -119,6 +121,10 @@ function RouteComponent() {
         </Dialog>
       }
     >
+      <KeytermSuggestions
+        suggestions={suggestions}
+        onAdd={(text) => create(text, "all")}
+      />
       <KeytermsListingView
         keyterms={keyterms}
         loading={loading}
@@ -7,6 +7,7 @@ import {
   ShortcutsListingView,
 } from "@/components/shortcuts-listing-view";
 import { Button } from "@/components/ui/button";
+import { Checkbox } from "@/components/ui/checkbox";
 import {
   Dialog,
   DialogContent,
@@ -36,14 +37,18 @@ function RouteComponent() {
   const [newTrigger, setNewTrigger] = useState("");
   const [newReplacement, setNewReplacement] = useState("");
   const [newCategory, setNewCategory] = useState("all");
+  const [newCaseSensitive, setNewCaseSensitive] = useState(false);
+  const [newWholeWord, setNewWholeWord] = useState(true);
 
   const handleAddShortcut = async () => {
     if (!newTrigger.trim() || !newReplacement.trim()) return;
-    await create(newTrigger.trim(), newReplacement.trim(), newCategory as ShortcutCategory);
-    setNewTrigger("");
-    setNewReplacement("");
-    setNewCategory("all");
-    setDialogOpen(false);
+    await create(
+      newTrigger.trim(),
+      newReplacement.trim(),
+      newCategory as ShortcutCategory,
+      { case_sensitive: newCaseSensitive, whole_word: newWholeWord }
+    );
+    resetDialog();
   };
 
   const resetDialog = () => {
@@ -51,6 +56,8 @@ function RouteComponent() {
     setNewTrigger("");
     setNewReplacement("");
     setNewCategory("all");
+    setNewCaseSensitive(false);
+    setNewWholeWord(true);
   };
 
   return (
@@ -102,7 +109,9 @@ function RouteComponent() {
                   placeholder="e.g., be right back"
                 />
                 <p className="text-muted-foreground text-xs">
-                  The full text that will replace your trigger.
+                  The full text that will replace your trigger. Use {"{date}"},{" "}
+                  {"{time}"} and {"{clipboard}"} to insert values, and{" "}
+                  {"{cursor}"} to place the cursor after pasting.
                 </p>
               </div>
               <div className="flex flex-col gap-2">
@@ -125,6 +134,30 @@ function RouteComponent() {
                   </SelectContent>
                 </Select>
               </div>
+              <div className="flex flex-col gap-3">
+                <div className="flex items-center gap-2">
+                  <Checkbox
+                    id="case-sensitive"
+                    checked={newCaseSensitive}
+                    onCheckedChange={(checked) =>
+                      setNewCaseSensitive(checked === true)
+                    }
+                  />
+                  <Label htmlFor="case-sensitive">Match case</Label>
+                </div>
+                <p className="text-muted-foreground text-xs -mt-2">
+                  Otherwise the replacement follows the trigger's case, so
+                  "BRB" expands in capitals.
+                </p>
+                <div className="flex items-center gap-2">
+                  <Checkbox
+                    id="whole-word"
+                    checked={newWholeWord}
+                    onCheckedChange={(checked) => setNewWholeWord(checked === true)}
+                  />
+                  <Label htmlFor="whole-word">Whole words only</Label>
+                </div>
+              </div>
             </div>
             <DialogFooter>
               <Button variant="outline" onClick={resetDialog}>
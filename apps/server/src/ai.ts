@@ -129,6 +129,21 @@ Let me know what you think!`,
 	},
 } as const;
 
+/**
+ * Style for dictation into notes and Markdown editors, applied in any category.
+ * The desktop app turns spoken commands into headings and list items first.
+ */
+export const MARKDOWN_STYLE = {
+	key: "markdown",
+	label: "Markdown",
+	prompt: `Markdown. Keep every heading (#) and list item (-) already in the text on its own line.
+
+Structure:
+- Blank line between paragraphs, headings and lists
+- No blank lines between items of the same list
+- Don't add headings or list items the user didn't dictate`,
+} as const;
+
 export type Category = keyof typeof STYLES;
 export type StyleKey<C extends Category> = keyof (typeof STYLES)[C];
 
@@ -199,10 +214,16 @@ export function buildSystemPrompt(
 		{ label: string; prompt: string }
 	>;
 
+	// The Markdown style works in every category, with the General rules so no
+	// category strips the structure (e.g. Personal's "no paragraph breaks")
+	const isMarkdown = styleKey === MARKDOWN_STYLE.key;
+
 	// Get style or fallback to default style for category
-	const style =
-		categoryStyles[styleKey] ?? categoryStyles[DEFAULT_STYLES[validCategory]];
-	const rules = CATEGORY_RULES[validCategory];
+	const style = isMarkdown
+		? MARKDOWN_STYLE
+		: (categoryStyles[styleKey] ??
+			categoryStyles[DEFAULT_STYLES[validCategory]]);
+	const rules = CATEGORY_RULES[isMarkdown ? "General" : validCategory];
 
 	// Build context string
 	const appContext = appName ? `\nContext: Formatting for ${appName}.` : "";